@@ -3,8 +3,15 @@ use argon2::{
     password_hash::{Error as Argon2PasswordHashError, PasswordHash, SaltString},
 };
 use rand::{RngCore, rngs::OsRng};
+use sha2::{Digest, Sha256};
+use std::sync::LazyLock;
 use thiserror::Error;
 
+/// Shortest password accepted at registration or on a password change.
+pub const MIN_PASSWORD_LENGTH: usize = 8;
+/// Longest password accepted. Argon2 would take more, but this keeps hashing cost bounded.
+pub const MAX_PASSWORD_LENGTH: usize = 128;
+
 /// Auth failures, wrapping the crate's dependency error types so callers depend
 /// on this one enum instead of `hex`/`argon2` internals.
 #[derive(Debug, Error)]
@@ -15,6 +22,12 @@ pub enum AuthError {
     PasswordHash(#[from] Argon2PasswordHashError),
     #[error("argon2 parameter error")]
     Argon2Params(#[from] Argon2Error),
+    #[error("password does not match")]
+    PasswordMismatch,
+    #[error("email address is not valid")]
+    InvalidEmail,
+    #[error("password must be between {min} and {max} characters")]
+    InvalidPasswordLength { min: usize, max: usize },
 }
 
 /// Converts a token string into bytes.
@@ -35,6 +48,42 @@ pub fn generate_token() -> [u8; 64] {
     token
 }
 
+/// Hashes a token for storage, so a leaked row cannot be replayed. Tokens already carry 512 bits
+/// of entropy, so a fast unsalted hash is enough; only passwords need Argon2.
+pub fn hash_token(token_bytes: &[u8]) -> Vec<u8> {
+    Sha256::digest(token_bytes).to_vec()
+}
+
+/// Checks that an email address is plausible: one `@`, a non-empty local part, and a dotted
+/// domain without whitespace. Whether it is deliverable is left to the confirmation email.
+pub fn validate_email(email: &str) -> Result<(), AuthError> {
+    let Some((local, domain)) = email.split_once('@') else {
+        return Err(AuthError::InvalidEmail);
+    };
+    let domain_is_valid = domain.contains('.')
+        && !domain.starts_with('.')
+        && !domain.ends_with('.')
+        && !domain.contains('@');
+    if local.is_empty() || !domain_is_valid || email.chars().any(char::is_whitespace) {
+        return Err(AuthError::InvalidEmail);
+    }
+
+    Ok(())
+}
+
+/// Checks a new password against the length policy. Length is counted in characters, not bytes.
+pub fn validate_password(password: &str) -> Result<(), AuthError> {
+    let length = password.chars().count();
+    if !(MIN_PASSWORD_LENGTH..=MAX_PASSWORD_LENGTH).contains(&length) {
+        return Err(AuthError::InvalidPasswordLength {
+            min: MIN_PASSWORD_LENGTH,
+            max: MAX_PASSWORD_LENGTH,
+        });
+    }
+
+    Ok(())
+}
+
 /// Hashes the given password using Argon2id. See [OWASP Password Storage Cheat Sheet](https://cheatsheetseries.owasp.org/cheatsheets/Password_Storage_Cheat_Sheet.html) for details.
 pub fn generate_password_hash(password: &str) -> Result<String, AuthError> {
     let password_bytes = password.as_bytes();
@@ -48,7 +97,8 @@ pub fn generate_password_hash(password: &str) -> Result<String, AuthError> {
     Ok(password_hash)
 }
 
-/// Checks that a given password matches a hashed password.
+/// Checks that a given password matches a hashed password. A wrong password is
+/// [`AuthError::PasswordMismatch`]; any other error means the stored hash is unusable.
 pub fn verify_password_against_hash(
     password_to_test: &str,
     hashed_password: &str,
@@ -57,11 +107,35 @@ pub fn verify_password_against_hash(
     let parsed_password_hash = PasswordHash::new(hashed_password)?;
 
     let argon2_params = get_argon2_params()?;
-    Argon2::from(argon2_params).verify_password(password_to_test_bytes, &parsed_password_hash)?;
+    Argon2::from(argon2_params)
+        .verify_password(password_to_test_bytes, &parsed_password_hash)
+        .map_err(|verify_error| match verify_error {
+            Argon2PasswordHashError::Password => AuthError::PasswordMismatch,
+            other_error => other_error.into(),
+        })?;
 
     Ok(())
 }
 
+/// The hash of a random password nobody knows, made once with the same parameters as real ones.
+static UNKNOWN_USER_PASSWORD_HASH: LazyLock<Option<String>> =
+    LazyLock::new(|| generate_password_hash(&encode_token(&generate_token().to_vec())).ok());
+
+/// Makes the hash [`verify_password_for_unknown_user`] checks against now, so the first login on
+/// an unknown email isn't the one that pays for it. Call it once at startup.
+pub fn prepare_unknown_user_password_hash() {
+    LazyLock::force(&UNKNOWN_USER_PASSWORD_HASH);
+}
+
+/// Does the work of [`verify_password_against_hash`] for a login on an email no account has, so
+/// the response takes as long as a wrong password would and timing can't reveal which emails have
+/// accounts. The caller treats it as a mismatch.
+pub fn verify_password_for_unknown_user(password_to_test: &str) {
+    if let Some(unknown_user_hash) = UNKNOWN_USER_PASSWORD_HASH.as_ref() {
+        let _ = verify_password_against_hash(password_to_test, unknown_user_hash);
+    }
+}
+
 fn get_argon2_params() -> Result<Params, Argon2Error> {
     ParamsBuilder::new().m_cost(16777).p_cost(2).build()
 }
@@ -75,6 +149,12 @@ mod tests {
         let password = "test";
         let hash = generate_password_hash(password).unwrap();
         verify_password_against_hash(password, &hash).unwrap();
+        assert!(matches!(
+            verify_password_against_hash("not the password", &hash),
+            Err(AuthError::PasswordMismatch)
+        ));
+        // Does the same work, without anything to match.
+        verify_password_for_unknown_user(password);
     }
 
     #[test]
@@ -88,4 +168,47 @@ mod tests {
 
         assert_eq!(token_owned, decoded_token);
     }
+
+    #[test]
+    fn token_hashing() {
+        let token = generate_token();
+        let hash = hash_token(&token);
+
+        assert_eq!(hash.len(), 32);
+        assert_eq!(hash, hash_token(&token));
+        assert_ne!(hash, hash_token(&generate_token()));
+    }
+
+    #[test]
+    fn email_validation() {
+        assert!(validate_email("owner@example.com").is_ok());
+        assert!(validate_email("first.last+fbkl@mail.example.org").is_ok());
+
+        for email in [
+            "",
+            "owner",
+            "@example.com",
+            "owner@",
+            "owner@example",
+            "owner@.com",
+            "owner@example.",
+            "owner@@example.com",
+            "own er@example.com",
+        ] {
+            assert!(
+                matches!(validate_email(email), Err(AuthError::InvalidEmail)),
+                "{email:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn password_validation() {
+        assert!(validate_password(&"a".repeat(MIN_PASSWORD_LENGTH)).is_ok());
+        assert!(validate_password(&"a".repeat(MAX_PASSWORD_LENGTH)).is_ok());
+        assert!(validate_password(&"a".repeat(MIN_PASSWORD_LENGTH - 1)).is_err());
+        assert!(validate_password(&"a".repeat(MAX_PASSWORD_LENGTH + 1)).is_err());
+        // Counted in characters, so multi-byte passwords are not penalized.
+        assert!(validate_password(&"é".repeat(MIN_PASSWORD_LENGTH)).is_ok());
+    }
 }
@@ -0,0 +1,94 @@
+//! A personal API token, which lets a script act as its user in one league without a session.
+//!
+//! Only `fbkl_auth::hash_token` of the token is stored; the token itself is shown to the user once.
+//! Every token can read; `scopes` lists the groups of mutations it may also call. Changing or
+//! resetting the password revokes all of the user's tokens.
+
+use async_graphql::Enum;
+use color_eyre::Result;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "api_token")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub user_id: i64,
+    /// The only league the token can act in.
+    pub league_id: i64,
+    /// What the user called the token, e.g. the script using it.
+    pub name: String,
+    #[serde(skip_serializing)]
+    pub token_hash: Vec<u8>,
+    /// The mutation scopes granted. Converted to/from `Vec<ApiTokenScope>`.
+    pub scopes: serde_json::Value,
+    pub last_used_at: Option<DateTimeWithTimeZone>,
+    pub revoked_at: Option<DateTimeWithTimeZone>,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+impl Model {
+    pub fn get_scopes(&self) -> Result<Vec<ApiTokenScope>> {
+        let scopes: Vec<ApiTokenScope> = serde_json::from_value(self.scopes.clone())?;
+        Ok(scopes)
+    }
+
+    pub const fn is_revoked(&self) -> bool {
+        self.revoked_at.is_some()
+    }
+}
+
+/// A group of mutations a token may call. A token with no scopes is read-only.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Enum, Serialize, Deserialize)]
+pub enum ApiTokenScope {
+    /// Bidding in auctions, and the commissioner's veteran auction setup.
+    Auction,
+    /// Rookie draft picks, and the commissioner's standings, lottery and draft start.
+    RookieDraft,
+    /// Raising, matching and declining RFAs.
+    Rfa,
+    /// IR, drops, rookie activations and the other roster moves.
+    Roster,
+    Keeper,
+    /// Proposing and answering trades, and the trade block.
+    Trade,
+    /// The remaining commissioner tools: deadlines, eligibility, league settings, teams and
+    /// invitations.
+    Commissioner,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    User,
+    #[sea_orm(
+        belongs_to = "super::league::Entity",
+        from = "Column::LeagueId",
+        to = "super::league::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    League,
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl Related<super::league::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::League.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -21,6 +21,7 @@ use super::{
     drop_contract::create_dropped_contract,
     expire_contract::expire_contract,
     free_agent_extension::{FreeAgentException, sign_rfa_or_ufa_contract_to_team},
+    relink_player::relink_contract_to_real_player,
     rookie_activation::create_rookie_contract_from_rd,
     rookie_development_international::{create_rd_contract_from_rdi, create_rdi_contract_from_rd},
     rookie_draft::new_contract_from_rookie_draft,
@@ -208,6 +209,11 @@ impl Model {
         )
     }
 
+    /// Creates a new contract in the history chain that names the real player a league player was merged into. Note that this doesn't do anything to insert the new contract or update the original.
+    pub fn relink_to_real_player(&self, real_player_id: i64) -> Result<ActiveModel> {
+        relink_contract_to_real_player(self, real_player_id)
+    }
+
     /// Creates a new Veteran or Rookie Extension contract from the current contract as a result of a team winning the contract during the Preseason Veteran Auction. Note that this doesn't do anything to insert the new contract or update the original.
     pub fn sign_rfa_or_ufa_contract_to_team(
         &self,
@@ -227,7 +233,7 @@ impl Model {
         sign_veteran_contract(self, team_id, salary)
     }
 
-    /// Creates a new contract in the history chain to denote that the contract has been traded to a new team. Note that this doesn't do anything to insert the new contract or update the original.
+    /// Creates a new contract in the history chain to denote that the contract has been traded to a new team. An IR contract comes off IR on the receiving roster (rules §10.3.2). Note that this doesn't do anything to insert the new contract or update the original.
     pub fn trade_contract_to_team(&self, new_team_id: i64) -> ActiveModel {
         trade_contract_to_team(self, new_team_id)
     }
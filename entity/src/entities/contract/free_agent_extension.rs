@@ -28,6 +28,25 @@ impl FreeAgentKind {
             _ => None,
         }
     }
+
+    /// RFAs re-sign at 10% off uncapped, original-team UFAs at 20% off capped at $8, and veteran
+    /// UFAs at 10% off capped at $5.
+    const fn exception_discount(&self) -> FreeAgentExceptionDiscount {
+        match self {
+            Self::Restricted => FreeAgentExceptionDiscount {
+                percent: 10,
+                max_discount: None,
+            },
+            Self::UnrestrictedOriginalTeam => FreeAgentExceptionDiscount {
+                percent: 20,
+                max_discount: Some(8),
+            },
+            Self::UnrestrictedVeteran => FreeAgentExceptionDiscount {
+                percent: 10,
+                max_discount: Some(5),
+            },
+        }
+    }
 }
 
 /// Whether the signing team holds the player's RFA/UFA re-sign exception, i.e. the discount that
@@ -45,6 +64,56 @@ pub enum FreeAgentException {
     NotHeld,
 }
 
+/// A team tried to sign a UFA at the re-sign discount without holding his exception (rules §16.4):
+/// it did not own him at the keeper deadline, or no snapshot names any holder.
+///
+/// Concrete (not an opaque `eyre!`) so callers can `downcast_ref` and report it as a typed failure.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FreeAgentExceptionNotHeld {
+    pub contract_id: i64,
+    pub signing_team_id: i64,
+    pub exception_holder_team_id: Option<i64>,
+}
+
+impl std::fmt::Display for FreeAgentExceptionNotHeld {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Team {} does not hold the re-sign exception for UFA contract {} (holder: {:?}).",
+            self.signing_team_id, self.contract_id, self.exception_holder_team_id
+        )
+    }
+}
+
+impl std::error::Error for FreeAgentExceptionNotHeld {}
+
+/// The re-sign discount an exception holder gets on a free agent: the share of the winning bid taken
+/// off (rounded up) and the most it may take off (rules §15.4.2, §16.4.1).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FreeAgentExceptionDiscount {
+    pub percent: i16,
+    pub max_discount: Option<i16>,
+}
+
+impl FreeAgentExceptionDiscount {
+    /// The discount held on a free agent contract of `kind`, or `None` if `kind` is not an RFA or UFA.
+    pub const fn for_kind(kind: ContractKind) -> Option<Self> {
+        match FreeAgentKind::from_contract_kind(kind) {
+            Some(fa_kind) => Some(fa_kind.exception_discount()),
+            None => None,
+        }
+    }
+
+    /// Applies the discount to `final_bid`, floored at $1.
+    pub fn apply(self, final_bid: i16) -> i16 {
+        discounted_salary(
+            final_bid,
+            f32::from(self.percent) / 100.0,
+            self.max_discount,
+        )
+    }
+}
+
 /// Creates a new Veteran or Rookie Extension contract from the given RFA or UFA contract as a result of a team winning the contract during the Preseason Veteran Auction.
 pub fn sign_rfa_or_ufa_contract_to_team(
     fa_contract: &contract::Model,
@@ -65,36 +134,30 @@ pub fn sign_rfa_or_ufa_contract_to_team(
         );
     }
 
+    let discount = fa_kind.exception_discount();
     let (new_contract_year, new_contract_type, new_salary) = match (fa_exception, fa_kind) {
         (FreeAgentException::Held, FreeAgentKind::Restricted) => (
             4,
             ContractKind::RookieExtension,
             // RFA 10% re-sign is uncapped, floored at the standard 4th-year salary the RFA contract already carries (rookie Y3 + 20%).
-            cmp::max(
-                discounted_salary(signing_amount, 0.1, None),
-                fa_contract.salary,
-            ),
+            cmp::max(discount.apply(signing_amount), fa_contract.salary),
         ),
         (FreeAgentException::HeldNoBid, FreeAgentKind::Restricted) => (
             4,
             ContractKind::RookieExtension,
             // Nobody bid, so the 10% comes straight off the carry salary; no bid means no floor to hold it up (rules §15.3.5).
-            discounted_salary(signing_amount, 0.1, None),
+            discount.apply(signing_amount),
         ),
         (FreeAgentException::HeldNoBid, _) => bail!(
             "Only a restricted free agent can be re-signed with no bid. Contract:\n{:#?}",
             fa_contract
         ),
-        (FreeAgentException::Held, FreeAgentKind::UnrestrictedOriginalTeam) => (
-            1,
-            ContractKind::Veteran,
-            discounted_salary(signing_amount, 0.2, Some(8)),
-        ),
-        (FreeAgentException::Held, FreeAgentKind::UnrestrictedVeteran) => (
-            1,
-            ContractKind::Veteran,
-            discounted_salary(signing_amount, 0.1, Some(5)),
-        ),
+        (FreeAgentException::Held, FreeAgentKind::UnrestrictedOriginalTeam) => {
+            (1, ContractKind::Veteran, discount.apply(signing_amount))
+        }
+        (FreeAgentException::Held, FreeAgentKind::UnrestrictedVeteran) => {
+            (1, ContractKind::Veteran, discount.apply(signing_amount))
+        }
         (FreeAgentException::NotHeld, _) => (1, ContractKind::Veteran, signing_amount),
     };
 
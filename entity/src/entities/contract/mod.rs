@@ -3,6 +3,7 @@ mod contract_entity;
 mod drop_contract;
 mod expire_contract;
 mod free_agent_extension;
+mod relink_player;
 mod rookie_activation;
 mod rookie_development_international;
 mod rookie_draft;
@@ -11,4 +12,6 @@ mod veteran_auction_contract;
 mod veteran_contract_signing;
 
 pub use contract_entity::*;
-pub use free_agent_extension::FreeAgentException;
+pub use free_agent_extension::{
+    FreeAgentException, FreeAgentExceptionDiscount, FreeAgentExceptionNotHeld,
+};
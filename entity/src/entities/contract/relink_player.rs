@@ -0,0 +1,111 @@
+use color_eyre::{Result, eyre::bail};
+use sea_orm::ActiveValue;
+
+use crate::contract::{self, ContractStatus};
+
+/// Creates a new contract in the history chain that names `real_player_id` alongside the league
+/// player it was signed with. Everything else about the contract stays as it was; the league player
+/// id is kept so roster and re-draft checks keyed on either id still find the player.
+pub fn relink_contract_to_real_player(
+    current_contract: &contract::Model,
+    real_player_id: i64,
+) -> Result<contract::ActiveModel> {
+    if current_contract.status != ContractStatus::Active {
+        bail!(
+            "Cannot relink a replaced or expired contract. Contract:\n{:#?}",
+            current_contract
+        );
+    }
+    let Some(league_player_id) = current_contract.league_player_id else {
+        bail!(
+            "Only a league player's contract can be relinked to a real player (contract id = {}).",
+            current_contract.id
+        );
+    };
+    if let Some(player_id) = current_contract.player_id {
+        bail!(
+            "Contract (id = {}) for league player {league_player_id} already names player {player_id}.",
+            current_contract.id
+        );
+    }
+
+    let mut relinked_contract: contract::ActiveModel = current_contract.clone().into();
+    relinked_contract.id = ActiveValue::NotSet;
+    relinked_contract.player_id = ActiveValue::Set(Some(real_player_id));
+    relinked_contract.previous_contract_id = ActiveValue::Set(Some(current_contract.id));
+    relinked_contract.created_at = ActiveValue::NotSet;
+    relinked_contract.updated_at = ActiveValue::NotSet;
+
+    Ok(relinked_contract)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::LazyLock;
+
+    use chrono::{DateTime, FixedOffset};
+    use color_eyre::Result;
+    use sea_orm::ActiveValue;
+
+    use crate::contract::{
+        self, ContractKind, ContractStatus, relink_player::relink_contract_to_real_player,
+    };
+
+    static NOW: LazyLock<DateTime<FixedOffset>> = LazyLock::new(|| {
+        DateTime::parse_from_str("2026 Oct 19 12:09:14.274 +0000", "%Y %b %d %H:%M:%S%.3f %z")
+            .unwrap()
+    });
+
+    fn generate_contract() -> contract::Model {
+        contract::Model {
+            id: 4,
+            kind: ContractKind::RookieDevelopment,
+            year_number: 2,
+            salary: 2,
+            is_ir: false,
+            end_of_season_year: 2026,
+            league_id: 1,
+            league_player_id: Some(7),
+            player_id: None,
+            original_contract_id: Some(1),
+            previous_contract_id: Some(3),
+            team_id: Some(1),
+            status: ContractStatus::Active,
+            created_at: NOW.to_owned(),
+            updated_at: NOW.to_owned(),
+        }
+    }
+
+    #[test]
+    fn relinked_contract_names_both_players() -> Result<()> {
+        let test_contract = generate_contract();
+
+        let relinked_contract = relink_contract_to_real_player(&test_contract, 42)?;
+        assert_eq!(relinked_contract.player_id, ActiveValue::Set(Some(42)));
+        assert_eq!(
+            relinked_contract.league_player_id,
+            ActiveValue::Set(Some(7))
+        );
+        assert_eq!(
+            relinked_contract.previous_contract_id,
+            ActiveValue::Set(Some(4))
+        );
+        assert_eq!(
+            relinked_contract.original_contract_id,
+            ActiveValue::Set(Some(1))
+        );
+        assert_eq!(relinked_contract.year_number, ActiveValue::Set(2));
+        assert_eq!(relinked_contract.salary, ActiveValue::Set(2));
+
+        Ok(())
+    }
+
+    #[test]
+    fn real_player_contract_cannot_be_relinked() {
+        let mut test_contract = generate_contract();
+        test_contract.league_player_id = None;
+        test_contract.player_id = Some(9);
+
+        assert!(relink_contract_to_real_player(&test_contract, 42).is_err());
+    }
+}
@@ -2,6 +2,8 @@ use sea_orm::ActiveValue;
 
 use crate::contract;
 
+/// A traded IR contract lands on the receiving roster off IR (rules §10.3.2), so the new contract in
+/// the chain always has `is_ir` cleared.
 pub fn trade_contract_to_team(
     contract_model: &contract::Model,
     new_team_id: i64,
@@ -9,7 +11,55 @@ pub fn trade_contract_to_team(
     let mut new_contract: contract::ActiveModel = contract_model.clone().into();
     new_contract.id = ActiveValue::NotSet;
     new_contract.team_id = ActiveValue::Set(Some(new_team_id));
+    new_contract.is_ir = ActiveValue::Set(false);
     new_contract.previous_contract_id = ActiveValue::Set(Some(contract_model.id));
 
     new_contract
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use sea_orm::ActiveValue;
+
+    use crate::contract::{
+        self, ContractKind, ContractStatus, trade_contract::trade_contract_to_team,
+    };
+
+    fn generate_contract(is_ir: bool) -> contract::Model {
+        contract::Model {
+            id: 5,
+            kind: ContractKind::Veteran,
+            year_number: 2,
+            salary: 12,
+            is_ir,
+            end_of_season_year: 2025,
+            league_id: 1,
+            league_player_id: None,
+            player_id: Some(1),
+            original_contract_id: Some(3),
+            previous_contract_id: Some(4),
+            team_id: Some(1),
+            status: ContractStatus::Active,
+            created_at: Utc::now().into(),
+            updated_at: Utc::now().into(),
+        }
+    }
+
+    #[test]
+    fn traded_contract_moves_to_the_new_team() {
+        let traded = trade_contract_to_team(&generate_contract(false), 2);
+
+        assert_eq!(traded.id, ActiveValue::NotSet);
+        assert_eq!(traded.team_id, ActiveValue::Set(Some(2)));
+        assert_eq!(traded.previous_contract_id, ActiveValue::Set(Some(5)));
+        assert_eq!(traded.salary, ActiveValue::Unchanged(12));
+    }
+
+    #[test]
+    fn traded_ir_contract_comes_off_ir() {
+        let traded = trade_contract_to_team(&generate_contract(true), 2);
+
+        assert_eq!(traded.is_ir, ActiveValue::Set(false));
+    }
+}
@@ -0,0 +1,52 @@
+//! An email waiting to be sent, or the record of one that was.
+//!
+//! Rows are written through `fbkl_notifications::enqueue_email` inside the transaction of the event
+//! that triggered them, so a rolled-back event never sends mail. The scheduler delivers them.
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "email_outbox")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub to_address: String,
+    pub subject: String,
+    /// Emptied once the message is sent or dead-lettered, since it may hold a password-reset or
+    /// verification link.
+    #[sea_orm(column_type = "Text")]
+    pub body_text: String,
+    pub status: EmailOutboxStatus,
+    /// Delivery attempts started so far, including one that may be in flight.
+    pub attempts: i32,
+    /// A pending row is not picked up before this. Also pushed forward while a send is in flight,
+    /// so a second scheduler doesn't send the same row.
+    pub next_attempt_at: DateTimeWithTimeZone,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub last_error: Option<String>,
+    pub sent_at: Option<DateTimeWithTimeZone>,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(
+    Debug, Clone, Copy, Default, Eq, PartialEq, EnumIter, DeriveActiveEnum, Serialize, Deserialize,
+)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum EmailOutboxStatus {
+    /// Not yet delivered. Attempted again once `next_attempt_at` passes.
+    #[default]
+    #[sea_orm(string_value = "Pending")]
+    Pending,
+    #[sea_orm(string_value = "Sent")]
+    Sent,
+    /// Failed permanently, or ran out of attempts. Never retried automatically.
+    #[sea_orm(string_value = "DeadLettered")]
+    DeadLettered,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
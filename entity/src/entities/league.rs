@@ -1,5 +1,6 @@
 //! `SeaORM` Entity. Generated by sea-orm-codegen 0.9.2
 
+use async_graphql::Enum;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -9,10 +10,65 @@ pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i64,
     pub name: String,
+    pub trade_rejection_policy: TradeRejectionPolicy,
+    pub roster_legalization_policy: RosterLegalizationPolicy,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
 }
 
+/// What one team's rejection does to a trade between three or more teams. A two-team trade always
+/// ends on a rejection, since there is nobody left to trade with.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    Enum,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum TradeRejectionPolicy {
+    /// Any rejection closes the trade for every team (default).
+    #[default]
+    #[sea_orm(string_value = "EndsTrade")]
+    EndsTrade,
+    /// A rejection leaves the trade open: the other teams may still respond, and the proposer may
+    /// re-propose it without the rejecting team as the next trade in the chain.
+    #[sea_orm(string_value = "AllowsRepropose")]
+    AllowsRepropose,
+}
+
+/// What a weekly roster lock does with a team whose roster is still illegal.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    Enum,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum RosterLegalizationPolicy {
+    /// The team's moves stay pending for the commissioner to rule on (default).
+    #[default]
+    #[sea_orm(string_value = "LeavePending")]
+    LeavePending,
+    /// The lock makes the fewest drops and IR activations that legalize the roster, then tells the
+    /// team what it did.
+    #[sea_orm(string_value = "AutoLegalize")]
+    AutoLegalize,
+}
+
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
     #[sea_orm(has_many = "super::contract::Entity")]
@@ -0,0 +1,142 @@
+//! A single-use invitation to own or co-own a team in a league.
+//!
+//! Created by the commissioner with a token from `fbkl_auth::generate_token`. Redeeming it before
+//! `expires_at` attaches the redeeming user to `team_id` as a `TeamOwner` `team_user`; the row is
+//! then `Accepted` and points at that `team_user`, so it cannot be used again.
+
+use async_graphql::Enum;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "league_invitation")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub league_id: i64,
+    /// The team the invited user will own.
+    pub team_id: i64,
+    pub kind: LeagueInvitationKind,
+    #[serde(skip_serializing)]
+    pub token: Vec<u8>,
+    /// When set, only the user with this email can accept.
+    pub email: Option<String>,
+    pub status: LeagueInvitationStatus,
+    /// The season the accepting user joins the league in, copied onto their `team_user`.
+    pub first_end_of_season_year: i16,
+    pub expires_at: DateTimeWithTimeZone,
+    /// The commissioner who sent the invitation.
+    pub invited_by_team_user_id: i64,
+    /// The `team_user` created on acceptance.
+    pub accepted_team_user_id: Option<i64>,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+impl Model {
+    /// Whether the invitation can still be accepted at `now`.
+    pub fn is_open(&self, now: DateTimeWithTimeZone) -> bool {
+        self.status == LeagueInvitationStatus::Pending && now < self.expires_at
+    }
+}
+
+/// Which seat on the team the invitation fills.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    Enum,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum LeagueInvitationKind {
+    /// The team's only owner. The team must have no active owner when the invitation is accepted.
+    #[default]
+    #[sea_orm(string_value = "Owner")]
+    Owner,
+    /// A second owner alongside the team's current one.
+    #[sea_orm(string_value = "CoOwner")]
+    CoOwner,
+}
+
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    Enum,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum LeagueInvitationStatus {
+    /// Not yet accepted or revoked. Still unusable once `expires_at` has passed.
+    #[default]
+    #[sea_orm(string_value = "Pending")]
+    Pending,
+    #[sea_orm(string_value = "Accepted")]
+    Accepted,
+    /// Withdrawn by the commissioner before it was accepted.
+    #[sea_orm(string_value = "Revoked")]
+    Revoked,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::league::Entity",
+        from = "Column::LeagueId",
+        to = "super::league::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    League,
+    #[sea_orm(
+        belongs_to = "super::team::Entity",
+        from = "Column::TeamId",
+        to = "super::team::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Team,
+    #[sea_orm(
+        belongs_to = "super::team_user::Entity",
+        from = "Column::InvitedByTeamUserId",
+        to = "super::team_user::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    InvitedByTeamUser,
+    #[sea_orm(
+        belongs_to = "super::team_user::Entity",
+        from = "Column::AcceptedTeamUserId",
+        to = "super::team_user::Column::Id",
+        on_update = "Cascade",
+        on_delete = "SetNull"
+    )]
+    AcceptedTeamUser,
+}
+
+impl Related<super::league::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::League.def()
+    }
+}
+
+impl Related<super::team::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Team.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,5 +1,6 @@
 //! `SeaORM` Entity. Generated by sea-orm-codegen 0.9.2
 
+pub mod api_token;
 pub mod auction;
 pub mod auction_bid;
 pub mod auction_schedule;
@@ -8,19 +9,28 @@ pub mod deadline;
 pub mod draft_pick;
 pub mod draft_pick_draft_pick_option;
 pub mod draft_pick_option;
+pub mod email_outbox;
 pub mod job_run;
 pub mod league;
+pub mod league_invitation;
 pub mod league_player;
 pub mod league_team_season_standing;
 pub mod min_bid_tier_config;
+pub mod notification;
+pub mod notification_preference;
 pub mod player;
+pub mod player_name_alias;
 pub mod position;
 pub mod real_team;
+pub mod replacement_draft;
+pub mod replacement_draft_selection;
 pub mod rfa_compensation_pick;
 pub mod rfa_resolution;
 pub mod rookie_draft_lottery;
 pub mod rookie_draft_lottery_pick;
 pub mod rookie_draft_selection;
+pub mod roster_legalization_move;
+pub mod security_event;
 pub mod sessions;
 pub mod team;
 pub mod team_trade;
@@ -29,7 +39,10 @@ pub mod team_user;
 pub mod trade;
 pub mod trade_action;
 pub mod trade_asset;
+pub mod trade_block;
 pub mod transaction;
+pub mod ufa_exception;
 pub mod user;
 pub mod user_registration;
+pub mod user_verification;
 pub mod veteran_auction_ranking;
@@ -0,0 +1,130 @@
+//! One thing a user should know about, e.g. that they were outbid or a trade was proposed to them.
+//!
+//! Written by the `fbkl_logic` function that caused it, in that function's transaction, with one
+//! row per recipient user. Which channels a row goes out on is fixed from the recipient's
+//! `notification_preference` when it is written.
+
+use async_graphql::Enum;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "notification")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub user_id: i64,
+    pub league_id: i64,
+    pub kind: NotificationKind,
+    /// The row the notification is about; which table depends on `kind`.
+    pub subject_id: Option<i64>,
+    #[sea_orm(column_type = "Text")]
+    pub message: String,
+    /// Whether the notification center lists this row.
+    pub in_app: bool,
+    pub email_status: NotificationEmailStatus,
+    pub read_at: Option<DateTimeWithTimeZone>,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+/// What happened. `subject_id` points at the row named here.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    Hash,
+    Enum,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum NotificationKind {
+    /// Someone else's bid topped the recipient team's winning bid. Subject: `auction`.
+    #[sea_orm(string_value = "Outbid")]
+    Outbid,
+    /// The recipient team's rookie draft selection is on the clock. Subject: `rookie_draft_selection`.
+    #[sea_orm(string_value = "DraftPickOnTheClock")]
+    DraftPickOnTheClock,
+    /// A trade naming the recipient team was proposed. Subject: `trade`.
+    #[sea_orm(string_value = "TradeProposed")]
+    TradeProposed,
+    /// An RFA auction closed and the winner's raise window opened. Sent to the winner and the
+    /// player's original owner. Subject: `rfa_resolution`.
+    #[sea_orm(string_value = "RfaRaiseWindowOpened")]
+    RfaRaiseWindowOpened,
+    /// The original owner's window to match an RFA's winning bid opened. Subject: `rfa_resolution`.
+    #[sea_orm(string_value = "RfaMatchWindowOpened")]
+    RfaMatchWindowOpened,
+    /// The recipient team's roster was illegal at a lock, so its moves are held for the
+    /// commissioner's ruling. Subject: `deadline`.
+    #[sea_orm(string_value = "RosterLockFailed")]
+    RosterLockFailed,
+    /// The commissioner approved or rejected the moves a lock held for the recipient team.
+    /// Subject: `deadline` (the lock that held them).
+    #[sea_orm(string_value = "HeldRosterMovesRuled")]
+    HeldRosterMovesRuled,
+    /// The recipient team's roster was illegal at a weekly lock, so the league's auto-legalization
+    /// dropped or moved contracts for it. Subject: `deadline`.
+    #[sea_orm(string_value = "RosterAutoLegalized")]
+    RosterAutoLegalized,
+}
+
+/// Where the row stands on the email side.
+#[derive(
+    Debug, Clone, Copy, Default, Eq, PartialEq, EnumIter, DeriveActiveEnum, Serialize, Deserialize,
+)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum NotificationEmailStatus {
+    /// The recipient doesn't get this kind by email.
+    #[default]
+    #[sea_orm(string_value = "NotRequested")]
+    NotRequested,
+    /// Queued in the `email_outbox` when the notification was written.
+    #[sea_orm(string_value = "Queued")]
+    Queued,
+    /// Waiting for the recipient's next digest email.
+    #[sea_orm(string_value = "DigestPending")]
+    DigestPending,
+    /// Included in a digest email.
+    #[sea_orm(string_value = "Digested")]
+    Digested,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    User,
+    #[sea_orm(
+        belongs_to = "super::league::Entity",
+        from = "Column::LeagueId",
+        to = "super::league::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    League,
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl Related<super::league::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::League.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
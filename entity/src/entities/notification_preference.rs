@@ -0,0 +1,85 @@
+//! How one user wants to hear about one kind of notification.
+//!
+//! Sparse: a user with no row for a kind gets [`Model::default_for`]'s channels.
+
+use async_graphql::Enum;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::notification::NotificationKind;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "notification_preference")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub user_id: i64,
+    pub kind: NotificationKind,
+    /// Whether the notification center lists this kind.
+    pub in_app: bool,
+    pub email_delivery: NotificationEmailDelivery,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+impl Model {
+    /// The preference of a user who never chose one for `kind`: in-app, and emailed right away.
+    pub fn default_for(user_id: i64, kind: NotificationKind) -> Self {
+        Self {
+            id: 0,
+            user_id,
+            kind,
+            in_app: true,
+            email_delivery: NotificationEmailDelivery::Immediate,
+            created_at: DateTimeWithTimeZone::default(),
+            updated_at: DateTimeWithTimeZone::default(),
+        }
+    }
+}
+
+/// Whether, and how soon, a kind of notification is emailed.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    Enum,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum NotificationEmailDelivery {
+    /// One email per notification, as it happens.
+    #[default]
+    #[sea_orm(string_value = "Immediate")]
+    Immediate,
+    /// Collected into at most one email a day.
+    #[sea_orm(string_value = "Digest")]
+    Digest,
+    #[sea_orm(string_value = "Off")]
+    Off,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    User,
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
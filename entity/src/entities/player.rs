@@ -37,6 +37,12 @@ pub struct Model {
     pub nba_roster_source: NbaRosterSource,
     /// When the NBA facts above were last evaluated.
     pub nba_roster_asof: Option<DateTimeWithTimeZone>,
+    /// Whether the player is on the NBA injured reserve, which is what makes him IR-eligible here
+    /// (rules §10.1).
+    pub nba_injury_status: NbaInjuryStatus,
+    pub nba_injury_source: NbaRosterSource,
+    /// When the injury status was last reported.
+    pub nba_injury_asof: Option<DateTimeWithTimeZone>,
     /// Commissioner override of the derived classification (rules §3.1.2, §11.3.6). Wins when set.
     pub eligibility_override: Option<EligibilityClassification>,
     pub eligibility_override_reason: Option<String>,
@@ -139,12 +145,38 @@ pub enum NbaRosterSource {
     Unknown,
 }
 
+/// The player's standing on the NBA's injury report.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    Enum,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum NbaInjuryStatus {
+    #[default]
+    #[sea_orm(string_value = "Healthy")]
+    Healthy,
+    /// On the NBA injured reserve; the only status that may occupy an IR slot (rules §10.1).
+    #[sea_orm(string_value = "InjuredReserve")]
+    InjuredReserve,
+}
+
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
     #[sea_orm(has_many = "super::contract::Entity")]
     Contract,
     #[sea_orm(has_many = "super::league_player::Entity")]
     LeaguePlayer,
+    #[sea_orm(has_many = "super::player_name_alias::Entity")]
+    PlayerNameAlias,
     #[sea_orm(
         belongs_to = "super::position::Entity",
         from = "Column::PositionId",
@@ -169,6 +201,12 @@ impl Related<super::contract::Entity> for Entity {
     }
 }
 
+impl Related<super::player_name_alias::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PlayerNameAlias.def()
+    }
+}
+
 impl Related<super::league_player::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::LeaguePlayer.def()
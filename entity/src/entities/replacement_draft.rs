@@ -0,0 +1,82 @@
+//! A replacement owners draft (rules §2.4.4): after a season with more than one abandoned team, the
+//! commissioner may have the new owners draft from the combined contracts and picks of those teams.
+//! Drafted players keep their salary and contract years.
+
+use color_eyre::Result;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "replacement_draft")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub league_id: i64,
+    pub end_of_season_year: i16,
+    /// The deadline current when the draft started; its selections' transactions are tied to it.
+    pub deadline_id: i64,
+    /// The assets up for selection. Converted to/from `ReplacementDraftPool`.
+    pub pool: serde_json::Value,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+impl Model {
+    pub fn get_pool(&self) -> Result<ReplacementDraftPool> {
+        let pool: ReplacementDraftPool = serde_json::from_value(self.pool.clone())?;
+        Ok(pool)
+    }
+}
+
+/// What the abandoned teams held when the draft started.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ReplacementDraftPool {
+    /// The teams drafting, which are also the teams the pool came from.
+    pub team_ids: Vec<i64>,
+    /// Pooled contracts, by `original_contract_id`, so a contract is still found once a selection
+    /// has re-chained it.
+    pub original_contract_ids: Vec<i64>,
+    pub draft_pick_ids: Vec<i64>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::league::Entity",
+        from = "Column::LeagueId",
+        to = "super::league::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    League,
+    #[sea_orm(
+        belongs_to = "super::deadline::Entity",
+        from = "Column::DeadlineId",
+        to = "super::deadline::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Deadline,
+    #[sea_orm(has_many = "super::replacement_draft_selection::Entity")]
+    ReplacementDraftSelection,
+}
+
+impl Related<super::league::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::League.def()
+    }
+}
+
+impl Related<super::deadline::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Deadline.def()
+    }
+}
+
+impl Related<super::replacement_draft_selection::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ReplacementDraftSelection.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,129 @@
+//! One turn of a replacement owners draft's slate (rules §2.4.4). Resolved like a rookie draft
+//! selection: the lowest-`order` `Unused` row is on the clock, and a pass leaves the row `Skipped`.
+
+use async_graphql::Enum;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "replacement_draft_selection")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub replacement_draft_id: i64,
+    pub order: i16,
+    /// The team making this selection.
+    pub team_id: i64,
+    pub status: ReplacementDraftSelectionStatus,
+    /// The contract the selection produced, re-chained onto `team_id`.
+    pub contract_id: Option<i64>,
+    /// The draft pick selected.
+    pub draft_pick_id: Option<i64>,
+    /// The replacement-draft-selection transaction, set when the turn is resolved (1:1).
+    pub transaction_id: Option<i64>,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    Enum,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum ReplacementDraftSelectionStatus {
+    /// The turn has not been used or passed yet.
+    #[default]
+    #[sea_orm(string_value = "Unused")]
+    Unused,
+    /// The team took a contract or draft pick from the pool.
+    #[sea_orm(string_value = "Selected")]
+    Selected,
+    /// The team passed.
+    #[sea_orm(string_value = "Skipped")]
+    Skipped,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::replacement_draft::Entity",
+        from = "Column::ReplacementDraftId",
+        to = "super::replacement_draft::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    ReplacementDraft,
+    #[sea_orm(
+        belongs_to = "super::team::Entity",
+        from = "Column::TeamId",
+        to = "super::team::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Team,
+    #[sea_orm(
+        belongs_to = "super::contract::Entity",
+        from = "Column::ContractId",
+        to = "super::contract::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Contract,
+    #[sea_orm(
+        belongs_to = "super::draft_pick::Entity",
+        from = "Column::DraftPickId",
+        to = "super::draft_pick::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    DraftPick,
+    #[sea_orm(
+        belongs_to = "super::transaction::Entity",
+        from = "Column::TransactionId",
+        to = "super::transaction::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Transaction,
+}
+
+impl Related<super::replacement_draft::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ReplacementDraft.def()
+    }
+}
+
+impl Related<super::team::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Team.def()
+    }
+}
+
+impl Related<super::contract::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Contract.def()
+    }
+}
+
+impl Related<super::draft_pick::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::DraftPick.def()
+    }
+}
+
+impl Related<super::transaction::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Transaction.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
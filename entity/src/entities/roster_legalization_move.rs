@@ -0,0 +1,105 @@
+//! One move a weekly roster lock made on an illegal team's behalf, for leagues whose
+//! [`RosterLegalizationPolicy`](super::league::RosterLegalizationPolicy) is `AutoLegalize`.
+//!
+//! The move itself went through the normal drop or IR path, so its transaction and team update are
+//! recorded as usual; this row is how the team finds out it happened. `contract_id` points at the
+//! contract the move produced. `acknowledged_at` stays empty until the team has seen it.
+
+use async_graphql::Enum;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "roster_legalization_move")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub league_id: i64,
+    pub team_id: i64,
+    /// The roster lock that made the move.
+    pub deadline_id: i64,
+    pub contract_id: i64,
+    pub kind: RosterLegalizationMoveKind,
+    pub acknowledged_at: Option<DateTimeWithTimeZone>,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+/// What the lock did to the contract.
+#[derive(
+    Debug, Clone, Copy, Eq, PartialEq, Enum, EnumIter, DeriveActiveEnum, Serialize, Deserialize,
+)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum RosterLegalizationMoveKind {
+    /// Dropped from the team, with the usual cap penalty for rookie and veteran contracts.
+    #[sea_orm(string_value = "Drop")]
+    Drop,
+    /// Brought back from IR onto the active roster.
+    #[sea_orm(string_value = "ActivateFromIr")]
+    ActivateFromIr,
+    /// An RDI whose player reached an NBA roster, moved to RD/1 as rules §11.3.1 requires.
+    #[sea_orm(string_value = "ConvertRdiToRd")]
+    ConvertRdiToRd,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::league::Entity",
+        from = "Column::LeagueId",
+        to = "super::league::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    League,
+    #[sea_orm(
+        belongs_to = "super::team::Entity",
+        from = "Column::TeamId",
+        to = "super::team::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Team,
+    #[sea_orm(
+        belongs_to = "super::deadline::Entity",
+        from = "Column::DeadlineId",
+        to = "super::deadline::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Deadline,
+    #[sea_orm(
+        belongs_to = "super::contract::Entity",
+        from = "Column::ContractId",
+        to = "super::contract::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Contract,
+}
+
+impl Related<super::league::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::League.def()
+    }
+}
+
+impl Related<super::team::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Team.def()
+    }
+}
+
+impl Related<super::deadline::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Deadline.def()
+    }
+}
+
+impl Related<super::contract::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Contract.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
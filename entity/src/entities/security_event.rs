@@ -0,0 +1,82 @@
+//! One security-relevant thing that happened to an account, or to an email someone tried to log in
+//! as. Append-only.
+//!
+//! Login attempts double as the login throttle's counters (see `fbkl_logic::login_security`), so
+//! they are keyed by the attempted email even when no account has it.
+
+use async_graphql::Enum;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "security_event")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub kind: SecurityEventKind,
+    /// NULL when no account has the attempted email.
+    pub user_id: Option<i64>,
+    /// The email as attempted, trimmed and lowercased. Set for login events.
+    pub email: Option<String>,
+    pub ip_address: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub detail: Option<String>,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(
+    Debug, Clone, Copy, Eq, PartialEq, Enum, EnumIter, DeriveActiveEnum, Serialize, Deserialize,
+)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum SecurityEventKind {
+    #[sea_orm(string_value = "LoginSucceeded")]
+    LoginSucceeded,
+    /// A wrong password, or an email no account has.
+    #[sea_orm(string_value = "LoginFailed")]
+    LoginFailed,
+    /// Refused before the password was checked, by a rate limit or a lockout.
+    #[sea_orm(string_value = "LoginThrottled")]
+    LoginThrottled,
+    /// Enough consecutive failures that the email is locked out for a while.
+    #[sea_orm(string_value = "AccountLocked")]
+    AccountLocked,
+    /// The password was set through a reset link.
+    #[sea_orm(string_value = "PasswordReset")]
+    PasswordReset,
+    /// The password was changed while logged in.
+    #[sea_orm(string_value = "PasswordChanged")]
+    PasswordChanged,
+    #[sea_orm(string_value = "EmailChanged")]
+    EmailChanged,
+    /// A personal API token was created. `detail` is the token's name.
+    #[sea_orm(string_value = "ApiTokenCreated")]
+    ApiTokenCreated,
+    #[sea_orm(string_value = "ApiTokenRevoked")]
+    ApiTokenRevoked,
+}
+
+impl SecurityEventKind {
+    /// The kinds that count as a login attempt for throttling.
+    pub const LOGIN_ATTEMPTS: [Self; 2] = [Self::LoginSucceeded, Self::LoginFailed];
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id",
+        on_update = "Cascade",
+        on_delete = "SetNull"
+    )]
+    User,
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
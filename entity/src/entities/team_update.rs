@@ -20,8 +20,13 @@ pub struct Model {
     pub effective_date: Date,
     pub status: TeamUpdateStatus,
     pub team_id: i64,
-    /// This is always present unless the update was a configuration change.
+    /// Present for every roster change and for ownership changes (a `TeamUpdateConfigChange`
+    /// transaction); other configuration changes carry none.
     pub transaction_id: Option<i64>,
+    /// Owner-assigned position among the week's pending moves; `None` until the owner reorders
+    /// them. The lock commits the moves in this order, though legality is judged on the final
+    /// roster, not the order.
+    pub sequence: Option<i16>,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
 }
@@ -167,6 +172,10 @@ pub enum ContractUpdateType {
     LostViaFreeAgency,
     /// The original owner matched an RFA bid and re-signed the player (rules §15.3.2).
     RfaResign,
+    /// A contract is added to a team via a replacement owners draft (rules §2.4.4).
+    AddViaReplacementDraft,
+    /// A contract is taken from the team in a replacement owners draft.
+    LostViaReplacementDraft,
 }
 
 /// Stores data for an update to a team's draft pick.
@@ -190,11 +199,37 @@ pub enum DraftPickUpdateType {
     ForfeitedAsRfaCompensation,
     /// A draft pick is received as RFA compensation (rules §15.2).
     AddViaRfaCompensation,
+    /// A draft pick is added to the team via a replacement owners draft (rules §2.4.4).
+    AddViaReplacementDraft,
+    /// A draft pick is taken from the team in a replacement owners draft.
+    LostViaReplacementDraft,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct TeamSettingsChange {
     pub users: Vec<TeamUpdateSettingUser>,
+    /// Set when the change handed the team to a new owner (rules §2.4.1–§2.4.2).
+    #[serde(default)]
+    pub ownership_change: Option<TeamOwnershipChange>,
+}
+
+/// A team handed from one owner to another. The team's contracts and picks go with it unchanged.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TeamOwnershipChange {
+    pub kind: OwnershipChangeKind,
+    pub previous_user_id: i64,
+    pub new_user_id: i64,
+    /// Required for forced removals.
+    pub reason: Option<String>,
+}
+
+/// Why a team changed hands.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Enum, Serialize, Deserialize)]
+pub enum OwnershipChangeKind {
+    /// The owner left the league and was replaced (rules §2.4.1).
+    VoluntaryReplacement,
+    /// The commissioner removed the owner for collusion, inactivity or disruption (rules §2.4.2).
+    ForcedRemoval,
 }
 
 /// Like `team_user::Model`, but without the `created_at/updated_at`.
@@ -270,7 +305,6 @@ impl ActiveModelBehavior for ActiveModel {
         C: ConnectionTrait,
     {
         roster_change_requires_transaction(&self)?;
-        setting_change_requires_no_transaction(&self)?;
 
         Ok(self)
     }
@@ -290,20 +324,6 @@ fn roster_change_requires_transaction(model: &ActiveModel) -> Result<(), DbErr>
     }
 }
 
-fn setting_change_requires_no_transaction(model: &ActiveModel) -> Result<(), DbErr> {
-    let decoded_data = TeamUpdateData::from_json(model.data.as_ref().clone())
-        .map_err(|err| DbErr::Custom(err.to_string()))?;
-    let is_settings_update = matches!(decoded_data, TeamUpdateData::Settings(_));
-
-    if is_settings_update && model.transaction_id.is_set() {
-        Err(DbErr::Custom(
-            "a team update (setting change) requires transaction id to be unset.".to_string(),
-        ))
-    } else {
-        Ok(())
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -336,4 +356,29 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn settings_change_without_ownership_change_decodes() -> Result<()> {
+        let stored = serde_json::json!({
+            "Settings": {
+                "users": [{
+                    "id": 1,
+                    "league_role": "TeamOwner",
+                    "nickname": "Owner",
+                    "first_end_of_season_year": 2024,
+                    "final_end_of_season_year": null,
+                    "user_id": 2,
+                }],
+            },
+        });
+
+        let TeamUpdateData::Settings(settings_change) = TeamUpdateData::from_json(stored)? else {
+            panic!("expected a settings change");
+        };
+
+        assert_eq!(settings_change.users.len(), 1);
+        assert_eq!(settings_change.ownership_change, None);
+
+        Ok(())
+    }
 }
@@ -0,0 +1,163 @@
+//! One listing on a team's trade block: a contract or pick the team is shopping, or something it
+//! wants back.
+//!
+//! `kind` says which of `contract_id`, `draft_pick_id` or `need` is filled in, and `before_save`
+//! keeps the other two empty. A listed contract points at the row that was active when it was
+//! listed; contracts are replaced rather than updated, so readers follow the chain to the latest row
+//! and drop the listing once the team no longer holds it.
+
+use async_graphql::Enum;
+use async_trait::async_trait;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "trade_block")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub league_id: i64,
+    pub team_id: i64,
+    pub end_of_season_year: i16,
+    pub kind: TradeBlockKind,
+    pub contract_id: Option<i64>,
+    pub draft_pick_id: Option<i64>,
+    pub need: Option<TradeBlockNeed>,
+    /// Free text from the owner, e.g. "looking for a 2nd rounder".
+    pub note: Option<String>,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+/// What a trade block listing is for.
+#[derive(
+    Debug, Clone, Copy, Eq, PartialEq, Enum, EnumIter, DeriveActiveEnum, Serialize, Deserialize,
+)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum TradeBlockKind {
+    /// A contract the team will trade away.
+    #[sea_orm(string_value = "Contract")]
+    Contract,
+    /// A draft pick the team will trade away.
+    #[sea_orm(string_value = "DraftPick")]
+    DraftPick,
+    /// Something the team wants back; see [`TradeBlockNeed`].
+    #[sea_orm(string_value = "Need")]
+    Need,
+}
+
+/// What a team is looking for in return.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    Hash,
+    Enum,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum TradeBlockNeed {
+    /// To take back less salary than it sends.
+    #[sea_orm(string_value = "CapSpace")]
+    CapSpace,
+    /// To free a rookie development slot (rules §11).
+    #[sea_orm(string_value = "RookieDevelopmentSlot")]
+    RookieDevelopmentSlot,
+    /// To take back fewer contracts than it sends.
+    #[sea_orm(string_value = "RosterSpot")]
+    RosterSpot,
+    /// To take back draft picks.
+    #[sea_orm(string_value = "DraftPicks")]
+    DraftPicks,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::league::Entity",
+        from = "Column::LeagueId",
+        to = "super::league::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    League,
+    #[sea_orm(
+        belongs_to = "super::team::Entity",
+        from = "Column::TeamId",
+        to = "super::team::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Team,
+    #[sea_orm(
+        belongs_to = "super::contract::Entity",
+        from = "Column::ContractId",
+        to = "super::contract::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Contract,
+    #[sea_orm(
+        belongs_to = "super::draft_pick::Entity",
+        from = "Column::DraftPickId",
+        to = "super::draft_pick::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    DraftPick,
+}
+
+impl Related<super::league::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::League.def()
+    }
+}
+
+impl Related<super::team::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Team.def()
+    }
+}
+
+impl Related<super::contract::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Contract.def()
+    }
+}
+
+impl Related<super::draft_pick::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::DraftPick.def()
+    }
+}
+
+#[async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(self, _db: &C, _insert: bool) -> Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        let has_contract = self.contract_id.as_ref().is_some();
+        let has_draft_pick = self.draft_pick_id.as_ref().is_some();
+        let has_need = self.need.as_ref().is_some();
+        let matches_kind = match self.kind.as_ref() {
+            TradeBlockKind::Contract => has_contract && !has_draft_pick && !has_need,
+            TradeBlockKind::DraftPick => has_draft_pick && !has_contract && !has_need,
+            TradeBlockKind::Need => has_need && !has_contract && !has_draft_pick,
+        };
+        if !matches_kind {
+            return Err(DbErr::Custom(format!(
+                "a trade block listing of kind={:?} must set exactly the matching column. Team: {}.",
+                self.kind.as_ref(),
+                self.team_id.as_ref()
+            )));
+        }
+
+        Ok(self)
+    }
+}
@@ -29,8 +29,12 @@ pub struct Model {
     pub league_id: i64,
     /// Represents the deadline to which this transaction is tied. The reason this is necessary is because transactions may "happen" immediately, but they aren't executed until the date & time of their associated deadline.
     pub deadline_id: i64,
-    /// The contract acted on by a contract-kind transaction (drop / IR / RDI / rookie activation). Set only for those kinds; the specific role is recoverable from `kind`. Trade, auction, and rookie-draft-selection transactions carry no FK here — the child row owns a `transaction_id` FK back to this row.
+    /// The contract acted on by a contract-kind transaction (drop / IR / RDI / rookie activation / league player merge). Set only for those kinds; the specific role is recoverable from `kind`. Trade, auction, and rookie-draft-selection transactions carry no FK here — the child row owns a `transaction_id` FK back to this row.
     pub contract_id: Option<i64>,
+    /// Set when the commissioner waived the cap penalty on a `TeamUpdateDropContract` (rules §9.1.4); the penalty fold skips waived drops.
+    pub drop_penalty_waived_at: Option<DateTimeWithTimeZone>,
+    pub drop_penalty_waived_by_team_user_id: Option<i64>,
+    pub drop_penalty_waiver_reason: Option<String>,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
 }
@@ -74,13 +78,56 @@ impl Model {
         }
     }
 
+    /// Creates a new team configuration change (ownership change) transaction, tied to the league's
+    /// current deadline. Also note that this only creates the model, and does not persist the
+    /// transaction to the database.
+    pub fn new_team_config_change_transaction(deadline_model: &deadline::Model) -> ActiveModel {
+        ActiveModel {
+            end_of_season_year: ActiveValue::Set(deadline_model.end_of_season_year),
+            kind: ActiveValue::Set(TransactionKind::TeamUpdateConfigChange),
+            league_id: ActiveValue::Set(deadline_model.league_id),
+            deadline_id: ActiveValue::Set(deadline_model.id),
+            ..Default::default()
+        }
+    }
+
     pub fn new_rookie_draft_selection_transaction(deadline_model: &deadline::Model) -> ActiveModel {
         new_rookie_draft_selection_transaction(deadline_model)
     }
 
+    /// Creates a new replacement owners draft selection transaction. Also note that this only
+    /// creates the model, and does not persist the transaction to the database.
+    pub fn new_replacement_draft_selection_transaction(
+        deadline_model: &deadline::Model,
+    ) -> ActiveModel {
+        ActiveModel {
+            end_of_season_year: ActiveValue::Set(deadline_model.end_of_season_year),
+            kind: ActiveValue::Set(TransactionKind::ReplacementDraftSelection),
+            league_id: ActiveValue::Set(deadline_model.league_id),
+            deadline_id: ActiveValue::Set(deadline_model.id),
+            ..Default::default()
+        }
+    }
+
     pub fn new_trade_transaction(deadline_model: &deadline::Model) -> ActiveModel {
         new_trade_transaction(deadline_model)
     }
+
+    /// Creates a new league player merge transaction for the relinked contract. Also note that this
+    /// only creates the model, and does not persist the transaction to the database.
+    pub fn new_league_player_merge_transaction(
+        deadline_model: &deadline::Model,
+        relinked_contract_id: i64,
+    ) -> ActiveModel {
+        ActiveModel {
+            end_of_season_year: ActiveValue::Set(deadline_model.end_of_season_year),
+            kind: ActiveValue::Set(TransactionKind::LeaguePlayerMerge),
+            league_id: ActiveValue::Set(deadline_model.league_id),
+            deadline_id: ActiveValue::Set(deadline_model.id),
+            contract_id: ActiveValue::Set(Some(relinked_contract_id)),
+            ..Default::default()
+        }
+    }
 }
 
 /// Represents the different types of transactions that occur in a league.
@@ -134,6 +181,13 @@ pub enum TransactionKind {
     /// The original owner declined to match, so the winner signs and forfeits a pick (rules §15.2).
     #[sea_orm(string_value = "RfaDeclineAndForfeit")]
     RfaDeclineAndForfeit,
+    /// A turn of a replacement owners draft was used or passed (rules §2.4.4).
+    #[sea_orm(string_value = "ReplacementDraftSelection")]
+    ReplacementDraftSelection,
+    /// The commissioner merged a league player into the real player he became; the contract chain
+    /// now names the real player.
+    #[sea_orm(string_value = "LeaguePlayerMerge")]
+    LeaguePlayerMerge,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -0,0 +1,72 @@
+//! Who holds a designated unrestricted free agent's re-sign discount (rules §16.4).
+//!
+//! Written at keeper-deadline processing for every `UFA-OriginalTeam` and `UFA-FreeAgent`
+//! contract, while the contract's `team_id` still names the team that owned the player. A trade
+//! before the auction closes moves the player but not the discount, so every signing path reads the
+//! holder from here — the UFA counterpart of `rfa_resolution.original_owner_team_id`.
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "ufa_exception")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub league_id: i64,
+    pub end_of_season_year: i16,
+    /// The designated UFA contract, as it stood at the keeper deadline.
+    pub ufa_contract_id: i64,
+    /// The team that held the player at the keeper deadline (rules §16.4.1).
+    pub exception_holder_team_id: i64,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::league::Entity",
+        from = "Column::LeagueId",
+        to = "super::league::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    League,
+    #[sea_orm(
+        belongs_to = "super::contract::Entity",
+        from = "Column::UfaContractId",
+        to = "super::contract::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Contract,
+    #[sea_orm(
+        belongs_to = "super::team::Entity",
+        from = "Column::ExceptionHolderTeamId",
+        to = "super::team::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Team,
+}
+
+impl Related<super::league::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::League.def()
+    }
+}
+
+impl Related<super::contract::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Contract.def()
+    }
+}
+
+impl Related<super::team::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Team.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -13,6 +13,9 @@ pub struct Model {
     pub email: String,
     pub hashed_password: String,
     pub app_admin_status: UserAppAdminStatus,
+    /// Copied into each session at login. Bumped when the password changes, which ends every
+    /// session still carrying the old value.
+    pub session_generation: i32,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
 }
@@ -35,6 +38,8 @@ pub enum Relation {
     UserRegistration,
     #[sea_orm(has_many = "super::team_user::Entity")]
     TeamUser,
+    #[sea_orm(has_many = "super::user_verification::Entity")]
+    UserVerification,
 }
 
 impl Related<super::user_registration::Entity> for Entity {
@@ -49,6 +54,12 @@ impl Related<super::team_user::Entity> for Entity {
     }
 }
 
+impl Related<super::user_verification::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::UserVerification.def()
+    }
+}
+
 impl Related<super::team::Entity> for Entity {
     // The final relation is User -> TeamUser -> Team
     fn to() -> RelationDef {
@@ -0,0 +1,80 @@
+//! A single-use, expiring token that proves control of an account or an email address.
+//!
+//! Only `fbkl_auth::hash_token` of the token is stored; the token itself is handed to the user
+//! once. Issuing a new token of the same kind supersedes any still-pending one, so only the latest
+//! link works.
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "user_verification")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub user_id: i64,
+    pub kind: UserVerificationKind,
+    #[serde(skip_serializing)]
+    pub token_hash: Vec<u8>,
+    /// The address being verified. Only set for `EmailChange`.
+    pub new_email: Option<String>,
+    pub status: UserVerificationStatus,
+    pub expires_at: DateTimeWithTimeZone,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+impl Model {
+    /// Whether the token can still be redeemed at `now`.
+    pub fn is_open(&self, now: DateTimeWithTimeZone) -> bool {
+        self.status == UserVerificationStatus::Pending && now < self.expires_at
+    }
+}
+
+/// What redeeming the token does.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum UserVerificationKind {
+    /// Lets the holder set a new password without knowing the current one.
+    #[sea_orm(string_value = "PasswordReset")]
+    PasswordReset,
+    /// Moves the account to `new_email`, proving the user can receive mail there.
+    #[sea_orm(string_value = "EmailChange")]
+    EmailChange,
+}
+
+#[derive(
+    Debug, Clone, Copy, Default, Eq, PartialEq, EnumIter, DeriveActiveEnum, Serialize, Deserialize,
+)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum UserVerificationStatus {
+    #[default]
+    #[sea_orm(string_value = "Pending")]
+    Pending,
+    /// The token was redeemed.
+    #[sea_orm(string_value = "Used")]
+    Used,
+    /// A newer token of the same kind was issued before this one was redeemed.
+    #[sea_orm(string_value = "Superseded")]
+    Superseded,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    User,
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
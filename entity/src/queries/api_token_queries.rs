@@ -0,0 +1,126 @@
+use color_eyre::Result;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter,
+    QueryOrder, prelude::DateTimeWithTimeZone, sea_query::Expr,
+};
+use tracing::instrument;
+
+use crate::api_token::{self, ApiTokenScope};
+
+/// An API token to store.
+#[derive(Clone, Debug)]
+pub struct NewApiToken {
+    pub user_id: i64,
+    pub league_id: i64,
+    pub name: String,
+    pub token_hash: Vec<u8>,
+    pub scopes: Vec<ApiTokenScope>,
+}
+
+#[instrument(skip_all, fields(user_id = new_token.user_id, league_id = new_token.league_id))]
+pub async fn insert_api_token<C>(new_token: NewApiToken, db: &C) -> Result<api_token::Model>
+where
+    C: ConnectionTrait,
+{
+    let inserted = api_token::ActiveModel {
+        user_id: ActiveValue::Set(new_token.user_id),
+        league_id: ActiveValue::Set(new_token.league_id),
+        name: ActiveValue::Set(new_token.name),
+        token_hash: ActiveValue::Set(new_token.token_hash),
+        scopes: ActiveValue::Set(serde_json::to_value(new_token.scopes)?),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+    Ok(inserted)
+}
+
+/// The token with this hash, revoked or not.
+#[instrument(skip_all)]
+pub async fn find_api_token_by_token_hash<C>(
+    token_hash: Vec<u8>,
+    db: &C,
+) -> Result<Option<api_token::Model>>
+where
+    C: ConnectionTrait,
+{
+    Ok(api_token::Entity::find()
+        .filter(api_token::Column::TokenHash.eq(token_hash))
+        .one(db)
+        .await?)
+}
+
+#[instrument(skip(db))]
+pub async fn find_api_token_by_id<C>(id: i64, db: &C) -> Result<Option<api_token::Model>>
+where
+    C: ConnectionTrait,
+{
+    Ok(api_token::Entity::find_by_id(id).one(db).await?)
+}
+
+/// The user's tokens that have not been revoked, newest first.
+#[instrument(skip(db))]
+pub async fn find_active_api_tokens_for_user<C>(
+    user_id: i64,
+    db: &C,
+) -> Result<Vec<api_token::Model>>
+where
+    C: ConnectionTrait,
+{
+    Ok(api_token::Entity::find()
+        .filter(api_token::Column::UserId.eq(user_id))
+        .filter(api_token::Column::RevokedAt.is_null())
+        .order_by_desc(api_token::Column::CreatedAt)
+        .order_by_desc(api_token::Column::Id)
+        .all(db)
+        .await?)
+}
+
+#[instrument(skip(db))]
+pub async fn revoke_api_token<C>(
+    token_model: api_token::Model,
+    revoked_at: DateTimeWithTimeZone,
+    db: &C,
+) -> Result<api_token::Model>
+where
+    C: ConnectionTrait,
+{
+    let mut active_model: api_token::ActiveModel = token_model.into();
+    active_model.revoked_at = ActiveValue::Set(Some(revoked_at));
+    Ok(active_model.update(db).await?)
+}
+
+/// Revokes every token of the user that is not revoked yet, e.g. when their password changes.
+/// Returns how many were revoked.
+#[instrument(skip(db))]
+pub async fn revoke_api_tokens_for_user<C>(
+    user_id: i64,
+    revoked_at: DateTimeWithTimeZone,
+    db: &C,
+) -> Result<u64>
+where
+    C: ConnectionTrait,
+{
+    let update_result = api_token::Entity::update_many()
+        .col_expr(api_token::Column::RevokedAt, Expr::value(revoked_at))
+        .filter(api_token::Column::UserId.eq(user_id))
+        .filter(api_token::Column::RevokedAt.is_null())
+        .exec(db)
+        .await?;
+    Ok(update_result.rows_affected)
+}
+
+/// Stamps the token as used at `used_at`. A bare column update, so concurrent requests on one token
+/// never write back each other's stale rows.
+#[instrument(skip(db))]
+pub async fn touch_api_token<C>(id: i64, used_at: DateTimeWithTimeZone, db: &C) -> Result<()>
+where
+    C: ConnectionTrait,
+{
+    api_token::Entity::update_many()
+        .col_expr(api_token::Column::LastUsedAt, Expr::value(used_at))
+        .filter(api_token::Column::Id.eq(id))
+        .exec(db)
+        .await?;
+    Ok(())
+}
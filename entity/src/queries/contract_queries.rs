@@ -13,10 +13,11 @@ use tracing::instrument;
 
 use crate::{
     auction,
-    contract::{self, ContractKind, ContractStatus, FreeAgentException},
+    contract::{self, ContractKind, ContractStatus, FreeAgentException, FreeAgentExceptionNotHeld},
     deadline::{self, DeadlineKind},
     league_player, player,
     transaction::{self, TransactionKind},
+    ufa_exception_queries,
 };
 
 /// Moves a contract to IR and returns the new contract in the contract chain
@@ -145,6 +146,67 @@ where
     Ok(chain)
 }
 
+/// Every contract in the chains started by the given original contracts, oldest first.
+#[instrument(skip(db))]
+pub async fn find_contract_chains<C>(
+    original_contract_ids: Vec<i64>,
+    db: &C,
+) -> Result<Vec<contract::Model>>
+where
+    C: ConnectionTrait,
+{
+    if original_contract_ids.is_empty() {
+        return Ok(vec![]);
+    }
+    let contracts = contract::Entity::find()
+        .filter(contract::Column::OriginalContractId.is_in(original_contract_ids))
+        .order_by_asc(contract::Column::Id)
+        .all(db)
+        .await?;
+
+    Ok(contracts)
+}
+
+/// Every contract a player has ever had in a league, across all chains and seasons, oldest first.
+///
+/// `is_league_player` picks which id space `player_id` lives in, matching
+/// `contract::Model::new_contract_from_rookie_draft`. Whole chains are returned, so a real player's
+/// history includes the links from before a league player was merged into him.
+#[instrument(skip(db))]
+pub async fn find_league_contracts_for_player<C>(
+    league_id: i64,
+    player_id: i64,
+    is_league_player: bool,
+    db: &C,
+) -> Result<Vec<contract::Model>>
+where
+    C: ConnectionTrait,
+{
+    let player_column = if is_league_player {
+        contract::Column::LeaguePlayerId
+    } else {
+        contract::Column::PlayerId
+    };
+    let original_contract_ids: Vec<i64> = contract::Entity::find()
+        .filter(contract::Column::LeagueId.eq(league_id))
+        .filter(player_column.eq(player_id))
+        .all(db)
+        .await?
+        .into_iter()
+        .filter_map(|contract_model| contract_model.original_contract_id)
+        .collect();
+    if original_contract_ids.is_empty() {
+        return Ok(vec![]);
+    }
+    let contracts = contract::Entity::find()
+        .filter(contract::Column::OriginalContractId.is_in(original_contract_ids))
+        .order_by_asc(contract::Column::Id)
+        .all(db)
+        .await?;
+
+    Ok(contracts)
+}
+
 /// Retrieves all contracts currently active in a league. Note that this includes Free Agent contracts where the player had been signed onto a team at some point but ended the season as a free agent.
 #[instrument(skip(db))]
 pub async fn find_active_contracts_in_league<C>(
@@ -338,8 +400,10 @@ where
     Ok(contracts)
 }
 
+/// Contracts the team dropped after the keeper deadline whose cap penalty still stands, i.e. not
+/// waived by the commissioner under rules §9.1.4.
 #[instrument(skip(db))]
-pub async fn find_contracts_dropped_by_team_in_regular_season<C>(
+pub async fn find_penalized_contracts_dropped_by_team_in_regular_season<C>(
     team_id: i64,
     end_of_season_year: i16,
     db: &C,
@@ -361,7 +425,8 @@ where
                 .and(
                     deadline::Column::Kind
                         .is_not_in([DeadlineKind::PreseasonStart, DeadlineKind::PreseasonKeeper]),
-                ),
+                )
+                .and(transaction::Column::DropPenaltyWaivedAt.is_null()),
         )
         .all(db)
         .await?;
@@ -413,8 +478,28 @@ where
     Ok(updated_contract)
 }
 
+/// Relinks a league player's contract to the real player he was merged into and returns the new
+/// contract in the contract chain.
+pub async fn relink_contract_to_real_player<C>(
+    contract_model: contract::Model,
+    real_player_id: i64,
+    db: &C,
+) -> Result<contract::Model>
+where
+    C: ConnectionTrait,
+{
+    let contract_to_insert = contract_model.relink_to_real_player(real_player_id)?;
+    let updated_contract =
+        add_replacement_contract_to_chain(contract_model, contract_to_insert, db).await?;
+
+    Ok(updated_contract)
+}
+
 /// Signs an RFA/UFA contract to a team outside any auction: the RFA raise/match handshake and the
 /// no-bid re-sign both price the contract from the resolution rather than from a winning bid.
+///
+/// A UFA signed at the discount is checked against its keeper-deadline snapshot, so the discount
+/// cannot follow the player to a team that traded for him (rules §16.4).
 pub async fn sign_rfa_or_ufa_contract_to_team<C>(
     fa_contract_model: contract::Model,
     signing_team_id: i64,
@@ -425,6 +510,24 @@ pub async fn sign_rfa_or_ufa_contract_to_team<C>(
 where
     C: ConnectionTrait,
 {
+    if fa_exception == FreeAgentException::Held
+        && matches!(
+            fa_contract_model.kind,
+            ContractKind::UnrestrictedFreeAgentOriginalTeam
+                | ContractKind::UnrestrictedFreeAgentVeteran
+        )
+    {
+        let maybe_exception_holder_team_id =
+            ufa_exception_queries::find_free_agent_exception_holder(&fa_contract_model, db).await?;
+        if maybe_exception_holder_team_id != Some(signing_team_id) {
+            return Err(FreeAgentExceptionNotHeld {
+                contract_id: fa_contract_model.id,
+                signing_team_id,
+                exception_holder_team_id: maybe_exception_holder_team_id,
+            }
+            .into());
+        }
+    }
     let signed_contract_model_to_insert = fa_contract_model.sign_rfa_or_ufa_contract_to_team(
         signing_team_id,
         signing_amount,
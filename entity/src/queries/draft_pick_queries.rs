@@ -1,7 +1,7 @@
 use color_eyre::eyre::{Result, eyre};
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, ExprTrait, LoaderTrait,
-    QueryFilter, QueryOrder, TransactionSession, TransactionTrait,
+    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, EntityTrait, ExprTrait,
+    LoaderTrait, QueryFilter, QueryOrder, TransactionSession, TransactionTrait,
 };
 use tracing::instrument;
 
@@ -83,3 +83,40 @@ where
 
     Ok(draft_picks)
 }
+
+/// Draft picks for seasons after `after_end_of_season_year` currently held by any of the teams.
+#[instrument(skip(db))]
+pub async fn find_future_draft_picks_owned_by_teams<C>(
+    team_ids: Vec<i64>,
+    after_end_of_season_year: i16,
+    db: &C,
+) -> Result<Vec<draft_pick::Model>>
+where
+    C: ConnectionTrait,
+{
+    let draft_picks = draft_pick::Entity::find()
+        .filter(draft_pick::Column::CurrentOwnerTeamId.is_in(team_ids))
+        .filter(draft_pick::Column::EndOfSeasonYear.gt(after_end_of_season_year))
+        .order_by_asc(draft_pick::Column::EndOfSeasonYear)
+        .order_by_asc(draft_pick::Column::Round)
+        .order_by_asc(draft_pick::Column::Id)
+        .all(db)
+        .await?;
+
+    Ok(draft_picks)
+}
+
+/// Hands the pick to a new team. Its original owner, and so its place in the draft order, is kept.
+#[instrument(skip(db))]
+pub async fn move_draft_pick_to_team<C>(
+    draft_pick_model: draft_pick::Model,
+    new_team_id: i64,
+    db: &C,
+) -> Result<draft_pick::Model>
+where
+    C: ConnectionTrait,
+{
+    let mut draft_pick_to_update: draft_pick::ActiveModel = draft_pick_model.into();
+    draft_pick_to_update.current_owner_team_id = ActiveValue::Set(new_team_id);
+    Ok(draft_pick_to_update.update(db).await?)
+}
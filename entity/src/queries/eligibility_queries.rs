@@ -2,7 +2,8 @@
 //!
 //! Two deliberately separate writes: the NBA *facts* (which the feed also sets) and the
 //! commissioner's override of the *derived classification*. The pair is identical for both player
-//! tables, so one macro generates both.
+//! tables, so one macro generates both. A merged league player keeps his override on his own row,
+//! where it applies to the real player he became in that league only.
 
 use chrono::Utc;
 use color_eyre::Result;
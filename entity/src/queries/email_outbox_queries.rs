@@ -0,0 +1,115 @@
+use color_eyre::Result;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter,
+    QueryOrder, QuerySelect, TransactionSession, TransactionTrait,
+    prelude::DateTimeWithTimeZone,
+    sea_query::{LockBehavior, LockType},
+};
+use tracing::instrument;
+
+use crate::email_outbox::{self, EmailOutboxStatus};
+
+#[instrument(skip_all)]
+pub async fn insert_email_outbox<C>(
+    email_to_insert: email_outbox::ActiveModel,
+    db: &C,
+) -> Result<email_outbox::Model>
+where
+    C: ConnectionTrait,
+{
+    Ok(email_to_insert.insert(db).await?)
+}
+
+/// Claims up to `limit` pending messages due at `now`, oldest first, for one delivery attempt.
+///
+/// Each claimed row has its `attempts` bumped and `next_attempt_at` pushed to `lease_until`, and
+/// rows locked by a concurrent claim are skipped, so two schedulers never send the same message.
+/// A claim that is never resolved (the process died mid-send) is picked up again after the lease.
+#[instrument(skip(db))]
+pub async fn claim_due_email_outbox_messages<C>(
+    now: DateTimeWithTimeZone,
+    lease_until: DateTimeWithTimeZone,
+    limit: u64,
+    db: &C,
+) -> Result<Vec<email_outbox::Model>>
+where
+    C: ConnectionTrait + TransactionTrait,
+{
+    let transaction = db.begin().await?;
+
+    let due_messages = email_outbox::Entity::find()
+        .filter(email_outbox::Column::Status.eq(EmailOutboxStatus::Pending))
+        .filter(email_outbox::Column::NextAttemptAt.lte(now))
+        .order_by_asc(email_outbox::Column::NextAttemptAt)
+        .order_by_asc(email_outbox::Column::Id)
+        .limit(limit)
+        .lock_with_behavior(LockType::Update, LockBehavior::SkipLocked)
+        .all(&transaction)
+        .await?;
+
+    let mut claimed_messages = Vec::with_capacity(due_messages.len());
+    for message_model in due_messages {
+        let attempts = message_model.attempts + 1;
+        let mut active_model: email_outbox::ActiveModel = message_model.into();
+        active_model.attempts = ActiveValue::Set(attempts);
+        active_model.next_attempt_at = ActiveValue::Set(lease_until);
+        claimed_messages.push(active_model.update(&transaction).await?);
+    }
+
+    transaction.commit().await?;
+    Ok(claimed_messages)
+}
+
+/// Marks the message delivered and clears its body, which may carry a single-use link that
+/// shouldn't outlive the send.
+#[instrument(skip(db), fields(email_outbox_id = message_model.id))]
+pub async fn mark_email_outbox_message_sent<C>(
+    message_model: email_outbox::Model,
+    sent_at: DateTimeWithTimeZone,
+    db: &C,
+) -> Result<email_outbox::Model>
+where
+    C: ConnectionTrait,
+{
+    let mut active_model: email_outbox::ActiveModel = message_model.into();
+    active_model.status = ActiveValue::Set(EmailOutboxStatus::Sent);
+    active_model.body_text = ActiveValue::Set(String::new());
+    active_model.sent_at = ActiveValue::Set(Some(sent_at));
+    active_model.last_error = ActiveValue::Set(None);
+    Ok(active_model.update(db).await?)
+}
+
+/// Records a failed attempt and leaves the message pending until `next_attempt_at`.
+#[instrument(skip(db), fields(email_outbox_id = message_model.id))]
+pub async fn reschedule_email_outbox_message<C>(
+    message_model: email_outbox::Model,
+    error_message: String,
+    next_attempt_at: DateTimeWithTimeZone,
+    db: &C,
+) -> Result<email_outbox::Model>
+where
+    C: ConnectionTrait,
+{
+    let mut active_model: email_outbox::ActiveModel = message_model.into();
+    active_model.last_error = ActiveValue::Set(Some(error_message));
+    active_model.next_attempt_at = ActiveValue::Set(next_attempt_at);
+    Ok(active_model.update(db).await?)
+}
+
+/// Records a failed attempt and stops retrying the message. The body is cleared as on a send, so
+/// an undeliverable reset link doesn't sit in the table either.
+#[instrument(skip(db), fields(email_outbox_id = message_model.id))]
+pub async fn dead_letter_email_outbox_message<C>(
+    message_model: email_outbox::Model,
+    error_message: String,
+    db: &C,
+) -> Result<email_outbox::Model>
+where
+    C: ConnectionTrait,
+{
+    let mut active_model: email_outbox::ActiveModel = message_model.into();
+    active_model.status = ActiveValue::Set(EmailOutboxStatus::DeadLettered);
+    active_model.body_text = ActiveValue::Set(String::new());
+    active_model.last_error = ActiveValue::Set(Some(error_message));
+    Ok(active_model.update(db).await?)
+}
@@ -0,0 +1,103 @@
+use color_eyre::{Result, eyre::eyre};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter,
+    QueryOrder, QuerySelect, prelude::DateTimeWithTimeZone,
+};
+use tracing::instrument;
+
+use crate::league_invitation::{self, LeagueInvitationStatus};
+
+#[instrument(skip(db))]
+pub async fn insert_league_invitation<C>(
+    invitation_to_insert: league_invitation::ActiveModel,
+    db: &C,
+) -> Result<league_invitation::Model>
+where
+    C: ConnectionTrait,
+{
+    Ok(invitation_to_insert.insert(db).await?)
+}
+
+#[instrument(skip(token, db))]
+pub async fn find_league_invitation_by_token<C>(
+    token: Vec<u8>,
+    db: &C,
+) -> Result<Option<league_invitation::Model>>
+where
+    C: ConnectionTrait,
+{
+    Ok(league_invitation::Entity::find()
+        .filter(league_invitation::Column::Token.eq(token))
+        .one(db)
+        .await?)
+}
+
+/// Looks up a single invitation, scoped to `league_id` like `find_team_by_id_in_league`.
+#[instrument(skip(db))]
+pub async fn find_league_invitation_by_id_in_league<C>(
+    invitation_id: i64,
+    league_id: i64,
+    db: &C,
+) -> Result<league_invitation::Model>
+where
+    C: ConnectionTrait,
+{
+    league_invitation::Entity::find_by_id(invitation_id)
+        .filter(league_invitation::Column::LeagueId.eq(league_id))
+        .one(db)
+        .await?
+        .ok_or_else(|| eyre!("Could not find invitation {invitation_id} in league {league_id}."))
+}
+
+/// Re-reads an invitation under a row lock, so racing accepts of one token serialize and the later
+/// one sees it closed. Only meaningful inside a db transaction.
+#[instrument(skip(db))]
+pub async fn find_league_invitation_by_id_for_update<C>(
+    invitation_id: i64,
+    db: &C,
+) -> Result<league_invitation::Model>
+where
+    C: ConnectionTrait,
+{
+    league_invitation::Entity::find_by_id(invitation_id)
+        .lock_exclusive()
+        .one(db)
+        .await?
+        .ok_or_else(|| eyre!("Could not find invitation {invitation_id}."))
+}
+
+/// The league's invitations that can still be accepted at `now`, oldest first.
+#[instrument(skip(db))]
+pub async fn find_pending_league_invitations<C>(
+    league_id: i64,
+    now: DateTimeWithTimeZone,
+    db: &C,
+) -> Result<Vec<league_invitation::Model>>
+where
+    C: ConnectionTrait,
+{
+    let invitations = league_invitation::Entity::find()
+        .filter(league_invitation::Column::LeagueId.eq(league_id))
+        .filter(league_invitation::Column::Status.eq(LeagueInvitationStatus::Pending))
+        .filter(league_invitation::Column::ExpiresAt.gt(now))
+        .order_by_asc(league_invitation::Column::CreatedAt)
+        .all(db)
+        .await?;
+    Ok(invitations)
+}
+
+#[instrument(skip(db))]
+pub async fn set_league_invitation_status<C>(
+    invitation_model: league_invitation::Model,
+    status: LeagueInvitationStatus,
+    accepted_team_user_id: Option<i64>,
+    db: &C,
+) -> Result<league_invitation::Model>
+where
+    C: ConnectionTrait,
+{
+    let mut active_model: league_invitation::ActiveModel = invitation_model.into();
+    active_model.status = ActiveValue::Set(status);
+    active_model.accepted_team_user_id = ActiveValue::Set(accepted_team_user_id);
+    Ok(active_model.update(db).await?)
+}
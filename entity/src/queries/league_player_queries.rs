@@ -116,3 +116,36 @@ where
         .await?;
     Ok(league_players)
 }
+
+/// The league player in a league already merged into `player_id`, if any.
+#[instrument(skip(db))]
+pub async fn find_league_player_linked_to_player<C>(
+    league_id: i64,
+    player_id: i64,
+    db: &C,
+) -> Result<Option<league_player::Model>>
+where
+    C: ConnectionTrait,
+{
+    let league_player_model = league_player::Entity::find()
+        .filter(league_player::Column::LeagueId.eq(league_id))
+        .filter(league_player::Column::RealPlayerId.eq(player_id))
+        .one(db)
+        .await?;
+    Ok(league_player_model)
+}
+
+/// Points a league player at the real player he became.
+#[instrument(skip(db))]
+pub async fn link_league_player_to_real_player<C>(
+    league_player_model: league_player::Model,
+    player_id: i64,
+    db: &C,
+) -> Result<league_player::Model>
+where
+    C: ConnectionTrait,
+{
+    let mut active_model: league_player::ActiveModel = league_player_model.into();
+    active_model.real_player_id = ActiveValue::Set(Some(player_id));
+    Ok(active_model.update(db).await?)
+}
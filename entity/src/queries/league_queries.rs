@@ -1,11 +1,12 @@
-use color_eyre::eyre::Result;
+use color_eyre::eyre::{Result, eyre};
 use sea_orm::{
     ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, EntityTrait, ModelTrait,
     QueryFilter, TransactionSession, TransactionTrait,
 };
 
 use crate::{
-    league, team,
+    league::{self, RosterLegalizationPolicy, TradeRejectionPolicy},
+    team,
     team_user::{self, LeagueRole},
     user,
 };
@@ -59,6 +60,42 @@ where
     Ok((inserted_league, inserted_team, inserted_team_user))
 }
 
+pub async fn find_league_by_id<C>(league_id: i64, db: &C) -> Result<league::Model>
+where
+    C: ConnectionTrait,
+{
+    league::Entity::find_by_id(league_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| eyre!("Could not find league (id = {league_id})"))
+}
+
+pub async fn update_league_trade_rejection_policy<C>(
+    league_model: league::Model,
+    trade_rejection_policy: TradeRejectionPolicy,
+    db: &C,
+) -> Result<league::Model>
+where
+    C: ConnectionTrait,
+{
+    let mut league_to_update: league::ActiveModel = league_model.into();
+    league_to_update.trade_rejection_policy = ActiveValue::Set(trade_rejection_policy);
+    Ok(league_to_update.update(db).await?)
+}
+
+pub async fn update_league_roster_legalization_policy<C>(
+    league_model: league::Model,
+    roster_legalization_policy: RosterLegalizationPolicy,
+    db: &C,
+) -> Result<league::Model>
+where
+    C: ConnectionTrait,
+{
+    let mut league_to_update: league::ActiveModel = league_model.into();
+    league_to_update.roster_legalization_policy = ActiveValue::Set(roster_legalization_policy);
+    Ok(league_to_update.update(db).await?)
+}
+
 pub async fn find_leagues_by_name<C>(league_name: &str, db: &C) -> Result<Vec<league::Model>>
 where
     C: ConnectionTrait,
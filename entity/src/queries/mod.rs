@@ -1,26 +1,38 @@
+pub mod api_token_queries;
 pub mod auction_queries;
 pub mod auction_schedule_queries;
 pub mod contract_queries;
 pub mod deadline_queries;
 pub mod draft_pick_queries;
 pub mod eligibility_queries;
+pub mod email_outbox_queries;
 pub mod job_run_queries;
+pub mod league_invitation_queries;
 pub mod league_player_queries;
 pub mod league_queries;
 pub mod league_team_season_standing_queries;
+pub mod notification_preference_queries;
+pub mod notification_queries;
 pub mod pagination;
+pub mod player_name_alias_queries;
 pub mod player_queries;
 pub mod position_queries;
 pub mod real_team_queries;
+pub mod replacement_draft_queries;
 pub mod rfa_resolution_queries;
 pub mod rookie_draft_lottery_queries;
 pub mod rookie_draft_selection_queries;
+pub mod roster_legalization_move_queries;
+pub mod security_event_queries;
 pub mod team_queries;
 pub mod team_update_queries;
 pub mod team_user_queries;
 pub mod trade_action_queries;
 pub mod trade_asset_queries;
+pub mod trade_block_queries;
 pub mod trade_queries;
 pub mod transaction_queries;
+pub mod ufa_exception_queries;
 pub mod user_queries;
 pub mod user_registration_queries;
+pub mod user_verification_queries;
@@ -0,0 +1,87 @@
+use color_eyre::Result;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter,
+    QueryOrder,
+};
+use tracing::instrument;
+
+use crate::{
+    notification::NotificationKind,
+    notification_preference::{self, NotificationEmailDelivery},
+};
+
+/// The preferences the user has set. Kinds without a row use
+/// [`notification_preference::Model::default_for`].
+#[instrument(skip(db))]
+pub async fn find_notification_preferences_for_user<C>(
+    user_id: i64,
+    db: &C,
+) -> Result<Vec<notification_preference::Model>>
+where
+    C: ConnectionTrait,
+{
+    Ok(notification_preference::Entity::find()
+        .filter(notification_preference::Column::UserId.eq(user_id))
+        .order_by_asc(notification_preference::Column::Id)
+        .all(db)
+        .await?)
+}
+
+/// The user's preference for `kind`, falling back to the default when they never set one.
+#[instrument(skip(db))]
+pub async fn find_notification_preference<C>(
+    user_id: i64,
+    kind: NotificationKind,
+    db: &C,
+) -> Result<notification_preference::Model>
+where
+    C: ConnectionTrait,
+{
+    let maybe_preference = notification_preference::Entity::find()
+        .filter(notification_preference::Column::UserId.eq(user_id))
+        .filter(notification_preference::Column::Kind.eq(kind))
+        .one(db)
+        .await?;
+
+    Ok(maybe_preference
+        .unwrap_or_else(|| notification_preference::Model::default_for(user_id, kind)))
+}
+
+#[instrument(skip(db))]
+pub async fn upsert_notification_preference<C>(
+    user_id: i64,
+    kind: NotificationKind,
+    in_app: bool,
+    email_delivery: NotificationEmailDelivery,
+    db: &C,
+) -> Result<notification_preference::Model>
+where
+    C: ConnectionTrait,
+{
+    let maybe_existing = notification_preference::Entity::find()
+        .filter(notification_preference::Column::UserId.eq(user_id))
+        .filter(notification_preference::Column::Kind.eq(kind))
+        .one(db)
+        .await?;
+
+    let saved = match maybe_existing {
+        Some(existing) => {
+            let mut active_model: notification_preference::ActiveModel = existing.into();
+            active_model.in_app = ActiveValue::Set(in_app);
+            active_model.email_delivery = ActiveValue::Set(email_delivery);
+            active_model.update(db).await?
+        }
+        None => {
+            notification_preference::ActiveModel {
+                user_id: ActiveValue::Set(user_id),
+                kind: ActiveValue::Set(kind),
+                in_app: ActiveValue::Set(in_app),
+                email_delivery: ActiveValue::Set(email_delivery),
+                ..Default::default()
+            }
+            .insert(db)
+            .await?
+        }
+    };
+    Ok(saved)
+}
@@ -0,0 +1,145 @@
+use color_eyre::Result;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, PaginatorTrait, QueryFilter,
+    QueryOrder, QuerySelect,
+    prelude::DateTimeWithTimeZone,
+    sea_query::{Expr, LockBehavior, LockType},
+};
+use tracing::instrument;
+
+use crate::{
+    notification::{self, NotificationEmailStatus},
+    queries::pagination::{Paged, fetch_page},
+};
+
+#[instrument(skip_all)]
+pub async fn insert_notification<C>(
+    notification_to_insert: notification::ActiveModel,
+    db: &C,
+) -> Result<notification::Model>
+where
+    C: ConnectionTrait,
+{
+    Ok(notification_to_insert.insert(db).await?)
+}
+
+/// One page of a user's notification center, newest first.
+#[instrument(skip(db))]
+pub async fn find_notifications_for_user<C>(
+    user_id: i64,
+    unread_only: bool,
+    page: u64,
+    page_size: u64,
+    db: &C,
+) -> Result<Paged<notification::Model>>
+where
+    C: ConnectionTrait,
+{
+    let mut query = notification::Entity::find()
+        .filter(notification::Column::UserId.eq(user_id))
+        .filter(notification::Column::InApp.eq(true))
+        .order_by_desc(notification::Column::CreatedAt)
+        .order_by_desc(notification::Column::Id);
+
+    if unread_only {
+        query = query.filter(notification::Column::ReadAt.is_null());
+    }
+
+    fetch_page(query, page, page_size, db).await
+}
+
+#[instrument(skip(db))]
+pub async fn count_unread_notifications<C>(user_id: i64, db: &C) -> Result<u64>
+where
+    C: ConnectionTrait,
+{
+    Ok(notification::Entity::find()
+        .filter(notification::Column::UserId.eq(user_id))
+        .filter(notification::Column::InApp.eq(true))
+        .filter(notification::Column::ReadAt.is_null())
+        .count(db)
+        .await?)
+}
+
+/// Marks the user's unread notifications as read at `read_at`: the ones in `maybe_ids`, or all of
+/// them when it is `None`. Ids belonging to other users are ignored. Returns how many changed.
+#[instrument(skip(db))]
+pub async fn mark_notifications_read<C>(
+    user_id: i64,
+    maybe_ids: Option<Vec<i64>>,
+    read_at: DateTimeWithTimeZone,
+    db: &C,
+) -> Result<u64>
+where
+    C: ConnectionTrait,
+{
+    let mut update = notification::Entity::update_many()
+        .col_expr(notification::Column::ReadAt, Expr::value(read_at))
+        .filter(notification::Column::UserId.eq(user_id))
+        .filter(notification::Column::ReadAt.is_null());
+
+    if let Some(ids) = maybe_ids {
+        update = update.filter(notification::Column::Id.is_in(ids));
+    }
+
+    Ok(update.exec(db).await?.rows_affected)
+}
+
+/// The users with a notification that has waited for a digest email since `due_before` or earlier.
+#[instrument(skip(db))]
+pub async fn find_users_with_due_digests<C>(
+    due_before: DateTimeWithTimeZone,
+    db: &C,
+) -> Result<Vec<i64>>
+where
+    C: ConnectionTrait,
+{
+    Ok(notification::Entity::find()
+        .filter(notification::Column::EmailStatus.eq(NotificationEmailStatus::DigestPending))
+        .filter(notification::Column::CreatedAt.lte(due_before))
+        .select_only()
+        .column(notification::Column::UserId)
+        .distinct()
+        .order_by_asc(notification::Column::UserId)
+        .into_tuple::<i64>()
+        .all(db)
+        .await?)
+}
+
+/// Locks the user's notifications still waiting for a digest email, oldest first. Rows another
+/// transaction holds are skipped, so overlapping digest runs never put a notification in two
+/// digests. Only meaningful inside a db transaction.
+#[instrument(skip(db))]
+pub async fn claim_digest_pending_notifications<C>(
+    user_id: i64,
+    db: &C,
+) -> Result<Vec<notification::Model>>
+where
+    C: ConnectionTrait,
+{
+    Ok(notification::Entity::find()
+        .filter(notification::Column::UserId.eq(user_id))
+        .filter(notification::Column::EmailStatus.eq(NotificationEmailStatus::DigestPending))
+        .order_by_asc(notification::Column::CreatedAt)
+        .order_by_asc(notification::Column::Id)
+        .lock_with_behavior(LockType::Update, LockBehavior::SkipLocked)
+        .all(db)
+        .await?)
+}
+
+#[instrument(skip(db))]
+pub async fn set_notifications_email_status<C>(
+    ids: Vec<i64>,
+    email_status: NotificationEmailStatus,
+    db: &C,
+) -> Result<u64>
+where
+    C: ConnectionTrait,
+{
+    Ok(notification::Entity::update_many()
+        .col_expr(notification::Column::EmailStatus, Expr::value(email_status))
+        .filter(notification::Column::Id.is_in(ids))
+        .exec(db)
+        .await?
+        .rows_affected)
+}
@@ -0,0 +1,60 @@
+use color_eyre::Result;
+use sea_orm::{
+    ActiveValue, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QueryOrder,
+    prelude::DateTimeWithTimeZone, sea_query::OnConflict,
+};
+use tracing::instrument;
+
+use crate::player_name_alias;
+
+/// Records `name` as a former name of the player. A name already recorded for the player is left
+/// as it was.
+#[instrument(skip(db))]
+pub async fn insert_player_name_alias<C>(
+    player_id: i64,
+    name: String,
+    replaced_at: DateTimeWithTimeZone,
+    db: &C,
+) -> Result<()>
+where
+    C: ConnectionTrait,
+{
+    let alias_to_insert = player_name_alias::ActiveModel {
+        id: ActiveValue::NotSet,
+        player_id: ActiveValue::Set(player_id),
+        name: ActiveValue::Set(name),
+        replaced_at: ActiveValue::Set(replaced_at),
+        created_at: ActiveValue::NotSet,
+        updated_at: ActiveValue::NotSet,
+    };
+    player_name_alias::Entity::insert(alias_to_insert)
+        .on_conflict(
+            OnConflict::columns([
+                player_name_alias::Column::PlayerId,
+                player_name_alias::Column::Name,
+            ])
+            .do_nothing()
+            .to_owned(),
+        )
+        .exec_without_returning(db)
+        .await?;
+
+    Ok(())
+}
+
+/// Every former name of the given players, oldest first.
+#[instrument(skip(db))]
+pub async fn find_player_name_aliases<C>(
+    player_ids: Vec<i64>,
+    db: &C,
+) -> Result<Vec<player_name_alias::Model>>
+where
+    C: ConnectionTrait,
+{
+    let aliases = player_name_alias::Entity::find()
+        .filter(player_name_alias::Column::PlayerId.is_in(player_ids))
+        .order_by_asc(player_name_alias::Column::ReplacedAt)
+        .all(db)
+        .await?;
+    Ok(aliases)
+}
@@ -3,10 +3,14 @@ use std::collections::HashSet;
 use color_eyre::{Result, eyre::eyre};
 use sea_orm::sea_query::Expr;
 use sea_orm::{
-    ColumnTrait, ConnectionTrait, EntityTrait, ExprTrait, QueryFilter, QueryOrder, QuerySelect,
+    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, EntityTrait, ExprTrait,
+    QueryFilter, QueryOrder, QuerySelect, prelude::DateTimeWithTimeZone,
 };
 
-use crate::player;
+use crate::{
+    player::{self, NbaInjuryStatus, NbaRosterSource, PlayerStatus},
+    player_name_alias_queries,
+};
 
 pub async fn find_player_by_id<C>(id: i64, db: &C) -> Result<player::Model>
 where
@@ -17,7 +21,8 @@ where
 }
 
 /// Case- and accent-insensitive substring search on real (NBA) player names, for the player
-/// search box. `limit` bounds the result set — the real-player table spans every NBA season.
+/// search box. `limit` bounds the result set — the real-player table spans every NBA season. A
+/// player's former names (`player_name_alias`) match too.
 pub async fn search_players_by_name<C>(
     name_query: &str,
     limit: u64,
@@ -27,7 +32,8 @@ where
     C: ConnectionTrait,
 {
     let condition = Expr::cust_with_values(
-        "unaccent(name) ILIKE unaccent($1)",
+        "(unaccent(name) ILIKE unaccent($1) OR id IN \
+         (SELECT player_id FROM player_name_alias WHERE unaccent(name) ILIKE unaccent($1)))",
         [format!("%{name_query}%")],
     );
 
@@ -40,6 +46,8 @@ where
     Ok(player_models)
 }
 
+/// Real players going by, or formerly known by, any of the given names — so a historical
+/// `ContractUpdate.player_name_at_time` still resolves after a rename.
 // internal query API; generic hasher flexibility not needed
 #[allow(clippy::implicit_hasher)]
 pub async fn find_players_by_name<C>(
@@ -53,7 +61,11 @@ where
         .iter()
         .map(std::string::ToString::to_string)
         .collect();
-    let condition = Expr::cust_with_values("unaccent(name) = ANY($1)", [player_names_vec]);
+    let condition = Expr::cust_with_values(
+        "(unaccent(name) = ANY($1) OR id IN \
+         (SELECT player_id FROM player_name_alias WHERE unaccent(name) = ANY($1)))",
+        [player_names_vec],
+    );
 
     let player_models = player::Entity::find().filter(condition).all(db).await?;
     Ok(player_models)
@@ -98,3 +110,119 @@ where
         .await?;
     Ok(players)
 }
+
+/// Real players by their NBA player-index id, for feeds keyed on it.
+pub async fn find_players_by_nba_ids<C>(nba_ids: Vec<i32>, db: &C) -> Result<Vec<player::Model>>
+where
+    C: ConnectionTrait,
+{
+    let players = player::Entity::find()
+        .filter(player::Column::NbaId.is_in(nba_ids))
+        .all(db)
+        .await?;
+    Ok(players)
+}
+
+/// Real players by their ESPN player id, for feeds keyed on it.
+pub async fn find_players_by_espn_ids<C>(espn_ids: Vec<i32>, db: &C) -> Result<Vec<player::Model>>
+where
+    C: ConnectionTrait,
+{
+    let players = player::Entity::find()
+        .filter(player::Column::EspnId.is_in(espn_ids))
+        .all(db)
+        .await?;
+    Ok(players)
+}
+
+pub async fn find_players_with_nba_injury_status<C>(
+    nba_injury_status: NbaInjuryStatus,
+    db: &C,
+) -> Result<Vec<player::Model>>
+where
+    C: ConnectionTrait,
+{
+    let players = player::Entity::find()
+        .filter(player::Column::NbaInjuryStatus.eq(nba_injury_status))
+        .all(db)
+        .await?;
+    Ok(players)
+}
+
+/// Records the player's NBA injury status along with where it came from and when it was reported.
+pub async fn set_player_nba_injury_status<C>(
+    player_model: player::Model,
+    nba_injury_status: NbaInjuryStatus,
+    nba_injury_source: NbaRosterSource,
+    nba_injury_asof: DateTimeWithTimeZone,
+    db: &C,
+) -> Result<player::Model>
+where
+    C: ConnectionTrait,
+{
+    let mut active_model: player::ActiveModel = player_model.into();
+    active_model.nba_injury_status = ActiveValue::Set(nba_injury_status);
+    active_model.nba_injury_source = ActiveValue::Set(nba_injury_source);
+    active_model.nba_injury_asof = ActiveValue::Set(Some(nba_injury_asof));
+    Ok(active_model.update(db).await?)
+}
+
+pub async fn insert_player<C>(
+    player_to_insert: player::ActiveModel,
+    db: &C,
+) -> Result<player::Model>
+where
+    C: ConnectionTrait,
+{
+    Ok(player_to_insert.insert(db).await?)
+}
+
+/// Renames a player, keeping the name he went by until `renamed_at` as an alias.
+pub async fn rename_player<C>(
+    player_model: player::Model,
+    new_name: String,
+    renamed_at: DateTimeWithTimeZone,
+    db: &C,
+) -> Result<player::Model>
+where
+    C: ConnectionTrait,
+{
+    player_name_alias_queries::insert_player_name_alias(
+        player_model.id,
+        player_model.name.clone(),
+        renamed_at,
+        db,
+    )
+    .await?;
+    let mut active_model: player::ActiveModel = player_model.into();
+    active_model.name = ActiveValue::Set(new_name);
+    Ok(active_model.update(db).await?)
+}
+
+/// Moves a player to the real team he now plays for.
+pub async fn set_player_current_real_team<C>(
+    player_model: player::Model,
+    real_team_id: i64,
+    db: &C,
+) -> Result<player::Model>
+where
+    C: ConnectionTrait,
+{
+    let mut active_model: player::ActiveModel = player_model.into();
+    active_model.current_real_team_id = ActiveValue::Set(real_team_id);
+    Ok(active_model.update(db).await?)
+}
+
+/// Marks a player retired, or active again after a comeback.
+pub async fn set_player_status<C>(
+    player_model: player::Model,
+    status: PlayerStatus,
+    db: &C,
+) -> Result<player::Model>
+where
+    C: ConnectionTrait,
+{
+    let mut active_model: player::ActiveModel = player_model.into();
+    active_model.status = ActiveValue::Set(status);
+    Ok(active_model.update(db).await?)
+}
@@ -22,3 +22,11 @@ where
         .await?;
     Ok(positions)
 }
+
+pub async fn find_all_positions<C>(db: &C) -> Result<Vec<position::Model>>
+where
+    C: ConnectionTrait,
+{
+    let positions = position::Entity::find().all(db).await?;
+    Ok(positions)
+}
@@ -0,0 +1,167 @@
+//! Reads/writes for the replacement owners draft (rules §2.4.4) and its slate.
+
+use color_eyre::eyre::{Result, eyre};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter,
+    QueryOrder, QuerySelect,
+};
+use tracing::instrument;
+
+use crate::{
+    replacement_draft::{self, ReplacementDraftPool},
+    replacement_draft_selection::{self, ReplacementDraftSelectionStatus},
+};
+
+#[instrument(skip(db))]
+pub async fn insert_replacement_draft<C>(
+    league_id: i64,
+    end_of_season_year: i16,
+    deadline_id: i64,
+    pool: &ReplacementDraftPool,
+    db: &C,
+) -> Result<replacement_draft::Model>
+where
+    C: ConnectionTrait,
+{
+    let replacement_draft_to_insert = replacement_draft::ActiveModel {
+        league_id: ActiveValue::Set(league_id),
+        end_of_season_year: ActiveValue::Set(end_of_season_year),
+        deadline_id: ActiveValue::Set(deadline_id),
+        pool: ActiveValue::Set(serde_json::to_value(pool)?),
+        ..Default::default()
+    };
+    Ok(replacement_draft_to_insert.insert(db).await?)
+}
+
+#[instrument(skip(db))]
+pub async fn find_replacement_draft_for_league_season<C>(
+    league_id: i64,
+    end_of_season_year: i16,
+    db: &C,
+) -> Result<Option<replacement_draft::Model>>
+where
+    C: ConnectionTrait,
+{
+    Ok(replacement_draft::Entity::find()
+        .filter(replacement_draft::Column::LeagueId.eq(league_id))
+        .filter(replacement_draft::Column::EndOfSeasonYear.eq(end_of_season_year))
+        .one(db)
+        .await?)
+}
+
+#[instrument(skip(db))]
+pub async fn find_replacement_draft_by_id<C>(
+    replacement_draft_id: i64,
+    db: &C,
+) -> Result<replacement_draft::Model>
+where
+    C: ConnectionTrait,
+{
+    replacement_draft::Entity::find_by_id(replacement_draft_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| eyre!("Could not find replacement draft ({replacement_draft_id})."))
+}
+
+/// Pre-creates the draft's whole ordered slate as `Unused` rows, one per turn.
+#[instrument(skip(ordered_team_ids, db))]
+pub async fn build_replacement_draft_slate<C>(
+    replacement_draft_id: i64,
+    ordered_team_ids: Vec<i64>,
+    db: &C,
+) -> Result<()>
+where
+    C: ConnectionTrait,
+{
+    if ordered_team_ids.is_empty() {
+        return Ok(());
+    }
+
+    let mut models_to_insert = Vec::with_capacity(ordered_team_ids.len());
+    for (index, team_id) in ordered_team_ids.into_iter().enumerate() {
+        models_to_insert.push(replacement_draft_selection::ActiveModel {
+            replacement_draft_id: ActiveValue::Set(replacement_draft_id),
+            order: ActiveValue::Set(i16::try_from(index + 1)?),
+            team_id: ActiveValue::Set(team_id),
+            status: ActiveValue::Set(ReplacementDraftSelectionStatus::Unused),
+            ..Default::default()
+        });
+    }
+    replacement_draft_selection::Entity::insert_many(models_to_insert)
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
+
+/// The draft's full slate, in order — the board.
+#[instrument(skip(db))]
+pub async fn find_replacement_draft_selections<C>(
+    replacement_draft_id: i64,
+    db: &C,
+) -> Result<Vec<replacement_draft_selection::Model>>
+where
+    C: ConnectionTrait,
+{
+    Ok(replacement_draft_selection::Entity::find()
+        .filter(replacement_draft_selection::Column::ReplacementDraftId.eq(replacement_draft_id))
+        .order_by_asc(replacement_draft_selection::Column::Order)
+        .all(db)
+        .await?)
+}
+
+/// The selection that is on the clock: the lowest-`order` `Unused` row. `None` once every turn is
+/// used or passed.
+#[instrument(skip(db))]
+pub async fn get_on_the_clock_replacement_draft_selection<C>(
+    replacement_draft_id: i64,
+    db: &C,
+) -> Result<Option<replacement_draft_selection::Model>>
+where
+    C: ConnectionTrait,
+{
+    Ok(replacement_draft_selection::Entity::find()
+        .filter(replacement_draft_selection::Column::ReplacementDraftId.eq(replacement_draft_id))
+        .filter(
+            replacement_draft_selection::Column::Status.eq(ReplacementDraftSelectionStatus::Unused),
+        )
+        .order_by_asc(replacement_draft_selection::Column::Order)
+        .one(db)
+        .await?)
+}
+
+/// A slate row by id, row-locked so two clients cannot resolve the same turn. Only meaningful
+/// inside a db transaction.
+#[instrument(skip(db))]
+pub async fn find_replacement_draft_selection_by_id_for_update<C>(
+    selection_id: i64,
+    db: &C,
+) -> Result<replacement_draft_selection::Model>
+where
+    C: ConnectionTrait,
+{
+    replacement_draft_selection::Entity::find_by_id(selection_id)
+        .lock_exclusive()
+        .one(db)
+        .await?
+        .ok_or_else(|| eyre!("Could not find replacement draft selection ({selection_id})."))
+}
+
+/// Resolves a slate row to the asset taken, or to a pass when both are `None`.
+#[instrument(skip(db))]
+pub async fn record_replacement_draft_selection_result<C>(
+    selection_model: replacement_draft_selection::Model,
+    status: ReplacementDraftSelectionStatus,
+    maybe_contract_id: Option<i64>,
+    maybe_draft_pick_id: Option<i64>,
+    db: &C,
+) -> Result<replacement_draft_selection::Model>
+where
+    C: ConnectionTrait,
+{
+    let mut selection_to_update: replacement_draft_selection::ActiveModel = selection_model.into();
+    selection_to_update.status = ActiveValue::Set(status);
+    selection_to_update.contract_id = ActiveValue::Set(maybe_contract_id);
+    selection_to_update.draft_pick_id = ActiveValue::Set(maybe_draft_pick_id);
+    Ok(selection_to_update.update(db).await?)
+}
@@ -0,0 +1,79 @@
+//! Reads/writes for the moves a roster lock made to legalize a team.
+
+use chrono::{DateTime, FixedOffset, Utc};
+use color_eyre::Result;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter,
+    QueryOrder, sea_query::Expr,
+};
+use tracing::instrument;
+
+use crate::roster_legalization_move::{self, RosterLegalizationMoveKind};
+
+#[instrument(skip(db))]
+pub async fn insert_roster_legalization_move<C>(
+    league_id: i64,
+    team_id: i64,
+    deadline_id: i64,
+    contract_id: i64,
+    kind: RosterLegalizationMoveKind,
+    db: &C,
+) -> Result<roster_legalization_move::Model>
+where
+    C: ConnectionTrait,
+{
+    let move_to_insert = roster_legalization_move::ActiveModel {
+        id: ActiveValue::NotSet,
+        league_id: ActiveValue::Set(league_id),
+        team_id: ActiveValue::Set(team_id),
+        deadline_id: ActiveValue::Set(deadline_id),
+        contract_id: ActiveValue::Set(contract_id),
+        kind: ActiveValue::Set(kind),
+        acknowledged_at: ActiveValue::Set(None),
+        created_at: ActiveValue::NotSet,
+        updated_at: ActiveValue::NotSet,
+    };
+    Ok(move_to_insert.insert(db).await?)
+}
+
+/// The team's legalization moves, newest first; only the ones it has not acknowledged yet when
+/// `unacknowledged_only` is set.
+#[instrument(skip(db))]
+pub async fn find_roster_legalization_moves_for_team<C>(
+    team_id: i64,
+    unacknowledged_only: bool,
+    db: &C,
+) -> Result<Vec<roster_legalization_move::Model>>
+where
+    C: ConnectionTrait,
+{
+    let mut query = roster_legalization_move::Entity::find()
+        .filter(roster_legalization_move::Column::TeamId.eq(team_id));
+    if unacknowledged_only {
+        query = query.filter(roster_legalization_move::Column::AcknowledgedAt.is_null());
+    }
+
+    Ok(query
+        .order_by_desc(roster_legalization_move::Column::Id)
+        .all(db)
+        .await?)
+}
+
+/// Marks every unacknowledged legalization move of the team as seen. Returns how many were marked.
+#[instrument(skip(db))]
+pub async fn acknowledge_roster_legalization_moves_for_team<C>(team_id: i64, db: &C) -> Result<u64>
+where
+    C: ConnectionTrait,
+{
+    let update_result = roster_legalization_move::Entity::update_many()
+        .col_expr(
+            roster_legalization_move::Column::AcknowledgedAt,
+            Expr::value(DateTime::<FixedOffset>::from(Utc::now())),
+        )
+        .filter(roster_legalization_move::Column::TeamId.eq(team_id))
+        .filter(roster_legalization_move::Column::AcknowledgedAt.is_null())
+        .exec(db)
+        .await?;
+
+    Ok(update_result.rows_affected)
+}
@@ -0,0 +1,215 @@
+use color_eyre::Result;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, EntityTrait, ExprTrait,
+    PaginatorTrait, QueryFilter, QueryOrder, Statement,
+    prelude::DateTimeWithTimeZone,
+    sea_query::{Expr, Query},
+};
+use tracing::instrument;
+
+use crate::{
+    queries::pagination::{Paged, fetch_page},
+    security_event::{self, SecurityEventKind},
+    team, team_user,
+};
+
+/// A security event to record.
+#[derive(Clone, Debug)]
+pub struct NewSecurityEvent {
+    pub kind: SecurityEventKind,
+    pub user_id: Option<i64>,
+    /// Trimmed and lowercased, so case variants of one address count against the same limits.
+    pub email: Option<String>,
+    pub ip_address: Option<String>,
+    pub detail: Option<String>,
+}
+
+/// Records `new_event` as having happened at `occurred_at`. The throttle windows are measured
+/// against this, so pass the same clock the checks use.
+#[instrument(skip_all, fields(kind = ?new_event.kind, user_id = new_event.user_id))]
+pub async fn insert_security_event<C>(
+    new_event: NewSecurityEvent,
+    occurred_at: DateTimeWithTimeZone,
+    db: &C,
+) -> Result<security_event::Model>
+where
+    C: ConnectionTrait,
+{
+    let inserted = security_event::ActiveModel {
+        kind: ActiveValue::Set(new_event.kind),
+        user_id: ActiveValue::Set(new_event.user_id),
+        email: ActiveValue::Set(new_event.email),
+        ip_address: ActiveValue::Set(new_event.ip_address),
+        detail: ActiveValue::Set(new_event.detail),
+        created_at: ActiveValue::Set(occurred_at),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+    Ok(inserted)
+}
+
+/// Changes a recorded event's kind, e.g. to settle a login attempt reserved as a failure.
+#[instrument(skip(db))]
+pub async fn update_security_event_kind<C>(
+    event_model: security_event::Model,
+    kind: SecurityEventKind,
+    db: &C,
+) -> Result<security_event::Model>
+where
+    C: ConnectionTrait,
+{
+    let mut active_model: security_event::ActiveModel = event_model.into();
+    active_model.kind = ActiveValue::Set(kind);
+    Ok(active_model.update(db).await?)
+}
+
+/// Login attempts (successful or not) on `email` since `since`.
+#[instrument(skip(db))]
+pub async fn count_login_attempts_for_email_since<C>(
+    email: &str,
+    since: DateTimeWithTimeZone,
+    db: &C,
+) -> Result<u64>
+where
+    C: ConnectionTrait,
+{
+    Ok(security_event::Entity::find()
+        .filter(security_event::Column::Email.eq(email))
+        .filter(security_event::Column::Kind.is_in(SecurityEventKind::LOGIN_ATTEMPTS))
+        .filter(security_event::Column::CreatedAt.gt(since))
+        .count(db)
+        .await?)
+}
+
+/// Login attempts (successful or not) from `ip_address` since `since`.
+#[instrument(skip(db))]
+pub async fn count_login_attempts_for_ip_address_since<C>(
+    ip_address: &str,
+    since: DateTimeWithTimeZone,
+    db: &C,
+) -> Result<u64>
+where
+    C: ConnectionTrait,
+{
+    Ok(security_event::Entity::find()
+        .filter(security_event::Column::IpAddress.eq(ip_address))
+        .filter(security_event::Column::Kind.is_in(SecurityEventKind::LOGIN_ATTEMPTS))
+        .filter(security_event::Column::CreatedAt.gt(since))
+        .count(db)
+        .await?)
+}
+
+/// Whether a `LoginThrottled` event for `email` from `ip_address` was recorded since `since`.
+#[instrument(skip(db))]
+pub async fn has_login_throttled_event_since<C>(
+    email: &str,
+    ip_address: &str,
+    since: DateTimeWithTimeZone,
+    db: &C,
+) -> Result<bool>
+where
+    C: ConnectionTrait,
+{
+    let throttled_events = security_event::Entity::find()
+        .filter(security_event::Column::Kind.eq(SecurityEventKind::LoginThrottled))
+        .filter(security_event::Column::Email.eq(email))
+        .filter(security_event::Column::IpAddress.eq(ip_address))
+        .filter(security_event::Column::CreatedAt.gt(since))
+        .count(db)
+        .await?;
+    Ok(throttled_events > 0)
+}
+
+/// Takes a Postgres advisory lock on `key` that is held until the transaction ends, so callers
+/// sharing a key take turns. Only meaningful inside a db transaction.
+#[instrument(skip(db))]
+pub async fn lock_security_event_key<C>(key: &str, db: &C) -> Result<()>
+where
+    C: ConnectionTrait,
+{
+    db.execute_raw(Statement::from_sql_and_values(
+        db.get_database_backend(),
+        "SELECT pg_advisory_xact_lock(hashtextextended($1, 0))",
+        [key.into()],
+    ))
+    .await?;
+    Ok(())
+}
+
+/// Login attempts on `email` since `since`, newest first.
+#[instrument(skip(db))]
+pub async fn find_login_attempts_for_email_since<C>(
+    email: &str,
+    since: DateTimeWithTimeZone,
+    db: &C,
+) -> Result<Vec<security_event::Model>>
+where
+    C: ConnectionTrait,
+{
+    Ok(security_event::Entity::find()
+        .filter(security_event::Column::Email.eq(email))
+        .filter(security_event::Column::Kind.is_in(SecurityEventKind::LOGIN_ATTEMPTS))
+        .filter(security_event::Column::CreatedAt.gt(since))
+        .order_by_desc(security_event::Column::CreatedAt)
+        .order_by_desc(security_event::Column::Id)
+        .all(db)
+        .await?)
+}
+
+/// One page of every security event, newest first. For app admins.
+#[instrument(skip(db))]
+pub async fn find_security_events<C>(
+    maybe_kind: Option<SecurityEventKind>,
+    page: u64,
+    page_size: u64,
+    db: &C,
+) -> Result<Paged<security_event::Model>>
+where
+    C: ConnectionTrait,
+{
+    let mut query = security_event::Entity::find().order_by_desc(security_event::Column::Id);
+
+    if let Some(kind) = maybe_kind {
+        query = query.filter(security_event::Column::Kind.eq(kind));
+    }
+
+    fetch_page(query, page, page_size, db).await
+}
+
+/// One page of the security events of users who have ever held a seat in the league, newest
+/// first. For the league's commissioner. Attempts on emails without an account are left out.
+#[instrument(skip(db))]
+pub async fn find_security_events_for_league<C>(
+    league_id: i64,
+    maybe_kind: Option<SecurityEventKind>,
+    page: u64,
+    page_size: u64,
+    db: &C,
+) -> Result<Paged<security_event::Model>>
+where
+    C: ConnectionTrait,
+{
+    let mut query = security_event::Entity::find()
+        .filter(
+            security_event::Column::UserId.in_subquery(
+                Query::select()
+                    .column((team_user::Entity, team_user::Column::UserId))
+                    .from(team_user::Entity)
+                    .inner_join(
+                        team::Entity,
+                        Expr::col((team::Entity, team::Column::Id))
+                            .equals((team_user::Entity, team_user::Column::TeamId)),
+                    )
+                    .and_where(Expr::col((team::Entity, team::Column::LeagueId)).eq(league_id))
+                    .to_owned(),
+            ),
+        )
+        .order_by_desc(security_event::Column::Id);
+
+    if let Some(kind) = maybe_kind {
+        query = query.filter(security_event::Column::Kind.eq(kind));
+    }
+
+    fetch_page(query, page, page_size, db).await
+}
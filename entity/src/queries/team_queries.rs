@@ -2,8 +2,8 @@ use std::collections::HashMap;
 
 use color_eyre::{Result, eyre::eyre};
 use sea_orm::{
-    ColumnTrait, ConnectionTrait, EntityTrait, JoinType, LoaderTrait, QueryFilter, QuerySelect,
-    RelationTrait,
+    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, EntityTrait, JoinType,
+    LoaderTrait, QueryFilter, QuerySelect, RelationTrait,
 };
 use tracing::instrument;
 
@@ -40,6 +40,20 @@ where
         .ok_or_else(|| eyre!("Could not find team {team_id} in league {league_id}."))
 }
 
+/// Takes a row lock on the team, so changes to who holds its seats serialize. Only meaningful
+/// inside a db transaction.
+#[instrument(skip(db))]
+pub async fn find_team_by_id_for_update<C>(team_id: i64, db: &C) -> Result<team::Model>
+where
+    C: ConnectionTrait,
+{
+    team::Entity::find_by_id(team_id)
+        .lock_exclusive()
+        .one(db)
+        .await?
+        .ok_or_else(|| eyre!("Could not find team {team_id}."))
+}
+
 #[instrument(skip(db))]
 pub async fn find_teams_by_name_in_league<C>(
     league_id: i64,
@@ -56,6 +70,23 @@ where
     Ok(teams_by_name)
 }
 
+/// Adds a team to the league. It has no owner until an invitation for it is accepted.
+#[instrument(skip(db))]
+pub async fn insert_team<C>(league_id: i64, name: String, db: &C) -> Result<team::Model>
+where
+    C: ConnectionTrait,
+{
+    let inserted_team = team::ActiveModel {
+        name: ActiveValue::Set(name),
+        league_id: ActiveValue::Set(league_id),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+
+    Ok(inserted_team)
+}
+
 /// Finds the teams related to the given trade actions and returns a map of `trade_action` id to its related team.
 #[instrument(skip(db))]
 pub async fn find_teams_by_trade_actions<C>(
@@ -1,13 +1,14 @@
 use color_eyre::Result;
 use sea_orm::{
-    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, EntityTrait, ModelTrait,
-    QueryFilter, QueryOrder, sea_query::Expr,
+    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, EntityTrait, JoinType, ModelTrait,
+    QueryFilter, QueryOrder, QuerySelect, RelationTrait, sea_query::Expr,
 };
 use tracing::instrument;
 
 use crate::{
     deadline,
     team_update::{self, TeamUpdateStatus},
+    transaction,
 };
 
 /// Finds the `team_updates` related to the given deadline.
@@ -26,6 +27,30 @@ where
     Ok(team_updates)
 }
 
+/// Finds a team's `Pending` `team_updates` whose transaction belongs to the given deadline, i.e.
+/// the moves that week's roster lock will commit. Unordered; see `sequence`.
+#[instrument(skip(db))]
+pub async fn find_pending_team_updates_for_team_and_deadline<C>(
+    team_id: i64,
+    deadline_id: i64,
+    db: &C,
+) -> Result<Vec<team_update::Model>>
+where
+    C: ConnectionTrait,
+{
+    let team_updates = team_update::Entity::find()
+        .join(
+            JoinType::InnerJoin,
+            team_update::Relation::Transaction.def(),
+        )
+        .filter(team_update::Column::TeamId.eq(team_id))
+        .filter(team_update::Column::Status.eq(TeamUpdateStatus::Pending))
+        .filter(transaction::Column::DeadlineId.eq(deadline_id))
+        .all(db)
+        .await?;
+    Ok(team_updates)
+}
+
 /// Finds the `team_updates` related to the given transaction id.
 #[instrument(skip(db))]
 pub async fn find_team_updates_by_transaction<C>(
@@ -62,6 +87,25 @@ where
     Ok(team_updates)
 }
 
+/// Every `team_update` the given teams received as part of a transaction, oldest first. A
+/// player's league history reads the contract moves out of these.
+#[instrument(skip(db))]
+pub async fn find_transaction_team_updates_for_teams<C>(
+    team_ids: Vec<i64>,
+    db: &C,
+) -> Result<Vec<team_update::Model>>
+where
+    C: ConnectionTrait,
+{
+    let team_updates = team_update::Entity::find()
+        .filter(team_update::Column::TeamId.is_in(team_ids))
+        .filter(team_update::Column::TransactionId.is_not_null())
+        .order_by_asc(team_update::Column::Id)
+        .all(db)
+        .await?;
+    Ok(team_updates)
+}
+
 #[instrument(skip(db))]
 pub async fn insert_team_update<C>(
     team_update_to_insert: team_update::ActiveModel,
@@ -89,6 +133,20 @@ where
     Ok(status_set_to_in_progress)
 }
 
+#[instrument(skip(db))]
+pub async fn update_team_update_sequence<C>(
+    team_update_model: team_update::Model,
+    sequence: i16,
+    db: &C,
+) -> Result<team_update::Model>
+where
+    C: ConnectionTrait,
+{
+    let mut team_update_to_update: team_update::ActiveModel = team_update_model.into();
+    team_update_to_update.sequence = ActiveValue::Set(Some(sequence));
+    Ok(team_update_to_update.update(db).await?)
+}
+
 #[instrument(skip(db))]
 pub async fn update_team_updates_with_status<C>(
     team_update_model_ids: Vec<i64>,
@@ -1,13 +1,18 @@
 use color_eyre::eyre::{Result, eyre};
 use sea_orm::{
-    ColumnTrait, ConnectionTrait, EntityTrait, JoinType, ModelTrait, QueryFilter, QuerySelect,
-    RelationTrait,
+    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, EntityTrait, JoinType, ModelTrait,
+    QueryFilter, QuerySelect, RelationTrait,
 };
 use tracing::instrument;
 
-use crate::{league, team, team_user, user};
+use crate::{
+    league, team,
+    team_user::{self, LeagueRole},
+    user,
+};
 
-/// Retrieves the default team user for a team in a given season
+/// Retrieves the default team user for a team in a given season. In a season the team changed
+/// hands, that is the owner who still holds it.
 #[instrument(skip(db))]
 pub async fn find_default_team_user_for_team<C>(
     team_model: &team::Model,
@@ -20,12 +25,19 @@ where
     let team_user_models = team_model.get_team_users(db).await?;
     let default_team_user = team_user_models
         .into_iter()
-        .find(|team_user_model| {
+        .filter(|team_user_model| {
             team_user_model.first_end_of_season_year <= end_of_season_year
                 && team_user_model
                     .final_end_of_season_year
                     .is_none_or(|year| year >= end_of_season_year)
         })
+        .max_by_key(|team_user_model| {
+            (
+                team_user_model.league_role != LeagueRole::Inactive,
+                team_user_model.first_end_of_season_year,
+                team_user_model.id,
+            )
+        })
         .ok_or_else(|| eyre!("Could not find a default team_user for team (team_id = {}) and end-of-season year {}", team_model.id, end_of_season_year))?;
     Ok(default_team_user)
 }
@@ -53,6 +65,8 @@ where
     Ok(team_users)
 }
 
+/// The user's seat in the league. A user who left and came back also has an `Inactive` row from
+/// their old seat, so an active row wins, then the newest.
 #[instrument(skip(db))]
 pub async fn get_team_user_by_user_and_league<C>(
     user_id: &i64,
@@ -62,14 +76,13 @@ pub async fn get_team_user_by_user_and_league<C>(
 where
     C: ConnectionTrait,
 {
-    let team_users = team_user::Entity::find()
-        .find_also_related(team::Entity)
-        .join(JoinType::LeftJoin, team::Relation::League.def())
-        .filter(team_user::Column::UserId.eq(*user_id))
-        .filter(league::Column::Id.eq(*league_id))
-        .one(db)
-        .await?;
-    Ok(team_users)
+    let team_users = get_all_team_users_by_user_and_league(user_id, league_id, db).await?;
+    Ok(team_users.into_iter().max_by_key(|(team_user_model, _)| {
+        (
+            team_user_model.league_role != LeagueRole::Inactive,
+            team_user_model.id,
+        )
+    }))
 }
 
 #[instrument(skip(db))]
@@ -101,3 +114,66 @@ where
         .await?
         .ok_or_else(|| eyre!("Could not find team_user with id: {}", team_user_id))
 }
+
+/// The team's active (not `Inactive`) team users.
+#[instrument(skip(db))]
+pub async fn find_active_team_users_for_team<C>(
+    team_id: i64,
+    db: &C,
+) -> Result<Vec<team_user::Model>>
+where
+    C: ConnectionTrait,
+{
+    let team_users = team_user::Entity::find()
+        .filter(team_user::Column::TeamId.eq(team_id))
+        .filter(team_user::Column::LeagueRole.ne(LeagueRole::Inactive))
+        .all(db)
+        .await?;
+
+    Ok(team_users)
+}
+
+/// Marks the team user `Inactive` as of `final_end_of_season_year`. The row is kept, so the user
+/// stays attributable on the team's past trades and auctions.
+#[instrument(skip(db))]
+pub async fn retire_team_user<C>(
+    team_user_model: team_user::Model,
+    final_end_of_season_year: i16,
+    db: &C,
+) -> Result<team_user::Model>
+where
+    C: ConnectionTrait,
+{
+    let mut team_user_to_update: team_user::ActiveModel = team_user_model.into();
+    team_user_to_update.league_role = ActiveValue::Set(LeagueRole::Inactive);
+    team_user_to_update.final_end_of_season_year = ActiveValue::Set(Some(final_end_of_season_year));
+    let updated_team_user = team_user_to_update.update(db).await?;
+
+    Ok(updated_team_user)
+}
+
+#[instrument(skip(db))]
+pub async fn insert_team_owner<C>(
+    team_id: i64,
+    user_id: i64,
+    nickname: String,
+    first_end_of_season_year: i16,
+    db: &C,
+) -> Result<team_user::Model>
+where
+    C: ConnectionTrait,
+{
+    let inserted_team_user = team_user::ActiveModel {
+        league_role: ActiveValue::Set(LeagueRole::TeamOwner),
+        nickname: ActiveValue::Set(nickname),
+        first_end_of_season_year: ActiveValue::Set(first_end_of_season_year),
+        final_end_of_season_year: ActiveValue::Set(None),
+        team_id: ActiveValue::Set(team_id),
+        user_id: ActiveValue::Set(user_id),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+
+    Ok(inserted_team_user)
+}
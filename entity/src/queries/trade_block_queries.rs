@@ -0,0 +1,95 @@
+//! Reads/writes for trade block listings.
+//!
+//! Who may list what, and whether a listing still stands, is decided in `logic`; these are plain row
+//! accessors.
+
+use std::fmt::Debug;
+
+use color_eyre::{Result, eyre::eyre};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, EntityTrait, ModelTrait,
+    QueryFilter, QueryOrder,
+};
+use tracing::instrument;
+
+use crate::trade_block::{self, TradeBlockKind, TradeBlockNeed};
+
+/// A listing to insert. Exactly the column that matches `kind` should be filled in.
+#[derive(Clone, Debug)]
+pub struct NewTradeBlockListing {
+    pub league_id: i64,
+    pub team_id: i64,
+    pub end_of_season_year: i16,
+    pub kind: TradeBlockKind,
+    pub contract_id: Option<i64>,
+    pub draft_pick_id: Option<i64>,
+    pub need: Option<TradeBlockNeed>,
+    pub note: Option<String>,
+}
+
+#[instrument(skip(db))]
+pub async fn insert_trade_block_listing<C>(
+    new_listing: NewTradeBlockListing,
+    db: &C,
+) -> Result<trade_block::Model>
+where
+    C: ConnectionTrait,
+{
+    let listing_to_insert = trade_block::ActiveModel {
+        id: ActiveValue::NotSet,
+        league_id: ActiveValue::Set(new_listing.league_id),
+        team_id: ActiveValue::Set(new_listing.team_id),
+        end_of_season_year: ActiveValue::Set(new_listing.end_of_season_year),
+        kind: ActiveValue::Set(new_listing.kind),
+        contract_id: ActiveValue::Set(new_listing.contract_id),
+        draft_pick_id: ActiveValue::Set(new_listing.draft_pick_id),
+        need: ActiveValue::Set(new_listing.need),
+        note: ActiveValue::Set(new_listing.note),
+        created_at: ActiveValue::NotSet,
+        updated_at: ActiveValue::NotSet,
+    };
+    Ok(listing_to_insert.insert(db).await?)
+}
+
+#[instrument(skip(db))]
+pub async fn delete_trade_block_listing<C>(listing_model: trade_block::Model, db: &C) -> Result<()>
+where
+    C: ConnectionTrait,
+{
+    listing_model.delete(db).await?;
+    Ok(())
+}
+
+#[instrument(skip(db))]
+pub async fn find_trade_block_listing_by_id<C>(
+    trade_block_id: i64,
+    db: &C,
+) -> Result<trade_block::Model>
+where
+    C: ConnectionTrait,
+{
+    trade_block::Entity::find_by_id(trade_block_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| eyre!("Could not find trade block listing (id = {trade_block_id})"))
+}
+
+/// Every listing in a league's season, oldest first.
+#[instrument(skip(db))]
+pub async fn find_trade_block_listings_for_league_season<C>(
+    league_id: i64,
+    end_of_season_year: i16,
+    db: &C,
+) -> Result<Vec<trade_block::Model>>
+where
+    C: ConnectionTrait,
+{
+    let listings = trade_block::Entity::find()
+        .filter(trade_block::Column::LeagueId.eq(league_id))
+        .filter(trade_block::Column::EndOfSeasonYear.eq(end_of_season_year))
+        .order_by_asc(trade_block::Column::Id)
+        .all(db)
+        .await?;
+
+    Ok(listings)
+}
@@ -93,6 +93,31 @@ where
     Ok(updated_trade)
 }
 
+/// Inserts the next trade in `previous_trade_model`'s chain, e.g. a re-proposal. It keeps the
+/// league, season and `original_trade_id`; its teams, assets and actions are the caller's to add.
+#[instrument(skip(db))]
+pub async fn insert_next_trade_in_chain<C>(
+    previous_trade_model: &trade::Model,
+    db: &C,
+) -> Result<trade::Model>
+where
+    C: ConnectionTrait,
+{
+    let trade_model_to_insert = trade::ActiveModel {
+        id: ActiveValue::NotSet,
+        end_of_season_year: ActiveValue::Set(previous_trade_model.end_of_season_year),
+        status: ActiveValue::Set(TradeStatus::Proposed),
+        league_id: ActiveValue::Set(previous_trade_model.league_id),
+        original_trade_id: ActiveValue::Set(previous_trade_model.original_trade_id),
+        previous_trade_id: ActiveValue::Set(Some(previous_trade_model.id)),
+        transaction_id: ActiveValue::NotSet,
+        created_at: ActiveValue::NotSet,
+        updated_at: ActiveValue::NotSet,
+    };
+
+    Ok(trade_model_to_insert.insert(db).await?)
+}
+
 #[instrument(skip(db))]
 pub async fn validate_trade_is_latest_in_chain<C>(trade_model: &trade::Model, db: &C) -> Result<()>
 where
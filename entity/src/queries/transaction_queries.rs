@@ -1,7 +1,8 @@
+use chrono::Utc;
 use color_eyre::{Result, eyre::eyre};
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, ExprTrait, JoinType, QueryFilter,
-    QueryOrder, QuerySelect, RelationTrait, sea_query::Expr,
+    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, EntityTrait, ExprTrait, JoinType,
+    QueryFilter, QueryOrder, QuerySelect, RelationTrait, sea_query::Expr,
 };
 use tracing::instrument;
 
@@ -9,7 +10,7 @@ use crate::{
     auction,
     deadline::{self, DeadlineKind},
     queries::pagination::{Paged, fetch_page},
-    rookie_draft_selection, team_update, trade,
+    replacement_draft_selection, rookie_draft_selection, team_update, trade,
     transaction::{self, TransactionKind},
 };
 
@@ -24,6 +25,39 @@ where
         .ok_or_else(|| eyre!("Could not find transaction with id: {transaction_id}"))
 }
 
+/// The transactions with the given ids, oldest first.
+#[instrument(skip(db))]
+pub async fn find_transactions_by_ids<C>(
+    transaction_ids: Vec<i64>,
+    db: &C,
+) -> Result<Vec<transaction::Model>>
+where
+    C: ConnectionTrait,
+{
+    Ok(transaction::Entity::find()
+        .filter(transaction::Column::Id.is_in(transaction_ids))
+        .order_by_asc(transaction::Column::Id)
+        .all(db)
+        .await?)
+}
+
+/// The contract-kind transactions (drop / IR / RDI / rookie activation) that name one of the
+/// given contracts, oldest first.
+#[instrument(skip(db))]
+pub async fn find_transactions_for_contracts<C>(
+    contract_ids: Vec<i64>,
+    db: &C,
+) -> Result<Vec<transaction::Model>>
+where
+    C: ConnectionTrait,
+{
+    Ok(transaction::Entity::find()
+        .filter(transaction::Column::ContractId.is_in(contract_ids))
+        .order_by_asc(transaction::Column::Id)
+        .all(db)
+        .await?)
+}
+
 /// One page of a league's transaction audit feed, newest first, optionally narrowed to a single
 /// team or `TransactionKind`. The feed spans the league's whole history, so it is never unbounded.
 ///
@@ -192,6 +226,67 @@ where
     Ok(transaction_model)
 }
 
+#[instrument(skip(db))]
+pub async fn insert_replacement_draft_selection_transaction<C>(
+    deadline_model: &deadline::Model,
+    replacement_draft_selection_id: i64,
+    db: &C,
+) -> Result<transaction::Model>
+where
+    C: ConnectionTrait,
+{
+    let transaction_model = insert_transaction(
+        transaction::Model::new_replacement_draft_selection_transaction(deadline_model),
+        db,
+    )
+    .await?;
+    replacement_draft_selection::Entity::update_many()
+        .col_expr(
+            replacement_draft_selection::Column::TransactionId,
+            Expr::value(transaction_model.id),
+        )
+        .filter(replacement_draft_selection::Column::Id.eq(replacement_draft_selection_id))
+        .exec(db)
+        .await?;
+    Ok(transaction_model)
+}
+
+/// Records the commissioner's waiver of a drop's cap penalty (rules §9.1.4) along with its audit trail.
+#[instrument(skip(db))]
+pub async fn waive_drop_penalty<C>(
+    drop_transaction: transaction::Model,
+    team_user_id: i64,
+    reason: String,
+    db: &C,
+) -> Result<transaction::Model>
+where
+    C: ConnectionTrait,
+{
+    let mut active_model: transaction::ActiveModel = drop_transaction.into();
+    active_model.drop_penalty_waived_at = ActiveValue::Set(Some(Utc::now().into()));
+    active_model.drop_penalty_waived_by_team_user_id = ActiveValue::Set(Some(team_user_id));
+    active_model.drop_penalty_waiver_reason = ActiveValue::Set(Some(reason));
+    Ok(active_model.update(db).await?)
+}
+
+/// Moves the given transactions, and so their team updates, to another deadline.
+#[instrument(skip(db))]
+pub async fn update_transactions_deadline<C>(
+    transaction_ids: Vec<i64>,
+    deadline_id: i64,
+    db: &C,
+) -> Result<u64>
+where
+    C: ConnectionTrait,
+{
+    let update_result = transaction::Entity::update_many()
+        .col_expr(transaction::Column::DeadlineId, Expr::value(deadline_id))
+        .filter(transaction::Column::Id.is_in(transaction_ids))
+        .exec(db)
+        .await?;
+    Ok(update_result.rows_affected)
+}
+
 #[instrument(skip(db))]
 pub async fn insert_transaction<C>(
     transaction_to_insert: transaction::ActiveModel,
@@ -0,0 +1,109 @@
+//! Reads/writes for the UFA re-sign exception snapshot (rules §16.4).
+
+use color_eyre::Result;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter,
+    QueryOrder,
+};
+use tracing::instrument;
+
+use crate::{
+    contract::{self, ContractKind},
+    contract_queries, rfa_resolution_queries, ufa_exception,
+};
+
+#[instrument(skip(db))]
+pub async fn insert_ufa_exception<C>(
+    league_id: i64,
+    end_of_season_year: i16,
+    ufa_contract_id: i64,
+    exception_holder_team_id: i64,
+    db: &C,
+) -> Result<ufa_exception::Model>
+where
+    C: ConnectionTrait,
+{
+    let ufa_exception_to_insert = ufa_exception::ActiveModel {
+        league_id: ActiveValue::Set(league_id),
+        end_of_season_year: ActiveValue::Set(end_of_season_year),
+        ufa_contract_id: ActiveValue::Set(ufa_contract_id),
+        exception_holder_team_id: ActiveValue::Set(exception_holder_team_id),
+        ..Default::default()
+    };
+    Ok(ufa_exception_to_insert.insert(db).await?)
+}
+
+/// The snapshot for a designated UFA contract, if the keeper deadline has taken one.
+///
+/// Matches any contract in the same season's chain, because a trade between the keeper deadline and
+/// the auction replaces the contract row and the snapshot still points at the older id.
+#[instrument(skip(db))]
+pub async fn find_ufa_exception_for_contract<C>(
+    ufa_contract_id: i64,
+    db: &C,
+) -> Result<Option<ufa_exception::Model>>
+where
+    C: ConnectionTrait,
+{
+    let given_contract = contract_queries::find_contract_by_id(ufa_contract_id, db).await?;
+    let season_chain_ids: Vec<i64> = contract_queries::find_contract_chain(ufa_contract_id, db)
+        .await?
+        .into_iter()
+        .filter_map(|chain_contract| {
+            (chain_contract.end_of_season_year == given_contract.end_of_season_year)
+                .then_some(chain_contract.id)
+        })
+        .collect();
+
+    Ok(ufa_exception::Entity::find()
+        .filter(ufa_exception::Column::UfaContractId.is_in(season_chain_ids))
+        .one(db)
+        .await?)
+}
+
+/// Every snapshot in a league season, oldest first.
+#[instrument(skip(db))]
+pub async fn find_ufa_exceptions_for_league_season<C>(
+    league_id: i64,
+    end_of_season_year: i16,
+    db: &C,
+) -> Result<Vec<ufa_exception::Model>>
+where
+    C: ConnectionTrait,
+{
+    Ok(ufa_exception::Entity::find()
+        .filter(ufa_exception::Column::LeagueId.eq(league_id))
+        .filter(ufa_exception::Column::EndOfSeasonYear.eq(end_of_season_year))
+        .order_by_asc(ufa_exception::Column::Id)
+        .all(db)
+        .await?)
+}
+
+/// The team holding a free agent contract's re-sign discount (rules §15.4.2, §16.4.1), or `None`
+/// for a contract that carries no exception.
+///
+/// RFAs read their resolution row and UFAs their snapshot. A UFA with no snapshot has no holder:
+/// his current team may have traded for him after the keeper deadline, so it cannot stand in.
+#[instrument(skip(db))]
+pub async fn find_free_agent_exception_holder<C>(
+    fa_contract_model: &contract::Model,
+    db: &C,
+) -> Result<Option<i64>>
+where
+    C: ConnectionTrait,
+{
+    Ok(match fa_contract_model.kind {
+        ContractKind::RestrictedFreeAgent => {
+            rfa_resolution_queries::find_rfa_resolution_for_contract(fa_contract_model.id, db)
+                .await?
+                .map(|rfa_resolution_model| rfa_resolution_model.original_owner_team_id)
+        }
+        ContractKind::UnrestrictedFreeAgentOriginalTeam
+        | ContractKind::UnrestrictedFreeAgentVeteran => {
+            find_ufa_exception_for_contract(fa_contract_model.id, db)
+                .await?
+                .map(|ufa_exception_model| ufa_exception_model.exception_holder_team_id)
+        }
+        _ => None,
+    })
+}
@@ -7,6 +7,7 @@ use sea_orm::{
     ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, JoinType, QueryFilter,
     QuerySelect, RelationTrait, Set, TransactionSession, TransactionTrait,
 };
+use tracing::instrument;
 
 /// Inserts a new user + registration. Requires a token that's used for registration confirmation.
 pub async fn insert_user<C>(
@@ -54,3 +55,42 @@ where
         .all(conn)
         .await?)
 }
+
+pub async fn find_user_by_id<C>(user_id: i64, conn: &C) -> Result<Option<user::Model>>
+where
+    C: ConnectionTrait,
+{
+    Ok(user::Entity::find_by_id(user_id).one(conn).await?)
+}
+
+/// Replaces the user's password hash and bumps `session_generation`, so every session issued
+/// under the old password stops being accepted.
+#[instrument(skip(hashed_password, conn))]
+pub async fn update_user_password<C>(
+    user_model: user::Model,
+    hashed_password: String,
+    conn: &C,
+) -> Result<user::Model>
+where
+    C: ConnectionTrait,
+{
+    let next_session_generation = user_model.session_generation + 1;
+    let mut active_model: user::ActiveModel = user_model.into();
+    active_model.hashed_password = Set(hashed_password);
+    active_model.session_generation = Set(next_session_generation);
+    Ok(active_model.update(conn).await?)
+}
+
+#[instrument(skip(conn))]
+pub async fn update_user_email<C>(
+    user_model: user::Model,
+    email: String,
+    conn: &C,
+) -> Result<user::Model>
+where
+    C: ConnectionTrait,
+{
+    let mut active_model: user::ActiveModel = user_model.into();
+    active_model.email = Set(email);
+    Ok(active_model.update(conn).await?)
+}
@@ -0,0 +1,78 @@
+use color_eyre::Result;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter,
+    QuerySelect, prelude::DateTimeWithTimeZone, sea_query::Expr,
+};
+use tracing::instrument;
+
+use crate::user_verification::{self, UserVerificationKind, UserVerificationStatus};
+
+/// Stores a new token for `user_id`, superseding any of the same kind that are still pending.
+/// Run inside a transaction so two concurrent requests cannot both leave a live token.
+#[instrument(skip(token_hash, db))]
+pub async fn insert_user_verification<C>(
+    user_id: i64,
+    kind: UserVerificationKind,
+    token_hash: Vec<u8>,
+    new_email: Option<String>,
+    expires_at: DateTimeWithTimeZone,
+    db: &C,
+) -> Result<user_verification::Model>
+where
+    C: ConnectionTrait,
+{
+    user_verification::Entity::update_many()
+        .col_expr(
+            user_verification::Column::Status,
+            Expr::value(UserVerificationStatus::Superseded),
+        )
+        .filter(user_verification::Column::UserId.eq(user_id))
+        .filter(user_verification::Column::Kind.eq(kind))
+        .filter(user_verification::Column::Status.eq(UserVerificationStatus::Pending))
+        .exec(db)
+        .await?;
+
+    let inserted = user_verification::ActiveModel {
+        user_id: ActiveValue::Set(user_id),
+        kind: ActiveValue::Set(kind),
+        token_hash: ActiveValue::Set(token_hash),
+        new_email: ActiveValue::Set(new_email),
+        expires_at: ActiveValue::Set(expires_at),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+    Ok(inserted)
+}
+
+/// Reads a token under a row lock, so racing redemptions of one token serialize and the later one
+/// sees it used. Only meaningful inside a db transaction.
+#[instrument(skip(token_hash, db))]
+pub async fn find_user_verification_by_token_hash<C>(
+    token_hash: Vec<u8>,
+    kind: UserVerificationKind,
+    db: &C,
+) -> Result<Option<user_verification::Model>>
+where
+    C: ConnectionTrait,
+{
+    Ok(user_verification::Entity::find()
+        .filter(user_verification::Column::TokenHash.eq(token_hash))
+        .filter(user_verification::Column::Kind.eq(kind))
+        .lock_exclusive()
+        .one(db)
+        .await?)
+}
+
+#[instrument(skip(db))]
+pub async fn mark_user_verification_used<C>(
+    verification_model: user_verification::Model,
+    db: &C,
+) -> Result<user_verification::Model>
+where
+    C: ConnectionTrait,
+{
+    let mut active_model: user_verification::ActiveModel = verification_model.into();
+    active_model.status = ActiveValue::Set(UserVerificationStatus::Used);
+    Ok(active_model.update(db).await?)
+}
@@ -48,9 +48,11 @@ locals {
   worker_env = {
     FBKL_DATABASE_URL = var.supabase_database_url
   }
+  # The Function URL appends the caller's address to X-Forwarded-For, so the API trusts its last entry.
   api_env = {
-    FBKL_DATABASE_URL = var.supabase_database_url
-    SESSION_SECRET    = random_password.session_secret.result
+    FBKL_DATABASE_URL          = var.supabase_database_url
+    SESSION_SECRET             = random_password.session_secret.result
+    FBKL_TRUST_X_FORWARDED_FOR = "true"
   }
 }
 
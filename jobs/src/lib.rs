@@ -7,6 +7,10 @@
 //! later tick until `MAX_ATTEMPTS`, after which it stays `Failed` and must be retried manually
 //! from the commissioner console.
 //!
+//! The same loop queues users' notification digests ([`run_notification_digest_tick`]) and drains
+//! the `email_outbox` through [`run_email_outbox_tick`]; its retries and dead-lettering live in
+//! `fbkl-notifications`.
+//!
 //! Note for replay/backfill (`import-data`): historical replay calls `fbkl_logic` handlers
 //! directly and creates no `job_run` rows, so replayed deadlines look unprocessed to this
 //! poller. Before enabling the scheduler against a database containing replayed history, mark
@@ -23,10 +27,14 @@ use fbkl_entity::{
     rfa_resolution_queries,
     sea_orm::{DatabaseConnection, prelude::DateTimeWithTimeZone},
 };
-use fbkl_logic::auction::{
-    open_scheduled_auction, shorten_open_auctions_for_crunch_window,
-    slide_unbid_auctions_down_a_tier,
+use fbkl_logic::{
+    auction::{
+        open_scheduled_auction, shorten_open_auctions_for_crunch_window,
+        slide_unbid_auctions_down_a_tier,
+    },
+    notifications::send_notification_digests,
 };
+use fbkl_notifications::{ConfiguredMailer, Mailer, deliver_due_emails};
 use fbkl_transaction_processor::{
     ProcessOutcome, ProcessableEvent, ProcessableEventKind, process_deadline, process_event,
 };
@@ -118,6 +126,8 @@ pub async fn run_scheduler_tick(db: &DatabaseConnection) -> Result<TickSummary>
 
     summary.merge(run_rfa_window_tick(db, now).await?);
 
+    summary.merge(run_notification_digest_tick(db, now).await?);
+
     if summary != TickSummary::default() {
         info!(
             "Scheduler tick: {} processed, {} failed, {} skipped, {} blocked, {} errors",
@@ -195,6 +205,42 @@ pub async fn run_rfa_window_tick(
     Ok(summary)
 }
 
+/// Delivers the outbox emails due at `now` through `mailer`.
+///
+/// Counted into the scheduler's summary as processed (sent), skipped (left for a retry) and
+/// failed (dead-lettered).
+#[instrument(skip(db, mailer))]
+pub async fn run_email_outbox_tick<M>(
+    db: &DatabaseConnection,
+    mailer: &M,
+    now: DateTimeWithTimeZone,
+) -> Result<TickSummary>
+where
+    M: Mailer,
+{
+    let delivery_summary = deliver_due_emails(mailer, now, db).await?;
+    Ok(TickSummary {
+        processed: delivery_summary.sent,
+        skipped: delivery_summary.retried,
+        failed: delivery_summary.dead_lettered,
+        ..TickSummary::default()
+    })
+}
+
+/// Queues the notification digest emails due at `now`, counted as processed. Queuing needs no
+/// mailer, so this runs on every tick; the outbox tick sends them.
+#[instrument(skip(db))]
+pub async fn run_notification_digest_tick(
+    db: &DatabaseConnection,
+    now: DateTimeWithTimeZone,
+) -> Result<TickSummary> {
+    let digests_queued = send_notification_digests(now, db).await?;
+    Ok(TickSummary {
+        processed: digests_queued,
+        ..TickSummary::default()
+    })
+}
+
 /// Processes one synthesized event and counts its outcome into `summary`.
 async fn tally_event(db: &DatabaseConnection, event: ProcessableEvent, summary: &mut TickSummary) {
     match process_event(db, event).await {
@@ -300,7 +346,9 @@ async fn run_preseason_auction_tick(
 
 /// Spawns the scheduler loop on the tokio runtime. Tick errors are logged, never fatal —
 /// the loop runs until the returned handle is aborted (server shutdown).
-pub fn spawn_scheduler(db: DatabaseConnection) -> JoinHandle<()> {
+///
+/// Without a `mailer`, outbox emails are left queued.
+pub fn spawn_scheduler(db: DatabaseConnection, mailer: Option<ConfiguredMailer>) -> JoinHandle<()> {
     tokio::spawn(async move {
         let mut interval =
             tokio::time::interval(std::time::Duration::from_secs(SCHEDULER_TICK_INTERVAL_SECS));
@@ -316,6 +364,16 @@ pub fn spawn_scheduler(db: DatabaseConnection) -> JoinHandle<()> {
             if let Err(tick_error) = run_scheduler_tick(&db).await {
                 error!("Scheduler tick failed: {tick_error:?}");
             }
+            if let Some(mailer) = &mailer {
+                match run_email_outbox_tick(&db, mailer, Utc::now().fixed_offset()).await {
+                    Ok(summary) if summary != TickSummary::default() => info!(
+                        "Email outbox tick: {} sent, {} retried, {} dead-lettered",
+                        summary.processed, summary.skipped, summary.failed
+                    ),
+                    Ok(_) => {}
+                    Err(tick_error) => error!("Email outbox tick failed: {tick_error:?}"),
+                }
+            }
         }
     })
 }
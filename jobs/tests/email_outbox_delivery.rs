@@ -0,0 +1,104 @@
+//! The outbox round trip: rows enqueued through `fbkl_notifications` are claimed and delivered by
+//! the scheduler's outbox tick, with transient failures retried and permanent ones dead-lettered.
+
+use chrono::TimeDelta;
+use fbkl_entity::{
+    email_outbox::{self, EmailOutboxStatus},
+    sea_orm::EntityTrait,
+};
+use fbkl_jobs::run_email_outbox_tick;
+use fbkl_notifications::{
+    EMAIL_OUTBOX_MAX_ATTEMPTS, FileMailer, MailError, Mailer, OutboundEmail, enqueue_email,
+    retry_delay,
+};
+use fbkl_test_support::TestLeague;
+
+/// Writes ordinary recipients to files, and fails the two reserved ones the way a provider would.
+struct ScriptedMailer(FileMailer);
+
+impl Mailer for ScriptedMailer {
+    async fn send(&self, email: &OutboundEmail) -> Result<(), MailError> {
+        match email.to.as_str() {
+            "flaky@example.com" => Err(MailError::Transient("451 try again later".to_string())),
+            "bounce@example.com" => Err(MailError::Permanent("550 no such user".to_string())),
+            _ => self.0.send(email).await,
+        }
+    }
+}
+
+fn email_to(to: &str) -> OutboundEmail {
+    OutboundEmail {
+        to: to.to_string(),
+        subject: "Auction update".to_string(),
+        body_text: "You have been outbid.".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn outbox_delivers_retries_and_dead_letters() {
+    let Some(league) = TestLeague::create("email_outbox_delivery", 2026).await else {
+        return;
+    };
+    let mail_dir = std::env::temp_dir().join(format!("fbkl-email-outbox-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&mail_dir);
+    let mailer = ScriptedMailer(FileMailer::new(&mail_dir));
+
+    let delivered = enqueue_email(&email_to("owner@example.com"), &league.db)
+        .await
+        .unwrap();
+    let flaky = enqueue_email(&email_to("flaky@example.com"), &league.db)
+        .await
+        .unwrap();
+    let bounced = enqueue_email(&email_to("bounce@example.com"), &league.db)
+        .await
+        .unwrap();
+
+    // Rows default `next_attempt_at` to the insert time, so any later `now` finds all three due.
+    let now = delivered.next_attempt_at + TimeDelta::seconds(1);
+    let summary = run_email_outbox_tick(&league.db, &mailer, now)
+        .await
+        .unwrap();
+    assert_eq!(
+        (summary.processed, summary.skipped, summary.failed),
+        (1, 1, 1)
+    );
+
+    let find = |id| email_outbox::Entity::find_by_id(id).one(&league.db);
+    let delivered = find(delivered.id).await.unwrap().unwrap();
+    assert_eq!(delivered.status, EmailOutboxStatus::Sent);
+    assert_eq!(delivered.sent_at, Some(now));
+    assert!(delivered.body_text.is_empty());
+    assert_eq!(std::fs::read_dir(&mail_dir).unwrap().count(), 1);
+
+    let flaky = find(flaky.id).await.unwrap().unwrap();
+    assert_eq!(flaky.status, EmailOutboxStatus::Pending);
+    assert_eq!(flaky.attempts, 1);
+    assert_eq!(flaky.next_attempt_at, now + retry_delay(1));
+    assert_eq!(flaky.last_error.as_deref(), Some("451 try again later"));
+    assert_eq!(flaky.body_text, "You have been outbid.");
+
+    let bounced = find(bounced.id).await.unwrap().unwrap();
+    assert_eq!(bounced.status, EmailOutboxStatus::DeadLettered);
+    assert_eq!(bounced.attempts, 1);
+    assert!(bounced.body_text.is_empty());
+
+    // Not due again until its retry delay passes.
+    let summary = run_email_outbox_tick(&league.db, &mailer, now + TimeDelta::seconds(1))
+        .await
+        .unwrap();
+    assert_eq!(summary, Default::default());
+
+    // Keep failing it until the attempts run out.
+    let mut attempt_at = flaky.next_attempt_at;
+    for _ in 1..EMAIL_OUTBOX_MAX_ATTEMPTS {
+        run_email_outbox_tick(&league.db, &mailer, attempt_at)
+            .await
+            .unwrap();
+        attempt_at = find(flaky.id).await.unwrap().unwrap().next_attempt_at;
+    }
+    let flaky = find(flaky.id).await.unwrap().unwrap();
+    assert_eq!(flaky.status, EmailOutboxStatus::DeadLettered);
+    assert_eq!(flaky.attempts, EMAIL_OUTBOX_MAX_ATTEMPTS);
+
+    std::fs::remove_dir_all(&mail_dir).unwrap();
+}
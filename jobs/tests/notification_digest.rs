@@ -0,0 +1,137 @@
+//! Notifications fan out to a team's active users on the channels each chose, and digest users get
+//! one email once their oldest pending notification is a day old.
+
+use chrono::TimeDelta;
+use fbkl_entity::{
+    email_outbox,
+    notification::{NotificationEmailStatus, NotificationKind},
+    notification_preference::NotificationEmailDelivery,
+    notification_preference_queries, notification_queries,
+    sea_orm::{EntityTrait, PaginatorTrait, TransactionTrait},
+    team_user::LeagueRole,
+};
+use fbkl_jobs::run_notification_digest_tick;
+use fbkl_logic::notifications::{NOTIFICATION_DIGEST_PERIOD_HOURS, notify_team};
+use fbkl_test_support::TestLeague;
+
+#[tokio::test]
+async fn notifications_follow_preferences_and_digest_daily() {
+    let Some(league) = TestLeague::create("notification_digest", 2026).await else {
+        return;
+    };
+    let immediate_owner = league.add_team_user(LeagueRole::TeamOwner).await;
+    let digest_owner = league.add_team_user(LeagueRole::LeagueCommissioner).await;
+    let retired_owner = league.add_team_user(LeagueRole::Inactive).await;
+    notification_preference_queries::upsert_notification_preference(
+        digest_owner.user_id,
+        NotificationKind::TradeProposed,
+        false,
+        NotificationEmailDelivery::Digest,
+        &league.db,
+    )
+    .await
+    .unwrap();
+
+    let notifications = notify_team(
+        league.league_id,
+        league.team_id,
+        NotificationKind::TradeProposed,
+        Some(1),
+        "Another team proposed a trade to you.",
+        &league.db,
+    )
+    .await
+    .unwrap();
+    let status_for = |user_id| {
+        notifications
+            .iter()
+            .find(|notification_model| notification_model.user_id == user_id)
+            .map(|notification_model| (notification_model.in_app, notification_model.email_status))
+    };
+    assert_eq!(notifications.len(), 2);
+    assert_eq!(
+        status_for(immediate_owner.user_id),
+        Some((true, NotificationEmailStatus::Queued))
+    );
+    assert_eq!(
+        status_for(digest_owner.user_id),
+        Some((false, NotificationEmailStatus::DigestPending))
+    );
+    assert_eq!(status_for(retired_owner.user_id), None);
+    let count_outbox = || email_outbox::Entity::find().count(&league.db);
+    assert_eq!(count_outbox().await.unwrap(), 1);
+
+    // Email-only rows stay out of the notification center.
+    let listed = notification_queries::find_notifications_for_user(
+        digest_owner.user_id,
+        false,
+        0,
+        25,
+        &league.db,
+    )
+    .await
+    .unwrap();
+    assert_eq!(listed.total_items, 0);
+    assert_eq!(
+        notification_queries::count_unread_notifications(immediate_owner.user_id, &league.db)
+            .await
+            .unwrap(),
+        1
+    );
+
+    let created_at = notifications[0].created_at;
+    let summary = run_notification_digest_tick(&league.db, created_at + TimeDelta::hours(1))
+        .await
+        .unwrap();
+    assert_eq!(summary.processed, 0);
+
+    let digest_at = created_at + TimeDelta::hours(NOTIFICATION_DIGEST_PERIOD_HOURS);
+    // An overlapping run holding the rows keeps this one from digesting them a second time.
+    let overlapping_run = league.db.begin().await.unwrap();
+    let claimed = notification_queries::claim_digest_pending_notifications(
+        digest_owner.user_id,
+        &overlapping_run,
+    )
+    .await
+    .unwrap();
+    assert_eq!(claimed.len(), 1);
+    let summary = run_notification_digest_tick(&league.db, digest_at)
+        .await
+        .unwrap();
+    assert_eq!(summary.processed, 0);
+    overlapping_run.rollback().await.unwrap();
+
+    let summary = run_notification_digest_tick(&league.db, digest_at)
+        .await
+        .unwrap();
+    assert_eq!(summary.processed, 1);
+    assert_eq!(count_outbox().await.unwrap(), 2);
+    assert!(
+        notification_queries::find_users_with_due_digests(digest_at, &league.db)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    // Nothing left pending, so the next tick sends nothing.
+    let summary = run_notification_digest_tick(&league.db, digest_at)
+        .await
+        .unwrap();
+    assert_eq!(summary.processed, 0);
+
+    let marked = notification_queries::mark_notifications_read(
+        immediate_owner.user_id,
+        None,
+        digest_at,
+        &league.db,
+    )
+    .await
+    .unwrap();
+    assert_eq!(marked, 1);
+    assert_eq!(
+        notification_queries::count_unread_notifications(immediate_owner.user_id, &league.db)
+            .await
+            .unwrap(),
+        0
+    );
+}
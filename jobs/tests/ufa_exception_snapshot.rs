@@ -0,0 +1,146 @@
+//! Cover for the keeper-deadline snapshot of each UFA's exception holder (rules §16.4).
+//!
+//! A UFA traded between the keeper deadline and the close of his auction keeps his contract chain
+//! but not his discount: the holder stays the team that owned him at the deadline.
+//!
+//! It lives in `jobs` because that is where the scratch database is set up.
+
+use fbkl_entity::{
+    contract::{ContractKind, FreeAgentException, FreeAgentExceptionNotHeld},
+    contract_queries, ufa_exception_queries,
+};
+use fbkl_logic::deadline_processing::seed_ufa_exceptions;
+use fbkl_test_support::TestLeague;
+
+#[tokio::test]
+async fn seeds_one_snapshot_per_ufa_and_none_for_an_rfa() {
+    let Some(league) = TestLeague::create("ufa_exception_seed", 2026).await else {
+        return;
+    };
+    let original_team_ufa_player_id = league.add_veteran_player("Original Team UFA").await;
+    let original_team_ufa_contract = league
+        .add_owned_contract(
+            original_team_ufa_player_id,
+            ContractKind::UnrestrictedFreeAgentOriginalTeam,
+            12,
+            league.team_id,
+        )
+        .await;
+    let veteran_ufa_player_id = league.add_veteran_player("Veteran UFA").await;
+    let veteran_ufa_contract = league
+        .add_owned_contract(
+            veteran_ufa_player_id,
+            ContractKind::UnrestrictedFreeAgentVeteran,
+            9,
+            league.team_id,
+        )
+        .await;
+    let rfa_player_id = league.add_veteran_player("Restricted Vet").await;
+    league
+        .add_owned_contract(
+            rfa_player_id,
+            ContractKind::RestrictedFreeAgent,
+            7,
+            league.team_id,
+        )
+        .await;
+
+    let seeded = seed_ufa_exceptions(league.league_id, 2026, &league.db)
+        .await
+        .unwrap();
+
+    let mut seeded_contract_ids: Vec<i64> = seeded
+        .iter()
+        .map(|ufa_exception| ufa_exception.ufa_contract_id)
+        .collect();
+    seeded_contract_ids.sort_unstable();
+    assert_eq!(
+        seeded_contract_ids,
+        vec![original_team_ufa_contract.id, veteran_ufa_contract.id]
+    );
+    assert!(
+        seeded
+            .iter()
+            .all(|ufa_exception| ufa_exception.exception_holder_team_id == league.team_id)
+    );
+
+    // Re-processing the keeper deadline must not move a discount.
+    let seeded_again = seed_ufa_exceptions(league.league_id, 2026, &league.db)
+        .await
+        .unwrap();
+    assert!(seeded_again.is_empty());
+}
+
+#[tokio::test]
+async fn a_ufa_traded_after_the_keeper_deadline_leaves_the_discount_behind() {
+    let Some(league) = TestLeague::create("ufa_exception_traded", 2026).await else {
+        return;
+    };
+    let trading_partner_team_id = league.add_team("Trading Partner").await;
+    let player_id = league.add_veteran_player("Traded UFA").await;
+    let ufa_contract = league
+        .add_owned_contract(
+            player_id,
+            ContractKind::UnrestrictedFreeAgentOriginalTeam,
+            12,
+            league.team_id,
+        )
+        .await;
+
+    seed_ufa_exceptions(league.league_id, 2026, &league.db)
+        .await
+        .unwrap();
+    let traded_contract =
+        contract_queries::trade_contract_to_team(ufa_contract, trading_partner_team_id, &league.db)
+            .await
+            .unwrap();
+    assert_eq!(traded_contract.team_id, Some(trading_partner_team_id));
+
+    let maybe_exception_holder_team_id =
+        ufa_exception_queries::find_free_agent_exception_holder(&traded_contract, &league.db)
+            .await
+            .unwrap();
+    assert_eq!(maybe_exception_holder_team_id, Some(league.team_id));
+
+    // The new team cannot re-sign him at the discount it never held.
+    let traded_contract_id = traded_contract.id;
+    let error = contract_queries::sign_rfa_or_ufa_contract_to_team(
+        traded_contract,
+        trading_partner_team_id,
+        20,
+        FreeAgentException::Held,
+        &league.db,
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(
+        error.downcast_ref::<FreeAgentExceptionNotHeld>(),
+        Some(&FreeAgentExceptionNotHeld {
+            contract_id: traded_contract_id,
+            signing_team_id: trading_partner_team_id,
+            exception_holder_team_id: Some(league.team_id),
+        })
+    );
+}
+
+#[tokio::test]
+async fn a_ufa_without_a_snapshot_has_no_exception_holder() {
+    let Some(league) = TestLeague::create("ufa_exception_missing", 2026).await else {
+        return;
+    };
+    let player_id = league.add_veteran_player("Unsnapshotted UFA").await;
+    let ufa_contract = league
+        .add_owned_contract(
+            player_id,
+            ContractKind::UnrestrictedFreeAgentVeteran,
+            12,
+            league.team_id,
+        )
+        .await;
+
+    let maybe_exception_holder_team_id =
+        ufa_exception_queries::find_free_agent_exception_holder(&ufa_contract, &league.db)
+            .await
+            .unwrap();
+    assert_eq!(maybe_exception_holder_team_id, None);
+}
@@ -18,7 +18,7 @@ async fn main() -> Result<(), Error> {
     tracing::init_default_subscriber();
 
     let db = db().await?.clone();
-    let state = Arc::new(AppState { db: db.clone() });
+    let state = Arc::new(AppState::new(db.clone()));
     let session_layer = build_session_layer(&db);
     let schema = build_graphql_schema(db);
 
@@ -5,9 +5,15 @@
 //! tick — never `spawn_scheduler` (a forever loop Lambda can't run). Idempotency
 //! is owned by the transaction-processor's `job_run` claims, so `EventBridge`
 //! double-fires (and retries) are safe: an already-processed deadline is a no-op.
+//!
+//! Each invocation also drains the email outbox when `FBKL_MAILER` is configured. Outbox and
+//! notification digest claims skip rows another invocation holds, so overlapping runs never send
+//! an email twice.
 
-use fbkl_jobs::run_scheduler_tick;
+use chrono::Utc;
+use fbkl_jobs::{run_email_outbox_tick, run_scheduler_tick};
 use fbkl_lambdas::db;
+use fbkl_notifications::ConfiguredMailer;
 use lambda_runtime::{Error, LambdaEvent, run, service_fn, tracing};
 use serde_json::Value;
 
@@ -33,5 +39,19 @@ async fn handler(_event: LambdaEvent<Value>) -> Result<(), Error> {
         "scheduler tick complete"
     );
 
+    let mailer = ConfiguredMailer::from_env()
+        .map_err(|config_error| format!("mailer misconfigured: {config_error:?}"))?;
+    if let Some(mailer) = mailer {
+        let outbox_summary = run_email_outbox_tick(db, &mailer, Utc::now().fixed_offset())
+            .await
+            .map_err(|tick_error| format!("email outbox tick failed: {tick_error:?}"))?;
+        tracing::info!(
+            sent = outbox_summary.processed,
+            retried = outbox_summary.skipped,
+            dead_lettered = outbox_summary.failed,
+            "email outbox tick complete"
+        );
+    }
+
     Ok(())
 }
@@ -2,3 +2,6 @@ mod advance_league_contracts;
 pub use advance_league_contracts::*;
 
 mod create_team_contracts_for_annual_advancement;
+
+mod preview_contract_advancement;
+pub use preview_contract_advancement::*;
@@ -0,0 +1,238 @@
+//! The annual advancement, computed without writing anything, so the commissioner can check the
+//! season rollover and owners can see their result before `PreseasonStart` fires.
+
+use color_eyre::Result;
+use fbkl_entity::{
+    contract::{self, ContractKind},
+    contract_queries,
+    sea_orm::{ActiveValue, ConnectionTrait},
+};
+use multimap::MultiMap;
+use tracing::instrument;
+
+use crate::deadline_processing::ProjectedContractSeason;
+
+/// What the rollover does to one contract.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ContractAdvancementOutcome {
+    /// Stays under contract for another season.
+    Advances,
+    /// Runs out and heads to the auction as an RFA.
+    BecomesRestrictedFreeAgent,
+    /// Runs out and heads to the auction as a UFA.
+    BecomesUnrestrictedFreeAgent,
+    /// A free agent contract, expired instead of advanced.
+    Expires,
+    /// Cannot be advanced, which would fail the whole league's advancement.
+    Fails,
+}
+
+/// One active contract and what the rollover would make of it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContractAdvancementPreview {
+    pub contract: contract::Model,
+    pub outcome: ContractAdvancementOutcome,
+    /// The contract's next season; `None` when it expires or fails.
+    pub next_season: Option<ProjectedContractSeason>,
+    /// Why advancing the contract fails; `None` unless the outcome is `Fails`.
+    pub failure: Option<String>,
+}
+
+/// Every active contract a team holds, through the rollover.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TeamAdvancementPreview {
+    /// `None` for contracts no team holds.
+    pub team_id: Option<i64>,
+    pub contracts: Vec<ContractAdvancementPreview>,
+}
+
+/// Applies one annual advancement without touching the database.
+pub fn advance_contract_in_memory(current: &contract::Model) -> Result<contract::Model> {
+    let advanced = current.create_annual_contract_advancement()?;
+    Ok(contract::Model {
+        year_number: set_value(advanced.year_number, current.year_number),
+        kind: set_value(advanced.kind, current.kind),
+        salary: set_value(advanced.salary, current.salary),
+        end_of_season_year: set_value(advanced.end_of_season_year, current.end_of_season_year),
+        is_ir: false,
+        previous_contract_id: Some(current.id),
+        ..current.clone()
+    })
+}
+
+fn set_value<T>(value: ActiveValue<T>, fallback: T) -> T
+where
+    T: Into<fbkl_entity::sea_orm::Value>,
+{
+    match value {
+        ActiveValue::Set(value) | ActiveValue::Unchanged(value) => value,
+        ActiveValue::NotSet => fallback,
+    }
+}
+
+/// Previews `advance_league_contracts` for the league's active contracts, by team. Nothing is
+/// written.
+#[instrument(skip(db))]
+pub async fn preview_league_contract_advancement<C>(
+    league_id: i64,
+    db: &C,
+) -> Result<Vec<TeamAdvancementPreview>>
+where
+    C: ConnectionTrait,
+{
+    let active_league_contracts =
+        contract_queries::find_active_contracts_in_league(league_id, db).await?;
+
+    Ok(preview_contract_advancement(&active_league_contracts))
+}
+
+/// Groups the previews by team, in team id order with unheld contracts first.
+pub fn preview_contract_advancement(contracts: &[contract::Model]) -> Vec<TeamAdvancementPreview> {
+    let previews_by_team: MultiMap<Option<i64>, ContractAdvancementPreview> = contracts
+        .iter()
+        .map(|contract_model| (contract_model.team_id, preview_contract(contract_model)))
+        .collect();
+
+    let mut team_previews: Vec<TeamAdvancementPreview> = previews_by_team
+        .into_iter()
+        .map(|(team_id, mut contracts)| {
+            contracts.sort_by_key(|preview| preview.contract.id);
+            TeamAdvancementPreview { team_id, contracts }
+        })
+        .collect();
+    team_previews.sort_by_key(|team_preview| team_preview.team_id);
+
+    team_previews
+}
+
+fn preview_contract(contract_model: &contract::Model) -> ContractAdvancementPreview {
+    if contract_model.kind == ContractKind::FreeAgent {
+        return ContractAdvancementPreview {
+            contract: contract_model.clone(),
+            outcome: ContractAdvancementOutcome::Expires,
+            next_season: None,
+            failure: None,
+        };
+    }
+
+    match advance_contract_in_memory(contract_model) {
+        Ok(advanced) => ContractAdvancementPreview {
+            contract: contract_model.clone(),
+            outcome: match advanced.kind {
+                ContractKind::RestrictedFreeAgent => {
+                    ContractAdvancementOutcome::BecomesRestrictedFreeAgent
+                }
+                ContractKind::UnrestrictedFreeAgentOriginalTeam
+                | ContractKind::UnrestrictedFreeAgentVeteran => {
+                    ContractAdvancementOutcome::BecomesUnrestrictedFreeAgent
+                }
+                _ => ContractAdvancementOutcome::Advances,
+            },
+            next_season: Some(ProjectedContractSeason {
+                end_of_season_year: advanced.end_of_season_year,
+                kind: advanced.kind,
+                year_number: advanced.year_number,
+                salary: advanced.salary,
+            }),
+            failure: None,
+        },
+        Err(err) => ContractAdvancementPreview {
+            contract: contract_model.clone(),
+            outcome: ContractAdvancementOutcome::Fails,
+            next_season: None,
+            failure: Some(err.to_string()),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use fbkl_entity::contract::{ContractKind, ContractStatus, Model};
+
+    use super::{ContractAdvancementOutcome, preview_contract_advancement};
+    use crate::deadline_processing::ProjectedContractSeason;
+
+    fn contract(id: i64, team_id: Option<i64>, kind: ContractKind, year_number: i16) -> Model {
+        Model {
+            id,
+            year_number,
+            kind,
+            is_ir: false,
+            salary: 10,
+            end_of_season_year: 2025,
+            status: ContractStatus::Active,
+            league_id: 1,
+            league_player_id: None,
+            player_id: Some(id),
+            previous_contract_id: None,
+            original_contract_id: Some(id),
+            team_id,
+            created_at: Utc::now().fixed_offset(),
+            updated_at: Utc::now().fixed_offset(),
+        }
+    }
+
+    #[test]
+    fn previews_each_teams_rollover_without_writing() {
+        let contracts = vec![
+            contract(3, Some(2), ContractKind::Rookie, 3),
+            contract(1, Some(1), ContractKind::Veteran, 1),
+            contract(2, Some(1), ContractKind::Veteran, 3),
+            contract(4, None, ContractKind::FreeAgent, 1),
+        ];
+
+        let previews = preview_contract_advancement(&contracts);
+        assert_eq!(
+            previews
+                .iter()
+                .map(|team_preview| team_preview.team_id)
+                .collect::<Vec<_>>(),
+            vec![None, Some(1), Some(2)]
+        );
+
+        let unheld = &previews[0].contracts[0];
+        assert_eq!(unheld.outcome, ContractAdvancementOutcome::Expires);
+        assert_eq!(unheld.next_season, None);
+
+        let team_one = &previews[1].contracts;
+        assert_eq!(team_one[0].outcome, ContractAdvancementOutcome::Advances);
+        assert_eq!(
+            team_one[0].next_season,
+            Some(ProjectedContractSeason {
+                end_of_season_year: 2026,
+                kind: ContractKind::Veteran,
+                year_number: 2,
+                salary: 12,
+            })
+        );
+        assert_eq!(
+            team_one[1].outcome,
+            ContractAdvancementOutcome::BecomesUnrestrictedFreeAgent
+        );
+
+        let team_two = &previews[2].contracts;
+        assert_eq!(
+            team_two[0].outcome,
+            ContractAdvancementOutcome::BecomesRestrictedFreeAgent
+        );
+        assert_eq!(
+            team_two[0].next_season.as_ref().map(|season| season.salary),
+            Some(12)
+        );
+    }
+
+    #[test]
+    fn a_contract_the_rollover_would_reject_is_reported_not_raised() {
+        let previews = preview_contract_advancement(&[contract(
+            1,
+            Some(1),
+            ContractKind::RestrictedFreeAgent,
+            1,
+        )]);
+
+        let preview = &previews[0].contracts[0];
+        assert_eq!(preview.outcome, ContractAdvancementOutcome::Fails);
+        assert!(preview.failure.is_some());
+    }
+}
@@ -30,6 +30,7 @@ use super::{
 };
 use crate::{
     deadline_processing::{find_eligible_compensation_pick, name_compensation_pick},
+    notifications::notify_team,
     roster,
 };
 
@@ -191,6 +192,29 @@ where
     )?;
     auction_queries::set_auction_close_at(auction_id, new_close_at, &db_txn).await?;
 
+    let maybe_outbid_team = match maybe_latest_bid {
+        Some(latest_bid) => Some(latest_bid.get_team(&db_txn).await?),
+        None => None,
+    };
+    // A team topping its own bid has nobody to tell.
+    if let Some(outbid_team_model) =
+        maybe_outbid_team.filter(|team_model| team_model.id != bidding_team_user.team_id)
+    {
+        let auctioned_player = auctioned_contract.get_player(&db_txn).await?;
+        notify_team(
+            auctioned_contract.league_id,
+            outbid_team_model.id,
+            NotificationKind::Outbid,
+            Some(auction_id),
+            &format!(
+                "You have been outbid on {}: the leading bid is now ${bid_amount}.",
+                auctioned_player.get_name()
+            ),
+            &db_txn,
+        )
+        .await?;
+    }
+
     db_txn.commit().await?;
 
     Ok(inserted_bid)
@@ -2,23 +2,28 @@ use chrono::NaiveDate;
 use color_eyre::Result;
 use fbkl_entity::{
     auction, auction_bid,
-    contract::{self, ContractKind, FreeAgentException},
-    contract_queries, deadline, rfa_resolution_queries,
+    contract::{self, FreeAgentException},
+    contract_queries, deadline,
     sea_orm::{ActiveValue, ConnectionTrait, TransactionTrait},
     team_update::{
         self, ContractUpdate, ContractUpdateType, TeamUpdateAsset, TeamUpdateData, TeamUpdateStatus,
     },
     team_update_queries::{self, ContractUpdatePlayerData},
-    transaction, transaction_queries,
+    transaction, transaction_queries, ufa_exception_queries,
 };
 use tracing::instrument;
 
-use crate::roster::{
-    SalarySnapshot, calculate_team_contract_salary, calculate_team_contract_salary_with_model,
+use crate::{
+    ir::activate_ir_contracts_due_after_acquisition,
+    roster::{
+        SalarySnapshot, calculate_team_contract_salary, calculate_team_contract_salary_with_model,
+    },
 };
 
 /// Signs a contract to the team that submitted the last/winning bid to a preseason veteran auction before it ended. Creates + inserts the contract, transaction, and team update.
 ///
+/// The pickup also takes the team's recovered players off IR (§10.1.3).
+///
 /// `maybe_raised_bid_amount` sets the price for an RFA whose winner raised its own bid
 /// (rules §15.3.2.1); every other signing pays the winning bid.
 #[instrument(skip(db))]
@@ -67,6 +72,7 @@ where
         db,
     )
     .await?;
+    activate_ir_contracts_due_after_acquisition(winning_team_model.id, deadline_model, db).await?;
 
     Ok((
         signed_contract_model,
@@ -78,9 +84,8 @@ where
 /// Whether the auction winner also holds the player's re-sign discount (rules §15.4.2, §16.4.1).
 ///
 /// The discount belongs to whoever owned the player at the keeper deadline, so a trade during the
-/// auction hands over the player without handing over the discount. An RFA keeps that owner in its
-/// resolution row; a UFA has no resolution, so its designated contract is the only record of it and
-/// a UFA traded mid-auction still reads as its current team.
+/// auction hands over the player without handing over the discount. Both RFAs and UFAs carry that
+/// owner in a keeper-deadline snapshot rather than on the contract.
 #[instrument(skip(db))]
 async fn find_free_agent_exception<C>(
     auction_contract_model: &contract::Model,
@@ -90,18 +95,10 @@ async fn find_free_agent_exception<C>(
 where
     C: ConnectionTrait,
 {
-    let exception_holder_team_id = match auction_contract_model.kind {
-        ContractKind::RestrictedFreeAgent => {
-            rfa_resolution_queries::find_rfa_resolution_for_contract(auction_contract_model.id, db)
-                .await?
-                .map(|rfa_resolution_model| rfa_resolution_model.original_owner_team_id)
-        }
-        ContractKind::UnrestrictedFreeAgentOriginalTeam
-        | ContractKind::UnrestrictedFreeAgentVeteran => auction_contract_model.team_id,
-        _ => None,
-    };
+    let maybe_exception_holder_team_id =
+        ufa_exception_queries::find_free_agent_exception_holder(auction_contract_model, db).await?;
 
-    Ok(if exception_holder_team_id == Some(winning_team_id) {
+    Ok(if maybe_exception_holder_team_id == Some(winning_team_id) {
         FreeAgentException::Held
     } else {
         FreeAgentException::NotHeld
@@ -168,6 +165,7 @@ where
         status: ActiveValue::Set(TeamUpdateStatus::Pending),
         team_id: ActiveValue::Set(team_model.id),
         transaction_id: ActiveValue::Set(Some(auction_transaction_model.id)),
+        sequence: ActiveValue::NotSet,
         created_at: ActiveValue::NotSet,
         updated_at: ActiveValue::NotSet,
     };
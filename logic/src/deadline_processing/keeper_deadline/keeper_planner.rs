@@ -0,0 +1,261 @@
+use color_eyre::Result;
+use fbkl_constants::league_rules::{
+    KEEPER_CONTRACT_COUNT_LIMIT, KEEPER_CONTRACT_TOTAL_SALARY_LIMIT,
+};
+use fbkl_entity::contract::{self, ContractKind, FreeAgentExceptionDiscount};
+use tracing::instrument;
+
+use crate::annual_contract_advancement::advance_contract_in_memory;
+
+/// How many seasons past the current contract the keeper planner projects.
+pub const KEEPER_PLANNER_HORIZON_SEASONS: usize = 3;
+
+/// One projected season of a kept contract, after annual advancement.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProjectedContractSeason {
+    pub end_of_season_year: i16,
+    pub kind: ContractKind,
+    pub year_number: i16,
+    pub salary: i16,
+}
+
+/// The season a kept contract runs out and becomes an RFA or UFA, and the re-sign exception the
+/// team would hold on him at that auction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProjectedFreeAgency {
+    pub end_of_season_year: i16,
+    pub kind: ContractKind,
+    pub exception: FreeAgentExceptionDiscount,
+    /// The lowest a held RFA re-sign can come to: the 4th-year salary the RFA contract carries.
+    /// UFAs have no floor beyond $1.
+    pub minimum_salary: Option<i16>,
+}
+
+/// A candidate keeper projected forward through the planner horizon.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeeperProjection {
+    pub contract_id: i64,
+    /// Seasons the contract stays keepable, next season first; stops at free agency.
+    pub seasons: Vec<ProjectedContractSeason>,
+    /// `None` if the contract is still under contract at the end of the horizon.
+    pub free_agency: Option<ProjectedFreeAgency>,
+}
+
+/// Next season's keeper totals for the candidate set, measured against the league limits.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeeperSeasonTotals {
+    pub end_of_season_year: i16,
+    /// Contracts counted toward the limits; RD(I) contracts are exempt.
+    pub contract_count: usize,
+    pub total_salary: i16,
+    pub contract_count_limit: usize,
+    pub total_salary_limit: i16,
+    /// Candidates that will be RFAs or UFAs next season, and so cannot be kept.
+    pub unkeepable_contract_ids: Vec<i64>,
+}
+
+impl KeeperSeasonTotals {
+    pub fn within_limits(&self) -> bool {
+        self.unkeepable_contract_ids.is_empty()
+            && self.contract_count <= self.contract_count_limit
+            && self.total_salary <= self.total_salary_limit
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeeperPlan {
+    pub next_season: KeeperSeasonTotals,
+    pub projections: Vec<KeeperProjection>,
+}
+
+/// Projects each candidate keeper forward with the same advancement the end-of-season rollover
+/// uses, and totals next season's keepers against the 14-contract and $100 limits.
+///
+/// Read-only; the contracts are advanced in memory only.
+#[instrument]
+pub fn plan_keepers(contracts: &[contract::Model]) -> Result<KeeperPlan> {
+    let projections = contracts
+        .iter()
+        .map(project_contract)
+        .collect::<Result<Vec<_>>>()?;
+
+    let next_season_year = contracts
+        .iter()
+        .map(|contract| contract.end_of_season_year + 1)
+        .max()
+        .unwrap_or_default();
+    let mut next_season = KeeperSeasonTotals {
+        end_of_season_year: next_season_year,
+        contract_count: 0,
+        total_salary: 0,
+        contract_count_limit: KEEPER_CONTRACT_COUNT_LIMIT,
+        total_salary_limit: KEEPER_CONTRACT_TOTAL_SALARY_LIMIT,
+        unkeepable_contract_ids: vec![],
+    };
+    for projection in &projections {
+        let Some(season) = projection.seasons.first() else {
+            next_season
+                .unkeepable_contract_ids
+                .push(projection.contract_id);
+            continue;
+        };
+        if is_counted_toward_keeper_limits(season.kind) {
+            next_season.contract_count += 1;
+            next_season.total_salary += season.salary;
+        }
+    }
+
+    Ok(KeeperPlan {
+        next_season,
+        projections,
+    })
+}
+
+fn project_contract(contract_model: &contract::Model) -> Result<KeeperProjection> {
+    let mut projection = KeeperProjection {
+        contract_id: contract_model.id,
+        seasons: Vec::with_capacity(KEEPER_PLANNER_HORIZON_SEASONS),
+        free_agency: None,
+    };
+    if free_agency_for(contract_model).is_some() || contract_model.kind == ContractKind::FreeAgent {
+        return Ok(projection);
+    }
+
+    let mut current = contract_model.clone();
+    for _ in 0..KEEPER_PLANNER_HORIZON_SEASONS {
+        current = advance_contract_in_memory(&current)?;
+        if let Some(free_agency) = free_agency_for(&current) {
+            projection.free_agency = Some(free_agency);
+            break;
+        }
+        projection.seasons.push(ProjectedContractSeason {
+            end_of_season_year: current.end_of_season_year,
+            kind: current.kind,
+            year_number: current.year_number,
+            salary: current.salary,
+        });
+    }
+
+    Ok(projection)
+}
+
+fn free_agency_for(contract_model: &contract::Model) -> Option<ProjectedFreeAgency> {
+    let exception = FreeAgentExceptionDiscount::for_kind(contract_model.kind)?;
+    Some(ProjectedFreeAgency {
+        end_of_season_year: contract_model.end_of_season_year,
+        kind: contract_model.kind,
+        exception,
+        minimum_salary: (contract_model.kind == ContractKind::RestrictedFreeAgent)
+            .then_some(contract_model.salary),
+    })
+}
+
+const fn is_counted_toward_keeper_limits(kind: ContractKind) -> bool {
+    !matches!(
+        kind,
+        ContractKind::RookieDevelopment | ContractKind::RookieDevelopmentInternational
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, FixedOffset};
+    use color_eyre::Result;
+    use fbkl_entity::contract::{ContractKind, ContractStatus, FreeAgentExceptionDiscount, Model};
+
+    use super::{ProjectedContractSeason, plan_keepers};
+
+    fn contract(id: i64, kind: ContractKind, year_number: i16, salary: i16) -> Model {
+        let now: DateTime<FixedOffset> =
+            DateTime::parse_from_rfc3339("2026-03-01T00:00:00+00:00").unwrap();
+        Model {
+            id,
+            year_number,
+            kind,
+            is_ir: false,
+            salary,
+            end_of_season_year: 2026,
+            status: ContractStatus::Active,
+            league_id: 1,
+            league_player_id: None,
+            player_id: Some(id),
+            previous_contract_id: None,
+            original_contract_id: Some(id),
+            team_id: Some(1),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn projects_raises_freezes_and_free_agency() -> Result<()> {
+        let plan = plan_keepers(&[
+            contract(1, ContractKind::RookieDevelopment, 2, 3),
+            contract(2, ContractKind::Rookie, 2, 10),
+            contract(3, ContractKind::Veteran, 3, 30),
+        ])?;
+
+        let rd = &plan.projections[0];
+        assert_eq!(
+            rd.seasons,
+            vec![
+                ProjectedContractSeason {
+                    end_of_season_year: 2027,
+                    kind: ContractKind::RookieDevelopment,
+                    year_number: 3,
+                    salary: 3,
+                },
+                ProjectedContractSeason {
+                    end_of_season_year: 2028,
+                    kind: ContractKind::Rookie,
+                    year_number: 2,
+                    salary: 3,
+                },
+                ProjectedContractSeason {
+                    end_of_season_year: 2029,
+                    kind: ContractKind::Rookie,
+                    year_number: 3,
+                    salary: 4,
+                },
+            ]
+        );
+        assert_eq!(rd.free_agency, None);
+
+        let rookie = &plan.projections[1];
+        assert_eq!(rookie.seasons.len(), 1);
+        assert_eq!(rookie.seasons[0].salary, 12);
+        let rfa = rookie.free_agency.as_ref().unwrap();
+        assert_eq!(rfa.end_of_season_year, 2028);
+        assert_eq!(rfa.kind, ContractKind::RestrictedFreeAgent);
+        assert_eq!(rfa.minimum_salary, Some(15));
+
+        let veteran = &plan.projections[2];
+        assert!(veteran.seasons.is_empty());
+        let ufa = veteran.free_agency.as_ref().unwrap();
+        assert_eq!(ufa.kind, ContractKind::UnrestrictedFreeAgentVeteran);
+        assert_eq!(
+            ufa.exception,
+            FreeAgentExceptionDiscount {
+                percent: 10,
+                max_discount: Some(5),
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn next_season_totals_skip_rd_and_flag_expiring_contracts() -> Result<()> {
+        let plan = plan_keepers(&[
+            contract(1, ContractKind::RookieDevelopment, 1, 40),
+            contract(2, ContractKind::Veteran, 1, 50),
+            contract(3, ContractKind::RookieExtension, 5, 20),
+        ])?;
+
+        assert_eq!(plan.next_season.end_of_season_year, 2027);
+        assert_eq!(plan.next_season.contract_count, 1);
+        assert_eq!(plan.next_season.total_salary, 60);
+        assert_eq!(plan.next_season.unkeepable_contract_ids, vec![3]);
+        assert!(!plan.next_season.within_limits());
+        Ok(())
+    }
+}
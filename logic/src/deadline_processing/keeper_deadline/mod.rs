@@ -1,7 +1,11 @@
+mod keeper_planner;
 mod process_keeper_deadline;
 mod save_keeper_team_update;
 mod seed_rfa_resolutions;
+mod seed_ufa_exceptions;
 
+pub use keeper_planner::*;
 pub use process_keeper_deadline::*;
 pub use save_keeper_team_update::*;
 pub use seed_rfa_resolutions::*;
+pub use seed_ufa_exceptions::*;
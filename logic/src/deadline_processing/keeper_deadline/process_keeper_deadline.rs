@@ -14,7 +14,7 @@ use fbkl_entity::{
 use std::collections::HashMap;
 use tracing::instrument;
 
-use super::seed_rfa_resolutions;
+use super::{seed_rfa_resolutions, seed_ufa_exceptions};
 
 /// Processes the `team_updates` that have been created for the Keeper Deadline and sets the status for them.
 #[instrument(skip(db))]
@@ -63,8 +63,9 @@ where
         }
     }
 
-    // Runs last: the owner of each RFA is only settled once every keep and drop has been applied.
+    // Runs last: the owner of each RFA/UFA is only settled once every keep and drop has been applied.
     seed_rfa_resolutions(league_id, end_of_season_year, db).await?;
+    seed_ufa_exceptions(league_id, end_of_season_year, db).await?;
 
     Ok(())
 }
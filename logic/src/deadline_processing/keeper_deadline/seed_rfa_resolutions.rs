@@ -13,8 +13,8 @@
 //! while it is still correct, and every signing path reads the discount right from there rather
 //! than from the contract's current `team_id`.
 //!
-//! UFAs need no row: they have no raise/match handshake, and their discount right is read straight
-//! off the contract they carry into the auction.
+//! UFAs have no raise/match handshake and so no resolution row, but the same trade problem: their
+//! owner at the deadline is snapshotted separately by [`super::seed_ufa_exceptions`].
 
 use color_eyre::Result;
 use fbkl_entity::{
@@ -0,0 +1,70 @@
+//! Records who holds each designated unrestricted free agent's re-sign discount (rules §16.4).
+//!
+//! The UFA counterpart of [`super::seed_rfa_resolutions`]: a UFA traded between the keeper deadline
+//! and the close of his auction must not take the discount with him, so the owner at the deadline
+//! is copied into `ufa_exception` while the contract's `team_id` still names him.
+
+use color_eyre::Result;
+use fbkl_entity::{
+    contract::ContractKind, contract_queries, sea_orm::ConnectionTrait, ufa_exception,
+    ufa_exception_queries,
+};
+use tracing::{instrument, warn};
+
+/// Seeds one `ufa_exception` row per `UFA-OriginalTeam` and `UFA-FreeAgent` contract in the season.
+///
+/// Idempotent: a contract that already has a snapshot is skipped, so re-processing the keeper
+/// deadline cannot move a discount.
+#[instrument(skip(db))]
+pub async fn seed_ufa_exceptions<C>(
+    league_id: i64,
+    end_of_season_year: i16,
+    db: &C,
+) -> Result<Vec<ufa_exception::Model>>
+where
+    C: ConnectionTrait,
+{
+    let active_season_contracts = contract_queries::find_active_contracts_in_league_for_season(
+        league_id,
+        end_of_season_year,
+        db,
+    )
+    .await?;
+
+    let mut seeded_ufa_exceptions = vec![];
+    for contract_model in active_season_contracts {
+        if !matches!(
+            contract_model.kind,
+            ContractKind::UnrestrictedFreeAgentOriginalTeam
+                | ContractKind::UnrestrictedFreeAgentVeteran
+        ) {
+            continue;
+        }
+        let Some(exception_holder_team_id) = contract_model.team_id else {
+            warn!(
+                contract_id = contract_model.id,
+                "Skipping UFA exception for a contract that is unowned at the keeper deadline."
+            );
+            continue;
+        };
+        if ufa_exception_queries::find_ufa_exception_for_contract(contract_model.id, db)
+            .await?
+            .is_some()
+        {
+            continue;
+        }
+
+        seeded_ufa_exceptions.push(
+            ufa_exception_queries::insert_ufa_exception(
+                league_id,
+                end_of_season_year,
+                contract_model.id,
+                exception_holder_team_id,
+                db,
+            )
+            .await?,
+        );
+    }
+
+    Ok(seeded_ufa_exceptions)
+}
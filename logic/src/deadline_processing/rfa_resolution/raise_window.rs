@@ -16,7 +16,8 @@ use fbkl_constants::league_rules::{
     RFA_MATCH_WINDOW_HOURS, RFA_RAISE_WINDOW_HOURS, compensation_round_for_bid,
 };
 use fbkl_entity::{
-    auction, auction_bid, contract,
+    auction, auction_bid, contract, contract_queries,
+    notification::NotificationKind,
     rfa_resolution::{self, RfaResolutionStatus},
     rfa_resolution_queries::{self, ClosedRfaAuctionResult},
     sea_orm::{
@@ -26,6 +27,8 @@ use fbkl_entity::{
 };
 use tracing::{instrument, warn};
 
+use crate::notifications::notify_team;
+
 use super::{
     find_eligible_compensation_pick, name_compensation_pick,
     rfa_transaction::{find_rfa_handshake_deadline, insert_rfa_transaction},
@@ -91,6 +94,28 @@ where
         db,
     )
     .await?;
+
+    let auctioned_player = auction_contract_model.get_player(db).await?;
+    let message = format!(
+        "The RFA auction for {} closed at ${}; the winner's raise window is open.",
+        auctioned_player.get_name(),
+        winning_bid_model.bid_amount
+    );
+    for team_id in [
+        winning_team_model.id,
+        opened_rfa_resolution.original_owner_team_id,
+    ] {
+        notify_team(
+            opened_rfa_resolution.league_id,
+            team_id,
+            NotificationKind::RfaRaiseWindowOpened,
+            Some(opened_rfa_resolution.id),
+            &message,
+            db,
+        )
+        .await?;
+    }
+
     Ok(Some(opened_rfa_resolution))
 }
 
@@ -161,6 +186,7 @@ where
         &db_txn,
     )
     .await?;
+    notify_match_window_opened(&updated_rfa_resolution, &db_txn).await?;
     db_txn.commit().await?;
 
     Ok(updated_rfa_resolution)
@@ -178,16 +204,50 @@ pub async fn decline_to_raise<C>(
     db: &C,
 ) -> Result<rfa_resolution::Model>
 where
-    C: ConnectionTrait,
+    C: ConnectionTrait + TransactionTrait,
 {
     find_raisable_rfa_resolution(rfa_resolution_id, raising_team_id, db).await?;
-    rfa_resolution_queries::open_rfa_match_window(
+    let db_txn = db.begin().await?;
+    let updated_rfa_resolution = rfa_resolution_queries::open_rfa_match_window(
         rfa_resolution_id,
         None,
         match_deadline_from(now)?,
+        &db_txn,
+    )
+    .await?;
+    notify_match_window_opened(&updated_rfa_resolution, &db_txn).await?;
+    db_txn.commit().await?;
+
+    Ok(updated_rfa_resolution)
+}
+
+/// Tells the original owner that its window to match the (possibly raised) winning bid is open.
+async fn notify_match_window_opened<C>(
+    rfa_resolution_model: &rfa_resolution::Model,
+    db: &C,
+) -> Result<()>
+where
+    C: ConnectionTrait,
+{
+    let rfa_contract_model =
+        contract_queries::find_contract_by_id(rfa_resolution_model.rfa_contract_id, db).await?;
+    let rfa_player = rfa_contract_model.get_player(db).await?;
+    let match_price = rfa_resolution_model
+        .effective_bid()
+        .map_or_else(String::new, |bid| format!(" at ${bid}"));
+    notify_team(
+        rfa_resolution_model.league_id,
+        rfa_resolution_model.original_owner_team_id,
+        NotificationKind::RfaMatchWindowOpened,
+        Some(rfa_resolution_model.id),
+        &format!(
+            "Your window to match the winning bid{match_price} for {} is open.",
+            rfa_player.get_name()
+        ),
         db,
     )
-    .await
+    .await?;
+    Ok(())
 }
 
 /// Reads the resolution and checks that this team may act on it right now.
@@ -0,0 +1,124 @@
+//! Forced RDI → RD/1 conversion at the season-start legalization (rules §11.3.1, §11.3.5).
+//!
+//! An RDI whose player reaches an NBA roster mid-season keeps his slot until the next season-start
+//! legalization. Until then he is only flagged; the final preseason roster lock converts any such
+//! contract the owner has not already moved, activated or dropped in the declaration.
+
+use color_eyre::eyre::Result;
+use fbkl_entity::{
+    contract::{self, ContractKind},
+    contract_queries,
+    deadline::{self, DeadlineKind},
+    roster_legalization_move::RosterLegalizationMoveKind,
+    roster_legalization_move_queries,
+    sea_orm::ConnectionTrait,
+};
+use tracing::instrument;
+
+use crate::{
+    eligibility::{find_contract_player_eligibility_facts, is_due_for_rd_conversion},
+    rookie_development_international::move_rookie_development_international_contract_to_stateside,
+};
+
+use super::{LegalizationMove, TeamLegalization};
+
+/// The team's RDI contracts that the season-start legalization of
+/// `legalization_end_of_season_year` will convert to RD/1.
+#[instrument(skip(db))]
+pub async fn find_rdi_contracts_due_for_rd_conversion<C>(
+    team_id: i64,
+    legalization_end_of_season_year: i16,
+    db: &C,
+) -> Result<Vec<contract::Model>>
+where
+    C: ConnectionTrait,
+{
+    let team_contracts = contract_queries::find_active_contracts_for_team(team_id, db).await?;
+    filter_due_for_rd_conversion(team_contracts, legalization_end_of_season_year, db).await
+}
+
+/// Converts every RDI contract in the league whose player has reached an NBA roster, recording a
+/// `roster_legalization_move` for each so the team is told. Only runs at `PreseasonFinalRosterLock`.
+#[instrument(skip(db))]
+pub async fn convert_league_rdi_contracts_on_nba_rosters<C>(
+    final_roster_lock: &deadline::Model,
+    db: &C,
+) -> Result<Vec<TeamLegalization>>
+where
+    C: ConnectionTrait,
+{
+    if final_roster_lock.kind != DeadlineKind::PreseasonFinalRosterLock {
+        return Ok(vec![]);
+    }
+
+    let league_contracts =
+        contract_queries::find_active_contracts_in_league(final_roster_lock.league_id, db).await?;
+    let due_contracts =
+        filter_due_for_rd_conversion(league_contracts, final_roster_lock.end_of_season_year, db)
+            .await?;
+
+    let mut conversions: Vec<TeamLegalization> = vec![];
+    for contract_model in due_contracts {
+        let Some(team_id) = contract_model.team_id else {
+            continue;
+        };
+        let converted_contract = move_rookie_development_international_contract_to_stateside(
+            contract_model,
+            final_roster_lock,
+            db,
+        )
+        .await?;
+        roster_legalization_move_queries::insert_roster_legalization_move(
+            final_roster_lock.league_id,
+            team_id,
+            final_roster_lock.id,
+            converted_contract.id,
+            RosterLegalizationMoveKind::ConvertRdiToRd,
+            db,
+        )
+        .await?;
+
+        let applied_move = LegalizationMove {
+            contract_id: converted_contract.id,
+            kind: RosterLegalizationMoveKind::ConvertRdiToRd,
+        };
+        match conversions
+            .iter_mut()
+            .find(|conversion| conversion.team_id == team_id)
+        {
+            Some(conversion) => conversion.moves.push(applied_move),
+            None => conversions.push(TeamLegalization {
+                team_id,
+                moves: vec![applied_move],
+            }),
+        }
+    }
+
+    Ok(conversions)
+}
+
+async fn filter_due_for_rd_conversion<C>(
+    contracts: Vec<contract::Model>,
+    legalization_end_of_season_year: i16,
+    db: &C,
+) -> Result<Vec<contract::Model>>
+where
+    C: ConnectionTrait,
+{
+    let mut due_contracts = vec![];
+    for contract_model in contracts {
+        if contract_model.kind != ContractKind::RookieDevelopmentInternational {
+            continue;
+        }
+        let player_facts = find_contract_player_eligibility_facts(&contract_model, db).await?;
+        if is_due_for_rd_conversion(
+            &contract_model,
+            player_facts,
+            legalization_end_of_season_year,
+        ) {
+            due_contracts.push(contract_model);
+        }
+    }
+
+    Ok(due_contracts)
+}
@@ -0,0 +1,572 @@
+//! Automatic roster legalization at weekly locks, for leagues whose
+//! [`RosterLegalizationPolicy`](fbkl_entity::league::RosterLegalizationPolicy) is `AutoLegalize`.
+
+use std::fmt::Debug;
+
+use color_eyre::eyre::{Result, eyre};
+use fbkl_constants::league_rules::{
+    REGULAR_SEASON_INTL_ROOKIE_DEVELOPMENT_CONTRACTS_PER_ROSTER_LIMIT,
+    REGULAR_SEASON_IR_CONTRACTS_PER_ROSTER_LIMIT,
+    REGULAR_SEASON_ROOKIE_DEVELOPMENT_CONTRACTS_PER_ROSTER_LIMIT,
+    REGULAR_SEASON_VET_OR_ROOKIE_CONTRACTS_PER_ROSTER_LIMIT,
+};
+use fbkl_entity::{
+    contract::{self, ContractKind},
+    contract_queries,
+    deadline::{self},
+    notification::NotificationKind,
+    roster_legalization_move::RosterLegalizationMoveKind,
+    roster_legalization_move_queries,
+    sea_orm::ConnectionTrait,
+};
+use multimap::MultiMap;
+use tracing::instrument;
+
+use crate::{
+    drop_contract::drop_contract_from_team,
+    ir::{activate_contract_from_ir, find_ir_contracts_due_for_activation},
+    notifications::notify_team,
+    rookie_development_international::move_rookie_development_international_contract_to_stateside,
+    roster::{
+        RosterCounts, SalarySnapshot, calculate_team_contract_salary, dropped_contract_cap_penalty,
+        is_counted_toward_cap, salary_counted_toward_cap,
+    },
+    weekly_moves::find_same_week_auction_win_contract_ids,
+};
+
+use super::find_roster_violations;
+
+/// One move chosen to legalize a roster.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LegalizationMove {
+    pub contract_id: i64,
+    pub kind: RosterLegalizationMoveKind,
+}
+
+/// The moves applied to one team at a lock.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TeamLegalization {
+    pub team_id: i64,
+    pub moves: Vec<LegalizationMove>,
+}
+
+/// Legalizes every illegal roster in the league ahead of a weekly lock's validation.
+///
+/// Each chosen move goes through the normal drop or IR path against the lock deadline, so its
+/// transaction, team update and any drop penalty are recorded as if the owner had made it. Each
+/// move is kept as a `roster_legalization_move` row, and the team is notified of them all. A team
+/// the moves cannot fully fix is still caught by validation afterwards.
+#[instrument(skip(db))]
+pub async fn legalize_league_rosters<C>(
+    roster_lock_deadline: &deadline::Model,
+    db: &C,
+) -> Result<Vec<TeamLegalization>>
+where
+    C: ConnectionTrait,
+{
+    let league_contracts_by_team: MultiMap<i64, contract::Model> =
+        contract_queries::find_active_contracts_in_league(roster_lock_deadline.league_id, db)
+            .await?
+            .into_iter()
+            .filter_map(|contract_model| {
+                contract_model
+                    .team_id
+                    .map(|team_id| (team_id, contract_model))
+            })
+            .collect();
+
+    let mut legalizations = vec![];
+    for (team_id, team_contracts) in league_contracts_by_team.iter_all() {
+        let violations =
+            find_roster_violations(*team_id, team_contracts, roster_lock_deadline, db).await?;
+        if violations.is_empty() {
+            continue;
+        }
+
+        let salary_snapshot =
+            calculate_team_contract_salary(*team_id, team_contracts, roster_lock_deadline, db)
+                .await?;
+        let due_for_activation: Vec<i64> =
+            find_ir_contracts_due_for_activation(*team_id, team_contracts, db)
+                .await?
+                .iter()
+                .map(|contract_model| contract_model.id)
+                .collect();
+        let same_week_wins = find_same_week_auction_win_contract_ids(
+            *team_id,
+            team_contracts,
+            roster_lock_deadline,
+            db,
+        )
+        .await?;
+        let planned_moves = plan_roster_legalization(
+            team_contracts,
+            salary_snapshot,
+            &due_for_activation,
+            &same_week_wins,
+        );
+
+        let mut applied_moves = Vec::with_capacity(planned_moves.len());
+        let mut move_descriptions = Vec::with_capacity(planned_moves.len());
+        for planned_move in planned_moves {
+            let contract_model = team_contracts
+                .iter()
+                .find(|contract_model| contract_model.id == planned_move.contract_id)
+                .cloned()
+                .ok_or_else(|| {
+                    eyre!(
+                        "Planned a legalization move for a contract the team does not hold (contract_id = {}, team_id = {team_id})",
+                        planned_move.contract_id
+                    )
+                })?;
+            move_descriptions
+                .push(describe_legalization_move(&contract_model, planned_move.kind, db).await?);
+            let moved_contract = match planned_move.kind {
+                RosterLegalizationMoveKind::Drop => {
+                    drop_contract_from_team(contract_model, roster_lock_deadline, db).await?
+                }
+                RosterLegalizationMoveKind::ActivateFromIr => {
+                    activate_contract_from_ir(contract_model, roster_lock_deadline, db).await?
+                }
+                RosterLegalizationMoveKind::ConvertRdiToRd => {
+                    move_rookie_development_international_contract_to_stateside(
+                        contract_model,
+                        roster_lock_deadline,
+                        db,
+                    )
+                    .await?
+                }
+            };
+            roster_legalization_move_queries::insert_roster_legalization_move(
+                roster_lock_deadline.league_id,
+                *team_id,
+                roster_lock_deadline.id,
+                moved_contract.id,
+                planned_move.kind,
+                db,
+            )
+            .await?;
+            applied_moves.push(LegalizationMove {
+                contract_id: moved_contract.id,
+                kind: planned_move.kind,
+            });
+        }
+
+        if !move_descriptions.is_empty() {
+            notify_team(
+                roster_lock_deadline.league_id,
+                *team_id,
+                NotificationKind::RosterAutoLegalized,
+                Some(roster_lock_deadline.id),
+                &format!(
+                    "Your roster was illegal at {}, so the league made these moves for you: {}.",
+                    roster_lock_deadline.name,
+                    move_descriptions.join("; ")
+                ),
+                db,
+            )
+            .await?;
+        }
+
+        legalizations.push(TeamLegalization {
+            team_id: *team_id,
+            moves: applied_moves,
+        });
+    }
+    legalizations.sort_by_key(|legalization| legalization.team_id);
+
+    Ok(legalizations)
+}
+
+/// One line of the team's notification, e.g. `dropped Jane Doe ($4/1/Veteran)`.
+async fn describe_legalization_move<C>(
+    contract_model: &contract::Model,
+    kind: RosterLegalizationMoveKind,
+    db: &C,
+) -> Result<String>
+where
+    C: ConnectionTrait,
+{
+    let action = match kind {
+        RosterLegalizationMoveKind::Drop => "dropped",
+        RosterLegalizationMoveKind::ActivateFromIr => "activated from IR",
+        RosterLegalizationMoveKind::ConvertRdiToRd => "moved from RDI to RD",
+    };
+    let player = contract_model.get_player(db).await?;
+    Ok(format!(
+        "{action} {} (${}/{}/{:?})",
+        player.get_name(),
+        contract_model.salary,
+        contract_model.year_number,
+        contract_model.kind
+    ))
+}
+
+/// The fewest moves, by the rules below, that bring a roster within the regular-season limits.
+///
+/// 1. IR contracts due for activation (rules §10.1.3), then extra IR contracts lowest salary first,
+///    come back to the active roster, or are dropped when their kind's slots are already full.
+/// 2. Extra RD and RDI contracts are dropped, lowest salary first; they carry no cap penalty.
+/// 3. Extra veteran and rookie-scale contracts are dropped, lowest salary first.
+/// 4. While salary is over the cap, the cheapest contract whose drop clears the rest of the overage
+///    is dropped, or the priciest one if none does. A drop lowers the cap by its penalty, so only
+///    the salary beyond the penalty counts toward clearing the overage.
+///
+/// Active contracts in `protected` are never dropped: they are this week's auction wins, which
+/// rules §8.3.7 forbids dropping to make room for one another.
+///
+/// Stops early if nothing is left to drop, or no drop would lower the overage; validation then
+/// reports what is still broken.
+fn plan_roster_legalization(
+    team_contracts: &[contract::Model],
+    salary_snapshot: SalarySnapshot,
+    due_for_activation: &[i64],
+    protected: &[i64],
+) -> Vec<LegalizationMove> {
+    let mut roster = team_contracts.to_vec();
+    let mut cap = salary_snapshot.cap;
+    let mut moves = vec![];
+
+    let mut leaving_ir: Vec<i64> = roster
+        .iter()
+        .filter(|contract_model| {
+            contract_model.is_ir && due_for_activation.contains(&contract_model.id)
+        })
+        .map(|contract_model| contract_model.id)
+        .collect();
+    let counts = RosterCounts::from_contracts(&roster);
+    let extra_ir = counts.ir
+        - i16::try_from(leaving_ir.len()).unwrap_or(i16::MAX)
+        - REGULAR_SEASON_IR_CONTRACTS_PER_ROSTER_LIMIT;
+    let extra_ir_ids = lowest_salary_contract_ids(&roster, extra_ir, |contract_model| {
+        contract_model.is_ir && !leaving_ir.contains(&contract_model.id)
+    });
+    leaving_ir.extend(extra_ir_ids);
+    for contract_id in leaving_ir {
+        let Some(contract_kind) = roster
+            .iter()
+            .find(|contract_model| contract_model.id == contract_id)
+            .map(|contract_model| contract_model.kind)
+        else {
+            continue;
+        };
+        if has_open_slot(&RosterCounts::from_contracts(&roster), contract_kind) {
+            for contract_model in &mut roster {
+                if contract_model.id == contract_id {
+                    contract_model.is_ir = false;
+                }
+            }
+            moves.push(LegalizationMove {
+                contract_id,
+                kind: RosterLegalizationMoveKind::ActivateFromIr,
+            });
+        } else {
+            // Bringing it back would only push another contract out of its slot.
+            cap -= drop_from_roster(&mut roster, contract_id);
+            moves.push(LegalizationMove {
+                contract_id,
+                kind: RosterLegalizationMoveKind::Drop,
+            });
+        }
+    }
+
+    // Counted again, since activated IR contracts now take up their kind's slots.
+    let counts = RosterCounts::from_contracts(&roster);
+    let slot_overages = [
+        (
+            counts.rookie_development
+                - REGULAR_SEASON_ROOKIE_DEVELOPMENT_CONTRACTS_PER_ROSTER_LIMIT,
+            is_rookie_development as fn(&contract::Model) -> bool,
+        ),
+        (
+            counts.rookie_development_international
+                - REGULAR_SEASON_INTL_ROOKIE_DEVELOPMENT_CONTRACTS_PER_ROSTER_LIMIT,
+            is_rookie_development_international,
+        ),
+        (
+            counts.vet_or_rookie - REGULAR_SEASON_VET_OR_ROOKIE_CONTRACTS_PER_ROSTER_LIMIT,
+            is_active_counted_contract,
+        ),
+    ];
+    for (extra, is_in_slot) in slot_overages {
+        let droppable = |contract_model: &contract::Model| {
+            is_in_slot(contract_model) && !protected.contains(&contract_model.id)
+        };
+        for contract_id in lowest_salary_contract_ids(&roster, extra, droppable) {
+            cap -= drop_from_roster(&mut roster, contract_id);
+            moves.push(LegalizationMove {
+                contract_id,
+                kind: RosterLegalizationMoveKind::Drop,
+            });
+        }
+    }
+
+    loop {
+        let overage = salary_counted_toward_cap(&roster) - cap;
+        if overage <= 0 {
+            break;
+        }
+
+        let cap_relief = |contract_model: &contract::Model| {
+            contract_model.salary - dropped_contract_cap_penalty(contract_model.salary)
+        };
+        let droppable = roster.iter().filter(|contract_model| {
+            is_active_counted_contract(contract_model) && !protected.contains(&contract_model.id)
+        });
+        let clearing = droppable
+            .clone()
+            .filter(|contract_model| cap_relief(contract_model) >= overage)
+            .min_by_key(|contract_model| (contract_model.salary, contract_model.id));
+        let Some(best_drop) = clearing.or_else(|| {
+            droppable.max_by_key(|contract_model| {
+                (
+                    cap_relief(contract_model),
+                    std::cmp::Reverse(contract_model.id),
+                )
+            })
+        }) else {
+            break;
+        };
+        // A $1 contract frees nothing once its penalty is charged, so dropping it cannot help.
+        if cap_relief(best_drop) <= 0 {
+            break;
+        }
+        let contract_id = best_drop.id;
+
+        cap -= drop_from_roster(&mut roster, contract_id);
+        moves.push(LegalizationMove {
+            contract_id,
+            kind: RosterLegalizationMoveKind::Drop,
+        });
+    }
+
+    moves
+}
+
+/// The ids of the `count` lowest-salary contracts matching `filter`, ties broken by id.
+fn lowest_salary_contract_ids(
+    roster: &[contract::Model],
+    count: i16,
+    filter: impl Fn(&contract::Model) -> bool,
+) -> Vec<i64> {
+    let mut matching: Vec<&contract::Model> = roster
+        .iter()
+        .filter(|contract_model| filter(contract_model))
+        .collect();
+    matching.sort_by_key(|contract_model| (contract_model.salary, contract_model.id));
+    matching
+        .into_iter()
+        .take(usize::try_from(count).unwrap_or(0))
+        .map(|contract_model| contract_model.id)
+        .collect()
+}
+
+/// Removes the contract from the projected roster and returns the cap penalty its drop costs.
+fn drop_from_roster(roster: &mut Vec<contract::Model>, contract_id: i64) -> i16 {
+    let Some(index) = roster
+        .iter()
+        .position(|contract_model| contract_model.id == contract_id)
+    else {
+        return 0;
+    };
+    let dropped = roster.remove(index);
+    if is_counted_toward_cap(dropped.kind) {
+        dropped_contract_cap_penalty(dropped.salary)
+    } else {
+        0
+    }
+}
+
+/// Whether a contract of this kind could come off IR without going over its slot limit.
+const fn has_open_slot(counts: &RosterCounts, contract_kind: ContractKind) -> bool {
+    match contract_kind {
+        ContractKind::RookieDevelopment => {
+            counts.rookie_development < REGULAR_SEASON_ROOKIE_DEVELOPMENT_CONTRACTS_PER_ROSTER_LIMIT
+        }
+        ContractKind::RookieDevelopmentInternational => {
+            counts.rookie_development_international
+                < REGULAR_SEASON_INTL_ROOKIE_DEVELOPMENT_CONTRACTS_PER_ROSTER_LIMIT
+        }
+        ContractKind::Rookie | ContractKind::RookieExtension | ContractKind::Veteran => {
+            counts.vet_or_rookie < REGULAR_SEASON_VET_OR_ROOKIE_CONTRACTS_PER_ROSTER_LIMIT
+        }
+        _ => true,
+    }
+}
+
+fn is_rookie_development(contract_model: &contract::Model) -> bool {
+    !contract_model.is_ir && contract_model.kind == ContractKind::RookieDevelopment
+}
+
+fn is_rookie_development_international(contract_model: &contract::Model) -> bool {
+    !contract_model.is_ir && contract_model.kind == ContractKind::RookieDevelopmentInternational
+}
+
+fn is_active_counted_contract(contract_model: &contract::Model) -> bool {
+    !contract_model.is_ir && is_counted_toward_cap(contract_model.kind)
+}
+
+#[cfg(test)]
+mod tests {
+    use fbkl_entity::{
+        contract::{ContractKind, ContractStatus, Model},
+        roster_legalization_move::RosterLegalizationMoveKind,
+    };
+
+    use super::{LegalizationMove, plan_roster_legalization};
+    use crate::roster::SalarySnapshot;
+
+    fn contract(id: i64, kind: ContractKind, salary: i16, is_ir: bool) -> Model {
+        Model {
+            id,
+            year_number: 1,
+            kind,
+            is_ir,
+            salary,
+            end_of_season_year: 2025,
+            status: ContractStatus::Active,
+            league_id: 1,
+            league_player_id: None,
+            player_id: Some(id),
+            previous_contract_id: None,
+            original_contract_id: Some(id),
+            team_id: Some(7),
+            created_at: chrono::Utc::now().into(),
+            updated_at: chrono::Utc::now().into(),
+        }
+    }
+
+    fn drop(contract_id: i64) -> LegalizationMove {
+        LegalizationMove {
+            contract_id,
+            kind: RosterLegalizationMoveKind::Drop,
+        }
+    }
+
+    #[test]
+    fn fixes_slot_limits_with_the_cheapest_contracts() {
+        let mut contracts: Vec<Model> = (1..=21)
+            .map(|id| contract(id, ContractKind::Veteran, 5, false))
+            .collect();
+        contracts.push(contract(30, ContractKind::Veteran, 2, true));
+        contracts.push(contract(31, ContractKind::Veteran, 1, true));
+        contracts.push(contract(32, ContractKind::Rookie, 3, true));
+        contracts.extend((40..47).map(|id| {
+            contract(
+                id,
+                ContractKind::RookieDevelopment,
+                i16::try_from(id).unwrap(),
+                false,
+            )
+        }));
+
+        let moves = plan_roster_legalization(
+            &contracts,
+            SalarySnapshot {
+                salary: 105,
+                cap: 200,
+            },
+            &[],
+            &[],
+        );
+
+        // Two extra IR contracts: the cheaper one fills the last veteran slot, the next has no slot
+        // to come back to and is dropped.
+        assert_eq!(
+            moves,
+            vec![
+                LegalizationMove {
+                    contract_id: 31,
+                    kind: RosterLegalizationMoveKind::ActivateFromIr,
+                },
+                drop(30),
+                drop(40),
+            ]
+        );
+    }
+
+    #[test]
+    fn clears_the_cap_with_the_cheapest_sufficient_drop() {
+        let contracts = [
+            contract(1, ContractKind::Veteran, 50, false),
+            contract(2, ContractKind::Veteran, 20, false),
+            contract(3, ContractKind::Rookie, 10, false),
+            contract(4, ContractKind::Veteran, 9, false),
+        ];
+
+        // 89 against 80: dropping the $10 rookie frees 10 but costs 2 of cap, leaving 79 against
+        // 78. The $20 veteran frees 20 - 4 = 16, clearing the overage of 9 on its own.
+        let moves = plan_roster_legalization(
+            &contracts,
+            SalarySnapshot {
+                salary: 89,
+                cap: 80,
+            },
+            &[],
+            &[],
+        );
+
+        assert_eq!(moves, vec![drop(2)]);
+    }
+
+    #[test]
+    fn stops_when_no_drop_lowers_the_overage() {
+        let contracts = [
+            contract(1, ContractKind::Veteran, 1, false),
+            contract(2, ContractKind::Veteran, 1, false),
+        ];
+
+        // A $1 drop costs a $1 penalty, so it frees nothing against the overage.
+        let moves =
+            plan_roster_legalization(&contracts, SalarySnapshot { salary: 2, cap: 1 }, &[], &[]);
+
+        assert!(moves.is_empty());
+    }
+
+    #[test]
+    fn activates_recovered_ir_contracts_before_extra_ones() {
+        let mut contracts: Vec<Model> = (1..=10)
+            .map(|id| contract(id, ContractKind::Veteran, 5, false))
+            .collect();
+        contracts.push(contract(20, ContractKind::Veteran, 8, true));
+
+        let moves = plan_roster_legalization(
+            &contracts,
+            SalarySnapshot {
+                salary: 58,
+                cap: 200,
+            },
+            &[20],
+            &[],
+        );
+
+        assert_eq!(
+            moves,
+            vec![LegalizationMove {
+                contract_id: 20,
+                kind: RosterLegalizationMoveKind::ActivateFromIr,
+            }]
+        );
+    }
+
+    #[test]
+    fn never_drops_same_week_auction_wins() {
+        let mut contracts: Vec<Model> = (1..=21)
+            .map(|id| contract(id, ContractKind::Veteran, 5, false))
+            .collect();
+        contracts.push(contract(30, ContractKind::Veteran, 1, false));
+        contracts.push(contract(31, ContractKind::Veteran, 2, false));
+
+        let moves = plan_roster_legalization(
+            &contracts,
+            SalarySnapshot {
+                salary: 108,
+                cap: 200,
+            },
+            &[],
+            &[30, 31],
+        );
+
+        assert_eq!(moves, vec![drop(1)]);
+    }
+}
@@ -1,32 +1,111 @@
+use std::collections::{BTreeMap, HashSet};
+
 use color_eyre::eyre::Result;
 use fbkl_entity::{
-    deadline::{self},
+    contract_queries,
+    deadline::{self, DeadlineKind},
+    league::RosterLegalizationPolicy,
+    league_queries,
+    notification::NotificationKind,
     sea_orm::{ConnectionTrait, TransactionTrait},
-    team_update::TeamUpdateStatus,
-    team_update_queries,
+    team_update, team_update_queries,
 };
 use tracing::instrument;
 
-use super::validate_league_rosters;
+use crate::{notifications::notify_team, weekly_moves::commit_weekly_moves};
+
+use super::{
+    TeamRosterViolations, convert_league_rdi_contracts_on_nba_rosters, legalize_league_rosters,
+    validate_league_rosters, validate_rosters::format_team_contracts,
+};
 
+/// Locks every roster in the league at a roster-lock deadline.
+///
+/// Each team is judged on its final state alone (§13.1.3: a roster may be illegal mid-week). A
+/// legal team's moves for this deadline are committed (`Done`) in the owner's order. An illegal team's moves stay
+/// `Pending` until the commissioner rules on them with `rule_on_held_roster_moves` (§13.1.2),
+/// without holding up the rest of the league.
+/// Returns every illegal team with every rule it breaks.
+///
+/// At weekly locks in a league that auto-legalizes, illegal rosters are first fixed with drops and
+/// IR activations (`legalize_league_rosters`); those moves are committed with the team's own. At the
+/// final preseason lock, RDI contracts whose players reached an NBA roster are moved to RD/1 first,
+/// whatever the league's policy, since the rules require it.
 #[instrument(skip(db))]
-pub async fn lock_rosters<C>(deadline_model: &deadline::Model, db: &C) -> Result<()>
+pub async fn lock_rosters<C>(
+    deadline_model: &deadline::Model,
+    db: &C,
+) -> Result<Vec<TeamRosterViolations>>
 where
     C: ConnectionTrait + TransactionTrait,
 {
-    validate_league_rosters(deadline_model, db).await?;
-
-    let deadline_team_updates =
-        team_update_queries::find_team_updates_for_deadline(deadline_model, db).await?;
-    team_update_queries::update_team_updates_with_status(
-        deadline_team_updates
-            .into_iter()
-            .map(|team_update_model| team_update_model.id)
-            .collect(),
-        TeamUpdateStatus::Done,
-        db,
-    )
-    .await?;
+    for conversion in convert_league_rdi_contracts_on_nba_rosters(deadline_model, db).await? {
+        tracing::info!(
+            deadline_id = deadline_model.id,
+            team_id = conversion.team_id,
+            moves = ?conversion.moves,
+            "Converted RDI contracts on NBA rosters to RD/1 at lock."
+        );
+    }
+
+    let is_weekly_lock = matches!(
+        deadline_model.kind,
+        DeadlineKind::Week1RosterLock | DeadlineKind::InSeasonRosterLock
+    );
+    if is_weekly_lock {
+        let league_model = league_queries::find_league_by_id(deadline_model.league_id, db).await?;
+        if league_model.roster_legalization_policy == RosterLegalizationPolicy::AutoLegalize {
+            for legalization in legalize_league_rosters(deadline_model, db).await? {
+                tracing::info!(
+                    deadline_id = deadline_model.id,
+                    team_id = legalization.team_id,
+                    moves = ?legalization.moves,
+                    "Auto-legalized roster at lock."
+                );
+            }
+        }
+    }
+
+    let illegal_teams = validate_league_rosters(deadline_model, db).await?;
+    for team_violations in &illegal_teams {
+        let team_contracts =
+            contract_queries::find_active_contracts_for_team(team_violations.team_id, db).await?;
+        tracing::warn!(
+            deadline_id = deadline_model.id,
+            team_id = team_violations.team_id,
+            violations = ?team_violations.violations,
+            "Roster is illegal at lock; its moves stay pending. Contracts:\n{}",
+            format_team_contracts(&team_contracts, db).await?
+        );
+        notify_team(
+            deadline_model.league_id,
+            team_violations.team_id,
+            NotificationKind::RosterLockFailed,
+            Some(deadline_model.id),
+            &roster_lock_failed_message(team_violations),
+            db,
+        )
+        .await?;
+    }
+    let illegal_team_ids: HashSet<i64> = illegal_teams
+        .iter()
+        .map(|team_violations| team_violations.team_id)
+        .collect();
+
+    let mut legal_team_moves: BTreeMap<i64, Vec<team_update::Model>> = BTreeMap::new();
+    for team_update_model in
+        team_update_queries::find_team_updates_for_deadline(deadline_model, db).await?
+    {
+        if !illegal_team_ids.contains(&team_update_model.team_id) {
+            legal_team_moves
+                .entry(team_update_model.team_id)
+                .or_default()
+                .push(team_update_model);
+        }
+    }
+    for team_moves in legal_team_moves.into_values() {
+        commit_weekly_moves(team_moves, db).await?;
+    }
 
     // if deadline_model.kind == DeadlineKind::PreseasonFinalRosterLock {
     //     // Propagate failure so the wrapping DB transaction rolls back and the scheduler
@@ -40,5 +119,18 @@ where
     //     .wrap_err("Error generating future draft picks during final roster lock")?;
     // }
 
-    Ok(())
+    Ok(illegal_teams)
+}
+
+fn roster_lock_failed_message(team_violations: &TeamRosterViolations) -> String {
+    let violations: Vec<String> = team_violations
+        .violations
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    format!(
+        "Your roster was illegal at the lock, so your moves are held until the commissioner approves \
+         them or sends them back to be fixed by the next lock: {}",
+        violations.join(" ")
+    )
 }
@@ -1,5 +1,11 @@
+mod convert_rdi_contracts;
+mod legalize_rosters;
 mod lock_rosters;
+mod rule_on_held_roster_moves;
 mod validate_rosters;
 
+pub use convert_rdi_contracts::*;
+pub use legalize_rosters::*;
 pub use lock_rosters::*;
+pub use rule_on_held_roster_moves::*;
 pub use validate_rosters::*;
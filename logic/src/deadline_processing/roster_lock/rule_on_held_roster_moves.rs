@@ -0,0 +1,165 @@
+use std::collections::BTreeSet;
+
+use color_eyre::eyre::Result;
+use fbkl_entity::{
+    deadline::{self, DeadlineKind},
+    notification::NotificationKind,
+    sea_orm::{ConnectionTrait, prelude::DateTimeWithTimeZone},
+    team_update, team_update_queries, transaction_queries,
+};
+use tracing::instrument;
+
+use crate::{
+    notifications::notify_team,
+    weekly_moves::{commit_weekly_moves, find_upcoming_weekly_roster_lock},
+};
+
+/// The commissioner's ruling on the moves a roster lock held because the team's roster was
+/// illegal (§13.1.2).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeldRosterMovesRuling {
+    /// Let the roster stand: the held moves are committed as they are.
+    Approve,
+    /// Send the moves back to the owner: they join the queue of the next weekly roster lock still
+    /// to run, and that lock judges the roster again.
+    Reject,
+}
+
+/// Why a ruling was refused.
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum HeldRosterMovesRejection {
+    #[error("The deadline is not a roster lock that has passed.")]
+    NotAPassedRosterLock,
+    #[error("The team has no moves held at this roster lock.")]
+    NoHeldMoves,
+    #[error("No weekly roster lock is left to run this season to send the moves to.")]
+    NoLaterRosterLock,
+}
+
+/// Rules on the moves `lock_deadline_model` left `Pending` for an illegal team, and tells the
+/// team. Returns the moves as ruled on.
+///
+/// Approved moves are committed (`Done`) in the owner's order. Rejected ones are moved, with their
+/// transactions, to the first weekly roster lock after `now`, so they show in that week's queue
+/// until the owner makes the roster legal and that lock commits them. A lock that has already run
+/// would never judge them, hence `NoLaterRosterLock` once the season's last lock has passed.
+#[instrument(skip(db))]
+pub async fn rule_on_held_roster_moves<C>(
+    team_id: i64,
+    lock_deadline_model: &deadline::Model,
+    ruling: HeldRosterMovesRuling,
+    now: DateTimeWithTimeZone,
+    db: &C,
+) -> Result<Vec<team_update::Model>>
+where
+    C: ConnectionTrait,
+{
+    if !is_passed_roster_lock(lock_deadline_model, now) {
+        return Err(HeldRosterMovesRejection::NotAPassedRosterLock.into());
+    }
+    let held_moves = team_update_queries::find_pending_team_updates_for_team_and_deadline(
+        team_id,
+        lock_deadline_model.id,
+        db,
+    )
+    .await?;
+    if held_moves.is_empty() {
+        return Err(HeldRosterMovesRejection::NoHeldMoves.into());
+    }
+
+    let (ruled_moves, message) = match ruling {
+        HeldRosterMovesRuling::Approve => {
+            let committed_moves = commit_weekly_moves(held_moves, db).await?;
+            (
+                committed_moves,
+                format!(
+                    "The commissioner approved the moves held at {}; they are committed.",
+                    lock_deadline_model.name
+                ),
+            )
+        }
+        HeldRosterMovesRuling::Reject => {
+            let next_lock_model = find_upcoming_weekly_roster_lock(
+                lock_deadline_model.league_id,
+                lock_deadline_model.end_of_season_year,
+                now,
+                db,
+            )
+            .await?
+            .ok_or(HeldRosterMovesRejection::NoLaterRosterLock)?;
+            let transaction_ids: BTreeSet<i64> = held_moves
+                .iter()
+                .filter_map(|team_update_model| team_update_model.transaction_id)
+                .collect();
+            transaction_queries::update_transactions_deadline(
+                transaction_ids.into_iter().collect(),
+                next_lock_model.id,
+                db,
+            )
+            .await?;
+            (
+                held_moves,
+                format!(
+                    "The commissioner rejected the moves held at {}. Make your roster legal by {}, \
+                     when they are judged again.",
+                    lock_deadline_model.name, next_lock_model.name
+                ),
+            )
+        }
+    };
+
+    notify_team(
+        lock_deadline_model.league_id,
+        team_id,
+        NotificationKind::HeldRosterMovesRuled,
+        Some(lock_deadline_model.id),
+        &message,
+        db,
+    )
+    .await?;
+
+    Ok(ruled_moves)
+}
+
+fn is_passed_roster_lock(deadline_model: &deadline::Model, now: DateTimeWithTimeZone) -> bool {
+    matches!(
+        deadline_model.kind,
+        DeadlineKind::PreseasonFinalRosterLock
+            | DeadlineKind::Week1RosterLock
+            | DeadlineKind::InSeasonRosterLock
+    ) && deadline_model.date_time <= now
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Duration};
+    use fbkl_entity::deadline::{self, DeadlineKind};
+
+    use super::is_passed_roster_lock;
+
+    fn deadline(kind: DeadlineKind) -> deadline::Model {
+        let date_time = DateTime::parse_from_rfc3339("2026-11-02T12:00:00-06:00").unwrap();
+        deadline::Model {
+            id: 1,
+            date_time,
+            kind,
+            name: "Week 3 roster lock".to_owned(),
+            end_of_season_year: 2027,
+            league_id: 1,
+            created_at: date_time,
+            updated_at: date_time,
+        }
+    }
+
+    #[test]
+    fn only_passed_roster_locks_hold_moves() {
+        let lock = deadline(DeadlineKind::InSeasonRosterLock);
+        assert!(is_passed_roster_lock(&lock, lock.date_time));
+        assert!(!is_passed_roster_lock(
+            &lock,
+            lock.date_time - Duration::minutes(1)
+        ));
+        let auction_end = deadline(DeadlineKind::FreeAgentAuctionEnd);
+        assert!(!is_passed_roster_lock(&auction_end, auction_end.date_time));
+    }
+}
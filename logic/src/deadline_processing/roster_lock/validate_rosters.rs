@@ -7,22 +7,76 @@ use fbkl_constants::league_rules::{
     REGULAR_SEASON_VET_OR_ROOKIE_CONTRACTS_PER_ROSTER_LIMIT,
 };
 use fbkl_entity::{
-    contract::{self, ContractKind},
-    contract_queries,
+    contract, contract_queries,
     deadline::{self, DeadlineKind},
     sea_orm::ConnectionTrait,
 };
 use multimap::MultiMap;
 use tracing::instrument;
 
-use crate::roster::{SalarySnapshot, calculate_team_contract_salary};
+use crate::{
+    ir::find_ir_contracts_due_for_activation,
+    roster::{RosterCounts, SalarySnapshot, calculate_team_contract_salary},
+    weekly_moves::{SameWeekDropRejection, find_same_week_drop_violations},
+};
+
+/// One rule a roster breaks at a lock.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum RosterViolation {
+    #[error("Contract salaries ({salary}) exceed the team's cap ({cap}).")]
+    OverCap { salary: i16, cap: i16 },
+    #[error("Preseason roster has {count} contracts; the limit is {limit}.")]
+    OverPreseasonContractLimit { count: i16, limit: i16 },
+    #[error("Roster has {count} veteran or rookie-scale contracts; the limit is {limit}.")]
+    OverVetOrRookieLimit { count: i16, limit: i16 },
+    #[error("Roster has {count} rookie development contracts; the limit is {limit}.")]
+    OverRookieDevelopmentLimit { count: i16, limit: i16 },
+    #[error("Roster has {count} international rookie development contracts; the limit is {limit}.")]
+    OverRookieDevelopmentInternationalLimit { count: i16, limit: i16 },
+    #[error("Roster has {count} IR contracts; the limit is {limit}.")]
+    OverIrLimit { count: i16, limit: i16 },
+    #[error(
+        "Roster has {count} IR contracts whose players have left the NBA injured reserve and must come off IR with the team's pickup or trade."
+    )]
+    RecoveredPlayersOnIr { count: i16 },
+    #[error(
+        "Roster dropped players won at auction this week to make room for others won the same week (rules §8.3.7): {}.",
+        describe_same_week_drops(.drops)
+    )]
+    DroppedSameWeekAuctionWins { drops: Vec<SameWeekDropRejection> },
+}
+
+fn describe_same_week_drops(drops: &[SameWeekDropRejection]) -> String {
+    drops
+        .iter()
+        .map(|same_week_drop| {
+            format!(
+                "{} (contract {}) for {}",
+                same_week_drop.dropped_player,
+                same_week_drop.contract_id,
+                same_week_drop.other_player
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Every rule one team's roster breaks at a lock.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TeamRosterViolations {
+    pub team_id: i64,
+    pub violations: Vec<RosterViolation>,
+}
 
-/// Validate if a roster is ready for a lock.
+/// Validates every team's roster for a lock, each team independently.
+///
+/// Returns the teams whose rosters break a rule, with every rule each one breaks; an empty list
+/// means the whole league is legal.
 #[instrument(skip(db))]
 pub async fn validate_league_rosters<C>(
     roster_lock_deadline: &deadline::Model,
     db: &C,
-) -> Result<()>
+) -> Result<Vec<TeamRosterViolations>>
 where
     C: ConnectionTrait,
 {
@@ -44,26 +98,69 @@ where
             })
             .collect();
 
+    let mut illegal_teams = vec![];
     for (team_id, team_contracts) in league_contracts_by_team.iter_all() {
-        validate_roster_ir_slot_limits(*team_id, team_contracts, db).await?;
-        validate_roster_contract_type_limits_not_exceeded(
-            *team_id,
-            team_contracts,
-            roster_lock_deadline,
-            db,
-        )
-        .await?;
-        validate_roster_cap_not_exceeded(*team_id, team_contracts, roster_lock_deadline, db)
-            .await?;
+        let violations =
+            find_roster_violations(*team_id, team_contracts, roster_lock_deadline, db).await?;
+        if !violations.is_empty() {
+            illegal_teams.push(TeamRosterViolations {
+                team_id: *team_id,
+                violations,
+            });
+        }
     }
+    illegal_teams.sort_by_key(|team_violations| team_violations.team_id);
 
-    Ok(())
+    Ok(illegal_teams)
+}
+
+/// Every rule the given roster would break at `roster_lock_deadline`.
+#[instrument(skip(db))]
+pub async fn find_roster_violations<C>(
+    team_id: i64,
+    team_contracts: &[contract::Model],
+    roster_lock_deadline: &deadline::Model,
+    db: &C,
+) -> Result<Vec<RosterViolation>>
+where
+    C: ConnectionTrait,
+{
+    let mut violations = roster_contract_type_violations(
+        &RosterCounts::from_contracts(team_contracts),
+        roster_lock_deadline.kind,
+    )?;
+
+    let SalarySnapshot { salary, cap } =
+        calculate_team_contract_salary(team_id, team_contracts, roster_lock_deadline, db).await?;
+    if salary > cap {
+        violations.push(RosterViolation::OverCap { salary, cap });
+    }
+
+    let due_for_activation =
+        find_ir_contracts_due_for_activation(team_id, team_contracts, db).await?;
+    if !due_for_activation.is_empty() {
+        violations.push(RosterViolation::RecoveredPlayersOnIr {
+            count: i16::try_from(due_for_activation.len())?,
+        });
+    }
+
+    let same_week_drops = find_same_week_drop_violations(team_id, roster_lock_deadline, db).await?;
+    if !same_week_drops.is_empty() {
+        violations.push(RosterViolation::DroppedSameWeekAuctionWins {
+            drops: same_week_drops,
+        });
+    }
+
+    Ok(violations)
 }
 
 /// Formats a list of team contracts into a readable, newline-separated list for
 /// inclusion in error messages. Each line is `{player_name} ${value}/{year}/{kind}`,
 /// sorted by contract kind.
-async fn format_team_contracts<C>(team_contracts: &[contract::Model], db: &C) -> Result<String>
+pub(super) async fn format_team_contracts<C>(
+    team_contracts: &[contract::Model],
+    db: &C,
+) -> Result<String>
 where
     C: ConnectionTrait,
 {
@@ -89,66 +186,20 @@ where
         .join("\n"))
 }
 
-async fn validate_roster_cap_not_exceeded<C>(
-    team_id: i64,
-    team_contracts: &[contract::Model],
-    roster_lock_deadline: &deadline::Model,
-    db: &C,
-) -> Result<()>
-where
-    C: ConnectionTrait,
-{
-    let SalarySnapshot {
-        salary: total_contract_amount,
-        cap: team_salary_cap,
-    } = calculate_team_contract_salary(team_id, team_contracts, roster_lock_deadline, db).await?;
+pub(crate) fn roster_contract_type_violations(
+    counts: &RosterCounts,
+    deadline_kind: DeadlineKind,
+) -> Result<Vec<RosterViolation>> {
+    let mut violations = vec![];
 
-    if total_contract_amount > team_salary_cap {
-        let contracts_str = format_team_contracts(team_contracts, db).await?;
-        bail!(
-            "Roster contracts are invalid for roster lock: contract salaries exceed the team's cap. Deadline: {}, League: {}, End-of-season year: {}, Team: {}. Salary/Cap: {}/{}. Contracts:\n{}",
-            roster_lock_deadline.id,
-            roster_lock_deadline.league_id,
-            roster_lock_deadline.end_of_season_year,
-            team_id,
-            total_contract_amount,
-            team_salary_cap,
-            contracts_str
-        );
+    if counts.ir > REGULAR_SEASON_IR_CONTRACTS_PER_ROSTER_LIMIT {
+        violations.push(RosterViolation::OverIrLimit {
+            count: counts.ir,
+            limit: REGULAR_SEASON_IR_CONTRACTS_PER_ROSTER_LIMIT,
+        });
     }
 
-    Ok(())
-}
-
-async fn validate_roster_contract_type_limits_not_exceeded<C>(
-    team_id: i64,
-    team_contracts: &[contract::Model],
-    roster_lock_deadline: &deadline::Model,
-    db: &C,
-) -> Result<()>
-where
-    C: ConnectionTrait,
-{
-    let mut num_rd_contracts = 0;
-    let mut num_intl_rd_contracts = 0;
-    let mut num_v_r_contracts = 0;
-
-    for contract_model in team_contracts {
-        if contract_model.is_ir {
-            continue;
-        }
-
-        match contract_model.kind {
-            ContractKind::RookieDevelopment => num_rd_contracts += 1,
-            ContractKind::RookieDevelopmentInternational => num_intl_rd_contracts += 1,
-            ContractKind::Rookie | ContractKind::RookieExtension | ContractKind::Veteran => {
-                num_v_r_contracts += 1;
-            }
-            _ => (),
-        }
-    }
-
-    match roster_lock_deadline.kind {
+    match deadline_kind {
         DeadlineKind::PreseasonKeeper => {
             bail!("Not validating pre-season keeper deadline in this function.")
         }
@@ -159,13 +210,14 @@ where
         | DeadlineKind::PreseasonFaAuctionStart
         | DeadlineKind::PreseasonFaAuctionEnd
         | DeadlineKind::PreseasonRookieDraftStart => {
-            if num_rd_contracts + num_intl_rd_contracts + num_v_r_contracts
-                > PRE_SEASON_CONTRACTS_PER_ROSTER_LIMIT
-            {
-                bail!(
-                    "Preseason roster cannot exceed {} contracts.",
-                    PRE_SEASON_CONTRACTS_PER_ROSTER_LIMIT
-                )
+            let count = counts.rookie_development
+                + counts.rookie_development_international
+                + counts.vet_or_rookie;
+            if count > PRE_SEASON_CONTRACTS_PER_ROSTER_LIMIT {
+                violations.push(RosterViolation::OverPreseasonContractLimit {
+                    count,
+                    limit: PRE_SEASON_CONTRACTS_PER_ROSTER_LIMIT,
+                });
             }
         }
         DeadlineKind::PreseasonFinalRosterLock
@@ -176,61 +228,97 @@ where
         | DeadlineKind::FreeAgentAuctionEnd
         | DeadlineKind::TradeDeadlineAndPlayoffStart
         | DeadlineKind::SeasonEnd => {
-            if num_rd_contracts > REGULAR_SEASON_ROOKIE_DEVELOPMENT_CONTRACTS_PER_ROSTER_LIMIT {
-                bail!(
-                    "Roster cannot exceed {} rookie development contracts. (team = {}). Contracts:\n{}",
-                    REGULAR_SEASON_ROOKIE_DEVELOPMENT_CONTRACTS_PER_ROSTER_LIMIT,
-                    team_id,
-                    format_team_contracts(team_contracts, db).await?
-                );
+            if counts.rookie_development
+                > REGULAR_SEASON_ROOKIE_DEVELOPMENT_CONTRACTS_PER_ROSTER_LIMIT
+            {
+                violations.push(RosterViolation::OverRookieDevelopmentLimit {
+                    count: counts.rookie_development,
+                    limit: REGULAR_SEASON_ROOKIE_DEVELOPMENT_CONTRACTS_PER_ROSTER_LIMIT,
+                });
             }
 
-            if num_intl_rd_contracts
+            if counts.rookie_development_international
                 > REGULAR_SEASON_INTL_ROOKIE_DEVELOPMENT_CONTRACTS_PER_ROSTER_LIMIT
             {
-                bail!(
-                    "Roster cannot have more than {} international rookie development contract. (team = {}). Contracts:\n{}",
-                    REGULAR_SEASON_INTL_ROOKIE_DEVELOPMENT_CONTRACTS_PER_ROSTER_LIMIT,
-                    team_id,
-                    format_team_contracts(team_contracts, db).await?
-                );
+                violations.push(RosterViolation::OverRookieDevelopmentInternationalLimit {
+                    count: counts.rookie_development_international,
+                    limit: REGULAR_SEASON_INTL_ROOKIE_DEVELOPMENT_CONTRACTS_PER_ROSTER_LIMIT,
+                });
             }
 
-            if num_v_r_contracts > REGULAR_SEASON_VET_OR_ROOKIE_CONTRACTS_PER_ROSTER_LIMIT {
-                bail!(
-                    "Roster cannot have more than {} veteran or rookie-scale contracts. (team = {}). Contracts:\n{}",
-                    REGULAR_SEASON_VET_OR_ROOKIE_CONTRACTS_PER_ROSTER_LIMIT,
-                    team_id,
-                    format_team_contracts(team_contracts, db).await?
-                );
+            if counts.vet_or_rookie > REGULAR_SEASON_VET_OR_ROOKIE_CONTRACTS_PER_ROSTER_LIMIT {
+                violations.push(RosterViolation::OverVetOrRookieLimit {
+                    count: counts.vet_or_rookie,
+                    limit: REGULAR_SEASON_VET_OR_ROOKIE_CONTRACTS_PER_ROSTER_LIMIT,
+                });
             }
         }
     }
 
-    Ok(())
+    Ok(violations)
 }
 
-async fn validate_roster_ir_slot_limits<C>(
-    team_id: i64,
-    team_contracts: &[contract::Model],
-    db: &C,
-) -> Result<()>
-where
-    C: ConnectionTrait,
-{
-    // roster IR counts are far below i16::MAX
-    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
-    let number_ir_contracts = team_contracts
-        .iter()
-        .filter(|contract_model| contract_model.is_ir)
-        .count() as i16;
-    if !(0..=REGULAR_SEASON_IR_CONTRACTS_PER_ROSTER_LIMIT).contains(&number_ir_contracts) {
-        bail!(
-            "Cannot exceed {} IR contract on roster. (team = {}). Contracts:\n{}",
-            REGULAR_SEASON_IR_CONTRACTS_PER_ROSTER_LIMIT,
-            team_id,
-            format_team_contracts(team_contracts, db).await?
+#[cfg(test)]
+mod tests {
+    use fbkl_entity::deadline::DeadlineKind;
+
+    use super::{RosterViolation, roster_contract_type_violations};
+    use crate::{roster::RosterCounts, weekly_moves::SameWeekDropRejection};
+
+    #[test]
+    fn same_week_drops_name_each_dropped_player() {
+        let violation = RosterViolation::DroppedSameWeekAuctionWins {
+            drops: vec![SameWeekDropRejection {
+                contract_id: 12,
+                dropped_player: "Jane Doe".to_owned(),
+                other_player: "John Roe".to_owned(),
+            }],
+        };
+
+        assert_eq!(
+            violation.to_string(),
+            "Roster dropped players won at auction this week to make room for others won the same week (rules §8.3.7): Jane Doe (contract 12) for John Roe."
+        );
+    }
+
+    #[test]
+    fn reports_every_broken_limit_at_once() {
+        let counts = RosterCounts {
+            ir: 2,
+            rookie_development: 7,
+            rookie_development_international: 1,
+            vet_or_rookie: 23,
+        };
+
+        let violations =
+            roster_contract_type_violations(&counts, DeadlineKind::InSeasonRosterLock).unwrap();
+
+        assert_eq!(
+            violations,
+            vec![
+                RosterViolation::OverIrLimit { count: 2, limit: 1 },
+                RosterViolation::OverRookieDevelopmentLimit { count: 7, limit: 6 },
+                RosterViolation::OverVetOrRookieLimit {
+                    count: 23,
+                    limit: 22
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn preseason_deadlines_only_count_the_32_man_limit() {
+        let counts = RosterCounts {
+            ir: 0,
+            rookie_development: 8,
+            rookie_development_international: 2,
+            vet_or_rookie: 22,
+        };
+
+        assert!(
+            roster_contract_type_violations(&counts, DeadlineKind::PreseasonFaAuctionEnd)
+                .unwrap()
+                .is_empty()
         );
     }
-    Ok(())
 }
@@ -8,7 +8,10 @@ use fbkl_entity::{
 };
 use tracing::instrument;
 
-use crate::roster::{SalarySnapshot, calculate_team_contract_salary_with_model};
+use crate::{
+    roster::{SalarySnapshot, calculate_team_contract_salary_with_model},
+    weekly_moves::validate_same_week_drop,
+};
 
 use super::drop_contract_team_update::create_drop_contract_team_update;
 
@@ -29,6 +32,9 @@ where
             contract_model.id
         )
     })?;
+    let team_contracts = team_model.get_active_contracts(db).await?;
+    validate_same_week_drop(&contract_model, &team_contracts, deadline_model, db).await?;
+
     let SalarySnapshot {
         salary: original_salary,
         cap: original_salary_cap,
@@ -1,4 +1,6 @@
 mod drop_contract_from_team;
 mod drop_contract_team_update;
+mod waive_drop_penalty;
 
 pub use drop_contract_from_team::*;
+pub use waive_drop_penalty::*;
@@ -0,0 +1,151 @@
+use chrono::{DateTime, FixedOffset};
+use color_eyre::eyre::Result;
+use fbkl_entity::{
+    contract_queries,
+    deadline::{self, DeadlineKind},
+    deadline_queries::find_deadline_for_season_by_type,
+    sea_orm::ConnectionTrait,
+    transaction::{self, TransactionKind},
+    transaction_queries,
+};
+use tracing::instrument;
+
+use crate::roster::is_counted_toward_cap;
+
+/// Why a commissioner's §9.1.4 penalty waiver was refused.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum DropPenaltyWaiverRejection {
+    #[error("Transaction {transaction_id} is not a contract drop.")]
+    NotADrop { transaction_id: i64 },
+    #[error("Transaction {transaction_id} belongs to another league.")]
+    WrongLeague { transaction_id: i64 },
+    #[error("The drop in transaction {transaction_id} carried no cap penalty to waive.")]
+    NoPenalty { transaction_id: i64 },
+    #[error("The cap penalty for transaction {transaction_id} has already been waived.")]
+    AlreadyWaived { transaction_id: i64 },
+    #[error(
+        "Penalty waivers are only granted between the keeper deadline and the end of the drop's season."
+    )]
+    OutsideWaiverWindow,
+}
+
+/// Waives the 20% cap penalty on a regular-season drop of a player who has died (rules §9.1.4, the
+/// Nick Adenhart rule). The waiver is recorded on the drop transaction, so it covers `player`- and
+/// `league_player`-backed contracts alike.
+///
+/// Granted from the season's keeper deadline until its season end; earlier drops carry no penalty.
+#[instrument(skip(db))]
+pub async fn waive_drop_contract_penalty<C>(
+    league_id: i64,
+    drop_transaction: transaction::Model,
+    team_user_id: i64,
+    reason: String,
+    now: DateTime<FixedOffset>,
+    db: &C,
+) -> Result<transaction::Model>
+where
+    C: ConnectionTrait,
+{
+    let transaction_id = drop_transaction.id;
+    if drop_transaction.league_id != league_id {
+        return Err(DropPenaltyWaiverRejection::WrongLeague { transaction_id }.into());
+    }
+    let Some(contract_id) = drop_transaction
+        .contract_id
+        .filter(|_| drop_transaction.kind == TransactionKind::TeamUpdateDropContract)
+    else {
+        return Err(DropPenaltyWaiverRejection::NotADrop { transaction_id }.into());
+    };
+    if drop_transaction.drop_penalty_waived_at.is_some() {
+        return Err(DropPenaltyWaiverRejection::AlreadyWaived { transaction_id }.into());
+    }
+
+    let keeper_deadline = find_deadline_for_season_by_type(
+        league_id,
+        drop_transaction.end_of_season_year,
+        DeadlineKind::PreseasonKeeper,
+        db,
+    )
+    .await?;
+    let season_end = find_deadline_for_season_by_type(
+        league_id,
+        drop_transaction.end_of_season_year,
+        DeadlineKind::SeasonEnd,
+        db,
+    )
+    .await?;
+    if !is_within_waiver_window(&keeper_deadline, &season_end, now) {
+        return Err(DropPenaltyWaiverRejection::OutsideWaiverWindow.into());
+    }
+
+    let drop_deadline = drop_transaction.get_deadline(db).await?;
+    let dropped_contract = contract_queries::find_contract_by_id(contract_id, db).await?;
+    if drop_deadline.is_preseason_keeper_or_before()
+        || !is_counted_toward_cap(dropped_contract.kind)
+    {
+        return Err(DropPenaltyWaiverRejection::NoPenalty { transaction_id }.into());
+    }
+
+    transaction_queries::waive_drop_penalty(drop_transaction, team_user_id, reason, db).await
+}
+
+fn is_within_waiver_window(
+    keeper_deadline: &deadline::Model,
+    season_end: &deadline::Model,
+    now: DateTime<FixedOffset>,
+) -> bool {
+    keeper_deadline.date_time <= now && now < season_end.date_time
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, FixedOffset};
+    use fbkl_entity::deadline::{self, DeadlineKind};
+
+    use super::is_within_waiver_window;
+
+    fn deadline_at(kind: DeadlineKind, date_time: &str) -> deadline::Model {
+        let date_time = DateTime::parse_from_rfc3339(date_time).unwrap();
+        deadline::Model {
+            id: 1,
+            date_time,
+            kind,
+            name: format!("{kind:?}"),
+            end_of_season_year: 2027,
+            league_id: 1,
+            created_at: date_time,
+            updated_at: date_time,
+        }
+    }
+
+    fn at(date_time: &str) -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339(date_time).unwrap()
+    }
+
+    #[test]
+    fn waivers_open_at_the_keeper_deadline_and_close_at_season_end() {
+        let keeper = deadline_at(DeadlineKind::PreseasonKeeper, "2026-09-01T00:00:00+00:00");
+        let season_end = deadline_at(DeadlineKind::SeasonEnd, "2027-04-15T00:00:00+00:00");
+
+        assert!(!is_within_waiver_window(
+            &keeper,
+            &season_end,
+            at("2026-08-31T23:59:59+00:00")
+        ));
+        assert!(is_within_waiver_window(
+            &keeper,
+            &season_end,
+            at("2026-09-01T00:00:00+00:00")
+        ));
+        assert!(is_within_waiver_window(
+            &keeper,
+            &season_end,
+            at("2027-02-01T00:00:00+00:00")
+        ));
+        assert!(!is_within_waiver_window(
+            &keeper,
+            &season_end,
+            at("2027-04-15T00:00:00+00:00")
+        ));
+    }
+}
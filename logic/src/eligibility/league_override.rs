@@ -0,0 +1,84 @@
+//! A merged league player's override stays on the league player row, so it only holds in that
+//! player's league. The real player's own override, which holds everywhere, still wins.
+
+use color_eyre::Result;
+use fbkl_entity::{
+    contract::{self, RelatedPlayer},
+    league_player_queries,
+    player::EligibilityClassification,
+    sea_orm::ConnectionTrait,
+};
+use tracing::instrument;
+
+use super::PlayerEligibilityFacts;
+
+impl PlayerEligibilityFacts {
+    /// These facts with `league_override` filled in when the player has no override of its own.
+    #[must_use]
+    pub const fn with_league_override(
+        mut self,
+        league_override: Option<EligibilityClassification>,
+    ) -> Self {
+        if self.eligibility_override.is_none() {
+            self.eligibility_override = league_override;
+        }
+        self
+    }
+}
+
+/// The eligibility facts of the player on a contract, as they stand in the contract's league.
+#[instrument(skip(db))]
+pub async fn find_contract_player_eligibility_facts<C>(
+    contract_model: &contract::Model,
+    db: &C,
+) -> Result<PlayerEligibilityFacts>
+where
+    C: ConnectionTrait,
+{
+    Ok(match contract_model.get_player(db).await? {
+        RelatedPlayer::Player(player_model) => {
+            let league_override = league_player_queries::find_league_player_linked_to_player(
+                contract_model.league_id,
+                player_model.id,
+                db,
+            )
+            .await?
+            .and_then(|league_player_model| league_player_model.eligibility_override);
+            PlayerEligibilityFacts::from(&player_model).with_league_override(league_override)
+        }
+        RelatedPlayer::LeaguePlayer(league_player_model) => {
+            PlayerEligibilityFacts::from(&league_player_model)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use fbkl_entity::player::EligibilityClassification::{
+        Ineligible, RookieDraftEligible, VeteranAuctionEligible,
+    };
+
+    use super::PlayerEligibilityFacts;
+
+    #[test]
+    fn a_league_override_only_fills_in_for_a_player_without_one() {
+        let no_override = PlayerEligibilityFacts::default();
+        assert_eq!(
+            no_override
+                .with_league_override(Some(Ineligible))
+                .eligibility_override,
+            Some(Ineligible)
+        );
+
+        let own_override = PlayerEligibilityFacts {
+            eligibility_override: Some(VeteranAuctionEligible),
+            ..PlayerEligibilityFacts::default()
+        };
+        assert_eq!(
+            own_override
+                .with_league_override(Some(RookieDraftEligible))
+                .eligibility_override,
+            Some(VeteranAuctionEligible)
+        );
+    }
+}
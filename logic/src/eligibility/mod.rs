@@ -2,12 +2,14 @@
 //! belongs to, per spec 10.
 
 mod classify;
+mod league_override;
 mod pools;
 mod rdi;
 
 pub use classify::{PlayerEligibilityFacts, classify_player};
+pub use league_override::find_contract_player_eligibility_facts;
 pub use pools::{
     VeteranAuctionPool, build_in_season_fa_pool, build_rookie_draft_eligible_pool,
     build_veteran_auction_pool,
 };
-pub use rdi::validate_rdi_eligible;
+pub use rdi::{RdiIneligibility, is_due_for_rd_conversion, validate_rdi_eligible};
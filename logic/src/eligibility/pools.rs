@@ -118,12 +118,19 @@ where
     let league_players =
         league_player_queries::find_league_players_in_league(league_id, db).await?;
 
+    // A merged league player's override holds for his real player in this league only.
+    let league_overrides: HashMap<i64, EligibilityClassification> = league_players
+        .iter()
+        .filter_map(|model| Some((model.real_player_id?, model.eligibility_override?)))
+        .collect();
+
     let candidates = players
         .into_iter()
         .map(|model| {
             (
                 PlayerRef::Player(model.id),
-                PlayerEligibilityFacts::from(&model),
+                PlayerEligibilityFacts::from(&model)
+                    .with_league_override(league_overrides.get(&model.id).copied()),
                 RelatedPlayer::Player(model),
             )
         })
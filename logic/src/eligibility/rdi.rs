@@ -10,7 +10,7 @@
 //! is judged on what was true entering 2021, not on the player's career to date. §11.3.5's
 //! mid-season grace period is why "before the season" is strict — see `was_on_nba_roster_before`.
 
-use color_eyre::eyre::{Result, ensure};
+use color_eyre::eyre::Result;
 use fbkl_entity::{
     contract::{self, ContractKind},
     contract_queries,
@@ -21,6 +21,23 @@ use tracing::instrument;
 
 use super::{PlayerEligibilityFacts, classify_player};
 
+/// Why a player may not move to RDI (rules §11.3.1).
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum RdiIneligibility {
+    #[error(
+        "Contract (id = {contract_id}) player was not rookie-draft-eligible in {season}, so cannot move to RDI."
+    )]
+    NotRookieDraftEligible { contract_id: i64, season: i16 },
+    #[error(
+        "Contract (id = {contract_id}) player was on an NBA roster before {season}, so cannot move to RDI."
+    )]
+    OnNbaRosterBefore { contract_id: i64, season: i16 },
+    #[error(
+        "Contract (id = {contract_id}) was already a post-legalization RD contract, so cannot move to RDI."
+    )]
+    FormerRookieDevelopment { contract_id: i64 },
+}
+
 /// Validates that a contract's player may move to Rookie Development International.
 ///
 /// Rules §11.3.1, judged entering the contract's season: the player must be rookie-draft-eligible,
@@ -35,28 +52,45 @@ pub async fn validate_rdi_eligible<C>(
 where
     C: ConnectionTrait,
 {
+    let contract_id = contract_model.id;
     let season = contract_model.end_of_season_year;
-    ensure!(
-        classify_player(player_facts, season) == EligibilityClassification::RookieDraftEligible,
-        "Contract (id = {}) player was not rookie-draft-eligible in {season}, so cannot move to RDI.",
-        contract_model.id
-    );
-    ensure!(
-        !player_facts.was_on_nba_roster_before(season),
-        "Contract (id = {}) player was on an NBA roster before {season}, so cannot move to RDI.",
-        contract_model.id
-    );
-
-    let chain = contract_queries::find_contract_chain(contract_model.id, db).await?;
-    ensure!(
-        !has_legalized_rd_ancestor(&chain, contract_model.end_of_season_year),
-        "Contract (id = {}) was already a post-legalization RD contract, so cannot move to RDI.",
-        contract_model.id
-    );
+    if classify_player(player_facts, season) != EligibilityClassification::RookieDraftEligible {
+        return Err(RdiIneligibility::NotRookieDraftEligible {
+            contract_id,
+            season,
+        }
+        .into());
+    }
+    if player_facts.was_on_nba_roster_before(season) {
+        return Err(RdiIneligibility::OnNbaRosterBefore {
+            contract_id,
+            season,
+        }
+        .into());
+    }
+
+    let chain = contract_queries::find_contract_chain(contract_id, db).await?;
+    if has_legalized_rd_ancestor(&chain, contract_model.end_of_season_year) {
+        return Err(RdiIneligibility::FormerRookieDevelopment { contract_id }.into());
+    }
 
     Ok(())
 }
 
+/// Whether an RDI contract must be moved to RD/1 at the season-start legalization of
+/// `legalization_end_of_season_year` (rules §11.3.1): his player reached an NBA roster in an earlier
+/// season. §11.3.5 lets a mid-season signing wait for that legalization, which is why it is judged
+/// then and not the moment he signs.
+#[must_use]
+pub fn is_due_for_rd_conversion(
+    contract_model: &contract::Model,
+    player_facts: PlayerEligibilityFacts,
+    legalization_end_of_season_year: i16,
+) -> bool {
+    contract_model.kind == ContractKind::RookieDevelopmentInternational
+        && player_facts.was_on_nba_roster_before(legalization_end_of_season_year)
+}
+
 /// Proxy for "was RD at/after an in-season roster legalization": an RD contract past year 1, or an
 /// RD contract from an earlier season (which necessarily crossed that season's legalization).
 fn has_legalized_rd_ancestor(chain: &[contract::Model], end_of_season_year: i16) -> bool {
@@ -1,6 +1,10 @@
 mod activate_contract_from_ir;
 mod ir_team_update;
 mod move_contract_to_ir;
+mod nba_injury_report;
+mod recovered_ir_contracts;
 
 pub use activate_contract_from_ir::*;
 pub use move_contract_to_ir::*;
+pub use nba_injury_report::*;
+pub use recovered_ir_contracts::*;
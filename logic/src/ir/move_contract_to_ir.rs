@@ -1,6 +1,9 @@
 use color_eyre::eyre::{Result, ensure, eyre};
 use fbkl_entity::{
-    contract, contract_queries, deadline,
+    contract::{self, RelatedPlayer},
+    contract_queries,
+    deadline::{self, DeadlineKind},
+    player::NbaInjuryStatus,
     sea_orm::{ActiveValue, ConnectionTrait},
     team_update::ContractUpdateType,
     transaction::{self, TransactionKind},
@@ -12,6 +15,15 @@ use crate::roster::{SalarySnapshot, calculate_team_contract_salary_with_model};
 
 use super::ir_team_update::create_ir_team_update;
 
+/// Why a contract may not move to IR.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum IrRejection {
+    #[error("The player on contract {contract_id} is not on the NBA injured reserve.")]
+    NotOnNbaInjuredReserve { contract_id: i64 },
+}
+
+/// Only players on the NBA injured reserve may occupy the IR slot (rules §10.1), going by the
+/// player's current `nba_injury_status`.
 #[instrument(skip(db))]
 pub async fn move_contract_to_ir<C>(
     contract_model: contract::Model,
@@ -22,6 +34,13 @@ where
     C: ConnectionTrait,
 {
     validate_contract_eligibility(&contract_model)?;
+    validate_ir_slot_exists(deadline_model)?;
+    if !is_on_nba_injured_reserve(&contract_model.get_player(db).await?) {
+        return Err(IrRejection::NotOnNbaInjuredReserve {
+            contract_id: contract_model.id,
+        }
+        .into());
+    }
 
     let team_model = contract_model.get_team(db).await?.ok_or_else(|| {
         eyre!(
@@ -72,3 +91,33 @@ fn validate_contract_eligibility(contract_model: &contract::Model) -> Result<()>
     );
     Ok(())
 }
+
+/// League-created players are never on an NBA injury report, so they never qualify.
+pub fn is_on_nba_injured_reserve(related_player: &RelatedPlayer) -> bool {
+    match related_player {
+        RelatedPlayer::Player(player_model) => {
+            player_model.nba_injury_status == NbaInjuryStatus::InjuredReserve
+        }
+        RelatedPlayer::LeaguePlayer(_) => false,
+    }
+}
+
+/// The offseason roster has no IR slot (§5.1.2.1). The first IR move of a season is the direct-to-IR
+/// move of the season-start declaration (§5.1.3), made against `PreseasonFinalRosterLock`.
+fn validate_ir_slot_exists(deadline_model: &deadline::Model) -> Result<()> {
+    let is_offseason = matches!(
+        deadline_model.kind,
+        DeadlineKind::PreseasonStart
+            | DeadlineKind::PreseasonKeeper
+            | DeadlineKind::PreseasonVeteranAuctionStart
+            | DeadlineKind::PreseasonFaAuctionStart
+            | DeadlineKind::PreseasonFaAuctionEnd
+            | DeadlineKind::PreseasonRookieDraftStart
+    );
+    ensure!(
+        !is_offseason,
+        "There is no IR slot before the season-start roster declaration. (deadline_id = {})",
+        deadline_model.id
+    );
+    Ok(())
+}
@@ -0,0 +1,211 @@
+//! File-based import of the NBA injury report (spec 12), which sets `player.nba_injury_status`.
+//!
+//! The report is a CSV of `nba_id,status` lines, `status` being an [`NbaInjuryStatus`] name
+//! (`Healthy` or `InjuredReserve`). A header line, blank lines and `#` comments are skipped. The
+//! report is a full snapshot of the NBA injured reserve: anyone still marked `InjuredReserve` who is
+//! missing from it has come off IR.
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, FixedOffset};
+use color_eyre::eyre::Result;
+use fbkl_entity::{
+    player::{self, NbaInjuryStatus, NbaRosterSource},
+    player_queries,
+    sea_orm::ConnectionTrait,
+};
+use tracing::instrument;
+
+/// One player's line in the report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NbaInjuryReportRow {
+    pub nba_id: i32,
+    pub status: NbaInjuryStatus,
+}
+
+/// Why an injury report could not be read. Line numbers start at 1.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum NbaInjuryReportError {
+    #[error("Line {line_number} is not `nba_id,status`: {line}")]
+    MalformedLine { line_number: usize, line: String },
+    #[error("Line {line_number} has an unknown injury status: {status}")]
+    UnknownStatus { line_number: usize, status: String },
+    #[error("Line {line_number} lists NBA id {nba_id}, which appears earlier in the report.")]
+    DuplicatePlayer { line_number: usize, nba_id: i32 },
+}
+
+/// What an import changed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NbaInjuryImport {
+    /// Players whose status, source and as-of time were written.
+    pub updated_player_ids: Vec<i64>,
+    /// Report lines whose NBA id matches no player.
+    pub unmatched_nba_ids: Vec<i32>,
+}
+
+pub fn parse_nba_injury_report(
+    contents: &str,
+) -> Result<Vec<NbaInjuryReportRow>, NbaInjuryReportError> {
+    let mut rows = vec![];
+    let mut seen_nba_ids = HashSet::new();
+    for (index, raw_line) in contents.lines().enumerate() {
+        let line_number = index + 1;
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with('#') || line.eq_ignore_ascii_case("nba_id,status") {
+            continue;
+        }
+
+        let malformed = || NbaInjuryReportError::MalformedLine {
+            line_number,
+            line: line.to_owned(),
+        };
+        let (nba_id, status) = line.split_once(',').ok_or_else(malformed)?;
+        let nba_id: i32 = nba_id.trim().parse().map_err(|_| malformed())?;
+        let status = match status.trim() {
+            "Healthy" => NbaInjuryStatus::Healthy,
+            "InjuredReserve" => NbaInjuryStatus::InjuredReserve,
+            other => {
+                return Err(NbaInjuryReportError::UnknownStatus {
+                    line_number,
+                    status: other.to_owned(),
+                });
+            }
+        };
+        if !seen_nba_ids.insert(nba_id) {
+            return Err(NbaInjuryReportError::DuplicatePlayer {
+                line_number,
+                nba_id,
+            });
+        }
+
+        rows.push(NbaInjuryReportRow { nba_id, status });
+    }
+
+    Ok(rows)
+}
+
+/// Writes a parsed report as of `asof`. Players whose status was set at or after `asof` (a later
+/// report, or a commissioner correction) keep it.
+#[instrument(skip(rows, db))]
+pub async fn import_nba_injury_report<C>(
+    rows: &[NbaInjuryReportRow],
+    source: NbaRosterSource,
+    asof: DateTime<FixedOffset>,
+    db: &C,
+) -> Result<NbaInjuryImport>
+where
+    C: ConnectionTrait,
+{
+    let mut players_by_nba_id: HashMap<i32, player::Model> =
+        player_queries::find_players_by_nba_ids(rows.iter().map(|row| row.nba_id).collect(), db)
+            .await?
+            .into_iter()
+            .filter_map(|player_model| player_model.nba_id.map(|nba_id| (nba_id, player_model)))
+            .collect();
+
+    let mut import = NbaInjuryImport::default();
+    let mut updates: Vec<(player::Model, NbaInjuryStatus)> = vec![];
+    for row in rows {
+        match players_by_nba_id.remove(&row.nba_id) {
+            Some(player_model) => updates.push((player_model, row.status)),
+            None => import.unmatched_nba_ids.push(row.nba_id),
+        }
+    }
+    let reported_player_ids: HashSet<i64> = updates
+        .iter()
+        .map(|(player_model, _)| player_model.id)
+        .collect();
+    for player_model in
+        player_queries::find_players_with_nba_injury_status(NbaInjuryStatus::InjuredReserve, db)
+            .await?
+    {
+        if !reported_player_ids.contains(&player_model.id) {
+            updates.push((player_model, NbaInjuryStatus::Healthy));
+        }
+    }
+
+    for (player_model, status) in updates {
+        if !is_newer_report(player_model.nba_injury_asof, asof) {
+            continue;
+        }
+        let updated_player =
+            player_queries::set_player_nba_injury_status(player_model, status, source, asof, db)
+                .await?;
+        import.updated_player_ids.push(updated_player.id);
+    }
+
+    Ok(import)
+}
+
+fn is_newer_report(
+    current_asof: Option<DateTime<FixedOffset>>,
+    report_asof: DateTime<FixedOffset>,
+) -> bool {
+    current_asof.is_none_or(|current_asof| current_asof < report_asof)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+    use fbkl_entity::player::NbaInjuryStatus;
+
+    use super::{
+        NbaInjuryReportError, NbaInjuryReportRow, is_newer_report, parse_nba_injury_report,
+    };
+
+    #[test]
+    fn parses_rows_skipping_header_blanks_and_comments() {
+        let report =
+            "nba_id,status\n# pulled 2026-11-02\n\n1629029,InjuredReserve\n 203999 , Healthy \n";
+
+        assert_eq!(
+            parse_nba_injury_report(report),
+            Ok(vec![
+                NbaInjuryReportRow {
+                    nba_id: 1_629_029,
+                    status: NbaInjuryStatus::InjuredReserve,
+                },
+                NbaInjuryReportRow {
+                    nba_id: 203_999,
+                    status: NbaInjuryStatus::Healthy,
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn rejects_bad_lines_with_their_line_number() {
+        assert_eq!(
+            parse_nba_injury_report("1629029"),
+            Err(NbaInjuryReportError::MalformedLine {
+                line_number: 1,
+                line: "1629029".to_owned(),
+            })
+        );
+        assert_eq!(
+            parse_nba_injury_report("nba_id,status\n1629029,Questionable"),
+            Err(NbaInjuryReportError::UnknownStatus {
+                line_number: 2,
+                status: "Questionable".to_owned(),
+            })
+        );
+        assert_eq!(
+            parse_nba_injury_report("1,InjuredReserve\n1,Healthy"),
+            Err(NbaInjuryReportError::DuplicatePlayer {
+                line_number: 2,
+                nba_id: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn older_reports_do_not_overwrite_newer_statuses() {
+        let earlier = DateTime::parse_from_rfc3339("2026-11-01T12:00:00+00:00").unwrap();
+        let later = DateTime::parse_from_rfc3339("2026-11-02T12:00:00+00:00").unwrap();
+
+        assert!(is_newer_report(None, earlier));
+        assert!(is_newer_report(Some(earlier), later));
+        assert!(!is_newer_report(Some(later), earlier));
+        assert!(!is_newer_report(Some(later), later));
+    }
+}
@@ -0,0 +1,270 @@
+//! IR contracts whose players have come off the NBA injured reserve (rules §10.1.3).
+//!
+//! Such a player may stay in the IR slot until his team's next pickup or trade, and must come off
+//! IR with it. Only `player`-backed contracts carry an NBA injury status, so only they are flagged.
+
+use std::collections::HashMap;
+
+use color_eyre::eyre::Result;
+use fbkl_entity::{
+    contract::{self, RelatedPlayer},
+    contract_queries, deadline,
+    player::NbaInjuryStatus,
+    sea_orm::{ConnectionTrait, prelude::DateTimeWithTimeZone},
+};
+use tracing::instrument;
+
+use super::activate_contract_from_ir;
+
+/// An IR contract whose player is no longer on the NBA injured reserve.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecoveredIrContract {
+    pub contract: contract::Model,
+    /// When the player was reported healthy, if he ever was.
+    pub recovered_at: Option<DateTimeWithTimeZone>,
+}
+
+impl RecoveredIrContract {
+    /// The player has been back since this moment at the latest, and he was only put on IR while
+    /// injured, so acquisitions before it do not count.
+    fn flagged_since(&self) -> DateTimeWithTimeZone {
+        self.recovered_at
+            .map_or(self.contract.created_at, |recovered_at| {
+                recovered_at.max(self.contract.created_at)
+            })
+    }
+}
+
+/// The team's IR contracts whose players have come off the NBA injured reserve.
+#[instrument(skip(team_contracts, db))]
+pub async fn find_recovered_ir_contracts<C>(
+    team_contracts: &[contract::Model],
+    db: &C,
+) -> Result<Vec<RecoveredIrContract>>
+where
+    C: ConnectionTrait,
+{
+    let mut recovered = vec![];
+    for contract_model in team_contracts
+        .iter()
+        .filter(|contract_model| contract_model.is_ir)
+    {
+        let RelatedPlayer::Player(player_model) = contract_model.get_player(db).await? else {
+            continue;
+        };
+        if player_model.nba_injury_status != NbaInjuryStatus::InjuredReserve {
+            recovered.push(RecoveredIrContract {
+                contract: contract_model.clone(),
+                recovered_at: player_model.nba_injury_asof,
+            });
+        }
+    }
+
+    Ok(recovered)
+}
+
+/// Recovered IR contracts the team must now activate: it has picked up or traded for a contract
+/// since the player came off the NBA injured reserve.
+#[instrument(skip(team_contracts, db))]
+pub async fn find_ir_contracts_due_for_activation<C>(
+    team_id: i64,
+    team_contracts: &[contract::Model],
+    db: &C,
+) -> Result<Vec<contract::Model>>
+where
+    C: ConnectionTrait,
+{
+    let recovered = find_recovered_ir_contracts(team_contracts, db).await?;
+    if recovered.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let chain_contracts: HashMap<i64, contract::Model> = contract_queries::find_contract_chains(
+        team_contracts
+            .iter()
+            .filter_map(|contract_model| contract_model.original_contract_id)
+            .collect(),
+        db,
+    )
+    .await?
+    .into_iter()
+    .map(|contract_model| (contract_model.id, contract_model))
+    .collect();
+    let acquisition_times: Vec<DateTimeWithTimeZone> = team_contracts
+        .iter()
+        .filter_map(|contract_model| acquired_at(contract_model, &chain_contracts, team_id))
+        .collect();
+
+    Ok(recovered
+        .into_iter()
+        .filter(|recovered_contract| {
+            let flagged_since = recovered_contract.flagged_since();
+            acquisition_times
+                .iter()
+                .any(|acquired| *acquired > flagged_since)
+        })
+        .map(|recovered_contract| recovered_contract.contract)
+        .collect())
+}
+
+/// Takes the team's recovered players off IR once a pickup or trade has made them due (§10.1.3),
+/// recording each as a `FromIR` move against `deadline_model`. Returns the activated contracts.
+///
+/// Call it in the acquisition's db transaction, after the acquired contract is written, so the
+/// acquisition and the activations it forces commit together.
+#[instrument(skip(db))]
+pub async fn activate_ir_contracts_due_after_acquisition<C>(
+    team_id: i64,
+    deadline_model: &deadline::Model,
+    db: &C,
+) -> Result<Vec<contract::Model>>
+where
+    C: ConnectionTrait,
+{
+    let team_contracts = contract_queries::find_active_contracts_for_team(team_id, db).await?;
+    let due_for_activation =
+        find_ir_contracts_due_for_activation(team_id, &team_contracts, db).await?;
+
+    let mut activated_contracts = Vec::with_capacity(due_for_activation.len());
+    for contract_model in due_for_activation {
+        activated_contracts
+            .push(activate_contract_from_ir(contract_model, deadline_model, db).await?);
+    }
+    Ok(activated_contracts)
+}
+
+/// When the team took the player on, by signing or trade: the first link of the unbroken run of
+/// the contract's chain on `team_id`. Walking the whole run, rather than one link back, keeps an
+/// acquisition counting after the team's own IR or RD/RDI moves re-chain the contract.
+fn acquired_at(
+    contract_model: &contract::Model,
+    chain_contracts: &HashMap<i64, contract::Model>,
+    team_id: i64,
+) -> Option<DateTimeWithTimeZone> {
+    if contract_model.team_id != Some(team_id) {
+        return None;
+    }
+    let mut first_on_team = contract_model;
+    while let Some(previous) = first_on_team
+        .previous_contract_id
+        .and_then(|previous_id| chain_contracts.get(&previous_id))
+    {
+        if previous.team_id != Some(team_id) {
+            break;
+        }
+        first_on_team = previous;
+    }
+
+    Some(first_on_team.created_at)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chrono::{DateTime, FixedOffset};
+    use fbkl_entity::contract::{ContractKind, ContractStatus, Model};
+
+    use super::{RecoveredIrContract, acquired_at};
+
+    fn at(date_time: &str) -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339(date_time).unwrap()
+    }
+
+    fn contract(id: i64, team_id: Option<i64>, created_at: &str) -> Model {
+        Model {
+            id,
+            year_number: 1,
+            kind: ContractKind::Veteran,
+            is_ir: false,
+            salary: 10,
+            end_of_season_year: 2027,
+            status: ContractStatus::Active,
+            league_id: 1,
+            league_player_id: None,
+            player_id: Some(id),
+            previous_contract_id: None,
+            original_contract_id: Some(id),
+            team_id,
+            created_at: at(created_at),
+            updated_at: at(created_at),
+        }
+    }
+
+    fn chain(links: Vec<Model>) -> HashMap<i64, Model> {
+        links
+            .into_iter()
+            .map(|contract_model| (contract_model.id, contract_model))
+            .collect()
+    }
+
+    fn next_link(previous: &Model, id: i64, team_id: Option<i64>, created_at: &str) -> Model {
+        Model {
+            previous_contract_id: Some(previous.id),
+            original_contract_id: previous.original_contract_id,
+            ..contract(id, team_id, created_at)
+        }
+    }
+
+    #[test]
+    fn signings_and_trades_are_acquisitions_but_own_roster_moves_are_not() {
+        let free_agent = contract(1, None, "2026-10-01T00:00:00+00:00");
+        let signed = next_link(&free_agent, 2, Some(1), "2026-11-03T00:00:00+00:00");
+        let moved_to_ir = next_link(&signed, 3, Some(1), "2026-11-10T00:00:00+00:00");
+        let signing_chain = chain(vec![
+            free_agent.clone(),
+            signed.clone(),
+            moved_to_ir.clone(),
+        ]);
+        assert_eq!(
+            acquired_at(&signed, &signing_chain, 1),
+            Some(at("2026-11-03T00:00:00+00:00"))
+        );
+        // The IR move re-chains the contract, but the team still took him on when it signed him.
+        assert_eq!(
+            acquired_at(&moved_to_ir, &signing_chain, 1),
+            Some(at("2026-11-03T00:00:00+00:00"))
+        );
+        assert_eq!(acquired_at(&free_agent, &signing_chain, 1), None);
+
+        let traded_from = contract(4, Some(2), "2026-10-01T00:00:00+00:00");
+        let traded_for = next_link(&traded_from, 5, Some(1), "2026-11-05T00:00:00+00:00");
+        let trade_chain = chain(vec![traded_from, traded_for.clone()]);
+        assert_eq!(
+            acquired_at(&traded_for, &trade_chain, 1),
+            Some(at("2026-11-05T00:00:00+00:00"))
+        );
+
+        let drafted = contract(6, Some(1), "2026-10-01T00:00:00+00:00");
+        assert_eq!(
+            acquired_at(&drafted, &chain(vec![drafted.clone()]), 1),
+            Some(at("2026-10-01T00:00:00+00:00"))
+        );
+    }
+
+    #[test]
+    fn flagged_since_is_the_later_of_recovery_and_the_ir_move() {
+        let ir_contract = Model {
+            is_ir: true,
+            ..contract(1, Some(1), "2026-11-01T00:00:00+00:00")
+        };
+
+        let recovered_later = RecoveredIrContract {
+            contract: ir_contract.clone(),
+            recovered_at: Some(at("2026-11-10T00:00:00+00:00")),
+        };
+        assert_eq!(
+            recovered_later.flagged_since(),
+            at("2026-11-10T00:00:00+00:00")
+        );
+
+        let never_reported = RecoveredIrContract {
+            contract: ir_contract,
+            recovered_at: None,
+        };
+        assert_eq!(
+            never_reported.flagged_since(),
+            at("2026-11-01T00:00:00+00:00")
+        );
+    }
+}
@@ -0,0 +1,277 @@
+use chrono::{DateTime, FixedOffset, TimeDelta};
+use color_eyre::Result;
+use fbkl_entity::{
+    league_invitation::{self, LeagueInvitationKind, LeagueInvitationStatus},
+    league_invitation_queries,
+    sea_orm::{ActiveValue, ConnectionTrait},
+    team, team_queries,
+    team_user::{self, LeagueRole},
+    team_user_queries, user,
+};
+use tracing::instrument;
+
+/// How long an invitation can be accepted for.
+pub const LEAGUE_INVITATION_LIFETIME_DAYS: i64 = 14;
+
+/// A commissioner's invitation for one seat on a team.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewLeagueInvitation {
+    pub kind: LeagueInvitationKind,
+    /// Restricts acceptance to the user with this email.
+    pub email: Option<String>,
+    pub first_end_of_season_year: i16,
+    /// From `fbkl_auth::generate_token`.
+    pub token: Vec<u8>,
+}
+
+/// Why an invitation could not be created, accepted or revoked.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum LeagueInvitationRejection {
+    #[error("Team {team_id} already has an owner; invite a co-owner instead.")]
+    TeamHasOwner { team_id: i64 },
+    #[error("Team {team_id} has no owner for a co-owner to join.")]
+    TeamHasNoOwner { team_id: i64 },
+    #[error("This invitation has expired.")]
+    Expired,
+    #[error("This invitation was revoked.")]
+    Revoked,
+    #[error("This invitation has already been accepted.")]
+    AlreadyAccepted,
+    #[error("This invitation was sent to a different email address.")]
+    WrongRecipient,
+    #[error("User {user_id} is already an active member of this league.")]
+    AlreadyInLeague { user_id: i64 },
+}
+
+/// Creates an invitation to `team_model`, open for `LEAGUE_INVITATION_LIFETIME_DAYS` from `now`.
+#[instrument(skip(invitation, db))]
+pub async fn create_league_invitation<C>(
+    team_model: &team::Model,
+    invitation: NewLeagueInvitation,
+    invited_by: &team_user::Model,
+    now: DateTime<FixedOffset>,
+    db: &C,
+) -> Result<league_invitation::Model>
+where
+    C: ConnectionTrait,
+{
+    let active_team_users =
+        team_user_queries::find_active_team_users_for_team(team_model.id, db).await?;
+    check_seat(invitation.kind, team_model.id, active_team_users.len())?;
+
+    let email = invitation
+        .email
+        .map(|email| email.trim().to_owned())
+        .filter(|email| !email.is_empty());
+    league_invitation_queries::insert_league_invitation(
+        league_invitation::ActiveModel {
+            league_id: ActiveValue::Set(team_model.league_id),
+            team_id: ActiveValue::Set(team_model.id),
+            kind: ActiveValue::Set(invitation.kind),
+            token: ActiveValue::Set(invitation.token),
+            email: ActiveValue::Set(email),
+            status: ActiveValue::Set(LeagueInvitationStatus::Pending),
+            first_end_of_season_year: ActiveValue::Set(invitation.first_end_of_season_year),
+            expires_at: ActiveValue::Set(now + TimeDelta::days(LEAGUE_INVITATION_LIFETIME_DAYS)),
+            invited_by_team_user_id: ActiveValue::Set(invited_by.id),
+            accepted_team_user_id: ActiveValue::Set(None),
+            ..Default::default()
+        },
+        db,
+    )
+    .await
+}
+
+/// Makes `user_model` a `TeamOwner` of the invitation's team from its `first_end_of_season_year`,
+/// and closes the invitation. Returns the new `team_user`.
+///
+/// Run inside a transaction: the invitation and its team are locked before they are checked, so an
+/// invitation is accepted at most once and racing accepts can't overfill a seat.
+#[instrument(skip(db))]
+pub async fn accept_league_invitation<C>(
+    invitation_model: league_invitation::Model,
+    user_model: &user::Model,
+    nickname: String,
+    now: DateTime<FixedOffset>,
+    db: &C,
+) -> Result<team_user::Model>
+where
+    C: ConnectionTrait,
+{
+    let invitation_model =
+        league_invitation_queries::find_league_invitation_by_id_for_update(invitation_model.id, db)
+            .await?;
+    team_queries::find_team_by_id_for_update(invitation_model.team_id, db).await?;
+    check_open(&invitation_model, now)?;
+    let is_recipient = invitation_model
+        .email
+        .as_ref()
+        .is_none_or(|email| email.eq_ignore_ascii_case(&user_model.email));
+    if !is_recipient {
+        return Err(LeagueInvitationRejection::WrongRecipient.into());
+    }
+
+    let is_in_league = team_user_queries::get_all_team_users_by_user_and_league(
+        &user_model.id,
+        &invitation_model.league_id,
+        db,
+    )
+    .await?
+    .iter()
+    .any(|(team_user_model, _)| team_user_model.league_role != LeagueRole::Inactive);
+    if is_in_league {
+        return Err(LeagueInvitationRejection::AlreadyInLeague {
+            user_id: user_model.id,
+        }
+        .into());
+    }
+    let active_team_users =
+        team_user_queries::find_active_team_users_for_team(invitation_model.team_id, db).await?;
+    check_seat(
+        invitation_model.kind,
+        invitation_model.team_id,
+        active_team_users.len(),
+    )?;
+
+    let team_user_model = team_user_queries::insert_team_owner(
+        invitation_model.team_id,
+        user_model.id,
+        nickname,
+        invitation_model.first_end_of_season_year,
+        db,
+    )
+    .await?;
+    league_invitation_queries::set_league_invitation_status(
+        invitation_model,
+        LeagueInvitationStatus::Accepted,
+        Some(team_user_model.id),
+        db,
+    )
+    .await?;
+
+    Ok(team_user_model)
+}
+
+/// Withdraws an invitation that has not been accepted.
+#[instrument(skip(db))]
+pub async fn revoke_league_invitation<C>(
+    invitation_model: league_invitation::Model,
+    db: &C,
+) -> Result<league_invitation::Model>
+where
+    C: ConnectionTrait,
+{
+    match invitation_model.status {
+        LeagueInvitationStatus::Pending => {}
+        LeagueInvitationStatus::Accepted => {
+            return Err(LeagueInvitationRejection::AlreadyAccepted.into());
+        }
+        LeagueInvitationStatus::Revoked => return Err(LeagueInvitationRejection::Revoked.into()),
+    }
+
+    league_invitation_queries::set_league_invitation_status(
+        invitation_model,
+        LeagueInvitationStatus::Revoked,
+        None,
+        db,
+    )
+    .await
+}
+
+fn check_open(
+    invitation_model: &league_invitation::Model,
+    now: DateTime<FixedOffset>,
+) -> Result<(), LeagueInvitationRejection> {
+    match invitation_model.status {
+        LeagueInvitationStatus::Accepted => Err(LeagueInvitationRejection::AlreadyAccepted),
+        LeagueInvitationStatus::Revoked => Err(LeagueInvitationRejection::Revoked),
+        LeagueInvitationStatus::Pending if !invitation_model.is_open(now) => {
+            Err(LeagueInvitationRejection::Expired)
+        }
+        LeagueInvitationStatus::Pending => Ok(()),
+    }
+}
+
+/// An owner invitation needs an empty team; a co-owner invitation needs a team with an owner.
+const fn check_seat(
+    kind: LeagueInvitationKind,
+    team_id: i64,
+    active_team_user_count: usize,
+) -> Result<(), LeagueInvitationRejection> {
+    match (kind, active_team_user_count) {
+        (LeagueInvitationKind::Owner, 0) | (LeagueInvitationKind::CoOwner, 1..) => Ok(()),
+        (LeagueInvitationKind::Owner, _) => {
+            Err(LeagueInvitationRejection::TeamHasOwner { team_id })
+        }
+        (LeagueInvitationKind::CoOwner, 0) => {
+            Err(LeagueInvitationRejection::TeamHasNoOwner { team_id })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, FixedOffset, TimeDelta};
+    use fbkl_entity::league_invitation::{LeagueInvitationKind, LeagueInvitationStatus, Model};
+
+    use super::{LeagueInvitationRejection, check_open, check_seat};
+
+    fn now() -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339("2026-08-01T12:00:00+00:00").unwrap()
+    }
+
+    fn invitation(status: LeagueInvitationStatus) -> Model {
+        Model {
+            id: 1,
+            league_id: 1,
+            team_id: 2,
+            kind: LeagueInvitationKind::Owner,
+            token: vec![7; 64],
+            email: None,
+            status,
+            first_end_of_season_year: 2027,
+            expires_at: now() + TimeDelta::days(14),
+            invited_by_team_user_id: 1,
+            accepted_team_user_id: None,
+            created_at: now(),
+            updated_at: now(),
+        }
+    }
+
+    #[test]
+    fn an_invitation_is_single_use_and_expires() {
+        assert_eq!(
+            check_open(&invitation(LeagueInvitationStatus::Pending), now()),
+            Ok(())
+        );
+        assert_eq!(
+            check_open(
+                &invitation(LeagueInvitationStatus::Pending),
+                now() + TimeDelta::days(14)
+            ),
+            Err(LeagueInvitationRejection::Expired)
+        );
+        assert_eq!(
+            check_open(&invitation(LeagueInvitationStatus::Accepted), now()),
+            Err(LeagueInvitationRejection::AlreadyAccepted)
+        );
+        assert_eq!(
+            check_open(&invitation(LeagueInvitationStatus::Revoked), now()),
+            Err(LeagueInvitationRejection::Revoked)
+        );
+    }
+
+    #[test]
+    fn owners_fill_empty_teams_and_co_owners_join_owned_ones() {
+        assert_eq!(check_seat(LeagueInvitationKind::Owner, 2, 0), Ok(()));
+        assert_eq!(
+            check_seat(LeagueInvitationKind::Owner, 2, 1),
+            Err(LeagueInvitationRejection::TeamHasOwner { team_id: 2 })
+        );
+        assert_eq!(check_seat(LeagueInvitationKind::CoOwner, 2, 1), Ok(()));
+        assert_eq!(
+            check_seat(LeagueInvitationKind::CoOwner, 2, 0),
+            Err(LeagueInvitationRejection::TeamHasNoOwner { team_id: 2 })
+        );
+    }
+}
@@ -0,0 +1,8 @@
+//! Inviting owners into a league.
+//!
+//! The commissioner creates a team and an invitation for its seat; the invitee redeems the token,
+//! either while logged in or while registering, and becomes a `TeamOwner` of that team.
+
+mod league_invitation;
+
+pub use league_invitation::*;
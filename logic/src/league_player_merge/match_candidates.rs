@@ -0,0 +1,156 @@
+use color_eyre::Result;
+use fbkl_entity::{
+    contract_queries, league_player, player, player_queries, sea_orm::ConnectionTrait,
+};
+use tracing::instrument;
+
+/// Bounds the surname search; the real-player table spans every NBA season.
+const MATCH_SEARCH_LIMIT: u64 = 50;
+
+/// Generational suffixes the NBA data and league-entered names disagree on.
+const NAME_SUFFIXES: [&str; 6] = ["jr", "sr", "ii", "iii", "iv", "v"];
+
+/// A real player who may be the league player the commissioner is merging.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RealPlayerMatchCandidate {
+    pub player: player::Model,
+    /// Whether the real player first appeared in NBA data no earlier than the season the league
+    /// player was drafted, as the player he became must have. A player with no NBA season yet is
+    /// consistent with any draft year.
+    pub is_draft_year_consistent: bool,
+}
+
+/// Real players whose normalized name matches the league player's, consistent draft years first.
+///
+/// The league player's draft year is the season of his first contract in the league.
+#[instrument(skip(db))]
+pub async fn find_real_player_match_candidates<C>(
+    league_player_model: &league_player::Model,
+    db: &C,
+) -> Result<Vec<RealPlayerMatchCandidate>>
+where
+    C: ConnectionTrait,
+{
+    let normalized_name = normalize_player_name(&league_player_model.name);
+    let Some(surname) = surname_search_term(&league_player_model.name) else {
+        return Ok(vec![]);
+    };
+    let maybe_draft_year = contract_queries::find_league_contracts_for_player(
+        league_player_model.league_id,
+        league_player_model.id,
+        true,
+        db,
+    )
+    .await?
+    .first()
+    .map(|contract_model| contract_model.end_of_season_year);
+
+    let mut candidates: Vec<RealPlayerMatchCandidate> =
+        player_queries::search_players_by_name(surname, MATCH_SEARCH_LIMIT, db)
+            .await?
+            .into_iter()
+            .filter(|player_model| normalize_player_name(&player_model.name) == normalized_name)
+            .map(|player_model| RealPlayerMatchCandidate {
+                is_draft_year_consistent: is_draft_year_consistent(
+                    maybe_draft_year,
+                    player_model.nba_first_season_end_of_season_year,
+                ),
+                player: player_model,
+            })
+            .collect();
+    candidates.sort_by_key(|candidate| !candidate.is_draft_year_consistent);
+
+    Ok(candidates)
+}
+
+/// Folds a name to lowercase ASCII letters and single spaces, dropping punctuation and
+/// generational suffixes, so "Nikola Jokić" and "Kenyon Martin Jr." compare equal to their
+/// plain-typed spellings.
+pub fn normalize_player_name(name: &str) -> String {
+    let folded: String = name
+        .chars()
+        .flat_map(char::to_lowercase)
+        .map(fold_diacritic)
+        .filter(|character| character.is_ascii_alphanumeric() || character.is_whitespace())
+        .collect();
+    folded
+        .split_whitespace()
+        .filter(|word| !NAME_SUFFIXES.contains(word))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The last word of the name that is not a suffix, as typed, for the database's accent-insensitive
+/// search to narrow on.
+fn surname_search_term(name: &str) -> Option<&str> {
+    name.split_whitespace()
+        .map(|word| word.trim_end_matches(['.', ',']))
+        .rfind(|word| !word.is_empty() && !NAME_SUFFIXES.contains(&word.to_lowercase().as_str()))
+}
+
+fn is_draft_year_consistent(
+    maybe_draft_year: Option<i16>,
+    maybe_nba_first_season: Option<i16>,
+) -> bool {
+    match (maybe_draft_year, maybe_nba_first_season) {
+        (Some(draft_year), Some(nba_first_season)) => nba_first_season >= draft_year,
+        _ => true,
+    }
+}
+
+/// Maps the Latin letters common in NBA names to their unaccented form.
+const fn fold_diacritic(character: char) -> char {
+    match character {
+        'á' | 'à' | 'â' | 'ä' | 'ã' | 'å' | 'ā' => 'a',
+        'ç' | 'ć' | 'č' => 'c',
+        'đ' | 'ď' => 'd',
+        'é' | 'è' | 'ê' | 'ë' | 'ē' | 'ė' | 'ę' | 'ě' => 'e',
+        'ğ' => 'g',
+        'í' | 'ì' | 'î' | 'ï' | 'ī' | 'ı' => 'i',
+        'ł' => 'l',
+        'ñ' | 'ń' | 'ň' => 'n',
+        'ó' | 'ò' | 'ô' | 'ö' | 'õ' | 'ø' | 'ō' => 'o',
+        'ř' => 'r',
+        'ś' | 'š' | 'ş' => 's',
+        'ť' => 't',
+        'ú' | 'ù' | 'û' | 'ü' | 'ū' | 'ů' => 'u',
+        'ý' | 'ÿ' => 'y',
+        'ź' | 'ż' | 'ž' => 'z',
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_draft_year_consistent, normalize_player_name, surname_search_term};
+
+    #[test]
+    fn names_fold_accents_punctuation_and_suffixes() {
+        assert_eq!(normalize_player_name("Nikola Jokić"), "nikola jokic");
+        assert_eq!(normalize_player_name("Alperen Şengün"), "alperen sengun");
+        assert_eq!(normalize_player_name("Kenyon Martin Jr."), "kenyon martin");
+        assert_eq!(
+            normalize_player_name("D'Angelo  Russell"),
+            "dangelo russell"
+        );
+        assert_eq!(
+            normalize_player_name("Gary Trent Jr"),
+            normalize_player_name("gary trent")
+        );
+    }
+
+    #[test]
+    fn surname_skips_suffixes() {
+        assert_eq!(surname_search_term("Kenyon Martin Jr."), Some("Martin"));
+        assert_eq!(surname_search_term("Nikola Jokić"), Some("Jokić"));
+        assert_eq!(surname_search_term("  "), None);
+    }
+
+    #[test]
+    fn real_player_cannot_predate_the_league_draft() {
+        assert!(is_draft_year_consistent(Some(2025), Some(2026)));
+        assert!(is_draft_year_consistent(Some(2025), None));
+        assert!(is_draft_year_consistent(None, Some(2019)));
+        assert!(!is_draft_year_consistent(Some(2025), Some(2019)));
+    }
+}
@@ -0,0 +1,132 @@
+use color_eyre::Result;
+use fbkl_entity::{
+    contract::{self, ContractStatus},
+    contract_queries, deadline, league_player, league_player_queries, player,
+    sea_orm::ConnectionTrait,
+    transaction, transaction_queries,
+};
+use tracing::instrument;
+
+/// Why a league player could not be merged into a real player.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum LeaguePlayerMergeRejection {
+    #[error("League player {league_player_id} was already merged into player {player_id}.")]
+    AlreadyMerged {
+        league_player_id: i64,
+        player_id: i64,
+    },
+    #[error("Player {player_id} is already linked to league player {league_player_id}.")]
+    PlayerAlreadyLinked {
+        player_id: i64,
+        league_player_id: i64,
+    },
+    #[error(
+        "Player {player_id} already has an active contract (id = {contract_id}) in this league."
+    )]
+    PlayerUnderContract { player_id: i64, contract_id: i64 },
+}
+
+/// What a merge changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeaguePlayerMerge {
+    pub league_player: league_player::Model,
+    /// The new link at the end of each of the league player's active chains.
+    pub relinked_contracts: Vec<contract::Model>,
+    /// Whether the league player's eligibility override now applies to the real player in this
+    /// league. An override the real player already has, which applies in every league, wins.
+    pub carried_eligibility_override: bool,
+}
+
+/// Links `league_player_model` to `player_model` and adds a link naming the real player to each
+/// of the league player's active contract chains, recording a `LeaguePlayerMerge` transaction at
+/// `current_deadline` for each.
+///
+/// A real player can stand behind only one league player, and cannot already hold a contract of
+/// his own in the league — merging would leave him with two active chains.
+#[instrument(skip(db))]
+pub async fn merge_league_player<C>(
+    league_player_model: league_player::Model,
+    player_model: player::Model,
+    current_deadline: &deadline::Model,
+    db: &C,
+) -> Result<LeaguePlayerMerge>
+where
+    C: ConnectionTrait,
+{
+    let league_id = league_player_model.league_id;
+    if let Some(player_id) = league_player_model.real_player_id {
+        return Err(LeaguePlayerMergeRejection::AlreadyMerged {
+            league_player_id: league_player_model.id,
+            player_id,
+        }
+        .into());
+    }
+    if let Some(linked_league_player) =
+        league_player_queries::find_league_player_linked_to_player(league_id, player_model.id, db)
+            .await?
+    {
+        return Err(LeaguePlayerMergeRejection::PlayerAlreadyLinked {
+            player_id: player_model.id,
+            league_player_id: linked_league_player.id,
+        }
+        .into());
+    }
+    if let Some(player_contract) =
+        contract_queries::find_league_contracts_for_player(league_id, player_model.id, false, db)
+            .await?
+            .into_iter()
+            .find(|contract_model| contract_model.status == ContractStatus::Active)
+    {
+        return Err(LeaguePlayerMergeRejection::PlayerUnderContract {
+            player_id: player_model.id,
+            contract_id: player_contract.id,
+        }
+        .into());
+    }
+
+    let active_contracts: Vec<contract::Model> =
+        contract_queries::find_league_contracts_for_player(
+            league_id,
+            league_player_model.id,
+            true,
+            db,
+        )
+        .await?
+        .into_iter()
+        .filter(|contract_model| contract_model.status == ContractStatus::Active)
+        .collect();
+    let mut relinked_contracts = Vec::with_capacity(active_contracts.len());
+    for contract_model in active_contracts {
+        let relinked_contract =
+            contract_queries::relink_contract_to_real_player(contract_model, player_model.id, db)
+                .await?;
+        transaction_queries::insert_transaction(
+            transaction::Model::new_league_player_merge_transaction(
+                current_deadline,
+                relinked_contract.id,
+            ),
+            db,
+        )
+        .await?;
+        relinked_contracts.push(relinked_contract);
+    }
+
+    // The override stays on the league player row, so it follows the real player in this league
+    // only (`find_contract_player_eligibility_facts`); the real player's row is shared by every
+    // league and is never written.
+    let carried_eligibility_override = league_player_model.eligibility_override.is_some()
+        && player_model.eligibility_override.is_none();
+    let player_id = player_model.id;
+    let league_player = league_player_queries::link_league_player_to_real_player(
+        league_player_model,
+        player_id,
+        db,
+    )
+    .await?;
+
+    Ok(LeaguePlayerMerge {
+        league_player,
+        relinked_contracts,
+        carried_eligibility_override,
+    })
+}
@@ -0,0 +1,11 @@
+//! Merging a league-created player into the real (NBA) player he turned out to be.
+//!
+//! League players exist because a drafted player had no NBA entry yet. Once he shows up in the NBA
+//! data, the commissioner links the two: the league player records the real player id, and every
+//! active contract chain gains a link naming the real player, so nothing already written changes.
+
+mod match_candidates;
+mod merge_league_player;
+
+pub use match_candidates::*;
+pub use merge_league_player::*;
@@ -0,0 +1,3 @@
+mod season_start_declaration;
+
+pub use season_start_declaration::*;
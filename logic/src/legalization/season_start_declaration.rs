@@ -0,0 +1,392 @@
+//! The season-start roster declaration (rules §5.1.3, §11.4): the one time an owner cuts the
+//! 32-man offseason roster down to 22 active + 1 IR + 6 RD + 1 RDI.
+//!
+//! The declaration is simultaneous (§11.4.3), so it is judged as a whole on the roster it leaves:
+//! a player moved straight to IR frees his cap and roster spot for an RD/RDI player activated in
+//! the same declaration, on top of the $10 bump the `PreseasonFinalRosterLock` cap already carries.
+
+use std::{collections::HashSet, fmt::Debug};
+
+use color_eyre::eyre::Result;
+use fbkl_entity::{
+    contract::{self, ContractKind},
+    contract_queries,
+    deadline::{self, DeadlineKind},
+    sea_orm::{ConnectionTrait, TransactionSession, TransactionTrait},
+};
+use tracing::instrument;
+
+use crate::{
+    deadline_processing::{RosterViolation, roster_contract_type_violations},
+    drop_contract::drop_contract_from_team,
+    eligibility::{find_contract_player_eligibility_facts, is_due_for_rd_conversion},
+    ir::{is_on_nba_injured_reserve, move_contract_to_ir},
+    rookie_development_activation::activate_rookie_development_contract,
+    rookie_development_international::move_rookie_development_international_contract_to_stateside,
+    roster::{
+        RosterCounts, SalarySnapshot, calculate_team_contract_salary, dropped_contract_cap_penalty,
+        is_counted_toward_cap,
+    },
+};
+
+/// What the owner does with one contract in the declaration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeclarationAction {
+    /// Straight to IR, without first fitting on the 22-man roster (§5.1.3).
+    MoveToIr,
+    /// RD/RDI to a first-year rookie contract (§11.5).
+    ActivateRookieDevelopment,
+    /// RDI back to RD/1, as required once the player is on an NBA roster (§11.3.1).
+    MoveToRookieDevelopment,
+    /// Released: penalty-free for RD/RDI, the usual §9 penalty otherwise.
+    Drop,
+}
+
+/// One contract's part in a season-start declaration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeclarationMove {
+    pub contract_id: i64,
+    pub action: DeclarationAction,
+}
+
+/// Why a season-start declaration was refused. Nothing is applied when it is.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum DeclarationRejection {
+    #[error("Season-start declarations are only taken for the final preseason roster lock.")]
+    NotSeasonStart,
+    #[error("Contract {contract_id} is not on the team's roster.")]
+    ContractNotOnRoster { contract_id: i64 },
+    #[error("Contract {contract_id} appears more than once in the declaration.")]
+    DuplicateContract { contract_id: i64 },
+    /// RD/RDI contracts cannot go to IR, nor can a player who is not on the NBA injured reserve.
+    #[error("Contract {contract_id} ({kind:?}) cannot take {action:?}.")]
+    IneligibleMove {
+        contract_id: i64,
+        kind: ContractKind,
+        action: DeclarationAction,
+    },
+    #[error(
+        "Contract {contract_id}'s player has been on an NBA roster, so he must move to RD/1, be activated or be dropped."
+    )]
+    RdiPlayerOnNbaRoster { contract_id: i64 },
+    #[error("The declared roster is still illegal: {}", describe_violations(.0))]
+    IllegalRoster(Vec<RosterViolation>),
+}
+
+/// Validates an owner's season-start declaration as a whole and, if the roster it leaves is legal,
+/// applies every move atomically through the normal roster mutators. Returns the team's roster
+/// afterwards.
+///
+/// Moves are recorded against the `PreseasonFinalRosterLock` deadline, so the lock commits them with
+/// the rest of the league's.
+#[instrument(skip(db))]
+pub async fn submit_season_start_declaration<C>(
+    team_id: i64,
+    declaration: &[DeclarationMove],
+    final_roster_lock: &deadline::Model,
+    db: &C,
+) -> Result<Vec<contract::Model>>
+where
+    C: ConnectionTrait + TransactionTrait,
+{
+    if final_roster_lock.kind != DeadlineKind::PreseasonFinalRosterLock {
+        return Err(DeclarationRejection::NotSeasonStart.into());
+    }
+
+    let team_contracts = contract_queries::find_active_contracts_for_team(team_id, db).await?;
+    let mut on_nba_injured_reserve = HashSet::new();
+    for contract_model in &team_contracts {
+        let is_declared_to_ir = declaration.iter().any(|declaration_move| {
+            declaration_move.contract_id == contract_model.id
+                && declaration_move.action == DeclarationAction::MoveToIr
+        });
+        if is_declared_to_ir && is_on_nba_injured_reserve(&contract_model.get_player(db).await?) {
+            on_nba_injured_reserve.insert(contract_model.id);
+        }
+    }
+    let DeclaredRoster {
+        contracts: declared_contracts,
+        drop_penalty,
+    } = project_declaration(&team_contracts, declaration, &on_nba_injured_reserve)?;
+
+    for contract_model in &declared_contracts {
+        if contract_model.kind != ContractKind::RookieDevelopmentInternational {
+            continue;
+        }
+        let player_facts = find_contract_player_eligibility_facts(&contract_model, db).await?;
+        // An NBA signing during last season is only forced at this legalization (§11.3.5).
+        if is_due_for_rd_conversion(
+            contract_model,
+            player_facts,
+            final_roster_lock.end_of_season_year,
+        ) {
+            return Err(DeclarationRejection::RdiPlayerOnNbaRoster {
+                contract_id: contract_model.id,
+            }
+            .into());
+        }
+    }
+
+    let mut violations = roster_contract_type_violations(
+        &RosterCounts::from_contracts(&declared_contracts),
+        final_roster_lock.kind,
+    )?;
+    let SalarySnapshot { salary, cap } =
+        calculate_team_contract_salary(team_id, &declared_contracts, final_roster_lock, db).await?;
+    let cap = cap - drop_penalty;
+    if salary > cap {
+        violations.push(RosterViolation::OverCap { salary, cap });
+    }
+    if !violations.is_empty() {
+        return Err(DeclarationRejection::IllegalRoster(violations).into());
+    }
+
+    let db_txn = db.begin().await?;
+    for declaration_move in declaration {
+        let Some(contract_model) = team_contracts
+            .iter()
+            .find(|contract_model| contract_model.id == declaration_move.contract_id)
+            .cloned()
+        else {
+            continue;
+        };
+        match declaration_move.action {
+            DeclarationAction::MoveToIr => {
+                move_contract_to_ir(contract_model, final_roster_lock, &db_txn).await?;
+            }
+            DeclarationAction::ActivateRookieDevelopment => {
+                activate_rookie_development_contract(contract_model, final_roster_lock, &db_txn)
+                    .await?;
+            }
+            DeclarationAction::MoveToRookieDevelopment => {
+                move_rookie_development_international_contract_to_stateside(
+                    contract_model,
+                    final_roster_lock,
+                    &db_txn,
+                )
+                .await?;
+            }
+            DeclarationAction::Drop => {
+                drop_contract_from_team(contract_model, final_roster_lock, &db_txn).await?;
+            }
+        }
+    }
+    db_txn.commit().await?;
+
+    contract_queries::find_active_contracts_for_team(team_id, db).await
+}
+
+/// The roster a declaration leaves, and the cap its drops cost.
+#[derive(Debug)]
+struct DeclaredRoster {
+    contracts: Vec<contract::Model>,
+    drop_penalty: i16,
+}
+
+/// `on_nba_injured_reserve` holds the contracts whose players may go to IR (rules §10.1), so an
+/// ineligible IR move is refused here rather than halfway through applying the declaration.
+fn project_declaration(
+    team_contracts: &[contract::Model],
+    declaration: &[DeclarationMove],
+    on_nba_injured_reserve: &HashSet<i64>,
+) -> Result<DeclaredRoster, DeclarationRejection> {
+    let mut seen_contract_ids = HashSet::new();
+    let mut contracts = team_contracts.to_vec();
+    let mut drop_penalty = 0;
+
+    for declaration_move in declaration {
+        let contract_id = declaration_move.contract_id;
+        if !seen_contract_ids.insert(contract_id) {
+            return Err(DeclarationRejection::DuplicateContract { contract_id });
+        }
+        let Some(index) = contracts
+            .iter()
+            .position(|contract_model| contract_model.id == contract_id)
+        else {
+            return Err(DeclarationRejection::ContractNotOnRoster { contract_id });
+        };
+
+        let contract_model = &mut contracts[index];
+        let is_rookie_development = matches!(
+            contract_model.kind,
+            ContractKind::RookieDevelopment | ContractKind::RookieDevelopmentInternational
+        );
+        let is_eligible = match declaration_move.action {
+            // RD/RDI players have to be activated before they can go on IR (§11.6.1).
+            DeclarationAction::MoveToIr => {
+                !contract_model.is_ir
+                    && is_counted_toward_cap(contract_model.kind)
+                    && on_nba_injured_reserve.contains(&contract_id)
+            }
+            DeclarationAction::ActivateRookieDevelopment => is_rookie_development,
+            DeclarationAction::MoveToRookieDevelopment => {
+                contract_model.kind == ContractKind::RookieDevelopmentInternational
+            }
+            DeclarationAction::Drop => true,
+        };
+        if !is_eligible {
+            return Err(DeclarationRejection::IneligibleMove {
+                contract_id,
+                kind: contract_model.kind,
+                action: declaration_move.action,
+            });
+        }
+
+        match declaration_move.action {
+            DeclarationAction::MoveToIr => contract_model.is_ir = true,
+            DeclarationAction::ActivateRookieDevelopment => {
+                contract_model.kind = ContractKind::Rookie;
+                contract_model.year_number = 1;
+            }
+            DeclarationAction::MoveToRookieDevelopment => {
+                contract_model.kind = ContractKind::RookieDevelopment;
+                contract_model.year_number = 1;
+            }
+            DeclarationAction::Drop => {
+                let dropped = contracts.remove(index);
+                if is_counted_toward_cap(dropped.kind) {
+                    drop_penalty += dropped_contract_cap_penalty(dropped.salary);
+                }
+            }
+        }
+    }
+
+    Ok(DeclaredRoster {
+        contracts,
+        drop_penalty,
+    })
+}
+
+fn describe_violations(violations: &[RosterViolation]) -> String {
+    violations
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use fbkl_entity::contract::{ContractKind, ContractStatus, Model};
+
+    use std::collections::HashSet;
+
+    use super::{DeclarationAction, DeclarationMove, DeclarationRejection, project_declaration};
+
+    fn contract(id: i64, kind: ContractKind, salary: i16) -> Model {
+        Model {
+            id,
+            year_number: 2,
+            kind,
+            is_ir: false,
+            salary,
+            end_of_season_year: 2025,
+            status: ContractStatus::Active,
+            league_id: 1,
+            league_player_id: None,
+            player_id: Some(id),
+            previous_contract_id: None,
+            original_contract_id: Some(id),
+            team_id: Some(7),
+            created_at: chrono::Utc::now().into(),
+            updated_at: chrono::Utc::now().into(),
+        }
+    }
+
+    fn declare(contract_id: i64, action: DeclarationAction) -> DeclarationMove {
+        DeclarationMove {
+            contract_id,
+            action,
+        }
+    }
+
+    #[test]
+    fn applies_every_move_to_the_projected_roster() {
+        let contracts = [
+            contract(1, ContractKind::Veteran, 30),
+            contract(2, ContractKind::RookieDevelopment, 4),
+            contract(3, ContractKind::RookieDevelopmentInternational, 2),
+            contract(4, ContractKind::Veteran, 11),
+            contract(5, ContractKind::RookieDevelopment, 1),
+        ];
+
+        let declared = project_declaration(
+            &contracts,
+            &[
+                declare(1, DeclarationAction::MoveToIr),
+                declare(2, DeclarationAction::ActivateRookieDevelopment),
+                declare(3, DeclarationAction::MoveToRookieDevelopment),
+                declare(4, DeclarationAction::Drop),
+                declare(5, DeclarationAction::Drop),
+            ],
+            &HashSet::from([1]),
+        )
+        .unwrap();
+
+        assert!(declared.contracts[0].is_ir);
+        assert_eq!(declared.contracts[1].kind, ContractKind::Rookie);
+        assert_eq!(declared.contracts[1].year_number, 1);
+        assert_eq!(declared.contracts[2].kind, ContractKind::RookieDevelopment);
+        assert_eq!(declared.contracts.len(), 3);
+        // Only the veteran's drop costs cap: ceil(20% of $11); the RD drop is free.
+        assert_eq!(declared.drop_penalty, 3);
+    }
+
+    #[test]
+    fn refuses_moves_the_contract_cannot_take() {
+        let contracts = [
+            contract(1, ContractKind::RookieDevelopment, 4),
+            contract(2, ContractKind::Veteran, 10),
+        ];
+
+        let on_nba_injured_reserve = HashSet::from([1, 2]);
+
+        assert_eq!(
+            project_declaration(
+                &contracts,
+                &[declare(1, DeclarationAction::MoveToIr)],
+                &on_nba_injured_reserve,
+            )
+            .unwrap_err(),
+            DeclarationRejection::IneligibleMove {
+                contract_id: 1,
+                kind: ContractKind::RookieDevelopment,
+                action: DeclarationAction::MoveToIr,
+            }
+        );
+        assert_eq!(
+            project_declaration(
+                &contracts,
+                &[declare(2, DeclarationAction::MoveToIr)],
+                &HashSet::new(),
+            )
+            .unwrap_err(),
+            DeclarationRejection::IneligibleMove {
+                contract_id: 2,
+                kind: ContractKind::Veteran,
+                action: DeclarationAction::MoveToIr,
+            }
+        );
+        assert_eq!(
+            project_declaration(
+                &contracts,
+                &[
+                    declare(2, DeclarationAction::MoveToIr),
+                    declare(2, DeclarationAction::Drop),
+                ],
+                &on_nba_injured_reserve,
+            )
+            .unwrap_err(),
+            DeclarationRejection::DuplicateContract { contract_id: 2 }
+        );
+        assert_eq!(
+            project_declaration(
+                &contracts,
+                &[declare(9, DeclarationAction::Drop)],
+                &on_nba_injured_reserve,
+            )
+            .unwrap_err(),
+            DeclarationRejection::ContractNotOnRoster { contract_id: 9 }
+        );
+    }
+}
@@ -5,9 +5,19 @@ pub mod draft_picks;
 pub mod drop_contract;
 pub mod eligibility;
 pub mod ir;
+pub mod league_invitations;
+pub mod league_player_merge;
+pub mod legalization;
+pub mod login_security;
+pub mod notifications;
+pub mod player_history;
+pub mod player_sync;
+pub mod replacement_draft;
+pub mod reports;
 pub mod rookie_development_activation;
 pub mod rookie_development_international;
 pub mod rookie_draft;
 pub mod roster;
 pub mod team_ownership;
 pub mod trade;
+pub mod weekly_moves;
@@ -0,0 +1,344 @@
+use chrono::TimeDelta;
+use color_eyre::Result;
+use fbkl_entity::{
+    sea_orm::{ConnectionTrait, prelude::DateTimeWithTimeZone},
+    security_event::{self, SecurityEventKind},
+    security_event_queries::{self, NewSecurityEvent},
+};
+use tracing::instrument;
+
+/// The window the per-IP and per-account attempt limits are counted over.
+pub const LOGIN_RATE_LIMIT_WINDOW_MINUTES: i64 = 15;
+/// Attempts one IP address may make in the window, across every email. Generous enough for a
+/// household or office sharing an address.
+pub const LOGIN_ATTEMPTS_PER_IP_ADDRESS: u64 = 30;
+/// Attempts that may be made on one email in the window, from anywhere.
+pub const LOGIN_ATTEMPTS_PER_ACCOUNT: u64 = 10;
+/// Consecutive failures on one email before it is locked out.
+pub const LOGIN_LOCKOUT_THRESHOLD: usize = 5;
+/// The first lockout's length. Each further failure doubles it, up to the maximum.
+pub const LOGIN_LOCKOUT_BASE_MINUTES: i64 = 1;
+pub const LOGIN_LOCKOUT_MAX_MINUTES: i64 = 60;
+/// How far back consecutive failures are counted. A success also resets the count.
+pub const LOGIN_FAILURE_MEMORY_HOURS: i64 = 24;
+/// A refused email and address pair is logged as `LoginThrottled` at most once per this many
+/// minutes, so a script hammering a locked account doesn't flood the event log.
+pub const LOGIN_THROTTLED_EVENT_INTERVAL_MINUTES: i64 = 1;
+
+/// Why a login attempt was refused before its password was checked.
+///
+/// Every variant gets the same response, and unknown emails are throttled exactly like real ones,
+/// so none of them reveal whether an account exists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum LoginThrottle {
+    #[error("Too many login attempts from this address; try again after {retry_at}.")]
+    IpAddressRateLimited { retry_at: DateTimeWithTimeZone },
+    #[error("Too many login attempts on this account; try again after {retry_at}.")]
+    AccountRateLimited { retry_at: DateTimeWithTimeZone },
+    #[error("This account is locked after repeated failed logins until {locked_until}.")]
+    AccountLocked { locked_until: DateTimeWithTimeZone },
+}
+
+impl LoginThrottle {
+    /// When a retry is sure to get past this throttle.
+    pub const fn retry_at(&self) -> DateTimeWithTimeZone {
+        match self {
+            Self::IpAddressRateLimited { retry_at } | Self::AccountRateLimited { retry_at } => {
+                *retry_at
+            }
+            Self::AccountLocked { locked_until } => *locked_until,
+        }
+    }
+}
+
+/// One login attempt, as the throttle sees it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoginAttempt {
+    /// As typed; normalized with [`normalize_login_email`] before it is stored or counted.
+    pub email: String,
+    pub ip_address: String,
+}
+
+/// The key login attempts are counted under, so `Owner@Example.com ` and `owner@example.com` share
+/// one set of limits.
+pub fn normalize_login_email(email: &str) -> String {
+    email.trim().to_lowercase()
+}
+
+/// Makes other attempts from `attempt`'s address or on its email wait until this transaction
+/// ends. Take it before [`check_login_throttle`] and reserve the attempt in the same transaction, or
+/// a parallel burst can all pass the check before any of its failures is recorded.
+///
+/// Only meaningful inside a db transaction.
+#[instrument(skip(db))]
+pub async fn lock_login_attempt<C>(attempt: &LoginAttempt, db: &C) -> Result<()>
+where
+    C: ConnectionTrait,
+{
+    // Always the address first, so two attempts can't each hold the lock the other wants.
+    security_event_queries::lock_security_event_key(
+        &format!("login-ip-address:{}", attempt.ip_address),
+        db,
+    )
+    .await?;
+    security_event_queries::lock_security_event_key(
+        &format!("login-email:{}", normalize_login_email(&attempt.email)),
+        db,
+    )
+    .await
+}
+
+/// Checks whether `attempt` may go on to have its password checked at `now`, and `Some` says why
+/// it was refused. A refused attempt is recorded as `LoginThrottled`, unless the same email and
+/// address already were within [`LOGIN_THROTTLED_EVENT_INTERVAL_MINUTES`].
+#[instrument(skip(db))]
+pub async fn check_login_throttle<C>(
+    attempt: &LoginAttempt,
+    now: DateTimeWithTimeZone,
+    db: &C,
+) -> Result<Option<LoginThrottle>>
+where
+    C: ConnectionTrait,
+{
+    let email = normalize_login_email(&attempt.email);
+    let Some(throttle) = find_throttle(&email, &attempt.ip_address, now, db).await? else {
+        return Ok(None);
+    };
+
+    let recently_recorded = security_event_queries::has_login_throttled_event_since(
+        &email,
+        &attempt.ip_address,
+        now - TimeDelta::minutes(LOGIN_THROTTLED_EVENT_INTERVAL_MINUTES),
+        db,
+    )
+    .await?;
+    if !recently_recorded {
+        security_event_queries::insert_security_event(
+            NewSecurityEvent {
+                kind: SecurityEventKind::LoginThrottled,
+                user_id: None,
+                email: Some(email),
+                ip_address: Some(attempt.ip_address.clone()),
+                detail: Some(throttle.to_string()),
+            },
+            now,
+            db,
+        )
+        .await?;
+    }
+
+    Ok(Some(throttle))
+}
+
+/// Records the outcome of a login attempt whose password was checked, in one go. A failure that
+/// brings the email to [`LOGIN_LOCKOUT_THRESHOLD`] or more consecutive failures also records the
+/// lockout it starts.
+#[instrument(skip(db))]
+pub async fn record_login_attempt<C>(
+    attempt: &LoginAttempt,
+    maybe_user_id: Option<i64>,
+    succeeded: bool,
+    now: DateTimeWithTimeZone,
+    db: &C,
+) -> Result<()>
+where
+    C: ConnectionTrait,
+{
+    let reserved_attempt = reserve_login_attempt(attempt, maybe_user_id, now, db).await?;
+    settle_login_attempt(attempt, reserved_attempt, succeeded, db).await
+}
+
+/// Records `attempt` as a failure before its password is checked, so it counts toward every limit
+/// while the hash runs outside any transaction. [`settle_login_attempt`] records the real outcome.
+#[instrument(skip(db))]
+pub async fn reserve_login_attempt<C>(
+    attempt: &LoginAttempt,
+    maybe_user_id: Option<i64>,
+    now: DateTimeWithTimeZone,
+    db: &C,
+) -> Result<security_event::Model>
+where
+    C: ConnectionTrait,
+{
+    security_event_queries::insert_security_event(
+        NewSecurityEvent {
+            kind: SecurityEventKind::LoginFailed,
+            user_id: maybe_user_id,
+            email: Some(normalize_login_email(&attempt.email)),
+            ip_address: Some(attempt.ip_address.clone()),
+            detail: None,
+        },
+        now,
+        db,
+    )
+    .await
+}
+
+/// Settles an attempt [`reserve_login_attempt`] recorded: a success is relabeled as one, and a
+/// failure that brings the email to [`LOGIN_LOCKOUT_THRESHOLD`] or more consecutive failures also
+/// records the lockout it starts. Take [`lock_login_attempt`] first, so a parallel failure can't
+/// record the same lockout.
+#[instrument(skip(db))]
+pub async fn settle_login_attempt<C>(
+    attempt: &LoginAttempt,
+    reserved_attempt: security_event::Model,
+    succeeded: bool,
+    db: &C,
+) -> Result<()>
+where
+    C: ConnectionTrait,
+{
+    if succeeded {
+        security_event_queries::update_security_event_kind(
+            reserved_attempt,
+            SecurityEventKind::LoginSucceeded,
+            db,
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let email = normalize_login_email(&attempt.email);
+    let now = reserved_attempt.created_at;
+    let recent_attempts = security_event_queries::find_login_attempts_for_email_since(
+        &email,
+        now - TimeDelta::hours(LOGIN_FAILURE_MEMORY_HOURS),
+        db,
+    )
+    .await?;
+    let consecutive_failures = count_consecutive_failures(&recent_attempts);
+    if let Some(lockout) = lockout_duration(consecutive_failures) {
+        security_event_queries::insert_security_event(
+            NewSecurityEvent {
+                kind: SecurityEventKind::AccountLocked,
+                user_id: reserved_attempt.user_id,
+                email: Some(email),
+                ip_address: Some(attempt.ip_address.clone()),
+                detail: Some(format!(
+                    "Locked for {} minute(s) after {consecutive_failures} consecutive failed logins.",
+                    lockout.num_minutes()
+                )),
+            },
+            now,
+            db,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+async fn find_throttle<C>(
+    email: &str,
+    ip_address: &str,
+    now: DateTimeWithTimeZone,
+    db: &C,
+) -> Result<Option<LoginThrottle>>
+where
+    C: ConnectionTrait,
+{
+    let window_start = now - TimeDelta::minutes(LOGIN_RATE_LIMIT_WINDOW_MINUTES);
+    // Every attempt counted now has left the window by then.
+    let retry_at = now + TimeDelta::minutes(LOGIN_RATE_LIMIT_WINDOW_MINUTES);
+
+    let ip_address_attempts = security_event_queries::count_login_attempts_for_ip_address_since(
+        ip_address,
+        window_start,
+        db,
+    )
+    .await?;
+    if ip_address_attempts >= LOGIN_ATTEMPTS_PER_IP_ADDRESS {
+        return Ok(Some(LoginThrottle::IpAddressRateLimited { retry_at }));
+    }
+
+    let account_attempts =
+        security_event_queries::count_login_attempts_for_email_since(email, window_start, db)
+            .await?;
+    if account_attempts >= LOGIN_ATTEMPTS_PER_ACCOUNT {
+        return Ok(Some(LoginThrottle::AccountRateLimited { retry_at }));
+    }
+
+    Ok(find_lockout(email, now, db)
+        .await?
+        .filter(|locked_until| now < *locked_until)
+        .map(|locked_until| LoginThrottle::AccountLocked { locked_until }))
+}
+
+/// Until when `email` is locked out, if its recent failures have locked it at all. May be in the
+/// past.
+async fn find_lockout<C>(
+    email: &str,
+    now: DateTimeWithTimeZone,
+    db: &C,
+) -> Result<Option<DateTimeWithTimeZone>>
+where
+    C: ConnectionTrait,
+{
+    let recent_attempts = security_event_queries::find_login_attempts_for_email_since(
+        email,
+        now - TimeDelta::hours(LOGIN_FAILURE_MEMORY_HOURS),
+        db,
+    )
+    .await?;
+    let Some(latest_attempt) = recent_attempts.first() else {
+        return Ok(None);
+    };
+    Ok(
+        lockout_duration(count_consecutive_failures(&recent_attempts))
+            .map(|lockout| latest_attempt.created_at + lockout),
+    )
+}
+
+/// Failures since the latest success, given login attempts newest first.
+fn count_consecutive_failures(attempts_newest_first: &[security_event::Model]) -> usize {
+    attempts_newest_first
+        .iter()
+        .take_while(|attempt| attempt.kind == SecurityEventKind::LoginFailed)
+        .count()
+}
+
+/// How long `consecutive_failures` lock an email out for: nothing below the threshold, then the
+/// base length doubling with each further failure, capped.
+fn lockout_duration(consecutive_failures: usize) -> Option<TimeDelta> {
+    let doublings = consecutive_failures.checked_sub(LOGIN_LOCKOUT_THRESHOLD)?;
+    // Past six doublings the cap has long been reached; stopping there keeps the shift small.
+    let minutes = LOGIN_LOCKOUT_BASE_MINUTES << doublings.min(6);
+    Some(TimeDelta::minutes(minutes.min(LOGIN_LOCKOUT_MAX_MINUTES)))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeDelta;
+
+    use super::{LOGIN_LOCKOUT_THRESHOLD, lockout_duration, normalize_login_email};
+
+    #[test]
+    fn lockout_starts_at_the_threshold_and_doubles_to_the_cap() {
+        assert_eq!(lockout_duration(0), None);
+        assert_eq!(lockout_duration(LOGIN_LOCKOUT_THRESHOLD - 1), None);
+        assert_eq!(
+            lockout_duration(LOGIN_LOCKOUT_THRESHOLD),
+            Some(TimeDelta::minutes(1))
+        );
+        assert_eq!(
+            lockout_duration(LOGIN_LOCKOUT_THRESHOLD + 3),
+            Some(TimeDelta::minutes(8))
+        );
+        assert_eq!(
+            lockout_duration(LOGIN_LOCKOUT_THRESHOLD + 6),
+            Some(TimeDelta::minutes(60))
+        );
+        assert_eq!(
+            lockout_duration(LOGIN_LOCKOUT_THRESHOLD + 500),
+            Some(TimeDelta::minutes(60))
+        );
+    }
+
+    #[test]
+    fn login_emails_are_counted_case_insensitively() {
+        assert_eq!(
+            normalize_login_email("  Owner@Example.COM "),
+            "owner@example.com"
+        );
+    }
+}
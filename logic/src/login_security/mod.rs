@@ -0,0 +1,10 @@
+//! Throttling password logins, and the security event log behind it.
+//!
+//! Every login attempt is recorded as a `security_event` before the response goes out, and the
+//! next attempt is checked against those rows before its password is hashed. Keeping the counters
+//! in Postgres makes the limits hold across every server and Lambda instance, and refusing
+//! throttled attempts before Argon2 runs keeps the login route from being used to burn CPU.
+
+mod login_throttle;
+
+pub use login_throttle::*;
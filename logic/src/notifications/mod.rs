@@ -0,0 +1,11 @@
+//! Telling owners about things that happened to their teams.
+//!
+//! The `logic` function behind an event calls [`notify_team`] in its own transaction, which writes
+//! a `notification` row per active user of the team and, depending on each user's preferences,
+//! queues an email right away or leaves it for [`send_notification_digests`].
+
+mod notification_digest;
+mod notify_team;
+
+pub use notification_digest::*;
+pub use notify_team::*;
@@ -0,0 +1,64 @@
+use chrono::TimeDelta;
+use color_eyre::Result;
+use fbkl_entity::{
+    notification::NotificationEmailStatus,
+    notification_queries,
+    sea_orm::{ConnectionTrait, TransactionTrait, prelude::DateTimeWithTimeZone},
+    user_queries,
+};
+use fbkl_notifications::{enqueue_email, notification_digest_email};
+use tracing::instrument;
+
+/// How long a user's oldest pending notification waits before their digest goes out. Everything
+/// pending goes in that one email, so a user gets at most one digest per period.
+pub const NOTIFICATION_DIGEST_PERIOD_HOURS: i64 = 24;
+
+/// Queues a digest email for every user whose oldest digest-pending notification is at least
+/// [`NOTIFICATION_DIGEST_PERIOD_HOURS`] old at `now`, and marks the notifications in it
+/// `Digested`. Returns how many digests were queued.
+#[instrument(skip(db))]
+pub async fn send_notification_digests<C>(now: DateTimeWithTimeZone, db: &C) -> Result<usize>
+where
+    C: ConnectionTrait + TransactionTrait,
+{
+    let due_before = now - TimeDelta::hours(NOTIFICATION_DIGEST_PERIOD_HOURS);
+    let user_ids = notification_queries::find_users_with_due_digests(due_before, db).await?;
+
+    let mut digests_sent = 0;
+    for user_id in user_ids {
+        let transaction = db.begin().await?;
+        // An overlapping run may have claimed or digested the rows since the users were listed.
+        let user_notifications =
+            notification_queries::claim_digest_pending_notifications(user_id, &transaction).await?;
+        if user_notifications
+            .first()
+            .is_none_or(|oldest| oldest.created_at > due_before)
+        {
+            transaction.rollback().await?;
+            continue;
+        }
+        let ids: Vec<i64> = user_notifications.iter().map(|n| n.id).collect();
+
+        if let Some(user_model) = user_queries::find_user_by_id(user_id, &transaction).await? {
+            let messages: Vec<String> = user_notifications
+                .into_iter()
+                .map(|notification_model| notification_model.message)
+                .collect();
+            enqueue_email(
+                &notification_digest_email(&user_model.email, &messages),
+                &transaction,
+            )
+            .await?;
+            digests_sent += 1;
+        }
+        notification_queries::set_notifications_email_status(
+            ids,
+            NotificationEmailStatus::Digested,
+            &transaction,
+        )
+        .await?;
+        transaction.commit().await?;
+    }
+
+    Ok(digests_sent)
+}
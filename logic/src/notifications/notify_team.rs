@@ -0,0 +1,92 @@
+use color_eyre::Result;
+use fbkl_entity::{
+    notification::{self, NotificationEmailStatus, NotificationKind},
+    notification_preference::NotificationEmailDelivery,
+    notification_preference_queries, notification_queries,
+    sea_orm::{ActiveValue, ConnectionTrait},
+    team_user_queries, user_queries,
+};
+use fbkl_notifications::{enqueue_email, notification_email};
+use tracing::instrument;
+
+/// The subject line of the email for one notification of `kind`.
+pub const fn notification_email_subject(kind: NotificationKind) -> &'static str {
+    match kind {
+        NotificationKind::Outbid => "You have been outbid",
+        NotificationKind::DraftPickOnTheClock => "Your draft pick is on the clock",
+        NotificationKind::TradeProposed => "A trade was proposed to you",
+        NotificationKind::RfaRaiseWindowOpened => "An RFA raise window opened",
+        NotificationKind::RfaMatchWindowOpened => "An RFA match window opened",
+        NotificationKind::RosterLockFailed => "Your roster failed the lock",
+        NotificationKind::HeldRosterMovesRuled => "The commissioner ruled on your held moves",
+        NotificationKind::RosterAutoLegalized => "Your roster was legalized at the lock",
+    }
+}
+
+/// Notifies every active user of `team_id` of an event in `league_id`, on the channels each of
+/// them chose for `kind`. Users who turned every channel off for `kind` get no row.
+///
+/// Pass the transaction of the event, so nobody hears about something that rolled back.
+#[instrument(skip(message, db))]
+pub async fn notify_team<C>(
+    league_id: i64,
+    team_id: i64,
+    kind: NotificationKind,
+    subject_id: Option<i64>,
+    message: &str,
+    db: &C,
+) -> Result<Vec<notification::Model>>
+where
+    C: ConnectionTrait,
+{
+    let team_users = team_user_queries::find_active_team_users_for_team(team_id, db).await?;
+
+    let mut notifications = Vec::with_capacity(team_users.len());
+    for team_user_model in team_users {
+        let preference = notification_preference_queries::find_notification_preference(
+            team_user_model.user_id,
+            kind,
+            db,
+        )
+        .await?;
+        let email_status = match preference.email_delivery {
+            NotificationEmailDelivery::Immediate => NotificationEmailStatus::Queued,
+            NotificationEmailDelivery::Digest => NotificationEmailStatus::DigestPending,
+            NotificationEmailDelivery::Off => NotificationEmailStatus::NotRequested,
+        };
+        if !preference.in_app && email_status == NotificationEmailStatus::NotRequested {
+            continue;
+        }
+
+        if email_status == NotificationEmailStatus::Queued {
+            let Some(user_model) =
+                user_queries::find_user_by_id(team_user_model.user_id, db).await?
+            else {
+                continue;
+            };
+            enqueue_email(
+                &notification_email(&user_model.email, notification_email_subject(kind), message),
+                db,
+            )
+            .await?;
+        }
+
+        let inserted = notification_queries::insert_notification(
+            notification::ActiveModel {
+                user_id: ActiveValue::Set(team_user_model.user_id),
+                league_id: ActiveValue::Set(league_id),
+                kind: ActiveValue::Set(kind),
+                subject_id: ActiveValue::Set(subject_id),
+                message: ActiveValue::Set(message.to_string()),
+                in_app: ActiveValue::Set(preference.in_app),
+                email_status: ActiveValue::Set(email_status),
+                ..Default::default()
+            },
+            db,
+        )
+        .await?;
+        notifications.push(inserted);
+    }
+
+    Ok(notifications)
+}
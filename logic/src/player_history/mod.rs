@@ -0,0 +1,6 @@
+//! A player's whole league career, read back out of contract chains and the transactions behind
+//! each link.
+
+mod player_league_history;
+
+pub use player_league_history::*;
@@ -0,0 +1,577 @@
+//! The career timeline behind `playerLeagueHistory`.
+//!
+//! Every change to a player's contract adds a row to its chain, so walking the player's rows in id
+//! order replays his league career. What each row does not say is *why* it exists; that comes from
+//! the transaction behind it, found one of two ways:
+//!
+//! - contract-kind transactions (drop / IR / RDI / rookie activation / merge) name a contract
+//!   directly. All of them name the contract they produced, except a drop, which names the contract
+//!   it dropped.
+//! - everything else (trade, auction, draft, RFA re-sign, replacement draft) names no contract, so
+//!   the link is the receiving team's `team_update`, whose contract update carries the new row's id.
+//!
+//! A row with neither is classified from the chain alone: a new season is an advancement, and a
+//! chain root is where the player entered the league's books.
+
+use std::collections::{HashMap, HashSet};
+
+use color_eyre::Result;
+use fbkl_entity::{
+    contract::{self, ContractKind, ContractStatus},
+    contract_queries, deadline_queries,
+    sea_orm::ConnectionTrait,
+    team_update::{ContractUpdateType, TeamUpdateAsset, TeamUpdateData},
+    team_update_queries,
+    transaction::{self, TransactionKind},
+    transaction_queries,
+};
+use tracing::instrument;
+
+use crate::roster::{dropped_contract_cap_penalty, is_counted_toward_cap};
+
+/// What one link in a player's contract history was.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayerHistoryStepKind {
+    /// A chain root with nothing behind it: an auction pool entry or an imported contract.
+    ContractCreated,
+    Drafted,
+    WonAtAuction,
+    Traded,
+    RfaResigned,
+    TakenInReplacementDraft,
+    Dropped,
+    /// The contract rolled into the next season, including RFA/UFA designation (rules §14.4).
+    Advanced,
+    ToIr,
+    FromIr,
+    ToRdi,
+    FromRdi,
+    RookieActivated,
+    /// The league player was merged into the real player he became.
+    MergedIntoRealPlayer,
+    /// The player went unsigned and his contract ran out.
+    Expired,
+    /// Any other link, e.g. an imported contract change with no transaction recorded.
+    ContractChanged,
+}
+
+impl PlayerHistoryStepKind {
+    /// Whether this step brought the player to a team.
+    pub const fn is_acquisition(self) -> bool {
+        matches!(
+            self,
+            Self::Drafted
+                | Self::WonAtAuction
+                | Self::Traded
+                | Self::RfaResigned
+                | Self::TakenInReplacementDraft
+        )
+    }
+}
+
+/// One link of a player's contract history, with the transaction that produced it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlayerHistoryStep {
+    pub contract: contract::Model,
+    pub kind: PlayerHistoryStepKind,
+    /// The team on the link before this one, so a trade or drop shows where the player came from.
+    pub previous_team_id: Option<i64>,
+    /// The cap penalty the dropping team paid for this drop (rules §9.1). `None` for anything but a
+    /// penalized drop, including a waived one.
+    pub drop_penalty: Option<i16>,
+    pub transaction: Option<transaction::Model>,
+}
+
+/// Where a player finished a season: the last rostered link of that season's contracts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlayerSeasonLine {
+    pub end_of_season_year: i16,
+    pub team_id: i64,
+    pub contract_kind: ContractKind,
+    pub year_number: i16,
+    pub salary: i16,
+}
+
+/// A player's league career: every contract link in order, plus a season-by-season summary.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlayerLeagueHistory {
+    pub steps: Vec<PlayerHistoryStep>,
+    pub seasons: Vec<PlayerSeasonLine>,
+}
+
+/// The transaction a contract row is attributed to, and the team update type that linked it when
+/// the link came from a `team_update`.
+#[derive(Debug, Clone, Copy)]
+struct StepLink {
+    transaction_id: i64,
+    maybe_update_type: Option<ContractUpdateType>,
+}
+
+/// Walks every contract chain a player has had in a league.
+///
+/// `is_league_player` picks which id space `player_id` lives in, matching
+/// `contract::Model::new_contract_from_rookie_draft`.
+#[instrument(skip(db))]
+pub async fn player_league_history<C>(
+    league_id: i64,
+    player_id: i64,
+    is_league_player: bool,
+    db: &C,
+) -> Result<PlayerLeagueHistory>
+where
+    C: ConnectionTrait,
+{
+    let contracts = contract_queries::find_league_contracts_for_player(
+        league_id,
+        player_id,
+        is_league_player,
+        db,
+    )
+    .await?;
+    if contracts.is_empty() {
+        return Ok(PlayerLeagueHistory::default());
+    }
+
+    let links = find_step_links(&contracts, db).await?;
+    let transactions_by_id: HashMap<i64, transaction::Model> =
+        transaction_queries::find_transactions_by_ids(
+            links.values().map(|link| link.transaction_id).collect(),
+            db,
+        )
+        .await?
+        .into_iter()
+        .map(|transaction_model| (transaction_model.id, transaction_model))
+        .collect();
+
+    // Only a drop past the keeper deadline costs cap room, so drops need their deadline's kind.
+    let mut preseason_drop_deadline_ids = HashSet::new();
+    for deadline_id in transactions_by_id
+        .values()
+        .filter(|transaction_model| {
+            transaction_model.kind == TransactionKind::TeamUpdateDropContract
+        })
+        .map(|transaction_model| transaction_model.deadline_id)
+        .collect::<HashSet<i64>>()
+    {
+        if deadline_queries::find_deadline_by_id(deadline_id, db)
+            .await?
+            .is_preseason_keeper_or_before()
+        {
+            preseason_drop_deadline_ids.insert(deadline_id);
+        }
+    }
+
+    let contracts_by_id: HashMap<i64, &contract::Model> = contracts
+        .iter()
+        .map(|contract_model| (contract_model.id, contract_model))
+        .collect();
+    let steps = contracts
+        .iter()
+        .map(|contract_model| {
+            let maybe_previous = contract_model
+                .previous_contract_id
+                .and_then(|previous_contract_id| contracts_by_id.get(&previous_contract_id))
+                .copied();
+            let maybe_link = links.get(&contract_model.id);
+            let maybe_transaction = maybe_link
+                .and_then(|link| transactions_by_id.get(&link.transaction_id))
+                .cloned();
+            let kind = classify_step(
+                contract_model,
+                maybe_previous,
+                maybe_transaction
+                    .as_ref()
+                    .map(|transaction_model| transaction_model.kind),
+                maybe_link.and_then(|link| link.maybe_update_type),
+            );
+            let drop_penalty = match (kind, maybe_previous, &maybe_transaction) {
+                (
+                    PlayerHistoryStepKind::Dropped,
+                    Some(dropped_contract),
+                    Some(drop_transaction),
+                ) => drop_penalty(
+                    dropped_contract,
+                    drop_transaction,
+                    preseason_drop_deadline_ids.contains(&drop_transaction.deadline_id),
+                ),
+                _ => None,
+            };
+            PlayerHistoryStep {
+                contract: contract_model.clone(),
+                kind,
+                previous_team_id: maybe_previous.and_then(|previous| previous.team_id),
+                drop_penalty,
+                transaction: maybe_transaction,
+            }
+        })
+        .collect();
+
+    Ok(PlayerLeagueHistory {
+        steps,
+        seasons: season_lines(&contracts),
+    })
+}
+
+/// Attributes each contract row to the transaction that produced it, if any.
+async fn find_step_links<C>(contracts: &[contract::Model], db: &C) -> Result<HashMap<i64, StepLink>>
+where
+    C: ConnectionTrait,
+{
+    let contract_ids: HashSet<i64> = contracts
+        .iter()
+        .map(|contract_model| contract_model.id)
+        .collect();
+    let successor_ids: HashMap<i64, i64> = contracts
+        .iter()
+        .filter_map(|contract_model| {
+            contract_model
+                .previous_contract_id
+                .map(|previous_contract_id| (previous_contract_id, contract_model.id))
+        })
+        .collect();
+
+    let mut links = HashMap::new();
+    for transaction_model in transaction_queries::find_transactions_for_contracts(
+        contract_ids.iter().copied().collect(),
+        db,
+    )
+    .await?
+    {
+        let Some(named_contract_id) = transaction_model.contract_id else {
+            continue;
+        };
+        // A drop names the contract it dropped; the link it produced is that contract's successor.
+        let maybe_step_contract_id =
+            if transaction_model.kind == TransactionKind::TeamUpdateDropContract {
+                successor_ids.get(&named_contract_id).copied()
+            } else {
+                Some(named_contract_id)
+            };
+        if let Some(step_contract_id) = maybe_step_contract_id {
+            links.entry(step_contract_id).or_insert(StepLink {
+                transaction_id: transaction_model.id,
+                maybe_update_type: None,
+            });
+        }
+    }
+
+    let team_ids: Vec<i64> = contracts
+        .iter()
+        .filter_map(|contract_model| contract_model.team_id)
+        .collect::<HashSet<i64>>()
+        .into_iter()
+        .collect();
+    for team_update_model in
+        team_update_queries::find_transaction_team_updates_for_teams(team_ids, db).await?
+    {
+        let Some(transaction_id) = team_update_model.transaction_id else {
+            continue;
+        };
+        let TeamUpdateData::Assets(asset_summary) = team_update_model.get_data()? else {
+            continue;
+        };
+        for changed_asset in asset_summary.changed_assets {
+            let TeamUpdateAsset::Contracts(contract_updates) = changed_asset else {
+                continue;
+            };
+            for contract_update in contract_updates {
+                if !contract_ids.contains(&contract_update.contract_id) {
+                    continue;
+                }
+                let maybe_step_contract_id = match contract_update.update_type {
+                    ContractUpdateType::AddViaTrade
+                    | ContractUpdateType::AddViaAuction
+                    | ContractUpdateType::AddViaRookieDraft
+                    | ContractUpdateType::AddViaReplacementDraft
+                    | ContractUpdateType::RfaResign
+                    | ContractUpdateType::ActivateRookie
+                    | ContractUpdateType::ToIR
+                    | ContractUpdateType::FromIR
+                    | ContractUpdateType::ToRdi
+                    | ContractUpdateType::FromRdi
+                    | ContractUpdateType::ContractAdvanced => Some(contract_update.contract_id),
+                    // A keeper-deadline drop names the rostered contract, not the dropped row.
+                    ContractUpdateType::Drop => successor_ids
+                        .get(&contract_update.contract_id)
+                        .copied()
+                        .or(Some(contract_update.contract_id)),
+                    // The giving side of a move; the receiving side's update is the one linked.
+                    ContractUpdateType::TradedAway
+                    | ContractUpdateType::Keeper
+                    | ContractUpdateType::LostViaFreeAgency
+                    | ContractUpdateType::LostViaReplacementDraft => None,
+                };
+                if let Some(step_contract_id) = maybe_step_contract_id {
+                    links.entry(step_contract_id).or_insert(StepLink {
+                        transaction_id,
+                        maybe_update_type: Some(contract_update.update_type),
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(links)
+}
+
+/// Names one contract row, preferring the transaction behind it, then the team update that linked
+/// it, then what the chain itself shows.
+fn classify_step(
+    contract_model: &contract::Model,
+    maybe_previous: Option<&contract::Model>,
+    maybe_transaction_kind: Option<TransactionKind>,
+    maybe_update_type: Option<ContractUpdateType>,
+) -> PlayerHistoryStepKind {
+    if let Some(kind) = maybe_transaction_kind.and_then(step_kind_for_transaction) {
+        return kind;
+    }
+    match maybe_update_type {
+        Some(ContractUpdateType::Drop) => return PlayerHistoryStepKind::Dropped,
+        Some(ContractUpdateType::ContractAdvanced) => return PlayerHistoryStepKind::Advanced,
+        _ => {}
+    }
+
+    let Some(previous) = maybe_previous else {
+        return PlayerHistoryStepKind::ContractCreated;
+    };
+    if contract_model.end_of_season_year > previous.end_of_season_year {
+        PlayerHistoryStepKind::Advanced
+    } else if previous.team_id.is_some()
+        && contract_model.team_id.is_none()
+        && contract_model.kind == ContractKind::FreeAgent
+    {
+        PlayerHistoryStepKind::Dropped
+    } else if contract_model.status == ContractStatus::Expired {
+        PlayerHistoryStepKind::Expired
+    } else {
+        PlayerHistoryStepKind::ContractChanged
+    }
+}
+
+/// The step a transaction of this kind produces, for kinds that produce a contract link at all.
+const fn step_kind_for_transaction(
+    transaction_kind: TransactionKind,
+) -> Option<PlayerHistoryStepKind> {
+    match transaction_kind {
+        TransactionKind::Trade => Some(PlayerHistoryStepKind::Traded),
+        TransactionKind::AuctionDone | TransactionKind::RfaDeclineAndForfeit => {
+            Some(PlayerHistoryStepKind::WonAtAuction)
+        }
+        TransactionKind::RookieDraftSelection => Some(PlayerHistoryStepKind::Drafted),
+        TransactionKind::RfaResign => Some(PlayerHistoryStepKind::RfaResigned),
+        TransactionKind::ReplacementDraftSelection => {
+            Some(PlayerHistoryStepKind::TakenInReplacementDraft)
+        }
+        TransactionKind::TeamUpdateDropContract => Some(PlayerHistoryStepKind::Dropped),
+        TransactionKind::TeamUpdateToIr => Some(PlayerHistoryStepKind::ToIr),
+        TransactionKind::TeamUpdateFromIr => Some(PlayerHistoryStepKind::FromIr),
+        TransactionKind::TeamUpdateToRdi => Some(PlayerHistoryStepKind::ToRdi),
+        TransactionKind::TeamUpdateFromRdi => Some(PlayerHistoryStepKind::FromRdi),
+        TransactionKind::RookieContractActivation => Some(PlayerHistoryStepKind::RookieActivated),
+        TransactionKind::LeaguePlayerMerge => Some(PlayerHistoryStepKind::MergedIntoRealPlayer),
+        TransactionKind::PreseasonStart
+        | TransactionKind::PreseasonKeeper
+        | TransactionKind::TeamUpdateConfigChange
+        | TransactionKind::RfaRaiseBid => None,
+    }
+}
+
+/// The cap penalty a drop cost its team: 20% of a counted contract's salary, unless the drop came
+/// before the keeper deadline or the commissioner waived it (rules §9.1.4).
+fn drop_penalty(
+    dropped_contract: &contract::Model,
+    drop_transaction: &transaction::Model,
+    is_preseason_drop: bool,
+) -> Option<i16> {
+    (drop_transaction.kind == TransactionKind::TeamUpdateDropContract
+        && !is_preseason_drop
+        && drop_transaction.drop_penalty_waived_at.is_none()
+        && is_counted_toward_cap(dropped_contract.kind))
+    .then(|| dropped_contract_cap_penalty(dropped_contract.salary))
+}
+
+/// One line per season the player spent on a roster, from the last rostered link of that season.
+fn season_lines(contracts: &[contract::Model]) -> Vec<PlayerSeasonLine> {
+    let mut lines: Vec<PlayerSeasonLine> = vec![];
+    for contract_model in contracts {
+        let Some(team_id) = contract_model.team_id else {
+            continue;
+        };
+        let line = PlayerSeasonLine {
+            end_of_season_year: contract_model.end_of_season_year,
+            team_id,
+            contract_kind: contract_model.kind,
+            year_number: contract_model.year_number,
+            salary: contract_model.salary,
+        };
+        match lines
+            .iter_mut()
+            .find(|existing| existing.end_of_season_year == contract_model.end_of_season_year)
+        {
+            Some(existing) => *existing = line,
+            None => lines.push(line),
+        }
+    }
+    lines.sort_by_key(|line| line.end_of_season_year);
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use fbkl_entity::{
+        contract::{self, ContractKind, ContractStatus},
+        team_update::ContractUpdateType,
+        transaction::{self, TransactionKind},
+    };
+
+    use super::{
+        PlayerHistoryStepKind, PlayerSeasonLine, classify_step, drop_penalty, season_lines,
+    };
+
+    fn contract_link(
+        id: i64,
+        kind: ContractKind,
+        end_of_season_year: i16,
+        salary: i16,
+        maybe_team_id: Option<i64>,
+    ) -> contract::Model {
+        contract::Model {
+            id,
+            year_number: 1,
+            kind,
+            is_ir: false,
+            salary,
+            end_of_season_year,
+            status: ContractStatus::Active,
+            league_id: 1,
+            league_player_id: None,
+            player_id: Some(1),
+            previous_contract_id: (id > 1).then_some(id - 1),
+            original_contract_id: Some(1),
+            team_id: maybe_team_id,
+            created_at: chrono::Utc::now().into(),
+            updated_at: chrono::Utc::now().into(),
+        }
+    }
+
+    fn drop_transaction(maybe_waived: bool) -> transaction::Model {
+        transaction::Model {
+            id: 1,
+            end_of_season_year: 2026,
+            kind: TransactionKind::TeamUpdateDropContract,
+            league_id: 1,
+            deadline_id: 1,
+            contract_id: Some(1),
+            drop_penalty_waived_at: maybe_waived.then(|| chrono::Utc::now().into()),
+            drop_penalty_waived_by_team_user_id: None,
+            drop_penalty_waiver_reason: None,
+            created_at: chrono::Utc::now().into(),
+            updated_at: chrono::Utc::now().into(),
+        }
+    }
+
+    #[test]
+    fn the_transaction_names_the_step() {
+        let previous = contract_link(1, ContractKind::Veteran, 2026, 10, Some(1));
+        let traded = contract_link(2, ContractKind::Veteran, 2026, 10, Some(2));
+
+        assert_eq!(
+            classify_step(
+                &traded,
+                Some(&previous),
+                Some(TransactionKind::Trade),
+                Some(ContractUpdateType::AddViaTrade),
+            ),
+            PlayerHistoryStepKind::Traded
+        );
+        assert_eq!(
+            classify_step(
+                &traded,
+                Some(&previous),
+                Some(TransactionKind::RfaDeclineAndForfeit),
+                None,
+            ),
+            PlayerHistoryStepKind::WonAtAuction
+        );
+    }
+
+    #[test]
+    fn unlinked_steps_fall_back_to_the_chain() {
+        let root = contract_link(1, ContractKind::Veteran, 2025, 10, Some(1));
+        let advanced = contract_link(2, ContractKind::Veteran, 2026, 10, Some(1));
+        let dropped = contract_link(3, ContractKind::FreeAgent, 2026, 1, None);
+
+        assert_eq!(
+            classify_step(&root, None, None, None),
+            PlayerHistoryStepKind::ContractCreated
+        );
+        assert_eq!(
+            classify_step(&advanced, Some(&root), None, None),
+            PlayerHistoryStepKind::Advanced
+        );
+        // A keeper-deadline drop is linked through the keeper transaction's Drop update.
+        assert_eq!(
+            classify_step(
+                &dropped,
+                Some(&advanced),
+                Some(TransactionKind::PreseasonKeeper),
+                Some(ContractUpdateType::Drop),
+            ),
+            PlayerHistoryStepKind::Dropped
+        );
+        assert_eq!(
+            classify_step(&dropped, Some(&advanced), None, None),
+            PlayerHistoryStepKind::Dropped
+        );
+    }
+
+    #[test]
+    fn only_regular_season_unwaived_drops_of_counted_contracts_cost_cap() {
+        let veteran = contract_link(1, ContractKind::Veteran, 2026, 12, Some(1));
+        let rookie_development =
+            contract_link(1, ContractKind::RookieDevelopment, 2026, 2, Some(1));
+
+        // 20% of $12, rounded up.
+        assert_eq!(
+            drop_penalty(&veteran, &drop_transaction(false), false),
+            Some(3)
+        );
+        assert_eq!(drop_penalty(&veteran, &drop_transaction(false), true), None);
+        assert_eq!(drop_penalty(&veteran, &drop_transaction(true), false), None);
+        assert_eq!(
+            drop_penalty(&rookie_development, &drop_transaction(false), false),
+            None
+        );
+    }
+
+    #[test]
+    fn each_season_ends_on_its_last_rostered_link() {
+        let contracts = vec![
+            contract_link(1, ContractKind::RookieDevelopment, 2025, 2, Some(1)),
+            contract_link(2, ContractKind::Rookie, 2025, 2, Some(1)),
+            contract_link(3, ContractKind::Rookie, 2026, 2, Some(1)),
+            contract_link(4, ContractKind::Rookie, 2026, 2, Some(2)),
+            contract_link(5, ContractKind::FreeAgent, 2026, 2, None),
+        ];
+
+        assert_eq!(
+            season_lines(&contracts),
+            vec![
+                PlayerSeasonLine {
+                    end_of_season_year: 2025,
+                    team_id: 1,
+                    contract_kind: ContractKind::Rookie,
+                    year_number: 1,
+                    salary: 2,
+                },
+                PlayerSeasonLine {
+                    end_of_season_year: 2026,
+                    team_id: 2,
+                    contract_kind: ContractKind::Rookie,
+                    year_number: 1,
+                    salary: 2,
+                },
+            ]
+        );
+    }
+}
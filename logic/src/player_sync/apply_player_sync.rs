@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, FixedOffset};
+use color_eyre::{Result, eyre::eyre};
+use fbkl_entity::{
+    player::{self, NbaInjuryStatus, NbaRosterSource},
+    player_queries,
+    sea_orm::{ActiveValue, ConnectionTrait},
+};
+use tracing::instrument;
+
+use super::PlayerSyncReport;
+
+/// The `player` rows an applied sync wrote.
+#[derive(Debug, Default)]
+pub struct PlayerSyncApplied {
+    pub inserted_player_ids: Vec<i64>,
+    pub updated_player_ids: Vec<i64>,
+}
+
+/// Writes a reviewed report as of `asof`. Renamed players keep their old name as an alias, so
+/// searches and historical contract updates still find them. Unresolved rows are left alone.
+#[instrument(skip(report, db))]
+pub async fn apply_player_sync<C>(
+    report: &PlayerSyncReport,
+    source: NbaRosterSource,
+    asof: DateTime<FixedOffset>,
+    db: &C,
+) -> Result<PlayerSyncApplied>
+where
+    C: ConnectionTrait,
+{
+    let mut applied = PlayerSyncApplied::default();
+
+    let mut player_ids: Vec<i64> = report
+        .renames
+        .iter()
+        .map(|rename| rename.player_id)
+        .chain(
+            report
+                .real_team_changes
+                .iter()
+                .map(|change| change.player_id),
+        )
+        .chain(report.status_changes.iter().map(|change| change.player_id))
+        .collect();
+    player_ids.sort_unstable();
+    player_ids.dedup();
+    let mut players_by_id: HashMap<i64, player::Model> =
+        player_queries::find_players_by_ids(player_ids.clone(), db)
+            .await?
+            .into_iter()
+            .map(|player_model| (player_model.id, player_model))
+            .collect();
+
+    for rename in &report.renames {
+        let player_model = take_player(&mut players_by_id, rename.player_id)?;
+        let renamed =
+            player_queries::rename_player(player_model, rename.to_name.clone(), asof, db).await?;
+        players_by_id.insert(renamed.id, renamed);
+    }
+    for change in &report.real_team_changes {
+        let player_model = take_player(&mut players_by_id, change.player_id)?;
+        let moved =
+            player_queries::set_player_current_real_team(player_model, change.to_real_team_id, db)
+                .await?;
+        players_by_id.insert(moved.id, moved);
+    }
+    for change in &report.status_changes {
+        let player_model = take_player(&mut players_by_id, change.player_id)?;
+        let updated = player_queries::set_player_status(player_model, change.to_status, db).await?;
+        players_by_id.insert(updated.id, updated);
+    }
+    applied.updated_player_ids = player_ids;
+
+    for new_player in &report.new_players {
+        let entry = &new_player.entry;
+        let inserted = player_queries::insert_player(
+            player::ActiveModel {
+                name: ActiveValue::Set(entry.name.clone()),
+                espn_id: ActiveValue::Set(entry.espn_id),
+                nba_id: ActiveValue::Set(entry.nba_id),
+                position_id: ActiveValue::Set(new_player.position_id),
+                status: ActiveValue::Set(new_player.status),
+                is_rdi_eligible: ActiveValue::Set(false),
+                has_played_nba_game: ActiveValue::Set(entry.has_played_nba_game),
+                nba_first_season_end_of_season_year: ActiveValue::Set(
+                    entry.from_year.map(|from_year| from_year + 1),
+                ),
+                nba_roster_source: ActiveValue::Set(source),
+                nba_roster_asof: ActiveValue::Set(Some(asof)),
+                nba_injury_status: ActiveValue::Set(NbaInjuryStatus::Healthy),
+                nba_injury_source: ActiveValue::Set(NbaRosterSource::Unknown),
+                current_real_team_id: ActiveValue::Set(new_player.real_team_id),
+                ..Default::default()
+            },
+            db,
+        )
+        .await?;
+        applied.inserted_player_ids.push(inserted.id);
+    }
+
+    Ok(applied)
+}
+
+fn take_player(
+    players_by_id: &mut HashMap<i64, player::Model>,
+    player_id: i64,
+) -> Result<player::Model> {
+    players_by_id
+        .remove(&player_id)
+        .ok_or_else(|| eyre!("player {player_id} is no longer in the database"))
+}
@@ -0,0 +1,14 @@
+//! Keeping real (`player`) rows in step with the NBA/ESPN player index (spec 12).
+//!
+//! A sync is two steps. [`plan_player_sync`] diffs an index against the database and returns a
+//! report — new players, renames, real-team moves and retirements — for the commissioner to review;
+//! [`apply_player_sync`] writes a report. Players are matched by `nba_id`, then `espn_id`, never by
+//! name, so planning again after applying finds nothing to do.
+
+mod apply_player_sync;
+mod plan_player_sync;
+mod player_index;
+
+pub use apply_player_sync::*;
+pub use plan_player_sync::*;
+pub use player_index::*;
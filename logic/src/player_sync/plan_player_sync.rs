@@ -0,0 +1,438 @@
+use std::collections::HashMap;
+
+use color_eyre::Result;
+use fbkl_entity::{
+    player::{self, PlayerStatus},
+    player_queries, position_queries, real_team_queries,
+    sea_orm::ConnectionTrait,
+};
+use tracing::instrument;
+
+use super::PlayerIndexEntry;
+
+/// An index player with no `player` row yet, with the team, position and status he will be given.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewPlayer {
+    pub entry: PlayerIndexEntry,
+    pub real_team_id: i64,
+    pub position_id: i32,
+    pub status: PlayerStatus,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlayerRename {
+    pub player_id: i64,
+    pub from_name: String,
+    pub to_name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlayerRealTeamChange {
+    pub player_id: i64,
+    pub name: String,
+    pub from_real_team_id: i64,
+    pub to_real_team_id: i64,
+}
+
+/// A retirement, or a retired player's comeback.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlayerStatusChange {
+    pub player_id: i64,
+    pub name: String,
+    pub from_status: PlayerStatus,
+    pub to_status: PlayerStatus,
+}
+
+/// Why an index row cannot be applied as-is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UnresolvedReason {
+    /// The row names an NBA team with no `real_team` row.
+    UnknownRealTeam { real_team_nba_id: i32 },
+    /// A new player is on no team, and every `player` row needs one.
+    NoRealTeam,
+    /// The row's position maps to no `position` row.
+    UnknownPosition { position: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnresolvedIndexEntry {
+    pub entry: PlayerIndexEntry,
+    pub reason: UnresolvedReason,
+}
+
+/// What syncing an index would change, for the commissioner to review before applying.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlayerSyncReport {
+    pub new_players: Vec<NewPlayer>,
+    pub renames: Vec<PlayerRename>,
+    pub real_team_changes: Vec<PlayerRealTeamChange>,
+    pub status_changes: Vec<PlayerStatusChange>,
+    /// Rows left out of the changes above. A known player's other changes still apply.
+    pub unresolved: Vec<UnresolvedIndexEntry>,
+}
+
+impl PlayerSyncReport {
+    /// Whether applying the report would write nothing.
+    pub fn has_no_changes(&self) -> bool {
+        self.new_players.is_empty()
+            && self.renames.is_empty()
+            && self.real_team_changes.is_empty()
+            && self.status_changes.is_empty()
+    }
+}
+
+/// The database rows an index is compared against.
+#[derive(Debug, Default)]
+struct SyncLookups {
+    players_by_nba_id: HashMap<i32, player::Model>,
+    players_by_espn_id: HashMap<i32, player::Model>,
+    real_team_ids_by_nba_id: HashMap<i32, i64>,
+    position_ids_by_name: HashMap<String, i32>,
+}
+
+/// Diffs `entries` against the `player` table as of `end_of_season_year`'s season.
+///
+/// A player is retired once he is off every NBA roster and his last season ended before this one;
+/// a retired player back on a roster is active again.
+#[instrument(skip(entries, db))]
+pub async fn plan_player_sync<C>(
+    entries: &[PlayerIndexEntry],
+    end_of_season_year: i16,
+    db: &C,
+) -> Result<PlayerSyncReport>
+where
+    C: ConnectionTrait,
+{
+    let players_by_nba_id = player_queries::find_players_by_nba_ids(
+        entries.iter().filter_map(|entry| entry.nba_id).collect(),
+        db,
+    )
+    .await?
+    .into_iter()
+    .filter_map(|player_model| player_model.nba_id.map(|nba_id| (nba_id, player_model)))
+    .collect();
+    let players_by_espn_id = player_queries::find_players_by_espn_ids(
+        entries.iter().filter_map(|entry| entry.espn_id).collect(),
+        db,
+    )
+    .await?
+    .into_iter()
+    .filter_map(|player_model| player_model.espn_id.map(|espn_id| (espn_id, player_model)))
+    .collect();
+    let lookups = SyncLookups {
+        players_by_nba_id,
+        players_by_espn_id,
+        real_team_ids_by_nba_id: real_team_queries::get_all_real_teams(db)
+            .await?
+            .into_iter()
+            .map(|real_team_model| (real_team_model.nba_id, real_team_model.id))
+            .collect(),
+        position_ids_by_name: position_queries::find_all_positions(db)
+            .await?
+            .into_iter()
+            .map(|position_model| (position_model.name, position_model.id))
+            .collect(),
+    };
+
+    Ok(diff_player_index(entries, &lookups, end_of_season_year))
+}
+
+fn diff_player_index(
+    entries: &[PlayerIndexEntry],
+    lookups: &SyncLookups,
+    end_of_season_year: i16,
+) -> PlayerSyncReport {
+    let mut report = PlayerSyncReport::default();
+    for entry in entries {
+        let maybe_real_team_id = match entry.real_team_nba_id {
+            None => None,
+            Some(real_team_nba_id) => {
+                if let Some(real_team_id) = lookups.real_team_ids_by_nba_id.get(&real_team_nba_id) {
+                    Some(*real_team_id)
+                } else {
+                    report.unresolved.push(UnresolvedIndexEntry {
+                        entry: entry.clone(),
+                        reason: UnresolvedReason::UnknownRealTeam { real_team_nba_id },
+                    });
+                    continue;
+                }
+            }
+        };
+
+        let maybe_player = entry
+            .nba_id
+            .and_then(|nba_id| lookups.players_by_nba_id.get(&nba_id))
+            .or_else(|| {
+                entry
+                    .espn_id
+                    .and_then(|espn_id| lookups.players_by_espn_id.get(&espn_id))
+            });
+        let Some(player_model) = maybe_player else {
+            let Some(real_team_id) = maybe_real_team_id else {
+                report.unresolved.push(UnresolvedIndexEntry {
+                    entry: entry.clone(),
+                    reason: UnresolvedReason::NoRealTeam,
+                });
+                continue;
+            };
+            let position = position_name(&entry.position);
+            let Some(position_id) = lookups.position_ids_by_name.get(position) else {
+                report.unresolved.push(UnresolvedIndexEntry {
+                    entry: entry.clone(),
+                    reason: UnresolvedReason::UnknownPosition {
+                        position: entry.position.clone(),
+                    },
+                });
+                continue;
+            };
+            report.new_players.push(NewPlayer {
+                entry: entry.clone(),
+                real_team_id,
+                position_id: *position_id,
+                status: status_for(entry, end_of_season_year),
+            });
+            continue;
+        };
+
+        if player_model.name != entry.name {
+            report.renames.push(PlayerRename {
+                player_id: player_model.id,
+                from_name: player_model.name.clone(),
+                to_name: entry.name.clone(),
+            });
+        }
+        if let Some(real_team_id) = maybe_real_team_id
+            .filter(|real_team_id| *real_team_id != player_model.current_real_team_id)
+        {
+            report.real_team_changes.push(PlayerRealTeamChange {
+                player_id: player_model.id,
+                name: entry.name.clone(),
+                from_real_team_id: player_model.current_real_team_id,
+                to_real_team_id: real_team_id,
+            });
+        }
+        let status = status_for(entry, end_of_season_year);
+        if player_model.status != status {
+            report.status_changes.push(PlayerStatusChange {
+                player_id: player_model.id,
+                name: entry.name.clone(),
+                from_status: player_model.status,
+                to_status: status,
+            });
+        }
+    }
+
+    report
+}
+
+/// Retired once off every roster with a last season (a start year) before the current one.
+fn status_for(entry: &PlayerIndexEntry, end_of_season_year: i16) -> PlayerStatus {
+    let current_season_start_year = end_of_season_year - 1;
+    let played_this_season = entry
+        .to_year
+        .is_some_and(|to_year| to_year >= current_season_start_year);
+    if entry.is_rostered || played_this_season {
+        PlayerStatus::Active
+    } else {
+        PlayerStatus::Retired
+    }
+}
+
+/// Maps the NBA index's positions onto the ESPN position names `position` is seeded with.
+fn position_name(nba_position: &str) -> &str {
+    match nba_position {
+        "G-F" | "F-G" => "G/F",
+        "F-C" | "C-F" => "F/C",
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use fbkl_entity::player::{self, PlayerStatus};
+
+    use super::{
+        NewPlayer, PlayerRealTeamChange, PlayerRename, PlayerStatusChange, SyncLookups,
+        UnresolvedReason, diff_player_index,
+    };
+    use crate::player_sync::PlayerIndexEntry;
+
+    fn entry(nba_id: i32, name: &str, real_team_nba_id: Option<i32>) -> PlayerIndexEntry {
+        PlayerIndexEntry {
+            nba_id: Some(nba_id),
+            espn_id: None,
+            name: name.to_owned(),
+            real_team_nba_id,
+            position: "G-F".to_owned(),
+            from_year: Some(2020),
+            to_year: Some(2025),
+            is_rostered: real_team_nba_id.is_some(),
+            has_played_nba_game: true,
+        }
+    }
+
+    fn existing_player(id: i64, nba_id: i32, name: &str, real_team_id: i64) -> player::Model {
+        player::Model {
+            id,
+            is_rdi_eligible: false,
+            name: name.to_owned(),
+            photo_url: None,
+            thumbnail_url: None,
+            espn_id: None,
+            nba_id: Some(nba_id),
+            position_id: 1,
+            status: PlayerStatus::Active,
+            has_played_nba_game: true,
+            nba_first_season_end_of_season_year: Some(2021),
+            nba_roster_source: player::NbaRosterSource::Nba,
+            nba_roster_asof: None,
+            nba_injury_status: player::NbaInjuryStatus::Healthy,
+            nba_injury_source: player::NbaRosterSource::Unknown,
+            nba_injury_asof: None,
+            eligibility_override: None,
+            eligibility_override_reason: None,
+            eligibility_override_by_team_user_id: None,
+            eligibility_override_at: None,
+            current_real_team_id: real_team_id,
+            created_at: chrono::Utc::now().into(),
+            updated_at: chrono::Utc::now().into(),
+        }
+    }
+
+    fn lookups(players: Vec<player::Model>) -> SyncLookups {
+        SyncLookups {
+            players_by_nba_id: players
+                .into_iter()
+                .map(|player_model| (player_model.nba_id.unwrap(), player_model))
+                .collect(),
+            players_by_espn_id: HashMap::new(),
+            real_team_ids_by_nba_id: HashMap::from([(100, 1), (200, 2)]),
+            position_ids_by_name: HashMap::from([("G/F".to_owned(), 9)]),
+        }
+    }
+
+    #[test]
+    fn reports_renames_team_moves_retirements_and_new_players() {
+        let lookups = lookups(vec![
+            existing_player(1, 11, "Enes Kanter", 1),
+            existing_player(2, 12, "Traded Guard", 1),
+            existing_player(3, 13, "Old Timer", 1),
+        ]);
+        let mut retired = entry(13, "Old Timer", None);
+        retired.to_year = Some(2023);
+        let entries = vec![
+            entry(11, "Enes Freedom", Some(100)),
+            entry(12, "Traded Guard", Some(200)),
+            retired,
+            entry(14, "New Rookie", Some(200)),
+        ];
+
+        let report = diff_player_index(&entries, &lookups, 2026);
+        assert_eq!(
+            report.renames,
+            vec![PlayerRename {
+                player_id: 1,
+                from_name: "Enes Kanter".to_owned(),
+                to_name: "Enes Freedom".to_owned(),
+            }]
+        );
+        assert_eq!(
+            report.real_team_changes,
+            vec![PlayerRealTeamChange {
+                player_id: 2,
+                name: "Traded Guard".to_owned(),
+                from_real_team_id: 1,
+                to_real_team_id: 2,
+            }]
+        );
+        assert_eq!(
+            report.status_changes,
+            vec![PlayerStatusChange {
+                player_id: 3,
+                name: "Old Timer".to_owned(),
+                from_status: PlayerStatus::Active,
+                to_status: PlayerStatus::Retired,
+            }]
+        );
+        assert_eq!(
+            report.new_players,
+            vec![NewPlayer {
+                entry: entry(14, "New Rookie", Some(200)),
+                real_team_id: 2,
+                position_id: 9,
+                status: PlayerStatus::Active,
+            }]
+        );
+        assert!(report.unresolved.is_empty());
+    }
+
+    #[test]
+    fn an_unchanged_index_plans_nothing() {
+        let lookups = lookups(vec![existing_player(1, 11, "Steady Starter", 1)]);
+
+        let report = diff_player_index(&[entry(11, "Steady Starter", Some(100))], &lookups, 2026);
+        assert!(report.has_no_changes());
+        assert!(report.unresolved.is_empty());
+    }
+
+    #[test]
+    fn a_player_without_an_nba_id_is_matched_by_espn_id() {
+        let mut espn_only = existing_player(1, 0, "Imported Forward", 1);
+        espn_only.nba_id = None;
+        espn_only.espn_id = Some(31);
+        let lookups = SyncLookups {
+            players_by_espn_id: HashMap::from([(31, espn_only)]),
+            ..lookups(vec![])
+        };
+        let mut indexed = entry(11, "Imported Forward", Some(200));
+        indexed.espn_id = Some(31);
+
+        let report = diff_player_index(&[indexed], &lookups, 2026);
+        assert!(report.new_players.is_empty());
+        assert_eq!(
+            report.real_team_changes,
+            vec![PlayerRealTeamChange {
+                player_id: 1,
+                name: "Imported Forward".to_owned(),
+                from_real_team_id: 1,
+                to_real_team_id: 2,
+            }]
+        );
+    }
+
+    #[test]
+    fn rows_that_cannot_be_resolved_are_set_aside() {
+        let lookups = lookups(vec![]);
+        let mut unknown_position = entry(3, "Swingman", Some(100));
+        unknown_position.position = "W".to_owned();
+
+        let report = diff_player_index(
+            &[
+                entry(1, "Expansion Player", Some(999)),
+                entry(2, "Unsigned", None),
+                unknown_position,
+            ],
+            &lookups,
+            2026,
+        );
+        assert!(report.has_no_changes());
+        assert_eq!(
+            report
+                .unresolved
+                .iter()
+                .map(|unresolved| unresolved.reason.clone())
+                .collect::<Vec<_>>(),
+            vec![
+                UnresolvedReason::UnknownRealTeam {
+                    real_team_nba_id: 999
+                },
+                UnresolvedReason::NoRealTeam,
+                UnresolvedReason::UnknownPosition {
+                    position: "W".to_owned()
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,251 @@
+//! Reading the NBA player index (`stats.nba.com` `playerindex`) into sync entries.
+//!
+//! The file is the endpoint's JSON as downloaded: a `resultSets[0]` with `headers` and a `rowSet`
+//! of positional rows. Columns are found by header name, so added or reordered columns are fine.
+//! The endpoint has no ESPN id, but an index with an `ESPN_ID` column added carries one per row,
+//! which matches players imported from ESPN before they had an NBA id.
+
+use std::collections::HashSet;
+
+use serde_json::Value;
+
+/// One player's row in an index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlayerIndexEntry {
+    pub nba_id: Option<i32>,
+    pub espn_id: Option<i32>,
+    pub name: String,
+    /// The NBA id of the team the player is on; `None` for free agents and retired players.
+    pub real_team_nba_id: Option<i32>,
+    /// The index's position, e.g. `G`, `F-C`. Empty when the index has none.
+    pub position: String,
+    /// The first and last seasons played, as the *start* year of each season.
+    pub from_year: Option<i16>,
+    pub to_year: Option<i16>,
+    /// Whether the player is on a current NBA roster.
+    pub is_rostered: bool,
+    /// Null career points means no in-season appearance (spec 10), so this is the §3.1.2 fact.
+    pub has_played_nba_game: bool,
+}
+
+/// Why a player index could not be read. Row numbers start at 1.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum PlayerIndexError {
+    #[error("The player index is not valid JSON: {0}")]
+    NotJson(String),
+    #[error("The player index has no `resultSets[0]` with `headers` and `rowSet`.")]
+    MissingResultSet,
+    #[error("The player index has no `{header}` column.")]
+    MissingColumn { header: &'static str },
+    #[error("Row {row_number} is malformed: {reason}")]
+    MalformedRow { row_number: usize, reason: String },
+    #[error("Row {row_number} lists NBA id {nba_id}, which appears earlier in the index.")]
+    DuplicatePlayer { row_number: usize, nba_id: i32 },
+}
+
+struct Columns {
+    person_id: usize,
+    first_name: usize,
+    last_name: usize,
+    team_id: usize,
+    position: usize,
+    from_year: usize,
+    to_year: usize,
+    roster_status: Option<usize>,
+    points: Option<usize>,
+    espn_id: Option<usize>,
+}
+
+pub fn parse_nba_player_index(contents: &str) -> Result<Vec<PlayerIndexEntry>, PlayerIndexError> {
+    let index: Value =
+        serde_json::from_str(contents).map_err(|err| PlayerIndexError::NotJson(err.to_string()))?;
+    let result_set = index
+        .get("resultSets")
+        .and_then(|result_sets| result_sets.get(0))
+        .ok_or(PlayerIndexError::MissingResultSet)?;
+    let (Some(headers), Some(rows)) = (
+        result_set.get("headers").and_then(Value::as_array),
+        result_set.get("rowSet").and_then(Value::as_array),
+    ) else {
+        return Err(PlayerIndexError::MissingResultSet);
+    };
+    let headers: Vec<&str> = headers.iter().filter_map(Value::as_str).collect();
+    let column = |header: &'static str| {
+        headers
+            .iter()
+            .position(|candidate| *candidate == header)
+            .ok_or(PlayerIndexError::MissingColumn { header })
+    };
+    let columns = Columns {
+        person_id: column("PERSON_ID")?,
+        first_name: column("PLAYER_FIRST_NAME")?,
+        last_name: column("PLAYER_LAST_NAME")?,
+        team_id: column("TEAM_ID")?,
+        position: column("POSITION")?,
+        from_year: column("FROM_YEAR")?,
+        to_year: column("TO_YEAR")?,
+        roster_status: column("ROSTER_STATUS").ok(),
+        points: column("PTS").ok(),
+        espn_id: column("ESPN_ID").ok(),
+    };
+
+    let mut entries = Vec::with_capacity(rows.len());
+    let mut seen_nba_ids = HashSet::new();
+    for (index, row) in rows.iter().enumerate() {
+        let row_number = index + 1;
+        let entry = parse_row(row, &columns)
+            .map_err(|reason| PlayerIndexError::MalformedRow { row_number, reason })?;
+        if let Some(nba_id) = entry.nba_id.filter(|nba_id| !seen_nba_ids.insert(*nba_id)) {
+            return Err(PlayerIndexError::DuplicatePlayer { row_number, nba_id });
+        }
+        entries.push(entry);
+    }
+
+    Ok(entries)
+}
+
+fn parse_row(row: &Value, columns: &Columns) -> Result<PlayerIndexEntry, String> {
+    let row = row.as_array().ok_or("not an array")?;
+    let cell = |position: usize| row.get(position).unwrap_or(&Value::Null);
+
+    let nba_id = integer(cell(columns.person_id))
+        .ok_or("PERSON_ID is not a number")?
+        .try_into()
+        .map_err(|_| "PERSON_ID is out of range")?;
+    let name = [cell(columns.first_name), cell(columns.last_name)]
+        .iter()
+        .filter_map(|part| part.as_str())
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    if name.is_empty() {
+        return Err("the player has no name".to_owned());
+    }
+    let real_team_nba_id = integer(cell(columns.team_id))
+        .filter(|team_id| *team_id != 0)
+        .and_then(|team_id| i32::try_from(team_id).ok());
+    let espn_id = match columns
+        .espn_id
+        .map(cell)
+        .filter(|espn_id| !espn_id.is_null())
+    {
+        None => None,
+        Some(espn_id) => Some(
+            integer(espn_id)
+                .ok_or("ESPN_ID is not a number")?
+                .try_into()
+                .map_err(|_| "ESPN_ID is out of range")?,
+        ),
+    };
+
+    Ok(PlayerIndexEntry {
+        nba_id: Some(nba_id),
+        espn_id,
+        name,
+        real_team_nba_id,
+        position: cell(columns.position)
+            .as_str()
+            .unwrap_or_default()
+            .trim()
+            .to_owned(),
+        from_year: integer(cell(columns.from_year)).and_then(|year| i16::try_from(year).ok()),
+        to_year: integer(cell(columns.to_year)).and_then(|year| i16::try_from(year).ok()),
+        is_rostered: columns
+            .roster_status
+            .and_then(|position| integer(cell(position)))
+            .is_some_and(|roster_status| roster_status != 0),
+        has_played_nba_game: columns
+            .points
+            .is_some_and(|position| !cell(position).is_null()),
+    })
+}
+
+/// The index writes ids and years as numbers in some vintages and as strings in others, and
+/// `ROSTER_STATUS` as a float.
+// only whole-valued floats are cast, and the index keeps them well inside i64
+#[allow(clippy::cast_possible_truncation)]
+fn integer(value: &Value) -> Option<i64> {
+    match value {
+        Value::Number(number) => number.as_i64().or_else(|| {
+            number
+                .as_f64()
+                .filter(|float| float.fract().abs() < f64::EPSILON)
+                .map(|float| float as i64)
+        }),
+        Value::String(text) => text.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PlayerIndexEntry, PlayerIndexError, parse_nba_player_index};
+
+    const HEADERS: &str = r#""headers":["PERSON_ID","PLAYER_LAST_NAME","PLAYER_FIRST_NAME","TEAM_ID","POSITION","ROSTER_STATUS","PTS","FROM_YEAR","TO_YEAR"]"#;
+
+    #[test]
+    fn parses_rows_by_header() {
+        let index = format!(
+            r#"{{"resultSets":[{{{HEADERS},"rowSet":[
+                [1629029,"Dončić","Luka",1610612747,"F-G",1.0,28.2,"2018","2025"],
+                [202711,"Bogdanović","Bojan",0,"F",null,15.8,"2014","2024"],
+                [1642000,"Rookie","Undrafted",0,"",null,null,"2025","2025"]
+            ]}}]}}"#
+        );
+
+        let entries = parse_nba_player_index(&index).unwrap();
+        assert_eq!(
+            entries[0],
+            PlayerIndexEntry {
+                nba_id: Some(1_629_029),
+                espn_id: None,
+                name: "Luka Dončić".to_owned(),
+                real_team_nba_id: Some(1_610_612_747),
+                position: "F-G".to_owned(),
+                from_year: Some(2018),
+                to_year: Some(2025),
+                is_rostered: true,
+                has_played_nba_game: true,
+            }
+        );
+        assert_eq!(entries[1].real_team_nba_id, None);
+        assert!(!entries[1].is_rostered);
+        assert!(!entries[2].has_played_nba_game);
+    }
+
+    #[test]
+    fn reads_an_added_espn_id_column() {
+        let index = r#"{"resultSets":[{"headers":["PERSON_ID","PLAYER_LAST_NAME","PLAYER_FIRST_NAME","TEAM_ID","POSITION","FROM_YEAR","TO_YEAR","ESPN_ID"],"rowSet":[
+            [1629029,"Dončić","Luka",1610612747,"F-G","2018","2025","3945274"],
+            [1642000,"Rookie","Undrafted",0,"","2025","2025",null]
+        ]}]}"#;
+
+        let entries = parse_nba_player_index(index).unwrap();
+        assert_eq!(entries[0].espn_id, Some(3_945_274));
+        assert_eq!(entries[1].espn_id, None);
+    }
+
+    #[test]
+    fn rejects_missing_columns_and_duplicates() {
+        assert_eq!(
+            parse_nba_player_index(r#"{"resultSets":[{"headers":["PERSON_ID"],"rowSet":[]}]}"#),
+            Err(PlayerIndexError::MissingColumn {
+                header: "PLAYER_FIRST_NAME"
+            })
+        );
+        let index = format!(
+            r#"{{"resultSets":[{{{HEADERS},"rowSet":[
+                [1,"A","B",0,"G",null,1.0,"2020","2021"],
+                [1,"A","B",0,"G",null,1.0,"2020","2021"]
+            ]}}]}}"#
+        );
+        assert_eq!(
+            parse_nba_player_index(&index),
+            Err(PlayerIndexError::DuplicatePlayer {
+                row_number: 2,
+                nba_id: 1
+            })
+        );
+    }
+}
@@ -0,0 +1,96 @@
+//! Replacement draft order (§2.4.4). The rules leave the logistics to the commissioner, so the order
+//! is either a snake over a first-round order or a turn-by-turn order the commissioner sets.
+
+use std::collections::HashSet;
+
+use super::ReplacementDraftRejection;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplacementDraftOrder {
+    /// Rounds alternate direction, starting with `first_round`, until every pooled asset could be
+    /// taken.
+    Snake { first_round: Vec<i64> },
+    /// Every turn, in order, as the commissioner set it.
+    Custom(Vec<i64>),
+}
+
+/// The team making each turn, in order.
+///
+/// Every drafting team must appear; a snake's first round must name each exactly once.
+pub fn replacement_draft_order(
+    order: &ReplacementDraftOrder,
+    team_ids: &[i64],
+    pool_size: usize,
+) -> Result<Vec<i64>, ReplacementDraftRejection> {
+    let drafting_teams: HashSet<i64> = team_ids.iter().copied().collect();
+    match order {
+        ReplacementDraftOrder::Snake { first_round } => {
+            let first_round_teams: HashSet<i64> = first_round.iter().copied().collect();
+            if first_round.len() != drafting_teams.len() || first_round_teams != drafting_teams {
+                return Err(ReplacementDraftRejection::InvalidOrder);
+            }
+            let rounds = pool_size.div_ceil(first_round.len());
+            Ok((0..rounds)
+                .flat_map(|round| {
+                    let round_order: Vec<i64> = if round % 2 == 0 {
+                        first_round.clone()
+                    } else {
+                        first_round.iter().rev().copied().collect()
+                    };
+                    round_order
+                })
+                .collect())
+        }
+        ReplacementDraftOrder::Custom(turns) => {
+            let turn_teams: HashSet<i64> = turns.iter().copied().collect();
+            if turn_teams != drafting_teams {
+                return Err(ReplacementDraftRejection::InvalidOrder);
+            }
+            Ok(turns.clone())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ReplacementDraftOrder, ReplacementDraftRejection, replacement_draft_order};
+
+    #[test]
+    fn snake_rounds_alternate_until_the_pool_is_covered() {
+        let order = ReplacementDraftOrder::Snake {
+            first_round: vec![3, 1, 2],
+        };
+
+        assert_eq!(
+            replacement_draft_order(&order, &[1, 2, 3], 7),
+            Ok(vec![3, 1, 2, 2, 1, 3, 3, 1, 2])
+        );
+        assert_eq!(replacement_draft_order(&order, &[1, 2, 3], 0), Ok(vec![]));
+    }
+
+    #[test]
+    fn orders_must_cover_exactly_the_drafting_teams() {
+        let missing_team = ReplacementDraftOrder::Snake {
+            first_round: vec![1, 2],
+        };
+        let repeated_team = ReplacementDraftOrder::Snake {
+            first_round: vec![1, 2, 2, 3],
+        };
+        let outside_team = ReplacementDraftOrder::Custom(vec![1, 2, 3, 4]);
+
+        for order in [missing_team, repeated_team, outside_team] {
+            assert_eq!(
+                replacement_draft_order(&order, &[1, 2, 3], 6),
+                Err(ReplacementDraftRejection::InvalidOrder)
+            );
+        }
+        assert_eq!(
+            replacement_draft_order(
+                &ReplacementDraftOrder::Custom(vec![2, 2, 1, 3]),
+                &[1, 2, 3],
+                6
+            ),
+            Ok(vec![2, 2, 1, 3])
+        );
+    }
+}
@@ -0,0 +1,391 @@
+//! Making a replacement draft selection (§2.4.4).
+//!
+//! A drafted contract keeps its salary and years: it moves to the selecting team as a new link in
+//! its chain, exactly as a trade does. A drafted pick simply changes owner. Either way the
+//! selection records one transaction, with a `team_update` for the selecting team and, when the
+//! asset came from another team, one for the team that lost it. Drafting a contract takes the
+//! selecting team's recovered players off IR, as any pickup does (§10.1.3).
+
+use std::collections::HashSet;
+
+use color_eyre::Result;
+use fbkl_entity::{
+    contract::{self, ContractStatus},
+    contract_queries, deadline, deadline_queries, draft_pick_queries, replacement_draft_queries,
+    replacement_draft_selection::{self, ReplacementDraftSelectionStatus},
+    sea_orm::{ActiveValue, ConnectionTrait, TransactionSession, TransactionTrait},
+    team_update::{
+        self, ContractUpdate, ContractUpdateType, DraftPickUpdate, DraftPickUpdateType,
+        TeamUpdateAsset, TeamUpdateData, TeamUpdateStatus,
+    },
+    team_update_queries::{self, ContractUpdatePlayerData},
+    transaction_queries,
+};
+use tracing::instrument;
+
+use crate::{
+    ir::activate_ir_contracts_due_after_acquisition,
+    rookie_draft::{PickRejection, check_on_the_clock},
+    roster::{SalarySnapshot, calculate_team_contract_salary},
+};
+
+/// What a replacement owner takes with their turn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplacementDraftAsset {
+    /// A pooled player, by their current (active) contract id.
+    Contract(i64),
+    DraftPick(i64),
+}
+
+/// Why a selection's asset was refused. Turn-order problems reuse `PickRejection`.
+#[derive(Debug, Clone, thiserror::Error, PartialEq, Eq)]
+pub enum ReplacementSelectionRejection {
+    #[error("That asset is not in this replacement draft's pool.")]
+    AssetNotInPool,
+    #[error("That asset has already been taken in this replacement draft.")]
+    AssetAlreadyTaken,
+}
+
+/// Takes `asset` with the on-the-clock selection.
+#[instrument(skip(db))]
+pub async fn make_replacement_draft_selection<C>(
+    selection_id: i64,
+    asset: ReplacementDraftAsset,
+    db: &C,
+) -> Result<replacement_draft_selection::Model>
+where
+    C: ConnectionTrait + TransactionTrait,
+{
+    let db_txn = db.begin().await?;
+
+    // Locking the slate row first is what serializes two clients racing the same turn.
+    let selection_model =
+        replacement_draft_queries::find_replacement_draft_selection_by_id_for_update(
+            selection_id,
+            &db_txn,
+        )
+        .await?;
+    if selection_model.status != ReplacementDraftSelectionStatus::Unused {
+        return Err(PickRejection::SelectionAlreadyResolved { selection_id }.into());
+    }
+    assert_on_the_clock(&selection_model, &db_txn).await?;
+
+    let replacement_draft_model = replacement_draft_queries::find_replacement_draft_by_id(
+        selection_model.replacement_draft_id,
+        &db_txn,
+    )
+    .await?;
+    let pool = replacement_draft_model.get_pool()?;
+    let deadline_model =
+        deadline_queries::find_deadline_by_id(replacement_draft_model.deadline_id, &db_txn).await?;
+    let taken = TakenAssets::for_draft(replacement_draft_model.id, &db_txn).await?;
+    let selecting_team_id = selection_model.team_id;
+
+    let (maybe_contract_id, maybe_draft_pick_id, losing_team_id, changed_asset) = match asset {
+        ReplacementDraftAsset::Contract(contract_id) => {
+            let contract_model =
+                contract_queries::find_contract_by_id(contract_id, &db_txn).await?;
+            let chain_id = contract_model
+                .original_contract_id
+                .unwrap_or(contract_model.id);
+            check_asset_available(
+                &pool.original_contract_ids,
+                &taken.original_contract_ids,
+                chain_id,
+            )?;
+            let losing_team_id = match contract_model.team_id {
+                Some(team_id)
+                    if contract_model.status == ContractStatus::Active
+                        && pool.team_ids.contains(&team_id) =>
+                {
+                    team_id
+                }
+                // Released or otherwise moved on since the pool was snapshotted.
+                _ => return Err(ReplacementSelectionRejection::AssetNotInPool.into()),
+            };
+            let drafted_contract_model = if losing_team_id == selecting_team_id {
+                contract_model
+            } else {
+                contract_queries::trade_contract_to_team(contract_model, selecting_team_id, &db_txn)
+                    .await?
+            };
+            (
+                Some(drafted_contract_model.id),
+                None,
+                losing_team_id,
+                ChangedAsset::Contract(drafted_contract_model),
+            )
+        }
+        ReplacementDraftAsset::DraftPick(draft_pick_id) => {
+            check_asset_available(&pool.draft_pick_ids, &taken.draft_pick_ids, draft_pick_id)?;
+            let draft_pick_model =
+                draft_pick_queries::find_draft_pick_by_id(draft_pick_id, &db_txn).await?;
+            let losing_team_id = draft_pick_model.current_owner_team_id;
+            if !pool.team_ids.contains(&losing_team_id) {
+                return Err(ReplacementSelectionRejection::AssetNotInPool.into());
+            }
+            if losing_team_id != selecting_team_id {
+                draft_pick_queries::move_draft_pick_to_team(
+                    draft_pick_model,
+                    selecting_team_id,
+                    &db_txn,
+                )
+                .await?;
+            }
+            (
+                None,
+                Some(draft_pick_id),
+                losing_team_id,
+                ChangedAsset::DraftPick(draft_pick_id),
+            )
+        }
+    };
+
+    let mut updated_selection_model =
+        replacement_draft_queries::record_replacement_draft_selection_result(
+            selection_model,
+            ReplacementDraftSelectionStatus::Selected,
+            maybe_contract_id,
+            maybe_draft_pick_id,
+            &db_txn,
+        )
+        .await?;
+    let transaction_model = transaction_queries::insert_replacement_draft_selection_transaction(
+        &deadline_model,
+        updated_selection_model.id,
+        &db_txn,
+    )
+    .await?;
+    updated_selection_model.transaction_id = Some(transaction_model.id);
+
+    insert_team_update_for_selection(
+        selecting_team_id,
+        &changed_asset,
+        true,
+        &deadline_model,
+        transaction_model.id,
+        &db_txn,
+    )
+    .await?;
+    if losing_team_id != selecting_team_id {
+        insert_team_update_for_selection(
+            losing_team_id,
+            &changed_asset,
+            false,
+            &deadline_model,
+            transaction_model.id,
+            &db_txn,
+        )
+        .await?;
+    }
+    activate_ir_contracts_due_after_acquisition(selecting_team_id, &deadline_model, &db_txn)
+        .await?;
+
+    db_txn.commit().await?;
+
+    Ok(updated_selection_model)
+}
+
+/// Re-asserts inside the db transaction that this selection is still the lowest-`order` `Unused`
+/// row of its draft, so a turn cannot jump the queue.
+#[instrument(skip(db))]
+pub(super) async fn assert_on_the_clock<C>(
+    selection_model: &replacement_draft_selection::Model,
+    db: &C,
+) -> Result<()>
+where
+    C: ConnectionTrait,
+{
+    let maybe_on_the_clock =
+        replacement_draft_queries::get_on_the_clock_replacement_draft_selection(
+            selection_model.replacement_draft_id,
+            db,
+        )
+        .await?;
+    check_on_the_clock(
+        selection_model.id,
+        maybe_on_the_clock.map(|on_the_clock| (on_the_clock.id, on_the_clock.order)),
+    )?;
+    Ok(())
+}
+
+#[derive(Debug)]
+enum ChangedAsset {
+    Contract(contract::Model),
+    DraftPick(i64),
+}
+
+/// Assets already taken by earlier turns: contracts by chain id, picks by id.
+#[derive(Debug, Default)]
+struct TakenAssets {
+    original_contract_ids: HashSet<i64>,
+    draft_pick_ids: HashSet<i64>,
+}
+
+impl TakenAssets {
+    async fn for_draft<C>(replacement_draft_id: i64, db: &C) -> Result<Self>
+    where
+        C: ConnectionTrait,
+    {
+        let selections =
+            replacement_draft_queries::find_replacement_draft_selections(replacement_draft_id, db)
+                .await?;
+        let taken_contract_ids: Vec<i64> = selections
+            .iter()
+            .filter_map(|selection_model| selection_model.contract_id)
+            .collect();
+        let original_contract_ids = if taken_contract_ids.is_empty() {
+            HashSet::new()
+        } else {
+            contract_queries::find_contracts_by_ids(taken_contract_ids, db)
+                .await?
+                .iter()
+                .map(|contract_model| {
+                    contract_model
+                        .original_contract_id
+                        .unwrap_or(contract_model.id)
+                })
+                .collect()
+        };
+        Ok(Self {
+            original_contract_ids,
+            draft_pick_ids: selections
+                .iter()
+                .filter_map(|selection_model| selection_model.draft_pick_id)
+                .collect(),
+        })
+    }
+}
+
+fn check_asset_available(
+    pooled_ids: &[i64],
+    taken_ids: &HashSet<i64>,
+    asset_id: i64,
+) -> Result<(), ReplacementSelectionRejection> {
+    if !pooled_ids.contains(&asset_id) {
+        return Err(ReplacementSelectionRejection::AssetNotInPool);
+    }
+    if taken_ids.contains(&asset_id) {
+        return Err(ReplacementSelectionRejection::AssetAlreadyTaken);
+    }
+    Ok(())
+}
+
+/// The `Done` `team_update` for one side of a selection. Called after the asset has moved, so the
+/// team's current contracts are the "after" picture; the "before" is rebuilt from them.
+#[instrument(skip(db))]
+async fn insert_team_update_for_selection<C>(
+    team_id: i64,
+    changed_asset: &ChangedAsset,
+    is_selecting_team: bool,
+    deadline_model: &deadline::Model,
+    transaction_id: i64,
+    db: &C,
+) -> Result<()>
+where
+    C: ConnectionTrait,
+{
+    let contracts_after = contract_queries::find_active_contracts_for_team(team_id, db).await?;
+    let SalarySnapshot {
+        salary: new_salary,
+        cap: new_cap,
+    } = calculate_team_contract_salary(team_id, &contracts_after, deadline_model, db).await?;
+
+    let (changed_assets, previous) = match changed_asset {
+        ChangedAsset::Contract(drafted_contract_model) => {
+            let contracts_before: Vec<contract::Model> = if is_selecting_team {
+                contracts_after
+                    .iter()
+                    .filter(|contract_model| contract_model.id != drafted_contract_model.id)
+                    .cloned()
+                    .collect()
+            } else {
+                let mut contracts_before = contracts_after.clone();
+                contracts_before.push(drafted_contract_model.clone());
+                contracts_before
+            };
+            let previous =
+                calculate_team_contract_salary(team_id, &contracts_before, deadline_model, db)
+                    .await?;
+            let contract_update_player_data =
+                ContractUpdatePlayerData::from_contract_model(drafted_contract_model, db).await?;
+            (
+                TeamUpdateAsset::Contracts(vec![ContractUpdate {
+                    contract_id: drafted_contract_model.id,
+                    update_type: if is_selecting_team {
+                        ContractUpdateType::AddViaReplacementDraft
+                    } else {
+                        ContractUpdateType::LostViaReplacementDraft
+                    },
+                    player_name_at_time: contract_update_player_data.player_name,
+                    player_team_abbr_at_time: contract_update_player_data.real_team_abbr,
+                    player_team_name_at_time: contract_update_player_data.real_team_name,
+                }]),
+                previous,
+            )
+        }
+        // Picks carry no salary, so the snapshot is the same either side.
+        ChangedAsset::DraftPick(draft_pick_id) => (
+            TeamUpdateAsset::DraftPicks(vec![DraftPickUpdate {
+                draft_pick_id: *draft_pick_id,
+                update_type: if is_selecting_team {
+                    DraftPickUpdateType::AddViaReplacementDraft
+                } else {
+                    DraftPickUpdateType::LostViaReplacementDraft
+                },
+                added_draft_pick_option_id: None,
+            }]),
+            SalarySnapshot {
+                salary: new_salary,
+                cap: new_cap,
+            },
+        ),
+    };
+
+    let team_update_data = TeamUpdateData::from_assets(
+        contracts_after
+            .iter()
+            .map(|contract_model| contract_model.id)
+            .collect(),
+        vec![changed_assets],
+        new_salary,
+        new_cap,
+        previous.salary,
+        previous.cap,
+    );
+    team_update_queries::insert_team_update(
+        team_update::ActiveModel {
+            data: ActiveValue::Set(team_update_data.to_json()?),
+            effective_date: ActiveValue::Set(deadline_model.date_time.date_naive()),
+            status: ActiveValue::Set(TeamUpdateStatus::Done),
+            team_id: ActiveValue::Set(team_id),
+            transaction_id: ActiveValue::Set(Some(transaction_id)),
+            ..Default::default()
+        },
+        db,
+    )
+    .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::{ReplacementSelectionRejection, check_asset_available};
+
+    #[test]
+    fn only_untaken_pooled_assets_can_be_selected() {
+        let taken = HashSet::from([2]);
+
+        assert_eq!(check_asset_available(&[1, 2, 3], &taken, 1), Ok(()));
+        assert_eq!(
+            check_asset_available(&[1, 2, 3], &taken, 2),
+            Err(ReplacementSelectionRejection::AssetAlreadyTaken)
+        );
+        assert_eq!(
+            check_asset_available(&[1, 2, 3], &taken, 4),
+            Err(ReplacementSelectionRejection::AssetNotInPool)
+        );
+    }
+}
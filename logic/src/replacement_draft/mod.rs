@@ -0,0 +1,15 @@
+//! The replacement owners draft (§2.4.4): after a season with more than one abandoned team, the new
+//! owners draft from the combined contracts and picks those teams held. Players keep their salary
+//! and contract years; turns follow the rookie draft's on-the-clock and pass rules.
+
+mod draft_order;
+mod make_selection;
+mod pass_selection;
+mod start_draft;
+
+pub use draft_order::{ReplacementDraftOrder, replacement_draft_order};
+pub use make_selection::{
+    ReplacementDraftAsset, ReplacementSelectionRejection, make_replacement_draft_selection,
+};
+pub use pass_selection::pass_replacement_draft_selection;
+pub use start_draft::{ReplacementDraftRejection, start_replacement_draft};
@@ -0,0 +1,68 @@
+//! Passing a replacement draft turn. As in the rookie draft (§7.3.1), the row stays in the slate as
+//! `Skipped` and a transaction is recorded, but no `team_update`: no asset changed hands.
+
+use color_eyre::Result;
+use fbkl_entity::{
+    deadline_queries, replacement_draft_queries,
+    replacement_draft_selection::{self, ReplacementDraftSelectionStatus},
+    sea_orm::{ConnectionTrait, TransactionSession, TransactionTrait},
+    transaction_queries,
+};
+use tracing::instrument;
+
+use crate::rookie_draft::PickRejection;
+
+use super::make_selection::assert_on_the_clock;
+
+/// Passes the on-the-clock turn.
+#[instrument(skip(db))]
+pub async fn pass_replacement_draft_selection<C>(
+    selection_id: i64,
+    db: &C,
+) -> Result<replacement_draft_selection::Model>
+where
+    C: ConnectionTrait + TransactionTrait,
+{
+    let db_txn = db.begin().await?;
+
+    // Locking the slate row first is what serializes two clients racing the same turn.
+    let selection_model =
+        replacement_draft_queries::find_replacement_draft_selection_by_id_for_update(
+            selection_id,
+            &db_txn,
+        )
+        .await?;
+    if selection_model.status != ReplacementDraftSelectionStatus::Unused {
+        return Err(PickRejection::SelectionAlreadyResolved { selection_id }.into());
+    }
+    assert_on_the_clock(&selection_model, &db_txn).await?;
+
+    let replacement_draft_model = replacement_draft_queries::find_replacement_draft_by_id(
+        selection_model.replacement_draft_id,
+        &db_txn,
+    )
+    .await?;
+    let deadline_model =
+        deadline_queries::find_deadline_by_id(replacement_draft_model.deadline_id, &db_txn).await?;
+
+    let mut updated_selection_model =
+        replacement_draft_queries::record_replacement_draft_selection_result(
+            selection_model,
+            ReplacementDraftSelectionStatus::Skipped,
+            None,
+            None,
+            &db_txn,
+        )
+        .await?;
+    let transaction_model = transaction_queries::insert_replacement_draft_selection_transaction(
+        &deadline_model,
+        updated_selection_model.id,
+        &db_txn,
+    )
+    .await?;
+    updated_selection_model.transaction_id = Some(transaction_model.id);
+
+    db_txn.commit().await?;
+
+    Ok(updated_selection_model)
+}
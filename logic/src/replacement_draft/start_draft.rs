@@ -0,0 +1,124 @@
+//! Starting a replacement owners draft (§2.4.4).
+
+use std::collections::HashSet;
+
+use color_eyre::Result;
+use fbkl_entity::{
+    contract_queries, deadline, draft_pick_queries,
+    replacement_draft::{self, ReplacementDraftPool},
+    replacement_draft_queries,
+    sea_orm::{ConnectionTrait, TransactionSession, TransactionTrait},
+    team_queries,
+};
+use tracing::instrument;
+
+use super::{ReplacementDraftOrder, replacement_draft_order};
+
+/// Why a replacement draft could not be started.
+#[derive(Debug, Clone, thiserror::Error, PartialEq, Eq)]
+pub enum ReplacementDraftRejection {
+    #[error("A replacement draft needs more than one abandoned team (§2.4.4).")]
+    TooFewTeams,
+    #[error("Team {team_id} is not in this league.")]
+    TeamNotInLeague { team_id: i64 },
+    #[error("This season's replacement draft has already been started.")]
+    AlreadyStarted,
+    #[error("The draft order must include every drafting team, and only those teams.")]
+    InvalidOrder,
+}
+
+/// Pools the abandoned teams' active contracts and future draft picks and builds the slate.
+///
+/// The draft belongs to `current_deadline`'s season, and each selection's transaction is tied to
+/// that deadline.
+#[instrument(skip(db))]
+pub async fn start_replacement_draft<C>(
+    league_id: i64,
+    team_ids: Vec<i64>,
+    order: &ReplacementDraftOrder,
+    current_deadline: &deadline::Model,
+    db: &C,
+) -> Result<replacement_draft::Model>
+where
+    C: ConnectionTrait + TransactionTrait,
+{
+    let unique_team_ids: HashSet<i64> = team_ids.iter().copied().collect();
+    if unique_team_ids.len() != team_ids.len() || team_ids.len() < 2 {
+        return Err(ReplacementDraftRejection::TooFewTeams.into());
+    }
+    let league_team_ids: HashSet<i64> = team_queries::find_teams_in_league(league_id, db)
+        .await?
+        .iter()
+        .map(|team_model| team_model.id)
+        .collect();
+    if let Some(team_id) = team_ids
+        .iter()
+        .find(|team_id| !league_team_ids.contains(team_id))
+    {
+        return Err(ReplacementDraftRejection::TeamNotInLeague { team_id: *team_id }.into());
+    }
+    let end_of_season_year = current_deadline.end_of_season_year;
+    if replacement_draft_queries::find_replacement_draft_for_league_season(
+        league_id,
+        end_of_season_year,
+        db,
+    )
+    .await?
+    .is_some()
+    {
+        return Err(ReplacementDraftRejection::AlreadyStarted.into());
+    }
+
+    let db_txn = db.begin().await?;
+
+    let mut original_contract_ids = vec![];
+    for team_id in &team_ids {
+        original_contract_ids.extend(
+            contract_queries::find_active_contracts_for_team(*team_id, &db_txn)
+                .await?
+                .iter()
+                .map(|contract_model| {
+                    contract_model
+                        .original_contract_id
+                        .unwrap_or(contract_model.id)
+                }),
+        );
+    }
+    let draft_pick_ids: Vec<i64> = draft_pick_queries::find_future_draft_picks_owned_by_teams(
+        team_ids.clone(),
+        end_of_season_year,
+        &db_txn,
+    )
+    .await?
+    .iter()
+    .map(|draft_pick_model| draft_pick_model.id)
+    .collect();
+    let ordered_team_ids = replacement_draft_order(
+        order,
+        &team_ids,
+        original_contract_ids.len() + draft_pick_ids.len(),
+    )?;
+
+    let replacement_draft_model = replacement_draft_queries::insert_replacement_draft(
+        league_id,
+        end_of_season_year,
+        current_deadline.id,
+        &ReplacementDraftPool {
+            team_ids,
+            original_contract_ids,
+            draft_pick_ids,
+        },
+        &db_txn,
+    )
+    .await?;
+    replacement_draft_queries::build_replacement_draft_slate(
+        replacement_draft_model.id,
+        ordered_team_ids,
+        &db_txn,
+    )
+    .await?;
+
+    db_txn.commit().await?;
+
+    Ok(replacement_draft_model)
+}
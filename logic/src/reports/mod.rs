@@ -0,0 +1,5 @@
+//! Reports owners post to the league, generated from the same data the roster checks use.
+
+mod roster_report;
+
+pub use roster_report::*;
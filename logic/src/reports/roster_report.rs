@@ -0,0 +1,300 @@
+//! The roster report owners email after every trade and free-agent week (rules §11.11).
+
+use std::{collections::HashMap, fmt};
+
+use chrono::{DateTime, FixedOffset, NaiveDate};
+use color_eyre::eyre::Result;
+use fbkl_constants::league_rules::{
+    REGULAR_SEASON_ROOKIE_DEVELOPMENT_CONTRACTS_PER_ROSTER_LIMIT,
+    REGULAR_SEASON_VET_OR_ROOKIE_CONTRACTS_PER_ROSTER_LIMIT,
+};
+use fbkl_entity::{
+    contract, contract_queries,
+    deadline_queries::find_most_recent_deadline_by_datetime,
+    sea_orm::ConnectionTrait,
+    team,
+    team_update::{
+        self, ContractUpdateType, TeamUpdateAsset, TeamUpdateAssetSummary, TeamUpdateData,
+        TeamUpdateStatus,
+    },
+    team_update_queries,
+};
+use tracing::instrument;
+
+use crate::roster::{RosterCounts, SalarySnapshot, calculate_team_contract_salary};
+
+/// The §11.11 roster line, e.g. `$207/$210, 22/22 + 1 IR + 2/6 RD + 1 RDI`. IR and RDI are one
+/// slot each, so they are shown without a limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RosterReportLine {
+    pub salary: SalarySnapshot,
+    pub counts: RosterCounts,
+}
+
+impl fmt::Display for RosterReportLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "${}/${}, {}/{} + {} IR + {}/{} RD + {} RDI",
+            self.salary.salary,
+            self.salary.cap,
+            self.counts.vet_or_rookie,
+            REGULAR_SEASON_VET_OR_ROOKIE_CONTRACTS_PER_ROSTER_LIMIT,
+            self.counts.ir,
+            self.counts.rookie_development,
+            REGULAR_SEASON_ROOKIE_DEVELOPMENT_CONTRACTS_PER_ROSTER_LIMIT,
+            self.counts.rookie_development_international,
+        )
+    }
+}
+
+/// One contract changed by a move, named as it was at the time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RosterReportChange {
+    pub player_name: String,
+    pub update_type: ContractUpdateType,
+}
+
+impl fmt::Display for RosterReportChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = &self.player_name;
+        match self.update_type {
+            ContractUpdateType::Drop => write!(f, "Dropped {name}"),
+            ContractUpdateType::TradedAway => write!(f, "Traded away {name}"),
+            ContractUpdateType::AddViaTrade => write!(f, "Acquired {name} by trade"),
+            ContractUpdateType::AddViaAuction => write!(f, "Signed {name} at auction"),
+            ContractUpdateType::AddViaRookieDraft => write!(f, "Drafted {name}"),
+            ContractUpdateType::ActivateRookie => write!(f, "Activated rookie {name}"),
+            ContractUpdateType::ToIR => write!(f, "Moved {name} to IR"),
+            ContractUpdateType::FromIR => write!(f, "Activated {name} from IR"),
+            ContractUpdateType::ToRdi => write!(f, "Moved {name} to RDI"),
+            ContractUpdateType::FromRdi => write!(f, "Moved {name} to RD"),
+            ContractUpdateType::Keeper => write!(f, "Kept {name}"),
+            ContractUpdateType::ContractAdvanced => write!(f, "Advanced {name}"),
+            ContractUpdateType::LostViaFreeAgency => write!(f, "Lost {name} in free agency"),
+            ContractUpdateType::RfaResign => write!(f, "Re-signed {name} as an RFA"),
+            ContractUpdateType::AddViaReplacementDraft => {
+                write!(f, "Took {name} in the replacement draft")
+            }
+            ContractUpdateType::LostViaReplacementDraft => {
+                write!(f, "Lost {name} in the replacement draft")
+            }
+        }
+    }
+}
+
+/// One roster move and what it did to the team's salary and slots.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RosterReportMove {
+    pub team_update_id: i64,
+    pub effective_date: NaiveDate,
+    pub changes: Vec<RosterReportChange>,
+    pub before: SalarySnapshot,
+    /// Counts come from the contracts the team held right after the move.
+    pub after: RosterReportLine,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RosterReport {
+    pub team_name: String,
+    pub current: RosterReportLine,
+    /// Oldest first.
+    pub moves: Vec<RosterReportMove>,
+}
+
+impl RosterReport {
+    /// The report as Markdown, ready to paste into the league's Google Group.
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = format!("**{}**: {}\n", self.team_name, self.current);
+        if !self.moves.is_empty() {
+            markdown.push('\n');
+        }
+        for roster_move in &self.moves {
+            let changes = roster_move
+                .changes
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+            markdown.push_str(&format!(
+                "- {} {changes}: ${}/${} → {}\n",
+                roster_move.effective_date,
+                roster_move.before.salary,
+                roster_move.before.cap,
+                roster_move.after,
+            ));
+        }
+        markdown
+    }
+}
+
+/// The team's roster report as of `now`, with every roster move effective on or after `since`.
+/// Moves that errored are left out.
+#[instrument(skip(db))]
+pub async fn roster_report<C>(
+    team_model: &team::Model,
+    since: NaiveDate,
+    now: DateTime<FixedOffset>,
+    db: &C,
+) -> Result<RosterReport>
+where
+    C: ConnectionTrait,
+{
+    let deadline_model =
+        find_most_recent_deadline_by_datetime(team_model.league_id, now, db).await?;
+    let team_contracts =
+        contract_queries::find_active_contracts_for_team(team_model.id, db).await?;
+    let current = RosterReportLine {
+        salary: calculate_team_contract_salary(team_model.id, &team_contracts, &deadline_model, db)
+            .await?,
+        counts: RosterCounts::from_contracts(&team_contracts),
+    };
+
+    let mut summaries: Vec<(team_update::Model, TeamUpdateAssetSummary)> = vec![];
+    for team_update_model in
+        team_update_queries::find_team_updates_by_team(team_model.id, None, db).await?
+    {
+        if team_update_model.effective_date < since
+            || team_update_model.status == TeamUpdateStatus::Error
+        {
+            continue;
+        }
+        if let TeamUpdateData::Assets(summary) = team_update_model.get_data()? {
+            summaries.push((team_update_model, summary));
+        }
+    }
+    summaries.reverse();
+
+    let held_contracts: HashMap<i64, contract::Model> = contract_queries::find_contracts_by_ids(
+        summaries
+            .iter()
+            .flat_map(|(_, summary)| summary.all_contract_ids.iter().copied())
+            .collect(),
+        db,
+    )
+    .await?
+    .into_iter()
+    .map(|contract_model| (contract_model.id, contract_model))
+    .collect();
+
+    let moves = summaries
+        .into_iter()
+        .filter_map(|(team_update_model, summary)| {
+            report_move(&team_update_model, &summary, &held_contracts)
+        })
+        .collect();
+
+    Ok(RosterReport {
+        team_name: team_model.name.clone(),
+        current,
+        moves,
+    })
+}
+
+/// `None` for updates that changed no contracts, like a draft-pick-only trade.
+fn report_move(
+    team_update_model: &team_update::Model,
+    summary: &TeamUpdateAssetSummary,
+    held_contracts: &HashMap<i64, contract::Model>,
+) -> Option<RosterReportMove> {
+    let changes: Vec<RosterReportChange> = summary
+        .changed_assets
+        .iter()
+        .filter_map(|asset| match asset {
+            TeamUpdateAsset::Contracts(contract_updates) => Some(contract_updates),
+            TeamUpdateAsset::DraftPicks(_) => None,
+        })
+        .flatten()
+        .map(|contract_update| RosterReportChange {
+            player_name: contract_update.player_name_at_time.clone(),
+            update_type: contract_update.update_type,
+        })
+        .collect();
+    if changes.is_empty() {
+        return None;
+    }
+
+    Some(RosterReportMove {
+        team_update_id: team_update_model.id,
+        effective_date: team_update_model.effective_date,
+        changes,
+        before: SalarySnapshot {
+            salary: summary.previous_salary,
+            cap: summary.previous_salary_cap,
+        },
+        after: RosterReportLine {
+            salary: SalarySnapshot {
+                salary: summary.new_salary,
+                cap: summary.new_salary_cap,
+            },
+            counts: RosterCounts::from_contracts(
+                summary
+                    .all_contract_ids
+                    .iter()
+                    .filter_map(|contract_id| held_contracts.get(contract_id)),
+            ),
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use fbkl_entity::team_update::ContractUpdateType;
+
+    use super::{RosterReport, RosterReportChange, RosterReportLine, RosterReportMove};
+    use crate::roster::{RosterCounts, SalarySnapshot};
+
+    fn line(salary: i16, cap: i16, vet_or_rookie: i16) -> RosterReportLine {
+        RosterReportLine {
+            salary: SalarySnapshot { salary, cap },
+            counts: RosterCounts {
+                ir: 1,
+                rookie_development: 2,
+                rookie_development_international: 1,
+                vet_or_rookie,
+            },
+        }
+    }
+
+    #[test]
+    fn renders_the_rules_example_line() {
+        assert_eq!(
+            line(207, 210, 22).to_string(),
+            "$207/$210, 22/22 + 1 IR + 2/6 RD + 1 RDI"
+        );
+    }
+
+    #[test]
+    fn renders_markdown_with_each_move_before_and_after() {
+        let report = RosterReport {
+            team_name: "Bricklayers".to_owned(),
+            current: line(207, 210, 22),
+            moves: vec![RosterReportMove {
+                team_update_id: 9,
+                effective_date: NaiveDate::from_ymd_opt(2026, 11, 2).unwrap(),
+                changes: vec![
+                    RosterReportChange {
+                        player_name: "Jane Doe".to_owned(),
+                        update_type: ContractUpdateType::Drop,
+                    },
+                    RosterReportChange {
+                        player_name: "John Roe".to_owned(),
+                        update_type: ContractUpdateType::AddViaAuction,
+                    },
+                ],
+                before: SalarySnapshot {
+                    salary: 212,
+                    cap: 212,
+                },
+                after: line(207, 210, 22),
+            }],
+        };
+
+        assert_eq!(
+            report.to_markdown(),
+            "**Bricklayers**: $207/$210, 22/22 + 1 IR + 2/6 RD + 1 RDI\n\n\
+             - 2026-11-02 Dropped Jane Doe, Signed John Roe at auction: $212/$212 → $207/$210, 22/22 + 1 IR + 2/6 RD + 1 RDI\n"
+        );
+    }
+}
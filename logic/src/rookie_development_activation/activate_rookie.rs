@@ -1,17 +1,48 @@
-use color_eyre::eyre::{Result, ensure, eyre};
+use color_eyre::eyre::{Result, eyre};
 use fbkl_entity::{
     contract::{self, ContractKind, ContractStatus},
-    contract_queries, deadline,
+    contract_queries,
+    deadline::{self, DeadlineKind},
     sea_orm::{ActiveValue, ConnectionTrait},
     transaction::{self, TransactionKind},
     transaction_queries,
 };
 use tracing::instrument;
 
-use crate::roster::{SalarySnapshot, calculate_team_contract_salary_with_model};
+use crate::roster::{
+    RosterCounts, SalarySnapshot, calculate_team_contract_salary_with_model,
+    salary_counted_toward_cap,
+};
 
 use super::rookie_activation_team_update::create_rookie_activation_team_update;
 
+/// Why an RD(I) activation was refused.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ActivationRejection {
+    #[error(
+        "Contract (id = {contract_id}) is a {kind:?} contract, so it cannot be activated as a rookie contract."
+    )]
+    NotRookieDevelopment {
+        contract_id: i64,
+        kind: ContractKind,
+    },
+    #[error("Contract (id = {contract_id}) has status {status:?}, so it cannot be activated.")]
+    NotActive {
+        contract_id: i64,
+        status: ContractStatus,
+    },
+    #[error("Activating would put ${salary} of salary against a ${cap} salary cap.")]
+    InsufficientCap { salary: i16, cap: i16 },
+    #[error(
+        "There is no open rookie or veteran roster spot to activate contract (id = {contract_id}) into."
+    )]
+    NoRosterSpace { contract_id: i64 },
+}
+
+/// Activates an RD(I) contract into a rookie contract.
+///
+/// Needs cap and roster room for the activated contract, except at `PreseasonFinalRosterLock`: the
+/// season-start declaration judges the whole declared roster at once before applying its moves.
 #[instrument(skip(db))]
 pub async fn activate_rookie_development_contract<C>(
     contract_model: contract::Model,
@@ -34,6 +65,15 @@ where
         salary: original_salary,
         cap: original_salary_cap,
     } = calculate_team_contract_salary_with_model(&team_model, deadline_model, db).await?;
+    if deadline_model.kind != DeadlineKind::PreseasonFinalRosterLock {
+        let team_contracts = team_model.get_active_contracts(db).await?;
+        validate_activation_room(
+            &contract_model,
+            &team_contracts,
+            original_salary_cap,
+            deadline_model.kind,
+        )?;
+    }
     let activated_contract =
         contract_queries::activate_rookie_development_contract(contract_model, db).await?;
 
@@ -66,34 +106,87 @@ where
 
 /// Guards activation against non-RD(I) or stale contracts.
 fn validate_contract_is_activatable(contract_model: &contract::Model) -> Result<()> {
-    ensure!(
-        matches!(
-            contract_model.kind,
-            ContractKind::RookieDevelopment | ContractKind::RookieDevelopmentInternational
-        ),
-        "Contract (id = {}) is a {:?} contract, so it cannot be activated as a rookie contract.",
-        contract_model.id,
-        contract_model.kind
-    );
-    ensure!(
-        contract_model.status == ContractStatus::Active,
-        "Contract (id = {}) has status {:?}, so it cannot be activated.",
-        contract_model.id,
-        contract_model.status
-    );
+    if !matches!(
+        contract_model.kind,
+        ContractKind::RookieDevelopment | ContractKind::RookieDevelopmentInternational
+    ) {
+        return Err(ActivationRejection::NotRookieDevelopment {
+            contract_id: contract_model.id,
+            kind: contract_model.kind,
+        }
+        .into());
+    }
+    if contract_model.status != ContractStatus::Active {
+        return Err(ActivationRejection::NotActive {
+            contract_id: contract_model.id,
+            status: contract_model.status,
+        }
+        .into());
+    }
+
+    Ok(())
+}
+
+/// The activated contract counts against the cap and takes a rookie/veteran spot. A roster that is
+/// already illegal mid-week (§13.1.3) may still activate, as long as the activation doesn't add to
+/// how far over the limits it is.
+fn validate_activation_room(
+    contract_model: &contract::Model,
+    team_contracts: &[contract::Model],
+    salary_cap: i16,
+    deadline_kind: DeadlineKind,
+) -> Result<(), ActivationRejection> {
+    let activated_roster: Vec<contract::Model> = team_contracts
+        .iter()
+        .map(|team_contract| {
+            if team_contract.id == contract_model.id {
+                contract::Model {
+                    kind: ContractKind::Rookie,
+                    ..team_contract.clone()
+                }
+            } else {
+                team_contract.clone()
+            }
+        })
+        .collect();
+
+    let activated_salary = salary_counted_toward_cap(&activated_roster);
+    if activated_salary > salary_cap {
+        return Err(ActivationRejection::InsufficientCap {
+            salary: activated_salary,
+            cap: salary_cap,
+        });
+    }
+
+    let over_limit_before =
+        RosterCounts::from_contracts(team_contracts).over_limit_by(deadline_kind);
+    let over_limit_after =
+        RosterCounts::from_contracts(&activated_roster).over_limit_by(deadline_kind);
+    if over_limit_after > over_limit_before {
+        return Err(ActivationRejection::NoRosterSpace {
+            contract_id: contract_model.id,
+        });
+    }
 
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
-    use fbkl_entity::contract::{ContractKind, ContractStatus, Model};
+    use fbkl_entity::{
+        contract::{ContractKind, ContractStatus, Model},
+        deadline::DeadlineKind,
+    };
 
-    use super::validate_contract_is_activatable;
+    use super::{ActivationRejection, validate_activation_room, validate_contract_is_activatable};
 
     fn contract(kind: ContractKind, status: ContractStatus) -> Model {
+        numbered_contract(1, kind, status)
+    }
+
+    fn numbered_contract(id: i64, kind: ContractKind, status: ContractStatus) -> Model {
         Model {
-            id: 1,
+            id,
             year_number: 1,
             kind,
             is_ir: false,
@@ -136,4 +229,46 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn activation_needs_cap_room() {
+        let rd = numbered_contract(1, ContractKind::RookieDevelopment, ContractStatus::Active);
+        let veteran = numbered_contract(2, ContractKind::Veteran, ContractStatus::Active);
+        let roster = [rd.clone(), veteran];
+
+        assert_eq!(
+            validate_activation_room(&rd, &roster, 15, DeadlineKind::InSeasonRosterLock),
+            Err(ActivationRejection::InsufficientCap {
+                salary: 20,
+                cap: 15
+            })
+        );
+        assert!(
+            validate_activation_room(&rd, &roster, 20, DeadlineKind::InSeasonRosterLock).is_ok()
+        );
+    }
+
+    #[test]
+    fn activation_needs_an_open_roster_spot() {
+        let rd = numbered_contract(0, ContractKind::RookieDevelopment, ContractStatus::Active);
+        let mut roster: Vec<Model> = (1..=22)
+            .map(|id| numbered_contract(id, ContractKind::Veteran, ContractStatus::Active))
+            .collect();
+        roster.push(rd.clone());
+
+        assert_eq!(
+            validate_activation_room(&rd, &roster, i16::MAX, DeadlineKind::InSeasonRosterLock),
+            Err(ActivationRejection::NoRosterSpace { contract_id: 0 })
+        );
+        // The offseason roster is one 32-contract pool, so the activation takes no new spot.
+        assert!(
+            validate_activation_room(
+                &rd,
+                &roster,
+                i16::MAX,
+                DeadlineKind::PreseasonRookieDraftStart
+            )
+            .is_ok()
+        );
+    }
 }
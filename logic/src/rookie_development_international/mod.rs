@@ -5,18 +5,61 @@ mod rdi_team_update;
 pub use move_rd_contract_to_international::*;
 pub use move_rdi_contract_from_international::*;
 
-use color_eyre::eyre::{Result, ensure};
+use color_eyre::eyre::Result;
 use fbkl_entity::contract::{self, ContractKind};
 
+/// Why an RD↔RDI move was refused. RDI eligibility itself is
+/// [`RdiIneligibility`](crate::eligibility::RdiIneligibility).
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum RdiMoveRejection {
+    #[error(
+        "Contract (id = {contract_id}) is a {kind:?} contract, but this move requires a {expected:?} contract."
+    )]
+    WrongKind {
+        contract_id: i64,
+        kind: ContractKind,
+        expected: ContractKind,
+    },
+    #[error(
+        "Contract (id = {contract_id}) has already been activated, so it cannot move back to rookie development."
+    )]
+    AlreadyActivated { contract_id: i64 },
+    #[error("The team already holds {limit} RDI contract(s), the most a roster may have.")]
+    RdiSlotTaken { limit: i16 },
+}
+
 /// Guards an RD↔RDI move against being applied to the wrong contract kind (rules §11.3.1).
 fn validate_contract_kind(contract_model: &contract::Model, expected: ContractKind) -> Result<()> {
-    ensure!(
-        contract_model.kind == expected,
-        "Contract (id = {}) is a {:?} contract, but this move requires a {:?} contract.",
-        contract_model.id,
-        contract_model.kind,
-        expected
-    );
+    if contract_model.kind != expected {
+        return Err(RdiMoveRejection::WrongKind {
+            contract_id: contract_model.id,
+            kind: contract_model.kind,
+            expected,
+        }
+        .into());
+    }
+
+    Ok(())
+}
+
+/// An activated rookie never goes back to RD or RDI: once a contract's chain holds a rookie,
+/// rookie extension or veteran contract, the player has left rookie development for good.
+fn validate_not_activated(
+    contract_model: &contract::Model,
+    chain: &[contract::Model],
+) -> Result<(), RdiMoveRejection> {
+    let was_activated = chain.iter().any(|chain_contract| {
+        chain_contract.id < contract_model.id
+            && matches!(
+                chain_contract.kind,
+                ContractKind::Rookie | ContractKind::RookieExtension | ContractKind::Veteran
+            )
+    });
+    if was_activated {
+        return Err(RdiMoveRejection::AlreadyActivated {
+            contract_id: contract_model.id,
+        });
+    }
 
     Ok(())
 }
@@ -25,11 +68,15 @@ fn validate_contract_kind(contract_model: &contract::Model, expected: ContractKi
 mod tests {
     use fbkl_entity::contract::{ContractKind, ContractStatus, Model};
 
-    use super::validate_contract_kind;
+    use super::{RdiMoveRejection, validate_contract_kind, validate_not_activated};
 
     fn contract(kind: ContractKind) -> Model {
+        numbered_contract(1, kind)
+    }
+
+    fn numbered_contract(id: i64, kind: ContractKind) -> Model {
         Model {
-            id: 1,
+            id,
             year_number: 1,
             kind,
             is_ir: false,
@@ -77,4 +124,20 @@ mod tests {
 
         assert!(validate_contract_kind(&contract_model, ContractKind::RookieDevelopment).is_ok());
     }
+
+    #[test]
+    fn activated_chain_cannot_return_to_rookie_development() {
+        let rdi = numbered_contract(3, ContractKind::RookieDevelopmentInternational);
+        let chain = [
+            numbered_contract(1, ContractKind::RookieDevelopment),
+            numbered_contract(2, ContractKind::Rookie),
+            rdi.clone(),
+        ];
+
+        assert_eq!(
+            validate_not_activated(&rdi, &chain),
+            Err(RdiMoveRejection::AlreadyActivated { contract_id: 3 })
+        );
+        assert!(validate_not_activated(&rdi, &[chain[0].clone(), rdi.clone()]).is_ok());
+    }
 }
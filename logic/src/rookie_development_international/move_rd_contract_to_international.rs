@@ -1,6 +1,7 @@
 use color_eyre::eyre::{Result, eyre};
+use fbkl_constants::league_rules::REGULAR_SEASON_INTL_ROOKIE_DEVELOPMENT_CONTRACTS_PER_ROSTER_LIMIT;
 use fbkl_entity::{
-    contract::{self, ContractKind, RelatedPlayer},
+    contract::{self, ContractKind},
     contract_queries, deadline,
     sea_orm::{ActiveValue, ConnectionTrait},
     team_update::ContractUpdateType,
@@ -9,9 +10,15 @@ use fbkl_entity::{
 };
 use tracing::instrument;
 
-use crate::eligibility::{PlayerEligibilityFacts, validate_rdi_eligible};
+use crate::{
+    eligibility::{find_contract_player_eligibility_facts, validate_rdi_eligible},
+    roster::RosterCounts,
+};
 
-use super::{rdi_team_update::create_rdi_move_team_update, validate_contract_kind};
+use super::{
+    RdiMoveRejection, rdi_team_update::create_rdi_move_team_update, validate_contract_kind,
+    validate_not_activated,
+};
 
 #[instrument(skip(db))]
 pub async fn move_rookie_development_contract_to_international<C>(
@@ -23,10 +30,9 @@ where
     C: ConnectionTrait,
 {
     validate_contract_kind(&contract_model, ContractKind::RookieDevelopment)?;
-    let player_facts = match contract_model.get_player(db).await? {
-        RelatedPlayer::Player(model) => PlayerEligibilityFacts::from(&model),
-        RelatedPlayer::LeaguePlayer(model) => PlayerEligibilityFacts::from(&model),
-    };
+    let chain = contract_queries::find_contract_chain(contract_model.id, db).await?;
+    validate_not_activated(&contract_model, &chain)?;
+    let player_facts = find_contract_player_eligibility_facts(&contract_model, db).await?;
     validate_rdi_eligible(&contract_model, player_facts, db).await?;
 
     let team_model = contract_model.get_team(db).await?.ok_or_else(|| {
@@ -35,6 +41,15 @@ where
             contract_model.id
         )
     })?;
+    let team_contracts = team_model.get_active_contracts(db).await?;
+    if RosterCounts::from_contracts(&team_contracts).rookie_development_international
+        >= REGULAR_SEASON_INTL_ROOKIE_DEVELOPMENT_CONTRACTS_PER_ROSTER_LIMIT
+    {
+        return Err(RdiMoveRejection::RdiSlotTaken {
+            limit: REGULAR_SEASON_INTL_ROOKIE_DEVELOPMENT_CONTRACTS_PER_ROSTER_LIMIT,
+        }
+        .into());
+    }
     let moved_contract = contract_queries::move_rd_contract_to_rdi(contract_model, db).await?;
 
     // create transaction
@@ -9,7 +9,9 @@ use fbkl_entity::{
 };
 use tracing::instrument;
 
-use super::{rdi_team_update::create_rdi_move_team_update, validate_contract_kind};
+use super::{
+    rdi_team_update::create_rdi_move_team_update, validate_contract_kind, validate_not_activated,
+};
 
 #[instrument(skip(db))]
 pub async fn move_rookie_development_international_contract_to_stateside<C>(
@@ -20,11 +22,14 @@ pub async fn move_rookie_development_international_contract_to_stateside<C>(
 where
     C: ConnectionTrait,
 {
-    // §11.3.1 forced transition: leaving international is always legal, so kind is the only gate.
+    // §11.3.1 forced transition: leaving international is always legal for a contract that is
+    // still in rookie development, so no eligibility or slot check.
     validate_contract_kind(
         &contract_model,
         ContractKind::RookieDevelopmentInternational,
     )?;
+    let chain = contract_queries::find_contract_chain(contract_model.id, db).await?;
+    validate_not_activated(&contract_model, &chain)?;
 
     let team_model = contract_model.get_team(db).await?.ok_or_else(|| {
         eyre!(
@@ -16,6 +16,7 @@ use fbkl_entity::{
     contract_queries,
     deadline::DeadlineKind,
     deadline_queries, draft_pick_queries,
+    notification::NotificationKind,
     rookie_draft_selection::{self, RookieDraftSelectionStatus},
     rookie_draft_selection_queries,
     sea_orm::{ActiveValue, ConnectionTrait, TransactionSession, TransactionTrait},
@@ -29,6 +30,7 @@ use tracing::instrument;
 
 use crate::{
     eligibility::build_rookie_draft_eligible_pool,
+    notifications::notify_team,
     roster::{SalarySnapshot, calculate_team_contract_salary},
 };
 
@@ -224,6 +226,8 @@ where
     )
     .await?;
 
+    notify_on_the_clock(league_id, end_of_season_year, &db_txn).await?;
+
     db_txn.commit().await?;
 
     Ok(updated_selection_model)
@@ -301,15 +305,62 @@ where
         db,
     )
     .await?;
-    let Some(on_the_clock) = maybe_on_the_clock else {
-        return Err(PickRejection::DraftNotStarted.into());
+    check_on_the_clock(
+        selection_model.id,
+        maybe_on_the_clock.map(|on_the_clock| (on_the_clock.id, on_the_clock.order)),
+    )?;
+    Ok(())
+}
+
+/// Tells the owner of the selection now on the clock that it is their turn. Call after whatever
+/// moved the clock, in the same transaction; a finished draft has nobody to tell.
+#[instrument(skip(db))]
+pub(super) async fn notify_on_the_clock<C>(
+    league_id: i64,
+    end_of_season_year: i16,
+    db: &C,
+) -> Result<()>
+where
+    C: ConnectionTrait,
+{
+    let Some(on_the_clock) = rookie_draft_selection_queries::get_on_the_clock_selection(
+        league_id,
+        end_of_season_year,
+        db,
+    )
+    .await?
+    else {
+        return Ok(());
     };
-    if on_the_clock.id != selection_model.id {
+    notify_team(
+        league_id,
+        on_the_clock.current_owner_team_id,
+        NotificationKind::DraftPickOnTheClock,
+        Some(on_the_clock.id),
+        &format!(
+            "Your pick {} of the {end_of_season_year} rookie draft is on the clock.",
+            on_the_clock.order
+        ),
+        db,
+    )
+    .await?;
+    Ok(())
+}
+
+/// The turn rule every live draft shares: only the lowest-`order` unresolved row, given as its
+/// `(id, order)`, may be used or passed. No such row means the draft has not started (or is over).
+pub(crate) fn check_on_the_clock(
+    selection_id: i64,
+    on_the_clock: Option<(i64, i16)>,
+) -> Result<(), PickRejection> {
+    let Some((on_the_clock_id, on_the_clock_order)) = on_the_clock else {
+        return Err(PickRejection::DraftNotStarted);
+    };
+    if on_the_clock_id != selection_id {
         return Err(PickRejection::NotOnTheClock {
-            selection_id: selection_model.id,
-            on_the_clock_order: on_the_clock.order,
-        }
-        .into());
+            selection_id,
+            on_the_clock_order,
+        });
     }
     Ok(())
 }
@@ -328,7 +379,7 @@ mod tests {
     use fbkl_constants::league_rules::rookie_draft_salary_for_round;
     use fbkl_entity::contract::{self, ContractKind, ContractStatus};
 
-    use super::ReDraftBan;
+    use super::{PickRejection, ReDraftBan, check_on_the_clock};
 
     fn dropped_contract(
         maybe_player_id: Option<i64>,
@@ -380,4 +431,20 @@ mod tests {
         let salaries: Vec<i16> = (1..=5).map(rookie_draft_salary_for_round).collect();
         assert_eq!(salaries, vec![4, 3, 2, 1, 1]);
     }
+
+    #[test]
+    fn only_the_lowest_unresolved_row_is_on_the_clock() {
+        assert_eq!(check_on_the_clock(5, Some((5, 3))), Ok(()));
+        assert_eq!(
+            check_on_the_clock(6, Some((5, 3))),
+            Err(PickRejection::NotOnTheClock {
+                selection_id: 6,
+                on_the_clock_order: 3,
+            })
+        );
+        assert_eq!(
+            check_on_the_clock(5, None),
+            Err(PickRejection::DraftNotStarted)
+        );
+    }
 }
@@ -8,6 +8,7 @@ mod start_draft;
 
 pub use draft_order::{DraftSlot, compute_draft_order};
 pub use lottery::run_lottery;
+pub(crate) use make_pick::check_on_the_clock;
 pub use make_pick::{PickRejection, ReDraftBan, make_pick, re_draft_ban_check};
 pub use pass_pick::pass_pick;
 pub use start_draft::start_rookie_draft;
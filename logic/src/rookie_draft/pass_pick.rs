@@ -18,7 +18,7 @@ use fbkl_entity::{
 };
 use tracing::instrument;
 
-use super::make_pick::{PickRejection, assert_on_the_clock};
+use super::make_pick::{PickRejection, assert_on_the_clock, notify_on_the_clock};
 
 /// Passes the on-the-clock selection (§7.3.1).
 #[instrument(skip(db))]
@@ -67,6 +67,8 @@ where
     .await?;
     updated_selection_model.transaction_id = Some(transaction_model.id);
 
+    notify_on_the_clock(league_id, end_of_season_year, &db_txn).await?;
+
     db_txn.commit().await?;
 
     Ok(updated_selection_model)
@@ -14,7 +14,7 @@ use fbkl_entity::{
 };
 use tracing::instrument;
 
-use super::{compute_draft_order, run_lottery};
+use super::{compute_draft_order, make_pick::notify_on_the_clock, run_lottery};
 
 /// Runs the lottery and persists the full ordered slate of unused selections (§7.2).
 ///
@@ -66,6 +66,8 @@ where
     )
     .await?;
 
+    notify_on_the_clock(league_id, end_of_season_year, &db_txn).await?;
+
     db_txn.commit().await?;
 
     Ok(true)
@@ -1,3 +1,5 @@
+mod roster_counts;
 mod salary_calculation;
 
+pub use roster_counts::*;
 pub use salary_calculation::*;
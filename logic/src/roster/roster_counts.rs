@@ -0,0 +1,166 @@
+use fbkl_constants::league_rules::{
+    PRE_SEASON_CONTRACTS_PER_ROSTER_LIMIT,
+    REGULAR_SEASON_INTL_ROOKIE_DEVELOPMENT_CONTRACTS_PER_ROSTER_LIMIT,
+    REGULAR_SEASON_IR_CONTRACTS_PER_ROSTER_LIMIT,
+    REGULAR_SEASON_ROOKIE_DEVELOPMENT_CONTRACTS_PER_ROSTER_LIMIT,
+    REGULAR_SEASON_VET_OR_ROOKIE_CONTRACTS_PER_ROSTER_LIMIT,
+};
+use fbkl_entity::{
+    contract::{self, ContractKind},
+    deadline::DeadlineKind,
+};
+
+/// How many of a team's contracts fall into each roster-limited bucket. An IR contract only counts
+/// toward `ir`, never toward its contract kind's bucket.
+///
+/// This is the one place roster slots are counted, so the roster lock, the trade preview and any
+/// other legality check agree on what "22/22 + 1 IR + 6/6 RD + 1 RDI" means.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RosterCounts {
+    pub ir: i16,
+    pub rookie_development: i16,
+    pub rookie_development_international: i16,
+    pub vet_or_rookie: i16,
+}
+
+impl RosterCounts {
+    pub fn from_contracts<'a>(contracts: impl IntoIterator<Item = &'a contract::Model>) -> Self {
+        let mut counts = Self::default();
+        for contract_model in contracts {
+            if contract_model.is_ir {
+                counts.ir += 1;
+                continue;
+            }
+
+            match contract_model.kind {
+                ContractKind::RookieDevelopment => counts.rookie_development += 1,
+                ContractKind::RookieDevelopmentInternational => {
+                    counts.rookie_development_international += 1;
+                }
+                ContractKind::Rookie | ContractKind::RookieExtension | ContractKind::Veteran => {
+                    counts.vet_or_rookie += 1;
+                }
+                _ => (),
+            }
+        }
+
+        counts
+    }
+
+    /// Every non-IR contract that takes up a preseason roster spot.
+    pub const fn total_non_ir(&self) -> i16 {
+        self.rookie_development + self.rookie_development_international + self.vet_or_rookie
+    }
+
+    /// How many contracts the roster holds beyond the limits in force at the given deadline; 0 when
+    /// the roster fits. Keeper-deadline rosters are limited by `save_keeper_team_update` instead, so
+    /// they never count as over here.
+    pub fn over_limit_by(&self, deadline_kind: DeadlineKind) -> i16 {
+        match deadline_kind {
+            DeadlineKind::PreseasonStart | DeadlineKind::PreseasonKeeper => 0,
+            DeadlineKind::PreseasonVeteranAuctionStart
+            | DeadlineKind::PreseasonFaAuctionStart
+            | DeadlineKind::PreseasonFaAuctionEnd
+            | DeadlineKind::PreseasonRookieDraftStart => {
+                (self.total_non_ir() - PRE_SEASON_CONTRACTS_PER_ROSTER_LIMIT).max(0)
+            }
+            DeadlineKind::PreseasonFinalRosterLock
+            | DeadlineKind::Week1FreeAgentAuctionStart
+            | DeadlineKind::Week1FreeAgentAuctionEnd
+            | DeadlineKind::Week1RosterLock
+            | DeadlineKind::InSeasonRosterLock
+            | DeadlineKind::FreeAgentAuctionEnd
+            | DeadlineKind::TradeDeadlineAndPlayoffStart
+            | DeadlineKind::SeasonEnd => {
+                (self.ir - REGULAR_SEASON_IR_CONTRACTS_PER_ROSTER_LIMIT).max(0)
+                    + (self.rookie_development
+                        - REGULAR_SEASON_ROOKIE_DEVELOPMENT_CONTRACTS_PER_ROSTER_LIMIT)
+                        .max(0)
+                    + (self.rookie_development_international
+                        - REGULAR_SEASON_INTL_ROOKIE_DEVELOPMENT_CONTRACTS_PER_ROSTER_LIMIT)
+                        .max(0)
+                    + (self.vet_or_rookie - REGULAR_SEASON_VET_OR_ROOKIE_CONTRACTS_PER_ROSTER_LIMIT)
+                        .max(0)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fbkl_entity::{
+        contract::{ContractKind, ContractStatus, Model},
+        deadline::DeadlineKind,
+    };
+
+    use super::RosterCounts;
+
+    fn contract(kind: ContractKind, is_ir: bool) -> Model {
+        Model {
+            id: 1,
+            year_number: 1,
+            kind,
+            is_ir,
+            salary: 10,
+            end_of_season_year: 2025,
+            status: ContractStatus::Active,
+            league_id: 1,
+            league_player_id: None,
+            player_id: Some(1),
+            previous_contract_id: None,
+            original_contract_id: Some(1),
+            team_id: Some(7),
+            created_at: chrono::Utc::now().into(),
+            updated_at: chrono::Utc::now().into(),
+        }
+    }
+
+    #[test]
+    fn ir_contracts_only_count_toward_the_ir_slot() {
+        let contracts = [
+            contract(ContractKind::Veteran, true),
+            contract(ContractKind::Veteran, false),
+            contract(ContractKind::RookieDevelopment, false),
+            contract(ContractKind::RookieDevelopmentInternational, false),
+            contract(ContractKind::FreeAgent, false),
+        ];
+
+        assert_eq!(
+            RosterCounts::from_contracts(&contracts),
+            RosterCounts {
+                ir: 1,
+                rookie_development: 1,
+                rookie_development_international: 1,
+                vet_or_rookie: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn regular_season_overages_add_up_across_buckets() {
+        let counts = RosterCounts {
+            ir: 2,
+            rookie_development: 6,
+            rookie_development_international: 2,
+            vet_or_rookie: 24,
+        };
+
+        assert_eq!(counts.over_limit_by(DeadlineKind::InSeasonRosterLock), 4);
+    }
+
+    #[test]
+    fn preseason_only_limits_the_total() {
+        let counts = RosterCounts {
+            ir: 0,
+            rookie_development: 9,
+            rookie_development_international: 2,
+            vet_or_rookie: 22,
+        };
+
+        assert_eq!(
+            counts.over_limit_by(DeadlineKind::PreseasonRookieDraftStart),
+            1
+        );
+        assert_eq!(counts.over_limit_by(DeadlineKind::PreseasonKeeper), 0);
+    }
+}
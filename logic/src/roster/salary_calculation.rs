@@ -19,12 +19,38 @@ static CONTRACT_TYPES_COUNTED_TOWARD_CAP: [ContractKind; 3] = [
 ];
 
 /// A team's total counted salary and its salary cap at a given deadline.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct SalarySnapshot {
     pub salary: i16,
     pub cap: i16,
 }
 
+/// Whether a contract of this kind counts against the cap, and so carries a penalty when dropped.
+pub fn is_counted_toward_cap(contract_kind: ContractKind) -> bool {
+    CONTRACT_TYPES_COUNTED_TOWARD_CAP.contains(&contract_kind)
+}
+
+/// How much dropping a counted contract in the regular season lowers the team's cap: 20% of its
+/// salary, rounded up.
+// salaries are far below i16::MAX, so the rounded penalty never truncates
+#[allow(clippy::cast_possible_truncation)]
+pub fn dropped_contract_cap_penalty(salary: i16) -> i16 {
+    (f32::from(salary) * 0.2).ceil() as i16
+}
+
+/// The salary a set of contracts puts against the cap: every non-IR rookie, rookie extension and
+/// veteran contract. Dropped-contract penalties lower the cap instead, so they are not included.
+pub fn salary_counted_toward_cap<'a>(
+    contracts: impl IntoIterator<Item = &'a contract::Model>,
+) -> i16 {
+    contracts
+        .into_iter()
+        .filter(|contract_model| {
+            is_counted_toward_cap(contract_model.kind) && !contract_model.is_ir
+        })
+        .fold(0, |sum, contract_model| sum + contract_model.salary)
+}
+
 #[instrument(skip(db))]
 pub async fn calculate_team_contract_salary_with_model<C>(
     team_model: &team::Model,
@@ -68,16 +94,7 @@ where
     // `None` = §4.2.4 uncapped window (PreseasonStart → keeper deadline); i16::MAX makes cap comparisons trivially pass.
     let max_salary_cap_for_deadline = deadline_model.get_salary_cap(db).await?.unwrap_or(i16::MAX);
 
-    let contracts_counted_towards_cap: Vec<&contract::Model> = team_active_contracts
-        .iter()
-        .filter(|contract_model| {
-            CONTRACT_TYPES_COUNTED_TOWARD_CAP.contains(&contract_model.kind)
-                && !contract_model.is_ir
-        })
-        .collect();
-    let total_contract_amount = contracts_counted_towards_cap
-        .iter()
-        .fold(0, |sum, contract_model| sum + contract_model.salary);
+    let total_contract_amount = salary_counted_toward_cap(team_active_contracts);
 
     if deadline_model.kind == DeadlineKind::PreseasonKeeper {
         return Ok(SalarySnapshot {
@@ -87,22 +104,19 @@ where
     }
 
     let dropped_team_contracts =
-        contract_queries::find_contracts_dropped_by_team_in_regular_season(
+        contract_queries::find_penalized_contracts_dropped_by_team_in_regular_season(
             team_id,
             deadline_model.end_of_season_year,
             db,
         )
         .await?;
-    // salaries are far below i16::MAX, so the rounded penalty never truncates
-    #[allow(clippy::cast_possible_truncation)]
-    let dropped_contract_cap_penalty = dropped_team_contracts
+    let dropped_contracts_cap_penalty = dropped_team_contracts
         .iter()
-        .filter(|contract_model| CONTRACT_TYPES_COUNTED_TOWARD_CAP.contains(&contract_model.kind))
+        .filter(|contract_model| is_counted_toward_cap(contract_model.kind))
         .fold(0, |sum, dropped_contract| {
-            let penalty_amount_rounded_up = (f32::from(dropped_contract.salary) * 0.2).ceil();
-            sum + penalty_amount_rounded_up as i16
+            sum + dropped_contract_cap_penalty(dropped_contract.salary)
         });
-    let team_salary_cap = max_salary_cap_for_deadline - dropped_contract_cap_penalty;
+    let team_salary_cap = max_salary_cap_for_deadline - dropped_contracts_cap_penalty;
 
     Ok(SalarySnapshot {
         salary: total_contract_amount,
@@ -1,3 +1,5 @@
+mod ownership_transfer;
 mod team_user_access;
 
+pub use ownership_transfer::*;
 pub use team_user_access::*;
@@ -0,0 +1,191 @@
+//! Handing a team to a new owner (rules §2.4.1–§2.4.2).
+//!
+//! Only the owning `team_user` changes. The team keeps every contract, draft pick and team update;
+//! the departing owner's row is retired rather than deleted so past trades and auctions stay
+//! attributable.
+
+use chrono::{DateTime, FixedOffset};
+use color_eyre::eyre::{Result, bail};
+use fbkl_entity::{
+    deadline,
+    sea_orm::{ActiveValue, ConnectionTrait},
+    team,
+    team_update::{
+        self, OwnershipChangeKind, TeamOwnershipChange, TeamSettingsChange, TeamUpdateData,
+        TeamUpdateSettingUser, TeamUpdateStatus,
+    },
+    team_update_queries,
+    team_user::{self, LeagueRole},
+    team_user_queries, transaction, transaction_queries, user_queries,
+};
+use tracing::instrument;
+
+/// A commissioner's request to hand a team to a new owner.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnershipTransfer {
+    pub new_user_id: i64,
+    pub new_owner_nickname: String,
+    pub kind: OwnershipChangeKind,
+    /// Required for forced removals.
+    pub reason: Option<String>,
+}
+
+/// Why an ownership transfer was refused.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum OwnershipTransferRejection {
+    #[error("A forced removal requires a reason.")]
+    MissingReason,
+    #[error("Team {team_id} has no active owner to replace.")]
+    NoActiveOwner { team_id: i64 },
+    #[error(
+        "Team {team_id} is the commissioner's team; the commissioner role cannot be transferred with it."
+    )]
+    CommissionerTeam { team_id: i64 },
+    #[error("User {user_id} does not exist.")]
+    UnknownUser { user_id: i64 },
+    #[error("User {user_id} already owns team {team_id}.")]
+    AlreadyOwner { user_id: i64, team_id: i64 },
+    #[error("User {user_id} is already an active member of this league.")]
+    AlreadyInLeague { user_id: i64 },
+}
+
+/// Retires the team's owner as `Inactive` after `current_deadline`'s season and makes the
+/// transfer's new user its owner from that season on. Records a `TeamUpdateConfigChange` transaction
+/// whose team update carries the change, so the team's history shows who held it when.
+///
+/// A forced removal (§2.4.2) needs a `reason`; a voluntary replacement (§2.4.1) may give one.
+/// Returns the new owner's `team_user`.
+#[instrument(skip(db))]
+pub async fn transfer_team_ownership<C>(
+    team_model: &team::Model,
+    transfer: OwnershipTransfer,
+    current_deadline: &deadline::Model,
+    now: DateTime<FixedOffset>,
+    db: &C,
+) -> Result<team_user::Model>
+where
+    C: ConnectionTrait,
+{
+    let OwnershipTransfer {
+        new_user_id,
+        new_owner_nickname,
+        kind,
+        reason,
+    } = transfer;
+    let reason = reason
+        .map(|reason| reason.trim().to_owned())
+        .filter(|reason| !reason.is_empty());
+    if kind == OwnershipChangeKind::ForcedRemoval && reason.is_none() {
+        return Err(OwnershipTransferRejection::MissingReason.into());
+    }
+
+    let previous_owner = find_current_owner(team_model.id, db).await?;
+    validate_new_owner(team_model, &previous_owner, new_user_id, db).await?;
+
+    let end_of_season_year = current_deadline.end_of_season_year;
+    let previous_user_id = previous_owner.user_id;
+    let retired_owner =
+        team_user_queries::retire_team_user(previous_owner, end_of_season_year, db).await?;
+    let new_owner = team_user_queries::insert_team_owner(
+        team_model.id,
+        new_user_id,
+        new_owner_nickname,
+        end_of_season_year,
+        db,
+    )
+    .await?;
+
+    let transaction_model = transaction_queries::insert_transaction(
+        transaction::Model::new_team_config_change_transaction(current_deadline),
+        db,
+    )
+    .await?;
+    let team_update_data = TeamUpdateData::Settings(TeamSettingsChange {
+        users: vec![
+            TeamUpdateSettingUser::from_team_user(&retired_owner),
+            TeamUpdateSettingUser::from_team_user(&new_owner),
+        ],
+        ownership_change: Some(TeamOwnershipChange {
+            kind,
+            previous_user_id,
+            new_user_id,
+            reason,
+        }),
+    });
+    team_update_queries::insert_team_update(
+        team_update::ActiveModel {
+            data: ActiveValue::Set(team_update_data.to_json()?),
+            effective_date: ActiveValue::Set(now.date_naive()),
+            status: ActiveValue::Set(TeamUpdateStatus::Done),
+            team_id: ActiveValue::Set(team_model.id),
+            transaction_id: ActiveValue::Set(Some(transaction_model.id)),
+            ..Default::default()
+        },
+        db,
+    )
+    .await?;
+
+    Ok(new_owner)
+}
+
+async fn find_current_owner<C>(team_id: i64, db: &C) -> Result<team_user::Model>
+where
+    C: ConnectionTrait,
+{
+    let mut active_team_users =
+        team_user_queries::find_active_team_users_for_team(team_id, db).await?;
+    if active_team_users.len() > 1 {
+        bail!("Team (id = {team_id}) has more than one active team_user.");
+    }
+    let Some(owner) = active_team_users.pop() else {
+        return Err(OwnershipTransferRejection::NoActiveOwner { team_id }.into());
+    };
+    if owner.league_role == LeagueRole::LeagueCommissioner {
+        return Err(OwnershipTransferRejection::CommissionerTeam { team_id }.into());
+    }
+
+    Ok(owner)
+}
+
+async fn validate_new_owner<C>(
+    team_model: &team::Model,
+    previous_owner: &team_user::Model,
+    new_user_id: i64,
+    db: &C,
+) -> Result<()>
+where
+    C: ConnectionTrait,
+{
+    if previous_owner.user_id == new_user_id {
+        return Err(OwnershipTransferRejection::AlreadyOwner {
+            user_id: new_user_id,
+            team_id: team_model.id,
+        }
+        .into());
+    }
+    if user_queries::find_user_by_id(new_user_id, db)
+        .await?
+        .is_none()
+    {
+        return Err(OwnershipTransferRejection::UnknownUser {
+            user_id: new_user_id,
+        }
+        .into());
+    }
+    let is_in_league = team_user_queries::get_all_team_users_by_user_and_league(
+        &new_user_id,
+        &team_model.league_id,
+        db,
+    )
+    .await?
+    .iter()
+    .any(|(team_user_model, _)| team_user_model.league_role != LeagueRole::Inactive);
+    if is_in_league {
+        return Err(OwnershipTransferRejection::AlreadyInLeague {
+            user_id: new_user_id,
+        }
+        .into());
+    }
+
+    Ok(())
+}
@@ -132,6 +132,7 @@ where
             status: ActiveValue::Set(TeamUpdateStatus::Done),
             team_id: ActiveValue::Set(team_id),
             transaction_id: ActiveValue::Set(Some(trade_transaction.id)),
+            sequence: ActiveValue::NotSet,
             created_at: ActiveValue::NotSet,
             updated_at: ActiveValue::NotSet,
         };
@@ -193,11 +194,25 @@ where
             ContractUpdate {
                 contract_id: updated_contract_model.id,
                 update_type: ContractUpdateType::AddViaTrade,
-                player_name_at_time: player_name,
-                player_team_abbr_at_time: team_abbr,
-                player_team_name_at_time: team_name,
+                player_name_at_time: player_name.clone(),
+                player_team_abbr_at_time: team_abbr.clone(),
+                player_team_name_at_time: team_name.clone(),
             },
         );
+
+        // A traded IR contract comes off IR on the receiving roster (§10.3.2), which the receiving team has to accommodate like any other activation.
+        if contract_model.is_ir {
+            team_update_contract_assets_by_team_id.insert(
+                trade_asset_model.to_team_id,
+                ContractUpdate {
+                    contract_id: updated_contract_model.id,
+                    update_type: ContractUpdateType::FromIR,
+                    player_name_at_time: player_name,
+                    player_team_abbr_at_time: team_abbr,
+                    player_team_name_at_time: team_name,
+                },
+            );
+        }
     }
 
     Ok(team_update_contract_assets_by_team_id)
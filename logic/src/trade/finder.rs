@@ -0,0 +1,464 @@
+//! Trade finder: given an asset a team wants to move, search every other team's trade block for
+//! packages that would take it.
+//!
+//! Two shapes are enumerated per counterparty: one-for-one (the target for one listed asset) and
+//! two-for-one in either direction (the target plus one of the searching team's own listings for
+//! one listed asset, or the target for two listed assets). A package is only suggested if both
+//! rosters come out legal under the roster lock's checks — salary within the cap and
+//! [`RosterCounts::over_limit_by`] at 0 for the deadline the trade would be recorded against.
+//!
+//! Suggestions are ranked by salary balance, closest first; a trade is never blocked for being
+//! lopsided (spec 07), this is only an ordering.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+};
+
+use color_eyre::Result;
+use fbkl_entity::{
+    contract::{self, ContractStatus},
+    contract_queries,
+    deadline::DeadlineKind,
+    draft_pick_queries,
+    sea_orm::{ConnectionTrait, prelude::DateTimeWithTimeZone},
+    trade_block::TradeBlockNeed,
+};
+use tracing::instrument;
+
+use crate::roster::{RosterCounts, calculate_team_contract_salary, salary_counted_toward_cap};
+
+use super::{
+    TradeBlockAsset, TradeBlockRejection, find_standing_trade_block_listings,
+    preview_trade::project_team_contracts, process_trade::find_trade_deadline,
+};
+
+/// The asset the searching team wants to move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TradeFinderTarget {
+    Contract(i64),
+    DraftPick(i64),
+}
+
+/// One suggested trade between the searching team and a counterparty.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TradeFinderPackage {
+    pub counterparty_team_id: i64,
+    /// What the searching team sends: the target, and possibly one of its own listings.
+    pub sent: Vec<TradeBlockAsset>,
+    /// What the searching team gets back, all from the counterparty's block.
+    pub received: Vec<TradeBlockAsset>,
+    pub salary_sent: i16,
+    pub salary_received: i16,
+    /// `|salary_sent - salary_received|`; the ranking key.
+    pub salary_balance: i16,
+    /// Needs the counterparty listed that this package meets.
+    pub counterparty_needs_met: Vec<TradeBlockNeed>,
+}
+
+/// A team's roster going into the search.
+#[derive(Debug, Clone)]
+struct FinderTeam {
+    team_id: i64,
+    contracts: Vec<contract::Model>,
+    salary_cap: i16,
+}
+
+impl FinderTeam {
+    /// Whether the team is still legal after sending `outgoing` and receiving `incoming`.
+    fn is_legal_after(
+        &self,
+        outgoing: &[TradeBlockAsset],
+        incoming: &[TradeBlockAsset],
+        deadline_kind: DeadlineKind,
+    ) -> bool {
+        let outgoing_contract_ids: HashSet<i64> = contracts_in(outgoing)
+            .map(|contract_model| contract_model.id)
+            .collect();
+        let contracts_after = project_team_contracts(
+            self.team_id,
+            &self.contracts,
+            &outgoing_contract_ids,
+            contracts_in(incoming),
+        );
+
+        salary_counted_toward_cap(&contracts_after) <= self.salary_cap
+            && RosterCounts::from_contracts(&contracts_after).over_limit_by(deadline_kind) == 0
+    }
+}
+
+/// Suggests packages for `target`, best salary balance first.
+#[instrument(skip(db))]
+pub async fn find_trade_packages<C>(
+    league_id: i64,
+    end_of_season_year: i16,
+    team_id: i64,
+    target: TradeFinderTarget,
+    search_datetime: &DateTimeWithTimeZone,
+    db: &C,
+) -> Result<Vec<TradeFinderPackage>>
+where
+    C: ConnectionTrait,
+{
+    let target_asset = match target {
+        TradeFinderTarget::Contract(contract_id) => {
+            let contract_model = contract_queries::find_contract_by_id(contract_id, db)
+                .await?
+                .get_latest_in_chain(db)
+                .await?;
+            if contract_model.team_id != Some(team_id)
+                || contract_model.status != ContractStatus::Active
+            {
+                return Err(TradeBlockRejection::NotOwnedByTeam { team_id }.into());
+            }
+            TradeBlockAsset::Contract(contract_model)
+        }
+        TradeFinderTarget::DraftPick(draft_pick_id) => {
+            let draft_pick_model =
+                draft_pick_queries::find_draft_pick_by_id(draft_pick_id, db).await?;
+            if draft_pick_model.current_owner_team_id != team_id {
+                return Err(TradeBlockRejection::NotOwnedByTeam { team_id }.into());
+            }
+            TradeBlockAsset::DraftPick(draft_pick_model)
+        }
+    };
+
+    let deadline_model =
+        find_trade_deadline(league_id, end_of_season_year, search_datetime, db).await?;
+
+    let mut assets_by_team_id: HashMap<i64, Vec<TradeBlockAsset>> = HashMap::new();
+    let mut needs_by_team_id: HashMap<i64, Vec<TradeBlockNeed>> = HashMap::new();
+    for standing in find_standing_trade_block_listings(league_id, end_of_season_year, db).await? {
+        match standing.asset {
+            Some(asset) if asset != target_asset => assets_by_team_id
+                .entry(standing.listing.team_id)
+                .or_default()
+                .push(asset),
+            Some(_) => (),
+            None => needs_by_team_id
+                .entry(standing.listing.team_id)
+                .or_default()
+                .extend(standing.listing.need),
+        }
+    }
+    let own_extras = assets_by_team_id.remove(&team_id).unwrap_or_default();
+
+    let mut team_ids: Vec<i64> = assets_by_team_id.keys().copied().collect();
+    team_ids.push(team_id);
+    let mut active_contracts_by_team_id =
+        contract_queries::find_active_contracts_by_teams(team_ids.clone(), db).await?;
+    let mut finder_teams: HashMap<i64, FinderTeam> = HashMap::new();
+    for finder_team_id in team_ids {
+        let contracts = active_contracts_by_team_id
+            .remove(&finder_team_id)
+            .unwrap_or_default();
+        let salary_snapshot =
+            calculate_team_contract_salary(finder_team_id, &contracts, &deadline_model, db).await?;
+        finder_teams.insert(
+            finder_team_id,
+            FinderTeam {
+                team_id: finder_team_id,
+                contracts,
+                salary_cap: salary_snapshot.cap,
+            },
+        );
+    }
+
+    let Some(searching_team) = finder_teams.get(&team_id) else {
+        return Ok(vec![]);
+    };
+    let mut packages = vec![];
+    for (counterparty_team_id, counterparty_assets) in &assets_by_team_id {
+        let Some(counterparty_team) = finder_teams.get(counterparty_team_id) else {
+            continue;
+        };
+        let counterparty_needs = needs_by_team_id
+            .get(counterparty_team_id)
+            .map_or(&[][..], Vec::as_slice);
+
+        for (sent, received) in enumerate_packages(&target_asset, &own_extras, counterparty_assets)
+        {
+            if !searching_team.is_legal_after(&sent, &received, deadline_model.kind)
+                || !counterparty_team.is_legal_after(&received, &sent, deadline_model.kind)
+            {
+                continue;
+            }
+            packages.push(build_package(
+                counterparty_team,
+                sent,
+                received,
+                counterparty_needs,
+            ));
+        }
+    }
+    rank_packages(&mut packages);
+
+    Ok(packages)
+}
+
+/// Every (sent, received) pair of the one-for-one and two-for-one shapes.
+fn enumerate_packages(
+    target: &TradeBlockAsset,
+    own_extras: &[TradeBlockAsset],
+    counterparty_assets: &[TradeBlockAsset],
+) -> Vec<(Vec<TradeBlockAsset>, Vec<TradeBlockAsset>)> {
+    let mut packages = vec![];
+    for (index, counterparty_asset) in counterparty_assets.iter().enumerate() {
+        packages.push((vec![target.clone()], vec![counterparty_asset.clone()]));
+        for own_extra in own_extras {
+            packages.push((
+                vec![target.clone(), own_extra.clone()],
+                vec![counterparty_asset.clone()],
+            ));
+        }
+        for other_counterparty_asset in &counterparty_assets[index + 1..] {
+            packages.push((
+                vec![target.clone()],
+                vec![counterparty_asset.clone(), other_counterparty_asset.clone()],
+            ));
+        }
+    }
+
+    packages
+}
+
+fn build_package(
+    counterparty_team: &FinderTeam,
+    sent: Vec<TradeBlockAsset>,
+    received: Vec<TradeBlockAsset>,
+    counterparty_needs: &[TradeBlockNeed],
+) -> TradeFinderPackage {
+    // Incoming contracts land off IR, so their salary counts whatever their current `is_ir`.
+    let salary_of = |assets: &[TradeBlockAsset]| {
+        let contracts_off_ir: Vec<contract::Model> = contracts_in(assets)
+            .map(|contract_model| contract::Model {
+                is_ir: false,
+                ..contract_model.clone()
+            })
+            .collect();
+        salary_counted_toward_cap(&contracts_off_ir)
+    };
+    let salary_sent = salary_of(&sent);
+    let salary_received = salary_of(&received);
+
+    let rookie_development_count =
+        |contracts: &[contract::Model]| RosterCounts::from_contracts(contracts).rookie_development;
+    let counterparty_needs_met = counterparty_needs
+        .iter()
+        .copied()
+        .filter(|need| match need {
+            TradeBlockNeed::CapSpace => salary_sent < salary_received,
+            TradeBlockNeed::RosterSpot => {
+                contracts_in(&sent).count() < contracts_in(&received).count()
+            }
+            TradeBlockNeed::RookieDevelopmentSlot => {
+                let outgoing_contract_ids: HashSet<i64> = contracts_in(&received)
+                    .map(|contract_model| contract_model.id)
+                    .collect();
+                let contracts_after = project_team_contracts(
+                    counterparty_team.team_id,
+                    &counterparty_team.contracts,
+                    &outgoing_contract_ids,
+                    contracts_in(&sent),
+                );
+                rookie_development_count(&contracts_after)
+                    < rookie_development_count(&counterparty_team.contracts)
+            }
+            TradeBlockNeed::DraftPicks => sent
+                .iter()
+                .any(|asset| matches!(asset, TradeBlockAsset::DraftPick(_))),
+        })
+        .collect();
+
+    TradeFinderPackage {
+        counterparty_team_id: counterparty_team.team_id,
+        salary_balance: (salary_sent - salary_received).abs(),
+        sent,
+        received,
+        salary_sent,
+        salary_received,
+        counterparty_needs_met,
+    }
+}
+
+/// Closest salary balance first; ties go to packages meeting more of the counterparty's needs, then
+/// to fewer assets, then to the lower team id so the order is stable.
+fn rank_packages(packages: &mut [TradeFinderPackage]) {
+    packages.sort_by_key(|package| {
+        (
+            package.salary_balance,
+            std::cmp::Reverse(package.counterparty_needs_met.len()),
+            package.sent.len() + package.received.len(),
+            package.counterparty_team_id,
+        )
+    });
+}
+
+fn contracts_in(assets: &[TradeBlockAsset]) -> impl Iterator<Item = &contract::Model> {
+    assets.iter().filter_map(|asset| match asset {
+        TradeBlockAsset::Contract(contract_model) => Some(contract_model),
+        TradeBlockAsset::DraftPick(_) => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use fbkl_entity::{
+        contract::{self, ContractKind, ContractStatus},
+        deadline::DeadlineKind,
+        draft_pick,
+        trade_block::TradeBlockNeed,
+    };
+
+    use super::{FinderTeam, TradeBlockAsset, build_package, enumerate_packages, rank_packages};
+
+    fn contract(id: i64, team_id: i64, kind: ContractKind, salary: i16) -> contract::Model {
+        contract::Model {
+            id,
+            year_number: 1,
+            kind,
+            is_ir: false,
+            salary,
+            end_of_season_year: 2026,
+            status: ContractStatus::Active,
+            league_id: 1,
+            league_player_id: None,
+            player_id: Some(id),
+            previous_contract_id: None,
+            original_contract_id: Some(id),
+            team_id: Some(team_id),
+            created_at: chrono::Utc::now().into(),
+            updated_at: chrono::Utc::now().into(),
+        }
+    }
+
+    fn draft_pick(id: i64, team_id: i64) -> draft_pick::Model {
+        draft_pick::Model {
+            id,
+            round: 1,
+            end_of_season_year: 2027,
+            league_id: 1,
+            current_owner_team_id: team_id,
+            original_owner_team_id: team_id,
+            created_at: chrono::Utc::now().into(),
+            updated_at: chrono::Utc::now().into(),
+        }
+    }
+
+    fn team(team_id: i64, contracts: Vec<contract::Model>, salary_cap: i16) -> FinderTeam {
+        FinderTeam {
+            team_id,
+            contracts,
+            salary_cap,
+        }
+    }
+
+    #[test]
+    fn enumerates_one_for_one_and_both_two_for_one_shapes() {
+        let target = TradeBlockAsset::Contract(contract(1, 1, ContractKind::Veteran, 10));
+        let own_extra = TradeBlockAsset::DraftPick(draft_pick(2, 1));
+        let counterparty_assets = [
+            TradeBlockAsset::Contract(contract(3, 2, ContractKind::Veteran, 9)),
+            TradeBlockAsset::Contract(contract(4, 2, ContractKind::Veteran, 4)),
+        ];
+
+        let packages = enumerate_packages(&target, &[own_extra], &counterparty_assets);
+
+        // 2 one-for-one, 2 with the extra sent along, 1 taking both listed contracts back.
+        assert_eq!(packages.len(), 5);
+        assert_eq!(
+            packages
+                .iter()
+                .filter(|(sent, received)| sent.len() == 1 && received.len() == 2)
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn a_package_that_puts_a_team_over_its_cap_is_illegal() {
+        let searching_team = team(1, vec![contract(1, 1, ContractKind::Veteran, 10)], 200);
+        let sent = [TradeBlockAsset::Contract(contract(
+            1,
+            1,
+            ContractKind::Veteran,
+            10,
+        ))];
+        let received = [TradeBlockAsset::Contract(contract(
+            3,
+            2,
+            ContractKind::Veteran,
+            205,
+        ))];
+
+        assert!(!searching_team.is_legal_after(&sent, &received, DeadlineKind::InSeasonRosterLock));
+        assert!(searching_team.is_legal_after(&sent, &[], DeadlineKind::InSeasonRosterLock));
+    }
+
+    #[test]
+    fn incoming_ir_contracts_count_against_the_cap() {
+        let searching_team = team(1, vec![contract(1, 1, ContractKind::Veteran, 100)], 200);
+        let received = [TradeBlockAsset::Contract(contract::Model {
+            is_ir: true,
+            ..contract(3, 2, ContractKind::Veteran, 150)
+        })];
+
+        assert!(!searching_team.is_legal_after(&[], &received, DeadlineKind::InSeasonRosterLock));
+    }
+
+    #[test]
+    fn packages_rank_by_salary_balance_then_needs_met() {
+        let counterparty_team = team(2, vec![], 200);
+        let target = TradeBlockAsset::Contract(contract(1, 1, ContractKind::Veteran, 10));
+        let needs = [TradeBlockNeed::CapSpace];
+
+        let mut packages = vec![
+            build_package(
+                &counterparty_team,
+                vec![target.clone()],
+                vec![TradeBlockAsset::Contract(contract(
+                    3,
+                    2,
+                    ContractKind::Veteran,
+                    4,
+                ))],
+                &needs,
+            ),
+            build_package(
+                &counterparty_team,
+                vec![target.clone()],
+                vec![TradeBlockAsset::Contract(contract(
+                    4,
+                    2,
+                    ContractKind::Veteran,
+                    9,
+                ))],
+                &needs,
+            ),
+            build_package(
+                &counterparty_team,
+                vec![target],
+                vec![TradeBlockAsset::Contract(contract(
+                    5,
+                    2,
+                    ContractKind::Veteran,
+                    11,
+                ))],
+                &needs,
+            ),
+        ];
+        rank_packages(&mut packages);
+
+        let balances: Vec<i16> = packages
+            .iter()
+            .map(|package| package.salary_balance)
+            .collect();
+        assert_eq!(balances, vec![1, 1, 6]);
+        // Both are $1 off, but only taking back the $11 contract frees cap space for the counterparty.
+        assert_eq!(
+            packages[0].counterparty_needs_met,
+            vec![TradeBlockNeed::CapSpace]
+        );
+        assert!(packages[1].counterparty_needs_met.is_empty());
+    }
+}
@@ -1,16 +1,25 @@
 mod accept_trade;
 mod create_trade_team_update;
 mod external_trade_invalidation;
+pub mod finder;
+mod preview_trade;
 mod process_trade;
 mod process_trade_assets;
 mod propose_trade;
 mod reject_trade;
+mod repropose_trade;
+mod trade_block;
+mod trade_team_sides;
 mod validate_trade_assets;
 
 pub use accept_trade::*;
 pub use create_trade_team_update::MissingPreTradeSalary;
+pub use preview_trade::*;
 use process_trade::process_trade;
 use process_trade_assets::process_trade_assets;
 pub use propose_trade::*;
 pub use reject_trade::*;
+pub use repropose_trade::*;
+pub use trade_block::*;
+pub use trade_team_sides::*;
 use validate_trade_assets::validate_trade_assets;
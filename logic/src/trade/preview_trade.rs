@@ -0,0 +1,224 @@
+//! The projected cap and roster effect of an active trade on every team in it, computed without
+//! moving any asset.
+//!
+//! Overages are warnings, not rejections: §13 lets a roster be illegal mid-week as long as it is
+//! reconciled by the next roster lock, so a trade that leaves a team over its cap or roster limit
+//! still processes. The preview is how owners see that hit before they accept.
+
+use std::{collections::HashSet, fmt::Debug};
+
+use color_eyre::{Result, eyre::ensure};
+use fbkl_entity::{
+    contract, contract_queries,
+    sea_orm::{ConnectionTrait, prelude::DateTimeWithTimeZone},
+    trade,
+};
+use tracing::instrument;
+
+use crate::roster::{RosterCounts, calculate_team_contract_salary};
+
+use super::process_trade::{TradeAssetRelatedModelCache, find_trade_deadline};
+
+static EMPTY_VEC: &Vec<contract::Model> = &vec![];
+
+/// One team's side of a trade preview.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TradeTeamPreview {
+    pub team_id: i64,
+    pub salary_before: i16,
+    pub salary_after: i16,
+    /// The team's cap at the deadline the trade would be recorded against. Trading never changes it.
+    pub salary_cap: i16,
+    pub roster_after: RosterCounts,
+    /// Salary past the cap once the trade is processed; 0 when the team fits.
+    pub over_cap_by: i16,
+    /// Contracts past the roster limits once the trade is processed; 0 when the team fits.
+    pub over_roster_limit_by: i16,
+    /// Incoming IR contracts. They come off IR on arrival (§10.3.2), so they count against this
+    /// team's cap and roster from the moment the trade is processed.
+    pub contract_ids_activated_from_ir: Vec<i64>,
+}
+
+impl TradeTeamPreview {
+    /// Human-readable warnings for the hits this team takes. Empty when the trade leaves it legal.
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = vec![];
+        if !self.contract_ids_activated_from_ir.is_empty() {
+            warnings.push(format!(
+                "{} incoming player(s) come off IR and count against the cap and roster (rules §10.3.2).",
+                self.contract_ids_activated_from_ir.len()
+            ));
+        }
+        if self.over_cap_by > 0 {
+            warnings.push(format!(
+                "Salary would be ${}/${}, ${} over the cap. It must be legal by the next roster lock.",
+                self.salary_after, self.salary_cap, self.over_cap_by
+            ));
+        }
+        if self.over_roster_limit_by > 0 {
+            warnings.push(format!(
+                "Roster would be {} contract(s) over its limits. It must be legal by the next roster lock.",
+                self.over_roster_limit_by
+            ));
+        }
+
+        warnings
+    }
+}
+
+/// Projects every involved team's salary and roster counts as if the trade were processed at
+/// `preview_datetime`. Nothing is written.
+#[instrument(skip(db))]
+pub async fn preview_trade<C>(
+    trade_model: &trade::Model,
+    preview_datetime: &DateTimeWithTimeZone,
+    db: &C,
+) -> Result<Vec<TradeTeamPreview>>
+where
+    C: ConnectionTrait,
+{
+    ensure!(
+        trade_model.is_active(),
+        "Only an active trade can be previewed. (trade_id = {}, status = {:?})",
+        trade_model.id,
+        trade_model.status
+    );
+
+    let deadline_model = find_trade_deadline(
+        trade_model.league_id,
+        trade_model.end_of_season_year,
+        preview_datetime,
+        db,
+    )
+    .await?;
+    let mut team_ids: Vec<i64> = trade_model
+        .get_teams(db)
+        .await?
+        .into_iter()
+        .map(|team_model| team_model.id)
+        .collect();
+    team_ids.sort_unstable();
+
+    let trade_asset_related_models =
+        TradeAssetRelatedModelCache::from_trade_assets(trade_model.get_trade_assets(db).await?, db)
+            .await?;
+    let traded_contract_ids: HashSet<i64> = trade_asset_related_models
+        .trade_asset_contracts_by_trade_asset_id
+        .values()
+        .map(|(_, contract_model)| contract_model.id)
+        .collect();
+
+    let active_contracts_by_team_id =
+        contract_queries::find_active_contracts_by_teams(team_ids.clone(), db).await?;
+
+    let mut previews = Vec::with_capacity(team_ids.len());
+    for team_id in team_ids {
+        let contracts_before = active_contracts_by_team_id
+            .get_vec(&team_id)
+            .unwrap_or(EMPTY_VEC);
+
+        let incoming_contracts: Vec<&contract::Model> = trade_asset_related_models
+            .trade_asset_contracts_by_trade_asset_id
+            .values()
+            .filter(|(trade_asset_model, _)| trade_asset_model.to_team_id == team_id)
+            .map(|(_, contract_model)| contract_model)
+            .collect();
+        let mut contract_ids_activated_from_ir: Vec<i64> = incoming_contracts
+            .iter()
+            .filter(|contract_model| contract_model.is_ir)
+            .map(|contract_model| contract_model.id)
+            .collect();
+        contract_ids_activated_from_ir.sort_unstable();
+        let contracts_after = project_team_contracts(
+            team_id,
+            contracts_before,
+            &traded_contract_ids,
+            incoming_contracts,
+        );
+
+        let salary_before =
+            calculate_team_contract_salary(team_id, contracts_before, &deadline_model, db).await?;
+        let salary_after =
+            calculate_team_contract_salary(team_id, &contracts_after, &deadline_model, db).await?;
+        let roster_after = RosterCounts::from_contracts(&contracts_after);
+
+        previews.push(TradeTeamPreview {
+            team_id,
+            salary_before: salary_before.salary,
+            salary_after: salary_after.salary,
+            salary_cap: salary_after.cap,
+            roster_after,
+            over_cap_by: (salary_after.salary - salary_after.cap).max(0),
+            over_roster_limit_by: roster_after.over_limit_by(deadline_model.kind),
+            contract_ids_activated_from_ir,
+        });
+    }
+
+    Ok(previews)
+}
+
+/// A team's active contracts once `outgoing_contract_ids` leave and `incoming_contracts` arrive.
+/// Incoming contracts land off IR (§10.3.2), matching what `trade_contract_to_team` writes.
+pub(super) fn project_team_contracts<'a>(
+    team_id: i64,
+    contracts_before: &[contract::Model],
+    outgoing_contract_ids: &HashSet<i64>,
+    incoming_contracts: impl IntoIterator<Item = &'a contract::Model>,
+) -> Vec<contract::Model> {
+    contracts_before
+        .iter()
+        .filter(|contract_model| !outgoing_contract_ids.contains(&contract_model.id))
+        .cloned()
+        .chain(
+            incoming_contracts
+                .into_iter()
+                .map(|contract_model| contract::Model {
+                    team_id: Some(team_id),
+                    is_ir: false,
+                    ..contract_model.clone()
+                }),
+        )
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::roster::RosterCounts;
+
+    use super::TradeTeamPreview;
+
+    fn preview() -> TradeTeamPreview {
+        TradeTeamPreview {
+            team_id: 1,
+            salary_before: 200,
+            salary_after: 200,
+            salary_cap: 210,
+            roster_after: RosterCounts::default(),
+            over_cap_by: 0,
+            over_roster_limit_by: 0,
+            contract_ids_activated_from_ir: vec![],
+        }
+    }
+
+    #[test]
+    fn a_legal_side_has_no_warnings() {
+        assert!(preview().warnings().is_empty());
+    }
+
+    #[test]
+    fn each_hit_gets_its_own_warning() {
+        let over = TradeTeamPreview {
+            salary_after: 215,
+            over_cap_by: 5,
+            over_roster_limit_by: 1,
+            contract_ids_activated_from_ir: vec![9],
+            ..preview()
+        };
+
+        let warnings = over.warnings();
+        assert_eq!(warnings.len(), 3);
+        assert!(warnings[0].contains("§10.3.2"));
+        assert!(warnings[1].contains("$215/$210"));
+        assert!(warnings[2].contains("1 contract(s) over"));
+    }
+}
@@ -5,7 +5,7 @@ use std::{
 
 use color_eyre::{Result, eyre::eyre};
 use fbkl_entity::{
-    contract, contract_queries, deadline_queries, draft_pick, draft_pick_option,
+    contract, contract_queries, deadline, deadline_queries, draft_pick, draft_pick_option,
     sea_orm::{
         ActiveModelTrait, ActiveValue, ConnectionTrait, LoaderTrait, prelude::DateTimeWithTimeZone,
     },
@@ -15,7 +15,9 @@ use fbkl_entity::{
 };
 use tracing::instrument;
 
-use crate::roster::calculate_team_contract_salary;
+use crate::{
+    ir::activate_ir_contracts_due_after_acquisition, roster::calculate_team_contract_salary,
+};
 
 use super::{
     create_trade_team_update::{
@@ -107,6 +109,7 @@ impl TradeAssetRelatedModelCache {
 }
 
 /// Moves assets between teams for a created trade, updates the trade status to `completed`, creates the appropriate transaction, and invalidates all other pending trades that include any of the traded assets.
+/// Teams that receive a contract also have their recovered players taken off IR (§10.1.3).
 /// Returns the updated trade model.
 #[instrument(skip(db))]
 pub async fn process_trade<C>(
@@ -117,21 +120,13 @@ pub async fn process_trade<C>(
 where
     C: ConnectionTrait,
 {
-    let next_deadline = deadline_queries::find_next_deadline_for_season_by_datetime(
+    let next_deadline = find_trade_deadline(
         trade_model.league_id,
         trade_model.end_of_season_year,
-        *trade_datetime,
-        None,
+        trade_datetime,
         db,
     )
-    .await?
-    .ok_or_else(|| {
-        eyre!(
-            "Could not find a deadline for league (id = {}) season {} after {trade_datetime}.",
-            trade_model.league_id,
-            trade_model.end_of_season_year
-        )
-    })?;
+    .await?;
     let traded_trade_assets = trade_model.get_trade_assets(db).await?;
     let mut all_team_ids = HashSet::new();
     for traded_trade_asset in &traded_trade_assets {
@@ -193,16 +188,20 @@ where
         db,
     )
     .await?;
+    let all_team_ids: Vec<i64> = all_team_ids.into_iter().collect();
     insert_team_updates_from_completed_trade(
         team_update_assets_by_team_id,
         trade_datetime,
         &trade_transaction,
         &next_deadline,
         &team_salaries_before_trade,
-        all_team_ids.into_iter().collect(),
+        all_team_ids.clone(),
         db,
     )
     .await?;
+    for team_id in all_team_ids {
+        activate_ir_contracts_due_after_acquisition(team_id, &next_deadline, db).await?;
+    }
 
     invalidate_external_trades_with_traded_assets(&updated_trade, &trade_asset_related_models, db)
         .await?;
@@ -210,6 +209,32 @@ where
     Ok(updated_trade)
 }
 
+/// The deadline a trade made at `trade_datetime` is recorded against: the next one in the trade's season.
+#[instrument(skip(db))]
+pub(super) async fn find_trade_deadline<C>(
+    league_id: i64,
+    end_of_season_year: i16,
+    trade_datetime: &DateTimeWithTimeZone,
+    db: &C,
+) -> Result<deadline::Model>
+where
+    C: ConnectionTrait,
+{
+    deadline_queries::find_next_deadline_for_season_by_datetime(
+        league_id,
+        end_of_season_year,
+        *trade_datetime,
+        None,
+        db,
+    )
+    .await?
+    .ok_or_else(|| {
+        eyre!(
+            "Could not find a deadline for league (id = {league_id}) season {end_of_season_year} after {trade_datetime}."
+        )
+    })
+}
+
 #[instrument(skip(db))]
 async fn update_trade_status<C>(trade_model: trade::Model, db: &C) -> Result<trade::Model>
 where
@@ -18,6 +18,7 @@ pub struct UpdatedTradeAssetModelCache {
     // pub draft_pick_options_by_trade_asset_id: HashMap<i64, draft_pick_option::Model>,
 }
 
+/// Moves every traded asset to its receiving team. Traded contracts are re-chained through `trade_contract_to_team`, which also takes an IR contract off IR (§10.3.2).
 #[instrument(skip(db))]
 pub async fn process_trade_assets<C>(
     trade_asset_related_models: &TradeAssetRelatedModelCache,
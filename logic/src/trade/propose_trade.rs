@@ -1,5 +1,6 @@
 use color_eyre::{Result, eyre::eyre};
 use fbkl_entity::{
+    notification::NotificationKind,
     sea_orm::{
         ActiveModelTrait, ActiveValue, ConnectionTrait, ModelTrait, TransactionSession,
         TransactionTrait,
@@ -10,6 +11,8 @@ use fbkl_entity::{
 };
 use tracing::instrument;
 
+use crate::notifications::notify_team;
+
 /// Creates & inserts a new trade proposed by a team to 1 or more teams.
 ///
 /// Inserts the following entities: The (proposed) trade, the `team_trades` involved, the trade assets involved, and the proposal trade action.
@@ -68,6 +71,18 @@ where
     )
     .await?;
 
+    for team_id in to_team_ids {
+        notify_team(
+            league_id,
+            *team_id,
+            NotificationKind::TradeProposed,
+            Some(inserted_trade.id),
+            &format!("{} proposed a trade to you.", from_team_model.name),
+            &db_txn,
+        )
+        .await?;
+    }
+
     db_txn.commit().await?;
 
     Ok(inserted_trade)
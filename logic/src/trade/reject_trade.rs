@@ -1,5 +1,7 @@
 use color_eyre::Result;
 use fbkl_entity::{
+    league::TradeRejectionPolicy,
+    league_queries,
     sea_orm::{
         ActiveModelTrait, ActiveValue, ConnectionTrait, TransactionSession, TransactionTrait,
     },
@@ -10,7 +12,14 @@ use fbkl_entity::{
 };
 use tracing::instrument;
 
-/// Rejects a proposed trade: records the rejecting `team_user`'s `Reject` action and closes the trade.
+use super::find_trade_team_sides;
+
+/// Rejects a proposed trade: records the rejecting `team_user`'s `Reject` action and, unless the
+/// league's [`TradeRejectionPolicy`] keeps it open, closes the trade.
+///
+/// A trade kept open cannot be processed (every team has to accept), but the other teams may still
+/// respond and the proposer may re-propose it without the rejecting team
+/// (`repropose_trade_without_rejecting_teams`).
 ///
 /// Draft-pick options carried by the trade are left as-is; cancelling them
 /// (`DraftPickOptionStatus::CancelledViaTradeRejection`) belongs with the draft work in spec 02.
@@ -25,6 +34,24 @@ where
 {
     trade_queries::validate_trade_is_latest_in_chain(&trade_model, db).await?;
 
+    let league_model = league_queries::find_league_by_id(trade_model.league_id, db).await?;
+    let trade_team_sides = find_trade_team_sides(&trade_model, db).await?;
+    let rejected_by_proposer = trade_team_sides
+        .iter()
+        .any(|side| side.team_id == rejecting_team_user_model.team_id && side.is_proposer);
+    let teams_left = trade_team_sides
+        .iter()
+        .filter(|side| {
+            side.team_id != rejecting_team_user_model.team_id
+                && side.latest_action != Some(TradeActionType::Reject)
+        })
+        .count();
+    let ends_trade = rejection_ends_trade(
+        league_model.trade_rejection_policy,
+        teams_left,
+        rejected_by_proposer,
+    );
+
     let db_txn = db.begin().await?;
 
     let _rejected_trade_action = trade_action_queries::insert_trade_action(
@@ -35,11 +62,66 @@ where
     )
     .await?;
 
-    let mut trade_to_update: trade::ActiveModel = trade_model.into();
-    trade_to_update.status = ActiveValue::Set(TradeStatus::Rejected);
-    let updated_trade = trade_to_update.update(&db_txn).await?;
+    let updated_trade = if ends_trade {
+        let mut trade_to_update: trade::ActiveModel = trade_model.into();
+        trade_to_update.status = ActiveValue::Set(TradeStatus::Rejected);
+        trade_to_update.update(&db_txn).await?
+    } else {
+        trade_model
+    };
 
     db_txn.commit().await?;
 
     Ok(updated_trade)
 }
+
+/// Whether a rejection closes the trade. A rejection only leaves it open when the league allows
+/// re-proposing, the proposer is not the one rejecting, and at least two teams that have not
+/// rejected are left to trade with each other.
+const fn rejection_ends_trade(
+    policy: TradeRejectionPolicy,
+    teams_left: usize,
+    rejected_by_proposer: bool,
+) -> bool {
+    match policy {
+        TradeRejectionPolicy::EndsTrade => true,
+        TradeRejectionPolicy::AllowsRepropose => teams_left < 2 || rejected_by_proposer,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fbkl_entity::league::TradeRejectionPolicy;
+
+    use super::rejection_ends_trade;
+
+    #[test]
+    fn ends_trade_policy_always_closes_the_trade() {
+        assert!(rejection_ends_trade(
+            TradeRejectionPolicy::EndsTrade,
+            2,
+            false
+        ));
+    }
+
+    #[test]
+    fn repropose_policy_keeps_a_multi_team_trade_open() {
+        assert!(!rejection_ends_trade(
+            TradeRejectionPolicy::AllowsRepropose,
+            2,
+            false
+        ));
+        // The proposer has nobody left to trade with, e.g. in a two-team deal.
+        assert!(rejection_ends_trade(
+            TradeRejectionPolicy::AllowsRepropose,
+            1,
+            false
+        ));
+        // The proposer rejecting its own trade withdraws it.
+        assert!(rejection_ends_trade(
+            TradeRejectionPolicy::AllowsRepropose,
+            2,
+            true
+        ));
+    }
+}
@@ -0,0 +1,287 @@
+//! Re-proposing a multi-team trade without the teams that rejected it, for leagues whose
+//! [`TradeRejectionPolicy`](fbkl_entity::league::TradeRejectionPolicy) keeps such trades open.
+
+use std::{collections::HashSet, fmt::Debug};
+
+use color_eyre::{Result, eyre::eyre};
+use fbkl_entity::{
+    sea_orm::{
+        ActiveModelTrait, ActiveValue, ConnectionTrait, TransactionSession, TransactionTrait,
+    },
+    team_trade, team_user,
+    trade::{self, TradeStatus},
+    trade_action::TradeActionType,
+    trade_action_queries,
+    trade_asset::{self, FromTeamId, ToTeamId, TradeAssetType},
+    trade_queries,
+};
+use tracing::instrument;
+
+use super::{TradeTeamSide, find_trade_team_sides};
+
+/// Why a trade could not be re-proposed.
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum ReproposeRejection {
+    #[error("Only the proposing team can re-propose a trade.")]
+    NotProposer,
+    #[error("No team has rejected this trade.")]
+    NothingRejected,
+    #[error("Fewer than two teams would be left in the trade.")]
+    TooFewTeams,
+    #[error("No assets would be left in the trade.")]
+    NoAssetsLeft,
+}
+
+/// Re-proposes an open trade without every team whose latest action on it is a rejection.
+///
+/// The re-proposal is inserted as the next trade in the chain: the remaining teams, the assets that
+/// only move between them, and a fresh `Propose` action by the proposer. Assets sent to or from a
+/// rejecting team are dropped. The previous trade is marked rejected.
+#[instrument(skip(db))]
+pub async fn repropose_trade_without_rejecting_teams<C>(
+    trade_model: trade::Model,
+    proposing_team_user_model: &team_user::Model,
+    db: &C,
+) -> Result<trade::Model>
+where
+    C: ConnectionTrait + TransactionTrait,
+{
+    trade_queries::validate_trade_is_latest_in_chain(&trade_model, db).await?;
+    if !trade_model.is_active() {
+        return Err(eyre!(
+            "Trade {} is no longer open ({:?}).",
+            trade_model.id,
+            trade_model.status
+        ));
+    }
+
+    let trade_team_sides = find_trade_team_sides(&trade_model, db).await?;
+    let (remaining_team_ids, remaining_trade_assets) =
+        remaining_trade(&trade_team_sides, proposing_team_user_model.team_id)?;
+
+    let db_txn = db.begin().await?;
+
+    let inserted_trade = trade_queries::insert_next_trade_in_chain(&trade_model, &db_txn).await?;
+
+    for team_id in remaining_team_ids {
+        let team_trade_to_insert = team_trade::ActiveModel {
+            id: ActiveValue::NotSet,
+            team_id: ActiveValue::Set(team_id),
+            trade_id: ActiveValue::Set(inserted_trade.id),
+        };
+        let _inserted_team_trade_model = team_trade_to_insert.insert(&db_txn).await?;
+    }
+
+    for trade_asset_model in remaining_trade_assets {
+        let _inserted_trade_asset = copy_trade_asset(&trade_asset_model, inserted_trade.id)?
+            .insert(&db_txn)
+            .await?;
+    }
+
+    let _proposed_trade_action = trade_action_queries::insert_trade_action(
+        TradeActionType::Propose,
+        inserted_trade.id,
+        proposing_team_user_model.id,
+        &db_txn,
+    )
+    .await?;
+
+    let mut trade_to_update: trade::ActiveModel = trade_model.into();
+    trade_to_update.status = ActiveValue::Set(TradeStatus::Rejected);
+    let _rejected_trade = trade_to_update.update(&db_txn).await?;
+
+    db_txn.commit().await?;
+
+    Ok(inserted_trade)
+}
+
+/// The teams and assets left once every rejecting team is dropped, teams ordered by id.
+fn remaining_trade(
+    trade_team_sides: &[TradeTeamSide],
+    proposing_team_id: i64,
+) -> Result<(Vec<i64>, Vec<trade_asset::Model>), ReproposeRejection> {
+    let is_proposer = trade_team_sides
+        .iter()
+        .any(|side| side.team_id == proposing_team_id && side.is_proposer);
+    if !is_proposer {
+        return Err(ReproposeRejection::NotProposer);
+    }
+
+    let rejecting_team_ids: HashSet<i64> = trade_team_sides
+        .iter()
+        .filter(|side| side.latest_action == Some(TradeActionType::Reject))
+        .map(|side| side.team_id)
+        .collect();
+    if rejecting_team_ids.is_empty() {
+        return Err(ReproposeRejection::NothingRejected);
+    }
+
+    let remaining_team_ids: Vec<i64> = trade_team_sides
+        .iter()
+        .map(|side| side.team_id)
+        .filter(|team_id| !rejecting_team_ids.contains(team_id))
+        .collect();
+    if remaining_team_ids.len() < 2 {
+        return Err(ReproposeRejection::TooFewTeams);
+    }
+
+    // Every asset appears once as a send; receives would only repeat them.
+    let remaining_trade_assets: Vec<trade_asset::Model> = trade_team_sides
+        .iter()
+        .flat_map(|side| side.sends.iter())
+        .filter(|trade_asset_model| {
+            !rejecting_team_ids.contains(&trade_asset_model.from_team_id)
+                && !rejecting_team_ids.contains(&trade_asset_model.to_team_id)
+        })
+        .cloned()
+        .collect();
+    if remaining_trade_assets.is_empty() {
+        return Err(ReproposeRejection::NoAssetsLeft);
+    }
+
+    Ok((remaining_team_ids, remaining_trade_assets))
+}
+
+fn copy_trade_asset(
+    trade_asset_model: &trade_asset::Model,
+    trade_id: i64,
+) -> Result<trade_asset::ActiveModel> {
+    let from_team_id = FromTeamId(trade_asset_model.from_team_id);
+    let to_team_id = ToTeamId(trade_asset_model.to_team_id);
+    let missing_id = || {
+        eyre!(
+            "Trade asset {} ({:?}) is missing its asset id.",
+            trade_asset_model.id,
+            trade_asset_model.asset_type
+        )
+    };
+
+    let trade_asset_to_insert = match trade_asset_model.asset_type {
+        TradeAssetType::Contract => trade_asset::Model::from_contract(
+            Some(trade_id),
+            trade_asset_model.contract_id.ok_or_else(missing_id)?,
+            from_team_id,
+            to_team_id,
+        ),
+        TradeAssetType::DraftPick => trade_asset::Model::from_draft_pick(
+            Some(trade_id),
+            trade_asset_model.draft_pick_id.ok_or_else(missing_id)?,
+            from_team_id,
+            to_team_id,
+        ),
+        TradeAssetType::DraftPickOption => trade_asset::Model::from_draft_pick_option(
+            Some(trade_id),
+            trade_asset_model
+                .draft_pick_option_id
+                .ok_or_else(missing_id)?,
+            from_team_id,
+            to_team_id,
+        ),
+    };
+
+    Ok(trade_asset_to_insert)
+}
+
+#[cfg(test)]
+mod tests {
+    use fbkl_entity::{
+        trade_action::TradeActionType,
+        trade_asset::{self, TradeAssetType},
+    };
+
+    use super::{ReproposeRejection, TradeTeamSide, remaining_trade};
+
+    fn contract_asset(id: i64, from_team_id: i64, to_team_id: i64) -> trade_asset::Model {
+        trade_asset::Model {
+            id,
+            asset_type: TradeAssetType::Contract,
+            draft_pick_option_id: None,
+            contract_id: Some(id),
+            draft_pick_id: None,
+            from_team_id,
+            to_team_id,
+            trade_id: 1,
+        }
+    }
+
+    fn side(
+        team_id: i64,
+        latest_action: Option<TradeActionType>,
+        assets: &[trade_asset::Model],
+    ) -> TradeTeamSide {
+        TradeTeamSide {
+            team_id,
+            is_proposer: latest_action == Some(TradeActionType::Propose),
+            sends: assets
+                .iter()
+                .filter(|asset| asset.from_team_id == team_id)
+                .cloned()
+                .collect(),
+            receives: assets
+                .iter()
+                .filter(|asset| asset.to_team_id == team_id)
+                .cloned()
+                .collect(),
+            latest_action,
+        }
+    }
+
+    #[test]
+    fn drops_the_rejecting_team_and_its_assets() {
+        let assets = [
+            contract_asset(10, 1, 2),
+            contract_asset(11, 2, 1),
+            contract_asset(12, 3, 1),
+            contract_asset(13, 1, 3),
+        ];
+        let sides = [
+            side(1, Some(TradeActionType::Propose), &assets),
+            side(2, None, &assets),
+            side(3, Some(TradeActionType::Reject), &assets),
+        ];
+
+        let (team_ids, kept_assets) = remaining_trade(&sides, 1).unwrap();
+
+        assert_eq!(team_ids, vec![1, 2]);
+        let mut kept_asset_ids: Vec<i64> = kept_assets.iter().map(|asset| asset.id).collect();
+        kept_asset_ids.sort_unstable();
+        assert_eq!(kept_asset_ids, vec![10, 11]);
+    }
+
+    #[test]
+    fn refuses_when_not_the_proposer_or_nothing_is_left() {
+        let assets = [contract_asset(10, 1, 2), contract_asset(11, 3, 1)];
+        let sides = [
+            side(1, Some(TradeActionType::Propose), &assets),
+            side(2, Some(TradeActionType::Reject), &assets),
+            side(3, None, &assets),
+        ];
+
+        assert_eq!(
+            remaining_trade(&sides, 3).unwrap_err(),
+            ReproposeRejection::NotProposer
+        );
+        // Only 3 -> 1 is left and that asset involves no rejecting team, so it survives...
+        assert!(remaining_trade(&sides, 1).is_ok());
+
+        let nothing_between_them = [
+            side(1, Some(TradeActionType::Propose), &assets[..1]),
+            side(2, Some(TradeActionType::Reject), &assets[..1]),
+            side(3, None, &assets[..1]),
+        ];
+        // ...but with only 1 -> 2 in the deal, teams 1 and 3 have nothing to swap.
+        assert_eq!(
+            remaining_trade(&nothing_between_them, 1).unwrap_err(),
+            ReproposeRejection::NoAssetsLeft
+        );
+
+        let nobody_rejected = [
+            side(1, Some(TradeActionType::Propose), &assets),
+            side(2, None, &assets),
+        ];
+        assert_eq!(
+            remaining_trade(&nobody_rejected, 1).unwrap_err(),
+            ReproposeRejection::NothingRejected
+        );
+    }
+}
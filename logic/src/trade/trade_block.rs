@@ -0,0 +1,300 @@
+//! Listing contracts, picks and needs on a team's trade block, and reading back the listings that
+//! still stand.
+//!
+//! A listing is only a signal: it never moves an asset or reserves it. A contract that has since
+//! been traded, dropped or expired, or a pick that has changed hands, simply stops being returned
+//! by [`find_standing_trade_block_listings`]. Rejections are returned as [`TradeBlockRejection`] so
+//! the GraphQL layer can map them to their own error code.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+};
+
+use color_eyre::Result;
+use fbkl_entity::{
+    contract::{self, ContractStatus},
+    contract_queries, draft_pick, draft_pick_queries,
+    sea_orm::ConnectionTrait,
+    trade_block::{self, TradeBlockKind, TradeBlockNeed},
+    trade_block_queries::{self, NewTradeBlockListing},
+};
+use tracing::instrument;
+
+/// Why a listing was refused.
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum TradeBlockRejection {
+    #[error("Team {team_id} does not hold that asset.")]
+    NotOwnedByTeam { team_id: i64 },
+    #[error("That asset is not part of the {end_of_season_year} season.")]
+    WrongSeason { end_of_season_year: i16 },
+    #[error("That is already on the trade block.")]
+    AlreadyListed,
+    #[error("Trade block listing {trade_block_id} belongs to another team.")]
+    NotYourListing { trade_block_id: i64 },
+}
+
+/// What a standing listing offers. A contract is always the latest, active row in its chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TradeBlockAsset {
+    Contract(contract::Model),
+    DraftPick(draft_pick::Model),
+}
+
+/// A listing that still stands, with its asset resolved. `asset` is `None` for a need.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TradeBlockListing {
+    pub listing: trade_block::Model,
+    pub asset: Option<TradeBlockAsset>,
+}
+
+#[instrument(skip(db))]
+pub async fn list_contract_on_trade_block<C>(
+    team_id: i64,
+    contract_id: i64,
+    note: Option<String>,
+    db: &C,
+) -> Result<trade_block::Model>
+where
+    C: ConnectionTrait,
+{
+    let contract_model = contract_queries::find_contract_by_id(contract_id, db)
+        .await?
+        .get_latest_in_chain(db)
+        .await?;
+    if contract_model.team_id != Some(team_id) || contract_model.status != ContractStatus::Active {
+        return Err(TradeBlockRejection::NotOwnedByTeam { team_id }.into());
+    }
+
+    let standing_listings = find_standing_trade_block_listings(
+        contract_model.league_id,
+        contract_model.end_of_season_year,
+        db,
+    )
+    .await?;
+    let already_listed = standing_listings.iter().any(|standing| {
+        matches!(
+            &standing.asset,
+            Some(TradeBlockAsset::Contract(listed_contract))
+                if listed_contract.id == contract_model.id
+        )
+    });
+    if already_listed {
+        return Err(TradeBlockRejection::AlreadyListed.into());
+    }
+
+    trade_block_queries::insert_trade_block_listing(
+        NewTradeBlockListing {
+            league_id: contract_model.league_id,
+            team_id,
+            end_of_season_year: contract_model.end_of_season_year,
+            kind: TradeBlockKind::Contract,
+            contract_id: Some(contract_model.id),
+            draft_pick_id: None,
+            need: None,
+            note,
+        },
+        db,
+    )
+    .await
+}
+
+/// Lists a pick for the given season's block. The pick itself may be for a later draft.
+#[instrument(skip(db))]
+pub async fn list_draft_pick_on_trade_block<C>(
+    team_id: i64,
+    end_of_season_year: i16,
+    draft_pick_id: i64,
+    note: Option<String>,
+    db: &C,
+) -> Result<trade_block::Model>
+where
+    C: ConnectionTrait,
+{
+    let draft_pick_model = draft_pick_queries::find_draft_pick_by_id(draft_pick_id, db).await?;
+    if draft_pick_model.current_owner_team_id != team_id {
+        return Err(TradeBlockRejection::NotOwnedByTeam { team_id }.into());
+    }
+    if draft_pick_model.end_of_season_year < end_of_season_year {
+        return Err(TradeBlockRejection::WrongSeason { end_of_season_year }.into());
+    }
+
+    let standing_listings =
+        find_standing_trade_block_listings(draft_pick_model.league_id, end_of_season_year, db)
+            .await?;
+    let already_listed = standing_listings.iter().any(|standing| {
+        matches!(
+            &standing.asset,
+            Some(TradeBlockAsset::DraftPick(listed_pick)) if listed_pick.id == draft_pick_model.id
+        )
+    });
+    if already_listed {
+        return Err(TradeBlockRejection::AlreadyListed.into());
+    }
+
+    trade_block_queries::insert_trade_block_listing(
+        NewTradeBlockListing {
+            league_id: draft_pick_model.league_id,
+            team_id,
+            end_of_season_year,
+            kind: TradeBlockKind::DraftPick,
+            contract_id: None,
+            draft_pick_id: Some(draft_pick_model.id),
+            need: None,
+            note,
+        },
+        db,
+    )
+    .await
+}
+
+#[instrument(skip(db))]
+pub async fn list_trade_block_need<C>(
+    league_id: i64,
+    team_id: i64,
+    end_of_season_year: i16,
+    need: TradeBlockNeed,
+    note: Option<String>,
+    db: &C,
+) -> Result<trade_block::Model>
+where
+    C: ConnectionTrait,
+{
+    let standing_listings =
+        find_standing_trade_block_listings(league_id, end_of_season_year, db).await?;
+    let already_listed = standing_listings
+        .iter()
+        .any(|standing| standing.listing.team_id == team_id && standing.listing.need == Some(need));
+    if already_listed {
+        return Err(TradeBlockRejection::AlreadyListed.into());
+    }
+
+    trade_block_queries::insert_trade_block_listing(
+        NewTradeBlockListing {
+            league_id,
+            team_id,
+            end_of_season_year,
+            kind: TradeBlockKind::Need,
+            contract_id: None,
+            draft_pick_id: None,
+            need: Some(need),
+            note,
+        },
+        db,
+    )
+    .await
+}
+
+#[instrument(skip(db))]
+pub async fn remove_trade_block_listing<C>(team_id: i64, trade_block_id: i64, db: &C) -> Result<()>
+where
+    C: ConnectionTrait,
+{
+    let listing_model =
+        trade_block_queries::find_trade_block_listing_by_id(trade_block_id, db).await?;
+    if listing_model.team_id != team_id {
+        return Err(TradeBlockRejection::NotYourListing { trade_block_id }.into());
+    }
+
+    trade_block_queries::delete_trade_block_listing(listing_model, db).await
+}
+
+/// Every listing in the league's season that still stands, oldest first.
+///
+/// Contract listings are followed to the latest row in their chain and dropped once that row is no
+/// longer active on the listing team; pick listings are dropped once the pick has changed hands.
+#[instrument(skip(db))]
+pub async fn find_standing_trade_block_listings<C>(
+    league_id: i64,
+    end_of_season_year: i16,
+    db: &C,
+) -> Result<Vec<TradeBlockListing>>
+where
+    C: ConnectionTrait,
+{
+    let listings = trade_block_queries::find_trade_block_listings_for_league_season(
+        league_id,
+        end_of_season_year,
+        db,
+    )
+    .await?;
+
+    let listed_contract_ids: Vec<i64> = listings
+        .iter()
+        .filter_map(|listing| listing.contract_id)
+        .collect();
+    let original_contract_id_by_listed_contract_id: HashMap<i64, i64> =
+        contract_queries::find_contracts_by_ids(listed_contract_ids, db)
+            .await?
+            .into_iter()
+            .map(|contract_model| {
+                (
+                    contract_model.id,
+                    contract_model
+                        .original_contract_id
+                        .unwrap_or(contract_model.id),
+                )
+            })
+            .collect();
+    let active_contract_by_original_contract_id: HashMap<i64, contract::Model> =
+        contract_queries::find_active_contracts_in_league_for_season(
+            league_id,
+            end_of_season_year,
+            db,
+        )
+        .await?
+        .into_iter()
+        .map(|contract_model| {
+            (
+                contract_model
+                    .original_contract_id
+                    .unwrap_or(contract_model.id),
+                contract_model,
+            )
+        })
+        .collect();
+
+    let listed_draft_pick_ids: HashSet<i64> = listings
+        .iter()
+        .filter_map(|listing| listing.draft_pick_id)
+        .collect();
+    let mut draft_pick_by_id: HashMap<i64, draft_pick::Model> = HashMap::new();
+    for draft_pick_id in listed_draft_pick_ids {
+        let draft_pick_model = draft_pick_queries::find_draft_pick_by_id(draft_pick_id, db).await?;
+        draft_pick_by_id.insert(draft_pick_id, draft_pick_model);
+    }
+
+    let standing_listings = listings
+        .into_iter()
+        .filter_map(|listing| {
+            let asset = match listing.kind {
+                TradeBlockKind::Contract => {
+                    let contract_model = listing
+                        .contract_id
+                        .and_then(|contract_id| {
+                            original_contract_id_by_listed_contract_id.get(&contract_id)
+                        })
+                        .and_then(|original_contract_id| {
+                            active_contract_by_original_contract_id.get(original_contract_id)
+                        })
+                        .filter(|contract_model| contract_model.team_id == Some(listing.team_id))?;
+                    Some(TradeBlockAsset::Contract(contract_model.clone()))
+                }
+                TradeBlockKind::DraftPick => {
+                    let draft_pick_model = listing
+                        .draft_pick_id
+                        .and_then(|draft_pick_id| draft_pick_by_id.get(&draft_pick_id))
+                        .filter(|draft_pick_model| {
+                            draft_pick_model.current_owner_team_id == listing.team_id
+                        })?;
+                    Some(TradeBlockAsset::DraftPick(draft_pick_model.clone()))
+                }
+                TradeBlockKind::Need => None,
+            };
+
+            Some(TradeBlockListing { listing, asset })
+        })
+        .collect();
+
+    Ok(standing_listings)
+}
@@ -0,0 +1,186 @@
+//! Each team's view of a multi-team trade: what it sends, what it receives, and whether it has
+//! responded yet.
+
+use std::{collections::HashMap, fmt::Debug};
+
+use color_eyre::Result;
+use fbkl_entity::{
+    sea_orm::ConnectionTrait,
+    team_queries, trade,
+    trade_action::{self, TradeActionType},
+    trade_asset,
+};
+use tracing::instrument;
+
+/// One team's side of a trade.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TradeTeamSide {
+    pub team_id: i64,
+    /// Whether this team proposed the trade.
+    pub is_proposer: bool,
+    pub sends: Vec<trade_asset::Model>,
+    pub receives: Vec<trade_asset::Model>,
+    /// The team's most recent action on this trade; `None` while it has yet to respond.
+    pub latest_action: Option<TradeActionType>,
+}
+
+impl TradeTeamSide {
+    pub const fn is_awaiting(&self) -> bool {
+        self.latest_action.is_none()
+    }
+}
+
+/// Every involved team's side of the trade, ordered by team id.
+#[instrument(skip(db))]
+pub async fn find_trade_team_sides<C>(
+    trade_model: &trade::Model,
+    db: &C,
+) -> Result<Vec<TradeTeamSide>>
+where
+    C: ConnectionTrait,
+{
+    let team_ids: Vec<i64> = trade_model
+        .get_teams(db)
+        .await?
+        .into_iter()
+        .map(|team_model| team_model.id)
+        .collect();
+    let trade_assets = trade_model.get_trade_assets(db).await?;
+    let trade_actions = trade_model.get_trade_actions(db).await?;
+    let teams_by_trade_action_id =
+        team_queries::find_teams_by_trade_actions(&trade_actions, db).await?;
+    let trade_actions_with_team_ids: Vec<(trade_action::Model, i64)> = trade_actions
+        .into_iter()
+        .filter_map(|trade_action_model| {
+            let team_id = teams_by_trade_action_id.get(&trade_action_model.id)?.id;
+            Some((trade_action_model, team_id))
+        })
+        .collect();
+
+    Ok(build_trade_team_sides(
+        &team_ids,
+        &trade_assets,
+        &trade_actions_with_team_ids,
+    ))
+}
+
+fn build_trade_team_sides(
+    team_ids: &[i64],
+    trade_assets: &[trade_asset::Model],
+    trade_actions_with_team_ids: &[(trade_action::Model, i64)],
+) -> Vec<TradeTeamSide> {
+    let mut latest_action_by_team_id: HashMap<i64, &trade_action::Model> = HashMap::new();
+    for (trade_action_model, team_id) in trade_actions_with_team_ids {
+        let is_later = latest_action_by_team_id
+            .get(team_id)
+            .is_none_or(|latest| latest.id < trade_action_model.id);
+        if is_later {
+            latest_action_by_team_id.insert(*team_id, trade_action_model);
+        }
+    }
+
+    let mut sorted_team_ids = team_ids.to_vec();
+    sorted_team_ids.sort_unstable();
+    sorted_team_ids.dedup();
+
+    sorted_team_ids
+        .into_iter()
+        .map(|team_id| TradeTeamSide {
+            team_id,
+            is_proposer: trade_actions_with_team_ids.iter().any(
+                |(trade_action_model, action_team_id)| {
+                    *action_team_id == team_id
+                        && trade_action_model.action_type == TradeActionType::Propose
+                },
+            ),
+            sends: trade_assets
+                .iter()
+                .filter(|trade_asset_model| trade_asset_model.from_team_id == team_id)
+                .cloned()
+                .collect(),
+            receives: trade_assets
+                .iter()
+                .filter(|trade_asset_model| trade_asset_model.to_team_id == team_id)
+                .cloned()
+                .collect(),
+            latest_action: latest_action_by_team_id
+                .get(&team_id)
+                .map(|trade_action_model| trade_action_model.action_type),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use fbkl_entity::{
+        trade_action::{self, TradeActionType},
+        trade_asset::{self, TradeAssetType},
+    };
+
+    use super::build_trade_team_sides;
+
+    fn contract_asset(id: i64, from_team_id: i64, to_team_id: i64) -> trade_asset::Model {
+        trade_asset::Model {
+            id,
+            asset_type: TradeAssetType::Contract,
+            draft_pick_option_id: None,
+            contract_id: Some(id),
+            draft_pick_id: None,
+            from_team_id,
+            to_team_id,
+            trade_id: 1,
+        }
+    }
+
+    fn action(id: i64, action_type: TradeActionType) -> trade_action::Model {
+        trade_action::Model {
+            id,
+            action_type,
+            team_user_id: id,
+            trade_id: 1,
+            created_at: chrono::Utc::now().into(),
+            updated_at: chrono::Utc::now().into(),
+        }
+    }
+
+    #[test]
+    fn each_team_sees_what_it_sends_and_receives() {
+        let assets = [
+            contract_asset(10, 1, 2),
+            contract_asset(11, 2, 3),
+            contract_asset(12, 3, 1),
+        ];
+        let actions = [(action(1, TradeActionType::Propose), 1)];
+
+        let sides = build_trade_team_sides(&[3, 1, 2], &assets, &actions);
+
+        assert_eq!(
+            sides.iter().map(|side| side.team_id).collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        assert_eq!(sides[0].sends[0].id, 10);
+        assert_eq!(sides[0].receives[0].id, 12);
+        assert!(sides[0].is_proposer);
+        assert!(!sides[1].is_proposer);
+    }
+
+    #[test]
+    fn teams_without_an_action_are_awaiting() {
+        let actions = [
+            (action(1, TradeActionType::Propose), 1),
+            (action(2, TradeActionType::Reject), 2),
+        ];
+
+        let sides = build_trade_team_sides(&[1, 2, 3], &[], &actions);
+
+        assert_eq!(sides[1].latest_action, Some(TradeActionType::Reject));
+        assert_eq!(
+            sides
+                .iter()
+                .filter(|side| side.is_awaiting())
+                .map(|side| side.team_id)
+                .collect::<Vec<_>>(),
+            vec![3]
+        );
+    }
+}
@@ -0,0 +1,13 @@
+//! The week's roster moves (§13): adds, drops, IR moves and activations made before the upcoming
+//! weekly roster lock, which the owner may reorder and which only have to leave a legal roster by
+//! that lock, short of dropping one same-week auction win for another (§8.3.7).
+
+mod reorder_weekly_moves;
+mod same_week_auction_wins;
+mod team_week;
+mod weekly_move_deadline;
+
+pub use reorder_weekly_moves::*;
+pub use same_week_auction_wins::*;
+pub use team_week::*;
+pub use weekly_move_deadline::*;
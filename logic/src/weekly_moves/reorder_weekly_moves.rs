@@ -0,0 +1,93 @@
+use std::{collections::HashSet, fmt::Debug};
+
+use color_eyre::Result;
+use fbkl_entity::{sea_orm::ConnectionTrait, team_update, team_update_queries};
+use tracing::instrument;
+
+use super::order_weekly_moves;
+
+/// Why a reorder was refused.
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum WeeklyMoveRejection {
+    #[error("The new order must list each of this week's pending moves exactly once.")]
+    NotThisWeeksMoves,
+}
+
+/// Sets the owner's order for a team's pending moves at a weekly roster lock.
+///
+/// `ordered_team_update_ids` must list every pending move exactly once. The lock commits the moves
+/// in this order (see [`commit_weekly_moves`](super::commit_weekly_moves)); it never changes
+/// whether the roster is legal at the lock, which is judged on the final roster.
+#[instrument(skip(db))]
+pub async fn reorder_weekly_moves<C>(
+    team_id: i64,
+    lock_deadline_id: i64,
+    ordered_team_update_ids: &[i64],
+    db: &C,
+) -> Result<Vec<team_update::Model>>
+where
+    C: ConnectionTrait,
+{
+    let pending_moves = team_update_queries::find_pending_team_updates_for_team_and_deadline(
+        team_id,
+        lock_deadline_id,
+        db,
+    )
+    .await?;
+    let pending_move_ids: Vec<i64> = pending_moves
+        .iter()
+        .map(|team_update_model| team_update_model.id)
+        .collect();
+    validate_weekly_move_order(&pending_move_ids, ordered_team_update_ids)?;
+
+    let mut reordered_moves = Vec::with_capacity(pending_moves.len());
+    for team_update_model in pending_moves {
+        let position = ordered_team_update_ids
+            .iter()
+            .position(|id| *id == team_update_model.id)
+            .unwrap_or_default();
+        let sequence = i16::try_from(position + 1)?;
+        reordered_moves.push(
+            team_update_queries::update_team_update_sequence(team_update_model, sequence, db)
+                .await?,
+        );
+    }
+    order_weekly_moves(&mut reordered_moves);
+
+    Ok(reordered_moves)
+}
+
+fn validate_weekly_move_order(
+    pending_move_ids: &[i64],
+    ordered_team_update_ids: &[i64],
+) -> Result<(), WeeklyMoveRejection> {
+    let pending: HashSet<i64> = pending_move_ids.iter().copied().collect();
+    let ordered: HashSet<i64> = ordered_team_update_ids.iter().copied().collect();
+    if ordered.len() != ordered_team_update_ids.len() || ordered != pending {
+        return Err(WeeklyMoveRejection::NotThisWeeksMoves);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{WeeklyMoveRejection, validate_weekly_move_order};
+
+    #[test]
+    fn order_must_be_a_permutation_of_the_pending_moves() {
+        assert_eq!(validate_weekly_move_order(&[1, 2, 3], &[3, 1, 2]), Ok(()));
+        assert_eq!(
+            validate_weekly_move_order(&[1, 2, 3], &[3, 1]),
+            Err(WeeklyMoveRejection::NotThisWeeksMoves)
+        );
+        assert_eq!(
+            validate_weekly_move_order(&[1, 2], &[1, 2, 2]),
+            Err(WeeklyMoveRejection::NotThisWeeksMoves)
+        );
+        assert_eq!(
+            validate_weekly_move_order(&[1, 2], &[1, 9]),
+            Err(WeeklyMoveRejection::NotThisWeeksMoves)
+        );
+    }
+}
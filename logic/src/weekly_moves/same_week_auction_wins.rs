@@ -0,0 +1,420 @@
+//! Rules §8.3.7: a player won at auction may not be dropped to make room for another player won
+//! the same week until both have been legally added.
+//!
+//! A week runs from the previous weekly roster lock (or the final preseason lock) to the upcoming
+//! one, and is read from the team's `team_update` rows by `effective_date`: auction wins carry
+//! their auction's deadline date, drops the lock's. The same check runs before a drop and again
+//! at the lock, which also catches drops made before this rule was enforced.
+
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use color_eyre::eyre::Result;
+use fbkl_constants::league_rules::REGULAR_SEASON_VET_OR_ROOKIE_CONTRACTS_PER_ROSTER_LIMIT;
+use fbkl_entity::{
+    contract, contract_queries,
+    deadline::{self, DeadlineKind},
+    deadline_queries,
+    sea_orm::ConnectionTrait,
+    team_update::{
+        self, ContractUpdate, ContractUpdateType, TeamUpdateAsset, TeamUpdateData, TeamUpdateStatus,
+    },
+    team_update_queries,
+};
+use tracing::instrument;
+
+use crate::roster::RosterCounts;
+
+/// A drop that breaks rules §8.3.7.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error(
+    "{dropped_player} was won at auction this week and cannot be dropped to make room for {other_player}, also won this week, until both are legally on the roster (rules §8.3.7)."
+)]
+pub struct SameWeekDropRejection {
+    /// The contract dropped, or about to be.
+    pub contract_id: i64,
+    pub dropped_player: String,
+    pub other_player: String,
+}
+
+/// A player the team won at auction during the week.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SameWeekWin {
+    team_update_id: i64,
+    /// Identifies the contract across its later rows (IR moves and the like).
+    chain_id: i64,
+    player_name: String,
+}
+
+/// Rejects dropping `contract_model` if it was won at auction this week and the drop makes room
+/// for another player won this week. `team_contracts` is the roster before the drop.
+#[instrument(skip(team_contracts, db))]
+pub async fn validate_same_week_drop<C>(
+    contract_model: &contract::Model,
+    team_contracts: &[contract::Model],
+    lock_deadline: &deadline::Model,
+    db: &C,
+) -> Result<()>
+where
+    C: ConnectionTrait,
+{
+    let Some(team_id) = contract_model.team_id else {
+        return Ok(());
+    };
+    if contract_model.is_ir {
+        return Ok(());
+    }
+    let Some(updates) = find_week_team_updates(team_id, lock_deadline, db).await? else {
+        return Ok(());
+    };
+    let wins = same_week_wins(&updates, db).await?;
+
+    match same_week_drop_conflict(
+        chain_id(contract_model),
+        &wins,
+        RosterCounts::from_contracts(team_contracts).vet_or_rookie,
+    ) {
+        Some((dropped, other)) => Err(SameWeekDropRejection {
+            contract_id: contract_model.id,
+            dropped_player: dropped.player_name.clone(),
+            other_player: other.player_name.clone(),
+        }
+        .into()),
+        None => Ok(()),
+    }
+}
+
+/// Every drop in the week ending at `lock_deadline` that broke rules §8.3.7. Empty unless the
+/// deadline is a weekly roster lock.
+#[instrument(skip(db))]
+pub async fn find_same_week_drop_violations<C>(
+    team_id: i64,
+    lock_deadline: &deadline::Model,
+    db: &C,
+) -> Result<Vec<SameWeekDropRejection>>
+where
+    C: ConnectionTrait,
+{
+    let Some(updates) = find_week_team_updates(team_id, lock_deadline, db).await? else {
+        return Ok(vec![]);
+    };
+    let wins = same_week_wins(&updates, db).await?;
+    if wins.len() < 2 {
+        return Ok(vec![]);
+    }
+
+    let mut drops: Vec<(&team_update::Model, &[i64], &ContractUpdate)> = vec![];
+    for (team_update_model, data) in &updates {
+        let TeamUpdateData::Assets(summary) = data else {
+            continue;
+        };
+        for contract_update in contract_updates(data) {
+            if contract_update.update_type == ContractUpdateType::Drop {
+                drops.push((
+                    team_update_model,
+                    &summary.all_contract_ids,
+                    contract_update,
+                ));
+            }
+        }
+    }
+    let contracts_by_id: HashMap<i64, contract::Model> = contract_queries::find_contracts_by_ids(
+        drops
+            .iter()
+            .flat_map(|(_, held_contract_ids, drop_update)| {
+                held_contract_ids
+                    .iter()
+                    .copied()
+                    .chain(std::iter::once(drop_update.contract_id))
+            })
+            .collect(),
+        db,
+    )
+    .await?
+    .into_iter()
+    .map(|contract_model| (contract_model.id, contract_model))
+    .collect();
+
+    let mut violations = vec![];
+    for (team_update_model, held_contract_ids, drop_update) in drops {
+        let Some(dropped_contract) = contracts_by_id.get(&drop_update.contract_id) else {
+            continue;
+        };
+        if dropped_contract.is_ir {
+            continue;
+        }
+        let earlier_wins: Vec<SameWeekWin> = wins
+            .iter()
+            .filter(|win| win.team_update_id < team_update_model.id)
+            .cloned()
+            .collect();
+        // The team update records the roster after the drop; the dropped contract was one more.
+        let vet_or_rookie_before_drop = RosterCounts::from_contracts(
+            held_contract_ids
+                .iter()
+                .filter_map(|contract_id| contracts_by_id.get(contract_id)),
+        )
+        .vet_or_rookie
+            + 1;
+        if let Some((dropped, other)) = same_week_drop_conflict(
+            chain_id(dropped_contract),
+            &earlier_wins,
+            vet_or_rookie_before_drop,
+        ) {
+            violations.push(SameWeekDropRejection {
+                contract_id: dropped_contract.id,
+                dropped_player: dropped.player_name.clone(),
+                other_player: other.player_name.clone(),
+            });
+        }
+    }
+
+    Ok(violations)
+}
+
+/// The team's current contracts that were won at auction this week alongside another win, so a
+/// drop of any of them may be one §8.3.7 forbids. Lets automatic legalization steer clear of them.
+#[instrument(skip(team_contracts, db))]
+pub async fn find_same_week_auction_win_contract_ids<C>(
+    team_id: i64,
+    team_contracts: &[contract::Model],
+    lock_deadline: &deadline::Model,
+    db: &C,
+) -> Result<Vec<i64>>
+where
+    C: ConnectionTrait,
+{
+    let Some(updates) = find_week_team_updates(team_id, lock_deadline, db).await? else {
+        return Ok(vec![]);
+    };
+    let wins = same_week_wins(&updates, db).await?;
+    if wins.len() < 2 {
+        return Ok(vec![]);
+    }
+
+    Ok(team_contracts
+        .iter()
+        .filter(|contract_model| {
+            wins.iter()
+                .any(|win| win.chain_id == chain_id(contract_model))
+        })
+        .map(|contract_model| contract_model.id)
+        .collect())
+}
+
+/// The team's updates effective within the week ending at `lock_deadline`, oldest first. `None`
+/// when `lock_deadline` is not a weekly roster lock.
+async fn find_week_team_updates<C>(
+    team_id: i64,
+    lock_deadline: &deadline::Model,
+    db: &C,
+) -> Result<Option<Vec<(team_update::Model, TeamUpdateData)>>>
+where
+    C: ConnectionTrait,
+{
+    let season_deadlines = deadline_queries::find_sorted_deadlines_for_league_season(
+        lock_deadline.league_id,
+        lock_deadline.end_of_season_year,
+        db,
+    )
+    .await?;
+    let Some(week_start) = week_start_date(&season_deadlines, lock_deadline) else {
+        return Ok(None);
+    };
+    let week_end = lock_deadline.date_time.date_naive();
+
+    let mut updates = vec![];
+    for team_update_model in
+        team_update_queries::find_team_updates_by_team(team_id, None, db).await?
+    {
+        if team_update_model.status == TeamUpdateStatus::Error
+            || team_update_model.effective_date <= week_start
+            || team_update_model.effective_date > week_end
+        {
+            continue;
+        }
+        let data = team_update_model.get_data()?;
+        updates.push((team_update_model, data));
+    }
+    updates.sort_by_key(|(team_update_model, _)| team_update_model.id);
+
+    Ok(Some(updates))
+}
+
+async fn same_week_wins<C>(
+    updates: &[(team_update::Model, TeamUpdateData)],
+    db: &C,
+) -> Result<Vec<SameWeekWin>>
+where
+    C: ConnectionTrait,
+{
+    let won: Vec<(i64, &ContractUpdate)> = updates
+        .iter()
+        .flat_map(|(team_update_model, data)| {
+            contract_updates(data)
+                .filter(|contract_update| {
+                    contract_update.update_type == ContractUpdateType::AddViaAuction
+                })
+                .map(|contract_update| (team_update_model.id, contract_update))
+        })
+        .collect();
+    let won_contracts: HashMap<i64, contract::Model> = contract_queries::find_contracts_by_ids(
+        won.iter()
+            .map(|(_, contract_update)| contract_update.contract_id)
+            .collect(),
+        db,
+    )
+    .await?
+    .into_iter()
+    .map(|contract_model| (contract_model.id, contract_model))
+    .collect();
+
+    Ok(won
+        .into_iter()
+        .filter_map(|(team_update_id, contract_update)| {
+            won_contracts
+                .get(&contract_update.contract_id)
+                .map(|contract_model| SameWeekWin {
+                    team_update_id,
+                    chain_id: chain_id(contract_model),
+                    player_name: contract_update.player_name_at_time.clone(),
+                })
+        })
+        .collect())
+}
+
+fn contract_updates(data: &TeamUpdateData) -> impl Iterator<Item = &ContractUpdate> {
+    let assets = match data {
+        TeamUpdateData::Assets(summary) => summary.changed_assets.as_slice(),
+        TeamUpdateData::Settings(_) => &[],
+    };
+    assets
+        .iter()
+        .filter_map(|asset| match asset {
+            TeamUpdateAsset::Contracts(contract_updates) => Some(contract_updates),
+            TeamUpdateAsset::DraftPicks(_) => None,
+        })
+        .flatten()
+}
+
+const fn chain_id(contract_model: &contract::Model) -> i64 {
+    match contract_model.original_contract_id {
+        Some(original_contract_id) => original_contract_id,
+        None => contract_model.id,
+    }
+}
+
+/// The date the week ending at `lock_deadline` starts after: the previous weekly lock, or the
+/// final preseason lock for week 1. `None` if `lock_deadline` is not a weekly roster lock in
+/// `sorted_deadlines`.
+fn week_start_date(
+    sorted_deadlines: &[deadline::Model],
+    lock_deadline: &deadline::Model,
+) -> Option<NaiveDate> {
+    if !matches!(
+        lock_deadline.kind,
+        DeadlineKind::Week1RosterLock | DeadlineKind::InSeasonRosterLock
+    ) {
+        return None;
+    }
+
+    let lock_index = sorted_deadlines
+        .iter()
+        .position(|deadline_model| deadline_model.id == lock_deadline.id)?;
+    sorted_deadlines[..lock_index]
+        .iter()
+        .rev()
+        .find(|deadline_model| {
+            matches!(
+                deadline_model.kind,
+                DeadlineKind::PreseasonFinalRosterLock
+                    | DeadlineKind::Week1RosterLock
+                    | DeadlineKind::InSeasonRosterLock
+            )
+        })
+        .map(|deadline_model| deadline_model.date_time.date_naive())
+}
+
+/// The dropped win and the other win it makes room for, if the drop breaks §8.3.7: it is one of the
+/// week's wins, another player was won the same week, and the roster was over its veteran/rookie
+/// limit without the drop.
+fn same_week_drop_conflict(
+    dropped_chain_id: i64,
+    wins: &[SameWeekWin],
+    vet_or_rookie_before_drop: i16,
+) -> Option<(&SameWeekWin, &SameWeekWin)> {
+    if vet_or_rookie_before_drop <= REGULAR_SEASON_VET_OR_ROOKIE_CONTRACTS_PER_ROSTER_LIMIT {
+        return None;
+    }
+    let dropped = wins.iter().find(|win| win.chain_id == dropped_chain_id)?;
+    let other = wins.iter().find(|win| win.chain_id != dropped_chain_id)?;
+    Some((dropped, other))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Duration, NaiveDate};
+    use fbkl_entity::deadline::{self, DeadlineKind};
+
+    use super::{SameWeekWin, same_week_drop_conflict, week_start_date};
+
+    fn win(team_update_id: i64, chain_id: i64, player_name: &str) -> SameWeekWin {
+        SameWeekWin {
+            team_update_id,
+            chain_id,
+            player_name: player_name.to_owned(),
+        }
+    }
+
+    fn deadline_at(id: i64, kind: DeadlineKind, days: i64) -> deadline::Model {
+        let date_time = DateTime::parse_from_rfc3339("2026-10-19T19:00:00-05:00").unwrap()
+            + Duration::days(days);
+        deadline::Model {
+            id,
+            date_time,
+            kind,
+            name: format!("{kind:?}"),
+            end_of_season_year: 2027,
+            league_id: 1,
+            created_at: date_time,
+            updated_at: date_time,
+        }
+    }
+
+    #[test]
+    fn dropping_one_same_week_win_for_another_conflicts_only_when_over_the_limit() {
+        let wins = [win(1, 10, "Jane Doe"), win(2, 20, "John Roe")];
+
+        let (dropped, other) = same_week_drop_conflict(10, &wins, 23).unwrap();
+        assert_eq!(dropped.player_name, "Jane Doe");
+        assert_eq!(other.player_name, "John Roe");
+
+        // There was room for both, so the drop made room for nobody.
+        assert_eq!(same_week_drop_conflict(10, &wins, 22), None);
+        // Not a player won this week.
+        assert_eq!(same_week_drop_conflict(30, &wins, 23), None);
+        // No other player was won this week.
+        assert_eq!(same_week_drop_conflict(10, &wins[..1], 23), None);
+    }
+
+    #[test]
+    fn a_week_starts_at_the_previous_weekly_or_final_preseason_lock() {
+        let deadlines = [
+            deadline_at(1, DeadlineKind::PreseasonFinalRosterLock, 0),
+            deadline_at(2, DeadlineKind::Week1FreeAgentAuctionEnd, 3),
+            deadline_at(3, DeadlineKind::Week1RosterLock, 7),
+            deadline_at(4, DeadlineKind::FreeAgentAuctionEnd, 10),
+            deadline_at(5, DeadlineKind::InSeasonRosterLock, 14),
+        ];
+
+        assert_eq!(
+            week_start_date(&deadlines, &deadlines[2]),
+            NaiveDate::from_ymd_opt(2026, 10, 19)
+        );
+        assert_eq!(
+            week_start_date(&deadlines, &deadlines[4]),
+            NaiveDate::from_ymd_opt(2026, 10, 26)
+        );
+        assert_eq!(week_start_date(&deadlines, &deadlines[3]), None);
+    }
+}
@@ -0,0 +1,140 @@
+use std::fmt::Debug;
+
+use color_eyre::Result;
+use fbkl_entity::{
+    contract, contract_queries, deadline,
+    sea_orm::ConnectionTrait,
+    team_update::{self, TeamUpdateStatus},
+    team_update_queries,
+};
+use tracing::instrument;
+
+use crate::deadline_processing::{RosterViolation, find_roster_violations};
+
+/// One team's week: its pending moves and where they leave the roster at the lock.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TeamWeek {
+    /// The weekly roster lock that commits these moves.
+    pub lock_deadline: deadline::Model,
+    /// `Pending` moves in owner order (see [`order_weekly_moves`]).
+    pub moves: Vec<team_update::Model>,
+    /// The roster once every move is applied. Moves change contracts as they are made, so this is
+    /// the team's active contracts.
+    pub contracts: Vec<contract::Model>,
+    /// Every rule the roster would break if the lock ran now. Empty means legal at lock; anything
+    /// else is allowed mid-week (§13.1.3) but must be fixed before the lock.
+    pub violations: Vec<RosterViolation>,
+}
+
+impl TeamWeek {
+    pub fn is_legal_at_lock(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+#[instrument(skip(db))]
+pub async fn find_team_week<C>(
+    team_id: i64,
+    lock_deadline: &deadline::Model,
+    db: &C,
+) -> Result<TeamWeek>
+where
+    C: ConnectionTrait,
+{
+    let mut moves = team_update_queries::find_pending_team_updates_for_team_and_deadline(
+        team_id,
+        lock_deadline.id,
+        db,
+    )
+    .await?;
+    order_weekly_moves(&mut moves);
+
+    let contracts = contract_queries::find_active_contracts_for_team(team_id, db).await?;
+    let violations = find_roster_violations(team_id, &contracts, lock_deadline, db).await?;
+
+    Ok(TeamWeek {
+        lock_deadline: lock_deadline.clone(),
+        moves,
+        contracts,
+        violations,
+    })
+}
+
+/// Commits a team's queued moves (`Done`) one at a time in owner order, so the moves are applied,
+/// and logged, in the sequence the owner set. Returns the committed moves in that order.
+#[instrument(skip(db))]
+pub async fn commit_weekly_moves<C>(
+    mut moves: Vec<team_update::Model>,
+    db: &C,
+) -> Result<Vec<team_update::Model>>
+where
+    C: ConnectionTrait,
+{
+    order_weekly_moves(&mut moves);
+    let mut committed_moves = Vec::with_capacity(moves.len());
+    for team_update_model in moves {
+        committed_moves.push(
+            team_update_queries::update_team_update_status(
+                team_update_model,
+                TeamUpdateStatus::Done,
+                db,
+            )
+            .await?,
+        );
+    }
+
+    Ok(committed_moves)
+}
+
+/// Owner-sequenced moves first, in sequence order; the rest follow in the order they were made.
+pub fn order_weekly_moves(moves: &mut [team_update::Model]) {
+    moves.sort_by_key(|team_update_model| {
+        (
+            team_update_model.sequence.is_none(),
+            team_update_model.sequence,
+            team_update_model.id,
+        )
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, Utc};
+    use fbkl_entity::{
+        sea_orm::prelude::Json,
+        team_update::{self, TeamUpdateStatus},
+    };
+
+    use super::order_weekly_moves;
+
+    fn pending_move(id: i64, sequence: Option<i16>) -> team_update::Model {
+        team_update::Model {
+            id,
+            data: Json::Null,
+            effective_date: NaiveDate::from_ymd_opt(2026, 10, 26).unwrap(),
+            status: TeamUpdateStatus::Pending,
+            team_id: 1,
+            transaction_id: Some(id),
+            sequence,
+            created_at: Utc::now().into(),
+            updated_at: Utc::now().into(),
+        }
+    }
+
+    #[test]
+    fn sequenced_moves_come_first_then_the_rest_by_id() {
+        let mut moves = vec![
+            pending_move(1, None),
+            pending_move(2, Some(2)),
+            pending_move(3, None),
+            pending_move(4, Some(1)),
+        ];
+
+        order_weekly_moves(&mut moves);
+
+        assert_eq!(
+            moves.iter().map(|m| m.id).collect::<Vec<_>>(),
+            vec![4, 2, 1, 3]
+        );
+    }
+}
@@ -0,0 +1,128 @@
+use color_eyre::Result;
+use fbkl_entity::{
+    deadline::{self, DeadlineKind},
+    deadline_queries,
+    sea_orm::{ConnectionTrait, prelude::DateTimeWithTimeZone},
+};
+use tracing::instrument;
+
+/// Deadlines after which the season's moves belong to a weekly roster lock.
+static IN_SEASON_DEADLINE_KINDS: [DeadlineKind; 7] = [
+    DeadlineKind::PreseasonFinalRosterLock,
+    DeadlineKind::Week1FreeAgentAuctionStart,
+    DeadlineKind::Week1FreeAgentAuctionEnd,
+    DeadlineKind::Week1RosterLock,
+    DeadlineKind::InSeasonRosterLock,
+    DeadlineKind::FreeAgentAuctionEnd,
+    DeadlineKind::TradeDeadlineAndPlayoffStart,
+];
+
+/// The deadline a roster move made at `datetime` is recorded against.
+///
+/// In season that is the upcoming `Week1RosterLock` / `InSeasonRosterLock`, so the move joins
+/// that week's queue and is committed by that lock. Outside the season, or once the last lock has
+/// passed, it is the most recent deadline, as before.
+#[instrument(skip(db))]
+pub async fn find_weekly_move_deadline<C>(
+    league_id: i64,
+    datetime: DateTimeWithTimeZone,
+    db: &C,
+) -> Result<deadline::Model>
+where
+    C: ConnectionTrait,
+{
+    let most_recent_deadline =
+        deadline_queries::find_most_recent_deadline_by_datetime(league_id, datetime, db).await?;
+    if !IN_SEASON_DEADLINE_KINDS.contains(&most_recent_deadline.kind) {
+        return Ok(most_recent_deadline);
+    }
+
+    let season_deadlines = deadline_queries::find_sorted_deadlines_for_league_season(
+        league_id,
+        most_recent_deadline.end_of_season_year,
+        db,
+    )
+    .await?;
+
+    Ok(next_weekly_roster_lock(&season_deadlines, datetime)
+        .cloned()
+        .unwrap_or(most_recent_deadline))
+}
+
+/// The upcoming weekly roster lock, if the season still has one.
+#[instrument(skip(db))]
+pub async fn find_upcoming_weekly_roster_lock<C>(
+    league_id: i64,
+    end_of_season_year: i16,
+    datetime: DateTimeWithTimeZone,
+    db: &C,
+) -> Result<Option<deadline::Model>>
+where
+    C: ConnectionTrait,
+{
+    let season_deadlines = deadline_queries::find_sorted_deadlines_for_league_season(
+        league_id,
+        end_of_season_year,
+        db,
+    )
+    .await?;
+
+    Ok(next_weekly_roster_lock(&season_deadlines, datetime).cloned())
+}
+
+/// First weekly roster lock strictly after `datetime` in deadlines sorted by date.
+fn next_weekly_roster_lock(
+    sorted_deadlines: &[deadline::Model],
+    datetime: DateTimeWithTimeZone,
+) -> Option<&deadline::Model> {
+    sorted_deadlines.iter().find(|deadline_model| {
+        matches!(
+            deadline_model.kind,
+            DeadlineKind::Week1RosterLock | DeadlineKind::InSeasonRosterLock
+        ) && deadline_model.date_time > datetime
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Duration};
+    use fbkl_entity::deadline::{self, DeadlineKind};
+
+    use super::next_weekly_roster_lock;
+
+    fn deadline_at(id: i64, kind: DeadlineKind, days: i64) -> deadline::Model {
+        let date_time = DateTime::parse_from_rfc3339("2026-10-19T19:00:00-05:00").unwrap()
+            + Duration::days(days);
+        deadline::Model {
+            id,
+            date_time,
+            kind,
+            name: format!("{kind:?}"),
+            end_of_season_year: 2027,
+            league_id: 1,
+            created_at: date_time,
+            updated_at: date_time,
+        }
+    }
+
+    #[test]
+    fn skips_past_locks_and_other_kinds() {
+        let deadlines = [
+            deadline_at(1, DeadlineKind::Week1RosterLock, 0),
+            deadline_at(2, DeadlineKind::FreeAgentAuctionEnd, 3),
+            deadline_at(3, DeadlineKind::InSeasonRosterLock, 7),
+            deadline_at(4, DeadlineKind::InSeasonRosterLock, 14),
+        ];
+
+        let now = deadlines[0].date_time + Duration::hours(1);
+
+        assert_eq!(
+            next_weekly_roster_lock(&deadlines, now).map(|d| d.id),
+            Some(3)
+        );
+        assert_eq!(
+            next_weekly_roster_lock(&deadlines, deadlines[3].date_time),
+            None
+        );
+    }
+}
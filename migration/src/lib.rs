@@ -33,6 +33,21 @@ mod m20260731_000003_create_rookie_draft_lottery_tables;
 mod m20260815_000001_create_rfa_resolution_tables;
 mod m20260815_000002_alter_rfa_resolution_raise_deadline_nullable;
 mod m20260817_000001_require_rfa_compensation_pick;
+mod m20261019_000001_create_trade_block;
+mod m20261019_000002_add_league_trade_rejection_policy;
+mod m20261019_000003_add_team_update_sequence;
+mod m20261019_000004_add_roster_legalization;
+mod m20261019_000005_add_drop_penalty_waiver;
+mod m20261019_000006_add_player_nba_injury_status;
+mod m20261019_000007_create_replacement_draft;
+mod m20261019_000008_create_ufa_exception;
+mod m20261019_000009_create_player_name_alias;
+mod m20261019_000010_create_league_invitation;
+mod m20261019_000011_create_user_verification;
+mod m20261019_000012_create_email_outbox;
+mod m20261019_000013_create_notification_tables;
+mod m20261019_000014_create_security_event;
+mod m20261019_000015_create_api_token;
 
 pub struct Migrator;
 
@@ -66,6 +81,21 @@ impl MigratorTrait for Migrator {
             Box::new(m20260815_000001_create_rfa_resolution_tables::Migration),
             Box::new(m20260815_000002_alter_rfa_resolution_raise_deadline_nullable::Migration),
             Box::new(m20260817_000001_require_rfa_compensation_pick::Migration),
+            Box::new(m20261019_000001_create_trade_block::Migration),
+            Box::new(m20261019_000002_add_league_trade_rejection_policy::Migration),
+            Box::new(m20261019_000003_add_team_update_sequence::Migration),
+            Box::new(m20261019_000004_add_roster_legalization::Migration),
+            Box::new(m20261019_000005_add_drop_penalty_waiver::Migration),
+            Box::new(m20261019_000006_add_player_nba_injury_status::Migration),
+            Box::new(m20261019_000007_create_replacement_draft::Migration),
+            Box::new(m20261019_000008_create_ufa_exception::Migration),
+            Box::new(m20261019_000009_create_player_name_alias::Migration),
+            Box::new(m20261019_000010_create_league_invitation::Migration),
+            Box::new(m20261019_000011_create_user_verification::Migration),
+            Box::new(m20261019_000012_create_email_outbox::Migration),
+            Box::new(m20261019_000013_create_notification_tables::Migration),
+            Box::new(m20261019_000014_create_security_event::Migration),
+            Box::new(m20261019_000015_create_api_token::Migration),
         ]
     }
 }
@@ -0,0 +1,157 @@
+//! Trade block listings: the contracts and picks a team is shopping, and what it wants back.
+//!
+//! One row per listing. `kind` says which of `contract_id`, `draft_pick_id` or `need` is filled in,
+//! the same shape `trade_asset` uses for its asset columns. A listed contract points at the row that
+//! was active when it was listed; readers follow the chain, so a later IR move or extension does not
+//! orphan the listing.
+
+use sea_orm_migration::prelude::*;
+
+use crate::{
+    m20220924_004529_create_league_tables::{League, Team},
+    m20221023_002183_create_contract::Contract,
+    m20221023_002184_create_draft_pick::DraftPick,
+    set_auto_updated_at_on_table,
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TradeBlock::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(TradeBlock::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(TradeBlock::LeagueId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(TradeBlock::TeamId).big_integer().not_null())
+                    .col(
+                        ColumnDef::new(TradeBlock::EndOfSeasonYear)
+                            .small_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(TradeBlock::Kind).string().not_null())
+                    .col(ColumnDef::new(TradeBlock::ContractId).big_integer())
+                    .col(ColumnDef::new(TradeBlock::DraftPickId).big_integer())
+                    .col(ColumnDef::new(TradeBlock::Need).string())
+                    .col(ColumnDef::new(TradeBlock::Note).string())
+                    .col(
+                        ColumnDef::new(TradeBlock::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .extra("DEFAULT CURRENT_TIMESTAMP".to_string()),
+                    )
+                    .col(
+                        ColumnDef::new(TradeBlock::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .extra("DEFAULT CURRENT_TIMESTAMP".to_string()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        set_auto_updated_at_on_table(manager, TradeBlock::Table.to_string()).await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("trade_block_fk_league")
+                    .from(TradeBlock::Table, TradeBlock::LeagueId)
+                    .to(League::Table, League::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("trade_block_fk_team")
+                    .from(TradeBlock::Table, TradeBlock::TeamId)
+                    .to(Team::Table, Team::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("trade_block_fk_contract")
+                    .from(TradeBlock::Table, TradeBlock::ContractId)
+                    .to(Contract::Table, Contract::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("trade_block_fk_draft_pick")
+                    .from(TradeBlock::Table, TradeBlock::DraftPickId)
+                    .to(DraftPick::Table, DraftPick::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await?;
+
+        // The finder reads every listing in a league's season at once.
+        manager
+            .create_index(
+                IndexCreateStatement::new()
+                    .name("trade_block_league_season")
+                    .table(TradeBlock::Table)
+                    .col(TradeBlock::LeagueId)
+                    .col(TradeBlock::EndOfSeasonYear)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(TradeBlock::Table)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+/// Learn more at <https://docs.rs/sea-query#iden>
+#[derive(Iden)]
+pub enum TradeBlock {
+    Table,
+    Id,
+    LeagueId,
+    TeamId,
+    EndOfSeasonYear,
+    Kind,
+    ContractId,
+    DraftPickId,
+    Need,
+    Note,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,41 @@
+//! Adds the league's multi-team trade rejection policy to `league`.
+//!
+//! `EndsTrade` keeps the old behavior: any rejection closes the trade for everyone. Under
+//! `AllowsRepropose` a rejection in a trade of three or more teams leaves it open, so the other
+//! teams can still respond and the proposer can re-propose without the rejecting team as the next
+//! trade in the chain.
+
+use sea_orm_migration::{
+    prelude::*,
+    sea_orm::{DatabaseBackend, Statement},
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+async fn run_sql(manager: &SchemaManager<'_>, sql: &str) -> Result<(), DbErr> {
+    manager
+        .get_connection()
+        .execute_raw(Statement::from_string(DatabaseBackend::Postgres, sql))
+        .await
+        .map(|_| ())
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        run_sql(
+            manager,
+            "ALTER TABLE league ADD COLUMN trade_rejection_policy VARCHAR NOT NULL DEFAULT 'EndsTrade'",
+        )
+        .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        run_sql(
+            manager,
+            "ALTER TABLE league DROP COLUMN trade_rejection_policy",
+        )
+        .await
+    }
+}
@@ -0,0 +1,36 @@
+//! Adds an owner-assigned `sequence` to `team_update`.
+//!
+//! A week's pending moves (the `Pending` rows whose transaction points at the upcoming roster lock)
+//! are shown and reordered by this column. Legality is still judged on the team's final state at
+//! the lock, so the order is for presentation and the audit log only.
+
+use sea_orm_migration::{
+    prelude::*,
+    sea_orm::{DatabaseBackend, Statement},
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+async fn run_sql(manager: &SchemaManager<'_>, sql: &str) -> Result<(), DbErr> {
+    manager
+        .get_connection()
+        .execute_raw(Statement::from_string(DatabaseBackend::Postgres, sql))
+        .await
+        .map(|_| ())
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        run_sql(
+            manager,
+            "ALTER TABLE team_update ADD COLUMN sequence SMALLINT",
+        )
+        .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        run_sql(manager, "ALTER TABLE team_update DROP COLUMN sequence").await
+    }
+}
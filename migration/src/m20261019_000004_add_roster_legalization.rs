@@ -0,0 +1,202 @@
+//! Automatic roster legalization at weekly locks.
+//!
+//! Adds the league's `roster_legalization_policy` (`LeavePending` keeps the old behavior: an illegal
+//! team's moves wait for the commissioner) and `roster_legalization_move`, one row per move the lock
+//! made on an illegal team's behalf, so the team can be told what was done. `acknowledged_at` is set
+//! once the team has seen it.
+
+use sea_orm_migration::{
+    prelude::*,
+    sea_orm::{DatabaseBackend, Statement},
+};
+
+use crate::{
+    m20220924_004529_create_league_tables::{League, Team},
+    m20221023_002183_create_contract::Contract,
+    m20221117_235325_create_transaction::Deadline,
+    set_auto_updated_at_on_table,
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+async fn run_sql(manager: &SchemaManager<'_>, sql: &str) -> Result<(), DbErr> {
+    manager
+        .get_connection()
+        .execute_raw(Statement::from_string(DatabaseBackend::Postgres, sql))
+        .await
+        .map(|_| ())
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        run_sql(
+            manager,
+            "ALTER TABLE league ADD COLUMN roster_legalization_policy VARCHAR NOT NULL DEFAULT 'LeavePending'",
+        )
+        .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(RosterLegalizationMove::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(RosterLegalizationMove::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(RosterLegalizationMove::LeagueId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(RosterLegalizationMove::TeamId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(RosterLegalizationMove::DeadlineId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(RosterLegalizationMove::ContractId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(RosterLegalizationMove::Kind)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(RosterLegalizationMove::AcknowledgedAt)
+                            .timestamp_with_time_zone(),
+                    )
+                    .col(
+                        ColumnDef::new(RosterLegalizationMove::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .extra("DEFAULT CURRENT_TIMESTAMP".to_string()),
+                    )
+                    .col(
+                        ColumnDef::new(RosterLegalizationMove::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .extra("DEFAULT CURRENT_TIMESTAMP".to_string()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        set_auto_updated_at_on_table(manager, RosterLegalizationMove::Table.to_string()).await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("roster_legalization_move_fk_league")
+                    .from(
+                        RosterLegalizationMove::Table,
+                        RosterLegalizationMove::LeagueId,
+                    )
+                    .to(League::Table, League::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("roster_legalization_move_fk_team")
+                    .from(
+                        RosterLegalizationMove::Table,
+                        RosterLegalizationMove::TeamId,
+                    )
+                    .to(Team::Table, Team::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("roster_legalization_move_fk_deadline")
+                    .from(
+                        RosterLegalizationMove::Table,
+                        RosterLegalizationMove::DeadlineId,
+                    )
+                    .to(Deadline::Table, Deadline::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("roster_legalization_move_fk_contract")
+                    .from(
+                        RosterLegalizationMove::Table,
+                        RosterLegalizationMove::ContractId,
+                    )
+                    .to(Contract::Table, Contract::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Teams read their own moves, newest first.
+        manager
+            .create_index(
+                IndexCreateStatement::new()
+                    .name("roster_legalization_move_team")
+                    .table(RosterLegalizationMove::Table)
+                    .col(RosterLegalizationMove::TeamId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(RosterLegalizationMove::Table)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        run_sql(
+            manager,
+            "ALTER TABLE league DROP COLUMN roster_legalization_policy",
+        )
+        .await
+    }
+}
+
+/// Learn more at <https://docs.rs/sea-query#iden>
+#[derive(Iden)]
+pub enum RosterLegalizationMove {
+    Table,
+    Id,
+    LeagueId,
+    TeamId,
+    DeadlineId,
+    ContractId,
+    Kind,
+    AcknowledgedAt,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,54 @@
+//! Commissioner waiver of the dropped-contract cap penalty (rules §9.1.4, the Nick Adenhart rule).
+//!
+//! The waiver lives on the drop transaction itself, so the regular-season penalty fold can skip it
+//! without caring whether the contract is `player`- or `league_player`-backed. The waiving
+//! commissioner, the time and the stated reason are the audit trail.
+
+use sea_orm_migration::{
+    prelude::*,
+    sea_orm::{DatabaseBackend, Statement},
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+async fn run_sql(manager: &SchemaManager<'_>, sql: &str) -> Result<(), DbErr> {
+    manager
+        .get_connection()
+        .execute_raw(Statement::from_string(DatabaseBackend::Postgres, sql))
+        .await
+        .map(|_| ())
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        run_sql(
+            manager,
+            "ALTER TABLE transaction
+                ADD COLUMN drop_penalty_waived_at TIMESTAMPTZ,
+                ADD COLUMN drop_penalty_waived_by_team_user_id BIGINT,
+                ADD COLUMN drop_penalty_waiver_reason TEXT",
+        )
+        .await?;
+        run_sql(
+            manager,
+            "ALTER TABLE transaction ADD CONSTRAINT transaction_fk_drop_penalty_waived_by_team_user
+                FOREIGN KEY (drop_penalty_waived_by_team_user_id) REFERENCES team_user(id)
+                ON UPDATE CASCADE ON DELETE SET NULL",
+        )
+        .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        run_sql(
+            manager,
+            "ALTER TABLE transaction
+                DROP CONSTRAINT IF EXISTS transaction_fk_drop_penalty_waived_by_team_user,
+                DROP COLUMN IF EXISTS drop_penalty_waiver_reason,
+                DROP COLUMN IF EXISTS drop_penalty_waived_by_team_user_id,
+                DROP COLUMN IF EXISTS drop_penalty_waived_at",
+        )
+        .await
+    }
+}
@@ -0,0 +1,65 @@
+//! NBA injury status on `player` (rules §10.1, spec 12), the fact that gates the IR slot.
+//!
+//! Mirrors `nba_roster_source` / `nba_roster_asof`: the status is set by the injury-report import
+//! or a commissioner correction, and its source and as-of time say which. Only real players carry
+//! it; league-created players are never on an NBA injury report.
+
+use sea_orm_migration::{
+    prelude::*,
+    sea_orm::{DatabaseBackend, Statement},
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+async fn run_sql(manager: &SchemaManager<'_>, sql: &str) -> Result<(), DbErr> {
+    manager
+        .get_connection()
+        .execute_raw(Statement::from_string(DatabaseBackend::Postgres, sql))
+        .await
+        .map(|_| ())
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        run_sql(
+            manager,
+            "ALTER TABLE player
+                ADD COLUMN nba_injury_status VARCHAR NOT NULL DEFAULT 'Healthy',
+                ADD COLUMN nba_injury_source VARCHAR NOT NULL DEFAULT 'Unknown',
+                ADD COLUMN nba_injury_asof TIMESTAMPTZ",
+        )
+        .await?;
+        // Only NBA-IR players could be moved to IR, so a player on an active IR contract was injured
+        // when he went there. Left `Healthy`, he would count as recovered (§10.1.3) the moment this
+        // runs; the next injury-report import clears the ones who have since come off the report.
+        run_sql(
+            manager,
+            "UPDATE player
+                SET nba_injury_status = 'InjuredReserve'
+                WHERE id IN (
+                    SELECT player_id FROM contract
+                    WHERE is_ir AND status = 'Active' AND player_id IS NOT NULL
+                )",
+        )
+        .await?;
+        // The import clears players who dropped off the report by looking up everyone still on IR.
+        run_sql(
+            manager,
+            "CREATE INDEX player_nba_injury_status ON player (nba_injury_status)",
+        )
+        .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        run_sql(
+            manager,
+            "ALTER TABLE player
+                DROP COLUMN IF EXISTS nba_injury_asof,
+                DROP COLUMN IF EXISTS nba_injury_source,
+                DROP COLUMN IF EXISTS nba_injury_status",
+        )
+        .await
+    }
+}
@@ -0,0 +1,300 @@
+//! The replacement owners draft (rules §2.4.4).
+//!
+//! `replacement_draft` is one commissioner-run draft after a season, over the combined contracts and
+//! draft picks of the abandoned teams (`pool`, JSON). `replacement_draft_selection` is its ordered
+//! slate, one row per turn, resolved to the asset taken or to a pass.
+
+use sea_orm_migration::prelude::*;
+
+use crate::{
+    m20220924_004529_create_league_tables::{League, Team},
+    m20221023_002183_create_contract::Contract,
+    m20221023_002184_create_draft_pick::DraftPick,
+    m20221117_235325_create_transaction::{Deadline, Transaction},
+    set_auto_updated_at_on_table,
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ReplacementDraft::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ReplacementDraft::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ReplacementDraft::LeagueId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ReplacementDraft::EndOfSeasonYear)
+                            .small_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ReplacementDraft::DeadlineId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ReplacementDraft::Pool)
+                            .json_binary()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ReplacementDraft::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .extra("DEFAULT CURRENT_TIMESTAMP".to_string()),
+                    )
+                    .col(
+                        ColumnDef::new(ReplacementDraft::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .extra("DEFAULT CURRENT_TIMESTAMP".to_string()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        set_auto_updated_at_on_table(manager, ReplacementDraft::Table.to_string()).await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("replacement_draft_fk_league")
+                    .from(ReplacementDraft::Table, ReplacementDraft::LeagueId)
+                    .to(League::Table, League::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("replacement_draft_fk_deadline")
+                    .from(ReplacementDraft::Table, ReplacementDraft::DeadlineId)
+                    .to(Deadline::Table, Deadline::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await?;
+
+        // At most one replacement draft per league season.
+        manager
+            .create_index(
+                IndexCreateStatement::new()
+                    .name("replacement_draft_league_season")
+                    .table(ReplacementDraft::Table)
+                    .col(ReplacementDraft::LeagueId)
+                    .col(ReplacementDraft::EndOfSeasonYear)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ReplacementDraftSelection::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ReplacementDraftSelection::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ReplacementDraftSelection::ReplacementDraftId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ReplacementDraftSelection::Order)
+                            .small_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ReplacementDraftSelection::TeamId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ReplacementDraftSelection::Status)
+                            .string()
+                            .not_null()
+                            .default("Unused"),
+                    )
+                    .col(ColumnDef::new(ReplacementDraftSelection::ContractId).big_integer())
+                    .col(ColumnDef::new(ReplacementDraftSelection::DraftPickId).big_integer())
+                    .col(ColumnDef::new(ReplacementDraftSelection::TransactionId).big_integer())
+                    .col(
+                        ColumnDef::new(ReplacementDraftSelection::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .extra("DEFAULT CURRENT_TIMESTAMP".to_string()),
+                    )
+                    .col(
+                        ColumnDef::new(ReplacementDraftSelection::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .extra("DEFAULT CURRENT_TIMESTAMP".to_string()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        set_auto_updated_at_on_table(manager, ReplacementDraftSelection::Table.to_string()).await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("replacement_draft_selection_fk_replacement_draft")
+                    .from(
+                        ReplacementDraftSelection::Table,
+                        ReplacementDraftSelection::ReplacementDraftId,
+                    )
+                    .to(ReplacementDraft::Table, ReplacementDraft::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("replacement_draft_selection_fk_team")
+                    .from(
+                        ReplacementDraftSelection::Table,
+                        ReplacementDraftSelection::TeamId,
+                    )
+                    .to(Team::Table, Team::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("replacement_draft_selection_fk_contract")
+                    .from(
+                        ReplacementDraftSelection::Table,
+                        ReplacementDraftSelection::ContractId,
+                    )
+                    .to(Contract::Table, Contract::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("replacement_draft_selection_fk_draft_pick")
+                    .from(
+                        ReplacementDraftSelection::Table,
+                        ReplacementDraftSelection::DraftPickId,
+                    )
+                    .to(DraftPick::Table, DraftPick::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("replacement_draft_selection_fk_transaction")
+                    .from(
+                        ReplacementDraftSelection::Table,
+                        ReplacementDraftSelection::TransactionId,
+                    )
+                    .to(Transaction::Table, Transaction::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                IndexCreateStatement::new()
+                    .name("replacement_draft_selection_order")
+                    .table(ReplacementDraftSelection::Table)
+                    .col(ReplacementDraftSelection::ReplacementDraftId)
+                    .col(ReplacementDraftSelection::Order)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(ReplacementDraftSelection::Table)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(ReplacementDraft::Table)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+/// Learn more at <https://docs.rs/sea-query#iden>
+#[derive(Iden)]
+pub enum ReplacementDraft {
+    Table,
+    Id,
+    LeagueId,
+    EndOfSeasonYear,
+    DeadlineId,
+    Pool,
+    CreatedAt,
+    UpdatedAt,
+}
+
+/// Learn more at <https://docs.rs/sea-query#iden>
+#[derive(Iden)]
+pub enum ReplacementDraftSelection {
+    Table,
+    Id,
+    ReplacementDraftId,
+    Order,
+    TeamId,
+    Status,
+    ContractId,
+    DraftPickId,
+    TransactionId,
+    CreatedAt,
+    UpdatedAt,
+}
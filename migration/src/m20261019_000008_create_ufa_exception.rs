@@ -0,0 +1,143 @@
+//! The UFA re-sign exception snapshot (rules §16.4).
+//!
+//! `ufa_exception` records, at keeper-deadline processing, which team holds each designated UFA's
+//! discount. It is the UFA counterpart of `rfa_resolution.original_owner_team_id`: a trade between
+//! the keeper deadline and the close of the auction moves the contract, not the discount.
+
+use sea_orm_migration::prelude::*;
+
+use crate::{
+    m20220924_004529_create_league_tables::{League, Team},
+    m20221023_002183_create_contract::Contract,
+    set_auto_updated_at_on_table,
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(UfaException::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(UfaException::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(UfaException::LeagueId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(UfaException::EndOfSeasonYear)
+                            .small_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(UfaException::UfaContractId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(UfaException::ExceptionHolderTeamId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(UfaException::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .extra("DEFAULT CURRENT_TIMESTAMP".to_string()),
+                    )
+                    .col(
+                        ColumnDef::new(UfaException::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .extra("DEFAULT CURRENT_TIMESTAMP".to_string()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        set_auto_updated_at_on_table(manager, UfaException::Table.to_string()).await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("ufa_exception_fk_league")
+                    .from(UfaException::Table, UfaException::LeagueId)
+                    .to(League::Table, League::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("ufa_exception_fk_contract")
+                    .from(UfaException::Table, UfaException::UfaContractId)
+                    .to(Contract::Table, Contract::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("ufa_exception_fk_exception_holder_team")
+                    .from(UfaException::Table, UfaException::ExceptionHolderTeamId)
+                    .to(Team::Table, Team::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await?;
+
+        // One snapshot per designated contract, so re-processing the keeper deadline cannot fork it.
+        manager
+            .create_index(
+                IndexCreateStatement::new()
+                    .name("ufa_exception_ufa_contract")
+                    .table(UfaException::Table)
+                    .col(UfaException::UfaContractId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(UfaException::Table)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+/// Learn more at <https://docs.rs/sea-query#iden>
+#[derive(Iden)]
+pub enum UfaException {
+    Table,
+    Id,
+    LeagueId,
+    EndOfSeasonYear,
+    UfaContractId,
+    ExceptionHolderTeamId,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,106 @@
+//! Former names of real players.
+//!
+//! The player sync renames `player` rows in place, so every name a player has been known by is kept
+//! here. Name search and lookups of historical `ContractUpdate.player_name_at_time` values consult
+//! it alongside `player.name`.
+
+use sea_orm_migration::prelude::*;
+
+use crate::{m20220922_012310_create_real_world_tables::Player, set_auto_updated_at_on_table};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PlayerNameAlias::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PlayerNameAlias::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(PlayerNameAlias::PlayerId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(PlayerNameAlias::Name).string().not_null())
+                    .col(
+                        ColumnDef::new(PlayerNameAlias::ReplacedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(PlayerNameAlias::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .extra("DEFAULT CURRENT_TIMESTAMP".to_string()),
+                    )
+                    .col(
+                        ColumnDef::new(PlayerNameAlias::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .extra("DEFAULT CURRENT_TIMESTAMP".to_string()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        set_auto_updated_at_on_table(manager, PlayerNameAlias::Table.to_string()).await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("player_name_alias_fk_player")
+                    .from(PlayerNameAlias::Table, PlayerNameAlias::PlayerId)
+                    .to(Player::Table, Player::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await?;
+
+        // A name a player goes back to is recorded once, so re-running a sync cannot duplicate it.
+        manager
+            .create_index(
+                IndexCreateStatement::new()
+                    .name("player_name_alias_player_name")
+                    .table(PlayerNameAlias::Table)
+                    .col(PlayerNameAlias::PlayerId)
+                    .col(PlayerNameAlias::Name)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(PlayerNameAlias::Table)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+/// Learn more at <https://docs.rs/sea-query#iden>
+#[derive(Iden)]
+pub enum PlayerNameAlias {
+    Table,
+    Id,
+    PlayerId,
+    Name,
+    ReplacedAt,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,186 @@
+//! Single-use invitations to own or co-own a team in a league.
+//!
+//! The commissioner creates one per open seat; whoever redeems the token before `expires_at`
+//! becomes a `TeamOwner` `team_user` on `team_id`. Accepted and revoked rows are kept as the
+//! record of who was let in, and by whom.
+
+use sea_orm_migration::prelude::*;
+
+use crate::{
+    m20220924_004529_create_league_tables::{League, Team, TeamUser},
+    set_auto_updated_at_on_table,
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(LeagueInvitation::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(LeagueInvitation::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(LeagueInvitation::LeagueId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(LeagueInvitation::TeamId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(LeagueInvitation::Kind)
+                            .string()
+                            .not_null()
+                            .default("Owner"),
+                    )
+                    .col(ColumnDef::new(LeagueInvitation::Token).binary().not_null())
+                    .col(ColumnDef::new(LeagueInvitation::Email).string())
+                    .col(
+                        ColumnDef::new(LeagueInvitation::Status)
+                            .string()
+                            .not_null()
+                            .default("Pending"),
+                    )
+                    .col(
+                        ColumnDef::new(LeagueInvitation::FirstEndOfSeasonYear)
+                            .small_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(LeagueInvitation::ExpiresAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(LeagueInvitation::InvitedByTeamUserId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(LeagueInvitation::AcceptedTeamUserId).big_integer())
+                    .col(
+                        ColumnDef::new(LeagueInvitation::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .extra("DEFAULT CURRENT_TIMESTAMP".to_string()),
+                    )
+                    .col(
+                        ColumnDef::new(LeagueInvitation::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .extra("DEFAULT CURRENT_TIMESTAMP".to_string()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        set_auto_updated_at_on_table(manager, LeagueInvitation::Table.to_string()).await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("league_invitation_fk_league")
+                    .from(LeagueInvitation::Table, LeagueInvitation::LeagueId)
+                    .to(League::Table, League::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("league_invitation_fk_team")
+                    .from(LeagueInvitation::Table, LeagueInvitation::TeamId)
+                    .to(Team::Table, Team::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("league_invitation_fk_invited_by_team_user")
+                    .from(
+                        LeagueInvitation::Table,
+                        LeagueInvitation::InvitedByTeamUserId,
+                    )
+                    .to(TeamUser::Table, TeamUser::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("league_invitation_fk_accepted_team_user")
+                    .from(
+                        LeagueInvitation::Table,
+                        LeagueInvitation::AcceptedTeamUserId,
+                    )
+                    .to(TeamUser::Table, TeamUser::Id)
+                    .on_delete(ForeignKeyAction::SetNull)
+                    .on_update(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Tokens are looked up on redemption and must never collide.
+        manager
+            .create_index(
+                IndexCreateStatement::new()
+                    .name("league_invitation_token")
+                    .table(LeagueInvitation::Table)
+                    .col(LeagueInvitation::Token)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(LeagueInvitation::Table)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+/// Learn more at <https://docs.rs/sea-query#iden>
+#[derive(Iden)]
+pub enum LeagueInvitation {
+    Table,
+    Id,
+    LeagueId,
+    TeamId,
+    Kind,
+    Token,
+    Email,
+    Status,
+    FirstEndOfSeasonYear,
+    ExpiresAt,
+    InvitedByTeamUserId,
+    AcceptedTeamUserId,
+    CreatedAt,
+    UpdatedAt,
+}
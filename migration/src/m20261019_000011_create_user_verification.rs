@@ -0,0 +1,144 @@
+//! Password resets and email changes, and a way to end a user's other sessions.
+//!
+//! `user_verification` holds one single-use, expiring token per request. Only a SHA-256 hash of
+//! the token is stored, so a leaked table cannot be replayed. `user.session_generation` is copied
+//! into each session at login; bumping it when the password changes ends every session that
+//! carries an older value.
+
+use sea_orm_migration::{
+    prelude::*,
+    sea_orm::{DatabaseBackend, Statement},
+};
+
+use crate::{m20220916_131202_create_user_table::User, set_auto_updated_at_on_table};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+async fn run_sql(manager: &SchemaManager<'_>, sql: &str) -> Result<(), DbErr> {
+    manager
+        .get_connection()
+        .execute_raw(Statement::from_string(DatabaseBackend::Postgres, sql))
+        .await
+        .map(|_| ())
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        run_sql(
+            manager,
+            r#"ALTER TABLE "user" ADD COLUMN session_generation INTEGER NOT NULL DEFAULT 0"#,
+        )
+        .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserVerification::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(UserVerification::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(UserVerification::UserId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(UserVerification::Kind).string().not_null())
+                    .col(
+                        ColumnDef::new(UserVerification::TokenHash)
+                            .binary()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(UserVerification::NewEmail).string())
+                    .col(
+                        ColumnDef::new(UserVerification::Status)
+                            .string()
+                            .not_null()
+                            .default("Pending"),
+                    )
+                    .col(
+                        ColumnDef::new(UserVerification::ExpiresAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(UserVerification::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .extra("DEFAULT CURRENT_TIMESTAMP".to_string()),
+                    )
+                    .col(
+                        ColumnDef::new(UserVerification::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .extra("DEFAULT CURRENT_TIMESTAMP".to_string()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        set_auto_updated_at_on_table(manager, UserVerification::Table.to_string()).await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("user_verification_fk_user")
+                    .from(UserVerification::Table, UserVerification::UserId)
+                    .to(User::Table, User::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Tokens are looked up by hash on redemption and must never collide.
+        manager
+            .create_index(
+                IndexCreateStatement::new()
+                    .name("user_verification_token_hash")
+                    .table(UserVerification::Table)
+                    .col(UserVerification::TokenHash)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(UserVerification::Table)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        run_sql(
+            manager,
+            r#"ALTER TABLE "user" DROP COLUMN session_generation"#,
+        )
+        .await
+    }
+}
+
+/// Learn more at <https://docs.rs/sea-query#iden>
+#[derive(Iden)]
+pub enum UserVerification {
+    Table,
+    Id,
+    UserId,
+    Kind,
+    TokenHash,
+    NewEmail,
+    Status,
+    ExpiresAt,
+    CreatedAt,
+    UpdatedAt,
+}
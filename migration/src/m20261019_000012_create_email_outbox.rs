@@ -0,0 +1,110 @@
+//! Outbound email, written in the same transaction as whatever triggered it.
+//!
+//! The scheduler delivers `Pending` rows once `next_attempt_at` has passed. A failed send is
+//! pushed back with a growing delay until the attempts run out, at which point the row is
+//! `DeadLettered` and kept, with its last error, for someone to look at.
+
+use sea_orm_migration::prelude::*;
+
+use crate::set_auto_updated_at_on_table;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(EmailOutbox::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(EmailOutbox::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(EmailOutbox::ToAddress).string().not_null())
+                    .col(ColumnDef::new(EmailOutbox::Subject).string().not_null())
+                    .col(ColumnDef::new(EmailOutbox::BodyText).text().not_null())
+                    .col(
+                        ColumnDef::new(EmailOutbox::Status)
+                            .string()
+                            .not_null()
+                            .default("Pending"),
+                    )
+                    .col(
+                        ColumnDef::new(EmailOutbox::Attempts)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(EmailOutbox::NextAttemptAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .extra("DEFAULT CURRENT_TIMESTAMP".to_string()),
+                    )
+                    .col(ColumnDef::new(EmailOutbox::LastError).text())
+                    .col(ColumnDef::new(EmailOutbox::SentAt).timestamp_with_time_zone())
+                    .col(
+                        ColumnDef::new(EmailOutbox::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .extra("DEFAULT CURRENT_TIMESTAMP".to_string()),
+                    )
+                    .col(
+                        ColumnDef::new(EmailOutbox::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .extra("DEFAULT CURRENT_TIMESTAMP".to_string()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        set_auto_updated_at_on_table(manager, EmailOutbox::Table.to_string()).await?;
+
+        // Every scheduler tick scans for due pending rows.
+        manager
+            .create_index(
+                IndexCreateStatement::new()
+                    .name("email_outbox_status_next_attempt_at")
+                    .table(EmailOutbox::Table)
+                    .col(EmailOutbox::Status)
+                    .col(EmailOutbox::NextAttemptAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(EmailOutbox::Table)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+/// Learn more at <https://docs.rs/sea-query#iden>
+#[derive(Iden)]
+pub enum EmailOutbox {
+    Table,
+    Id,
+    ToAddress,
+    Subject,
+    BodyText,
+    Status,
+    Attempts,
+    NextAttemptAt,
+    LastError,
+    SentAt,
+    CreatedAt,
+    UpdatedAt,
+}
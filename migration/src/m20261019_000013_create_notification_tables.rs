@@ -0,0 +1,243 @@
+//! The in-app notification center and each user's choice of how to hear about things.
+//!
+//! `notification` holds one row per recipient user, written by the `logic` function that caused
+//! it, in the same transaction. `email_status` tracks whether the row also went out by email, right
+//! away or in the user's next digest. `notification_preference` is sparse: a user without a row
+//! for a kind gets the defaults.
+
+use sea_orm_migration::prelude::*;
+
+use crate::{
+    m20220916_131202_create_user_table::User, m20220924_004529_create_league_tables::League,
+    set_auto_updated_at_on_table,
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Notification::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Notification::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(Notification::UserId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Notification::LeagueId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(Notification::Kind).string().not_null())
+                    .col(ColumnDef::new(Notification::SubjectId).big_integer())
+                    .col(ColumnDef::new(Notification::Message).text().not_null())
+                    .col(
+                        ColumnDef::new(Notification::InApp)
+                            .boolean()
+                            .not_null()
+                            .default(true),
+                    )
+                    .col(
+                        ColumnDef::new(Notification::EmailStatus)
+                            .string()
+                            .not_null()
+                            .default("NotRequested"),
+                    )
+                    .col(ColumnDef::new(Notification::ReadAt).timestamp_with_time_zone())
+                    .col(
+                        ColumnDef::new(Notification::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .extra("DEFAULT CURRENT_TIMESTAMP".to_string()),
+                    )
+                    .col(
+                        ColumnDef::new(Notification::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .extra("DEFAULT CURRENT_TIMESTAMP".to_string()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        set_auto_updated_at_on_table(manager, Notification::Table.to_string()).await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("notification_fk_user")
+                    .from(Notification::Table, Notification::UserId)
+                    .to(User::Table, User::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("notification_fk_league")
+                    .from(Notification::Table, Notification::LeagueId)
+                    .to(League::Table, League::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await?;
+
+        // The notification center lists a user's rows newest first.
+        manager
+            .create_index(
+                IndexCreateStatement::new()
+                    .name("notification_user_id_created_at")
+                    .table(Notification::Table)
+                    .col(Notification::UserId)
+                    .col(Notification::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(NotificationPreference::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(NotificationPreference::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(NotificationPreference::UserId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(NotificationPreference::Kind)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(NotificationPreference::InApp)
+                            .boolean()
+                            .not_null()
+                            .default(true),
+                    )
+                    .col(
+                        ColumnDef::new(NotificationPreference::EmailDelivery)
+                            .string()
+                            .not_null()
+                            .default("Immediate"),
+                    )
+                    .col(
+                        ColumnDef::new(NotificationPreference::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .extra("DEFAULT CURRENT_TIMESTAMP".to_string()),
+                    )
+                    .col(
+                        ColumnDef::new(NotificationPreference::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .extra("DEFAULT CURRENT_TIMESTAMP".to_string()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        set_auto_updated_at_on_table(manager, NotificationPreference::Table.to_string()).await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("notification_preference_fk_user")
+                    .from(
+                        NotificationPreference::Table,
+                        NotificationPreference::UserId,
+                    )
+                    .to(User::Table, User::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await?;
+
+        // One preference per user per kind; saving a preference upserts on this.
+        manager
+            .create_index(
+                IndexCreateStatement::new()
+                    .name("notification_preference_user_id_kind")
+                    .table(NotificationPreference::Table)
+                    .col(NotificationPreference::UserId)
+                    .col(NotificationPreference::Kind)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(NotificationPreference::Table)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(Notification::Table)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+/// Learn more at <https://docs.rs/sea-query#iden>
+#[derive(Iden)]
+pub enum Notification {
+    Table,
+    Id,
+    UserId,
+    LeagueId,
+    Kind,
+    SubjectId,
+    Message,
+    InApp,
+    EmailStatus,
+    ReadAt,
+    CreatedAt,
+    UpdatedAt,
+}
+
+/// Learn more at <https://docs.rs/sea-query#iden>
+#[derive(Iden)]
+pub enum NotificationPreference {
+    Table,
+    Id,
+    UserId,
+    Kind,
+    InApp,
+    EmailDelivery,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,127 @@
+//! An append-only log of security-relevant account activity, which also backs login throttling.
+//!
+//! Every login attempt writes a row keyed by the attempted email and the client IP, whether or not
+//! an account has that email, so the per-IP and per-account limits can be counted across every
+//! server instance. Lockouts and account changes (password resets, email changes) are recorded
+//! here too, for the commissioner and app admins to review.
+
+use sea_orm_migration::prelude::*;
+
+use crate::{m20220916_131202_create_user_table::User, set_auto_updated_at_on_table};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SecurityEvent::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SecurityEvent::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(SecurityEvent::Kind).string().not_null())
+                    .col(ColumnDef::new(SecurityEvent::UserId).big_integer())
+                    .col(ColumnDef::new(SecurityEvent::Email).string())
+                    .col(ColumnDef::new(SecurityEvent::IpAddress).string())
+                    .col(ColumnDef::new(SecurityEvent::Detail).text())
+                    .col(
+                        ColumnDef::new(SecurityEvent::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .extra("DEFAULT CURRENT_TIMESTAMP".to_string()),
+                    )
+                    .col(
+                        ColumnDef::new(SecurityEvent::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .extra("DEFAULT CURRENT_TIMESTAMP".to_string()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        set_auto_updated_at_on_table(manager, SecurityEvent::Table.to_string()).await?;
+
+        // Deleting a user keeps their events, detached.
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("security_event_fk_user")
+                    .from(SecurityEvent::Table, SecurityEvent::UserId)
+                    .to(User::Table, User::Id)
+                    .on_delete(ForeignKeyAction::SetNull)
+                    .on_update(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await?;
+
+        // The per-account login limit counts recent attempts on one email.
+        manager
+            .create_index(
+                IndexCreateStatement::new()
+                    .name("security_event_email_created_at")
+                    .table(SecurityEvent::Table)
+                    .col(SecurityEvent::Email)
+                    .col(SecurityEvent::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        // The per-IP login limit counts recent attempts from one address.
+        manager
+            .create_index(
+                IndexCreateStatement::new()
+                    .name("security_event_ip_address_created_at")
+                    .table(SecurityEvent::Table)
+                    .col(SecurityEvent::IpAddress)
+                    .col(SecurityEvent::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        // The commissioner's view lists the events of their league's users.
+        manager
+            .create_index(
+                IndexCreateStatement::new()
+                    .name("security_event_user_id_created_at")
+                    .table(SecurityEvent::Table)
+                    .col(SecurityEvent::UserId)
+                    .col(SecurityEvent::CreatedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(SecurityEvent::Table)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+/// Learn more at <https://docs.rs/sea-query#iden>
+#[derive(Iden)]
+pub enum SecurityEvent {
+    Table,
+    Id,
+    Kind,
+    UserId,
+    Email,
+    IpAddress,
+    Detail,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,126 @@
+//! Personal API tokens, for scripts and bots that call the GraphQL API without a session cookie.
+//!
+//! A token acts as its user in one league, with read access plus whichever mutation scopes it was
+//! created with. Only `fbkl_auth::hash_token` of the token is stored; the token itself is shown
+//! once, when it is created. Revoked rows are kept as the record of what had access.
+
+use sea_orm_migration::prelude::*;
+
+use crate::{
+    m20220916_131202_create_user_table::User, m20220924_004529_create_league_tables::League,
+    set_auto_updated_at_on_table,
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ApiToken::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ApiToken::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ApiToken::UserId).big_integer().not_null())
+                    .col(ColumnDef::new(ApiToken::LeagueId).big_integer().not_null())
+                    .col(ColumnDef::new(ApiToken::Name).string().not_null())
+                    .col(ColumnDef::new(ApiToken::TokenHash).binary().not_null())
+                    .col(ColumnDef::new(ApiToken::Scopes).json_binary().not_null())
+                    .col(ColumnDef::new(ApiToken::LastUsedAt).timestamp_with_time_zone())
+                    .col(ColumnDef::new(ApiToken::RevokedAt).timestamp_with_time_zone())
+                    .col(
+                        ColumnDef::new(ApiToken::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .extra("DEFAULT CURRENT_TIMESTAMP".to_string()),
+                    )
+                    .col(
+                        ColumnDef::new(ApiToken::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .extra("DEFAULT CURRENT_TIMESTAMP".to_string()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        set_auto_updated_at_on_table(manager, ApiToken::Table.to_string()).await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("api_token_fk_user")
+                    .from(ApiToken::Table, ApiToken::UserId)
+                    .to(User::Table, User::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("api_token_fk_league")
+                    .from(ApiToken::Table, ApiToken::LeagueId)
+                    .to(League::Table, League::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Every token-authenticated request looks its token up by hash.
+        manager
+            .create_index(
+                IndexCreateStatement::new()
+                    .name("api_token_token_hash")
+                    .table(ApiToken::Table)
+                    .col(ApiToken::TokenHash)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // Users list their own tokens.
+        manager
+            .create_index(
+                IndexCreateStatement::new()
+                    .name("api_token_user_id")
+                    .table(ApiToken::Table)
+                    .col(ApiToken::UserId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ApiToken::Table).if_exists().to_owned())
+            .await
+    }
+}
+
+/// Learn more at <https://docs.rs/sea-query#iden>
+#[derive(Iden)]
+pub enum ApiToken {
+    Table,
+    Id,
+    UserId,
+    LeagueId,
+    Name,
+    TokenHash,
+    Scopes,
+    LastUsedAt,
+    RevokedAt,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,69 @@
+use color_eyre::{
+    Result,
+    eyre::{WrapErr, eyre},
+};
+
+use crate::{
+    FileMailer, HttpMailer, MailError, Mailer, OutboundEmail, SENDGRID_SEND_ENDPOINT, SmtpMailer,
+};
+
+/// The mailer picked by deployment config, so the scheduler can hold one concrete type.
+#[derive(Debug)]
+pub enum ConfiguredMailer {
+    Smtp(SmtpMailer),
+    Http(HttpMailer),
+    File(FileMailer),
+}
+
+impl ConfiguredMailer {
+    /// Builds the mailer named by `FBKL_MAILER`:
+    ///
+    /// - `smtp`: `FBKL_SMTP_URL` and `FBKL_MAIL_FROM`.
+    /// - `http`: `FBKL_MAIL_HTTP_API_KEY` and `FBKL_MAIL_FROM`, plus `FBKL_MAIL_HTTP_ENDPOINT` for a
+    ///   provider other than `SendGrid`.
+    /// - `file`: `FBKL_MAIL_DIR`, defaulting to `./mail-outbox`.
+    ///
+    /// Returns `None` when `FBKL_MAILER` is unset. Mail then stays in the outbox, undelivered,
+    /// until a mailer is configured.
+    pub fn from_env() -> Result<Option<Self>> {
+        let Ok(mailer_kind) = std::env::var("FBKL_MAILER") else {
+            return Ok(None);
+        };
+
+        let mailer = match mailer_kind.as_str() {
+            "smtp" => Self::Smtp(SmtpMailer::from_url(
+                &required_env("FBKL_SMTP_URL")?,
+                &required_env("FBKL_MAIL_FROM")?,
+            )?),
+            "http" => Self::Http(HttpMailer::new(
+                std::env::var("FBKL_MAIL_HTTP_ENDPOINT")
+                    .unwrap_or_else(|_| SENDGRID_SEND_ENDPOINT.to_string()),
+                required_env("FBKL_MAIL_HTTP_API_KEY")?,
+                required_env("FBKL_MAIL_FROM")?,
+            )?),
+            "file" => Self::File(FileMailer::new(
+                std::env::var("FBKL_MAIL_DIR").unwrap_or_else(|_| "mail-outbox".to_string()),
+            )),
+            other => {
+                return Err(eyre!(
+                    "Unknown FBKL_MAILER {other:?}; expected smtp, http or file."
+                ));
+            }
+        };
+        Ok(Some(mailer))
+    }
+}
+
+impl Mailer for ConfiguredMailer {
+    async fn send(&self, email: &OutboundEmail) -> Result<(), MailError> {
+        match self {
+            Self::Smtp(mailer) => mailer.send(email).await,
+            Self::Http(mailer) => mailer.send(email).await,
+            Self::File(mailer) => mailer.send(email).await,
+        }
+    }
+}
+
+fn required_env(key: &str) -> Result<String> {
+    std::env::var(key).wrap_err_with(|| format!("{key} must be set for the configured mailer"))
+}
@@ -0,0 +1,70 @@
+//! The account emails the server sends. Links are built from the deployment's public base URL,
+//! e.g. `https://fbkl.example.com`, with no trailing slash.
+
+use crate::OutboundEmail;
+
+pub fn registration_confirmation_email(to: &str, base_url: &str, token: &str) -> OutboundEmail {
+    OutboundEmail {
+        to: to.to_string(),
+        subject: "Confirm your FBKL account".to_string(),
+        body_text: format!(
+            "Welcome to FBKL!\n\nConfirm your account by opening this link:\n\n{base_url}/confirm_registration?token={token}\n"
+        ),
+    }
+}
+
+pub fn password_reset_email(
+    to: &str,
+    base_url: &str,
+    token: &str,
+    lifetime_hours: i64,
+) -> OutboundEmail {
+    OutboundEmail {
+        to: to.to_string(),
+        subject: "Reset your FBKL password".to_string(),
+        body_text: format!(
+            "Someone asked to reset the password for this FBKL account.\n\nChoose a new password within {lifetime_hours} hour(s) here:\n\n{base_url}/reset_password?token={token}\n\nIf it wasn't you, ignore this email; your password has not changed.\n"
+        ),
+    }
+}
+
+pub fn email_change_confirmation_email(
+    to: &str,
+    base_url: &str,
+    token: &str,
+    lifetime_hours: i64,
+) -> OutboundEmail {
+    OutboundEmail {
+        to: to.to_string(),
+        subject: "Confirm your new FBKL email address".to_string(),
+        body_text: format!(
+            "Confirm that this is the new email address for your FBKL account within {lifetime_hours} hour(s):\n\n{base_url}/confirm_email_change?token={token}\n\nIf you didn't ask for this, ignore this email.\n"
+        ),
+    }
+}
+
+/// A single league notification, sent as it happens.
+pub fn notification_email(to: &str, subject: &str, message: &str) -> OutboundEmail {
+    OutboundEmail {
+        to: to.to_string(),
+        subject: format!("FBKL: {subject}"),
+        body_text: format!(
+            "{message}\n\nChoose which notifications you get by email in your FBKL notification settings.\n"
+        ),
+    }
+}
+
+/// The day's league notifications for a user who asked for a digest, oldest first.
+pub fn notification_digest_email(to: &str, messages: &[String]) -> OutboundEmail {
+    let lines: String = messages
+        .iter()
+        .map(|message| format!("- {message}\n"))
+        .collect();
+    OutboundEmail {
+        to: to.to_string(),
+        subject: format!("FBKL: {} new notification(s)", messages.len()),
+        body_text: format!(
+            "Here is what happened in your FBKL leagues:\n\n{lines}\nChoose which notifications you get by email in your FBKL notification settings.\n"
+        ),
+    }
+}
@@ -0,0 +1,97 @@
+use std::{
+    path::PathBuf,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use chrono::Utc;
+use tracing::info;
+
+use crate::{MailError, Mailer, OutboundEmail};
+
+/// Writes each email to its own text file in `dir` instead of sending it, and logs that it did.
+///
+/// For local development, where following a confirmation link means opening the file, and for
+/// tests, which read the directory back.
+#[derive(Debug)]
+pub struct FileMailer {
+    dir: PathBuf,
+    written: AtomicU64,
+}
+
+impl FileMailer {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            written: AtomicU64::new(0),
+        }
+    }
+
+    pub const fn dir(&self) -> &PathBuf {
+        &self.dir
+    }
+}
+
+impl Mailer for FileMailer {
+    async fn send(&self, email: &OutboundEmail) -> Result<(), MailError> {
+        let io_failure = |io_error: std::io::Error| MailError::Transient(io_error.to_string());
+        tokio::fs::create_dir_all(&self.dir)
+            .await
+            .map_err(io_failure)?;
+
+        // Timestamp first so a directory listing reads oldest to newest; the counter keeps two
+        // emails written in the same microsecond apart.
+        let sequence = self.written.fetch_add(1, Ordering::Relaxed);
+        let file_name = format!(
+            "{}-{sequence:04}.txt",
+            Utc::now().format("%Y%m%dT%H%M%S%.6f")
+        );
+        let path = self.dir.join(file_name);
+        let contents = format!(
+            "To: {}\nSubject: {}\n\n{}\n",
+            email.to, email.subject, email.body_text
+        );
+        tokio::fs::write(&path, contents)
+            .await
+            .map_err(io_failure)?;
+
+        info!(to = %email.to, subject = %email.subject, path = %path.display(), "Wrote email to file");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn writes_one_file_per_email() {
+        let dir = std::env::temp_dir().join(format!(
+            "fbkl-file-mailer-{}-{}",
+            std::process::id(),
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let mailer = FileMailer::new(&dir);
+
+        for subject in ["First", "Second"] {
+            mailer
+                .send(&OutboundEmail {
+                    to: "owner@example.com".to_string(),
+                    subject: subject.to_string(),
+                    body_text: "Hello.".to_string(),
+                })
+                .await
+                .unwrap();
+        }
+
+        let mut file_names = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect::<Vec<_>>();
+        file_names.sort();
+        assert_eq!(file_names.len(), 2);
+        let first = std::fs::read_to_string(&file_names[0]).unwrap();
+        assert_eq!(first, "To: owner@example.com\nSubject: First\n\nHello.\n");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,106 @@
+use color_eyre::Result;
+use reqwest::{Client, StatusCode};
+use serde_json::json;
+
+use crate::{MAIL_CONNECT_TIMEOUT, MAIL_SEND_TIMEOUT, MailError, Mailer, OutboundEmail};
+
+/// `SendGrid`'s v3 send endpoint, the default for [`HttpMailer`].
+pub const SENDGRID_SEND_ENDPOINT: &str = "https://api.sendgrid.com/v3/mail/send";
+
+/// Sends through an HTTP email provider that speaks `SendGrid`'s v3 `mail/send` JSON (`SendGrid`
+/// itself, or a compatible relay), authenticated with a bearer API key.
+#[derive(Debug, Clone)]
+pub struct HttpMailer {
+    client: Client,
+    endpoint: String,
+    api_key: String,
+    from: String,
+}
+
+impl HttpMailer {
+    pub fn new(endpoint: String, api_key: String, from: String) -> Result<Self> {
+        let client = Client::builder()
+            .connect_timeout(MAIL_CONNECT_TIMEOUT)
+            .timeout(MAIL_SEND_TIMEOUT)
+            .build()?;
+        Ok(Self {
+            client,
+            endpoint,
+            api_key,
+            from,
+        })
+    }
+}
+
+impl Mailer for HttpMailer {
+    async fn send(&self, email: &OutboundEmail) -> Result<(), MailError> {
+        let payload = json!({
+            "personalizations": [{ "to": [{ "email": email.to }] }],
+            "from": { "email": self.from },
+            "subject": email.subject,
+            "content": [{ "type": "text/plain", "value": email.body_text }],
+        });
+
+        let response = self
+            .client
+            .post(&self.endpoint)
+            .bearer_auth(&self.api_key)
+            .json(&payload)
+            .send()
+            .await
+            .map_err(|request_error| MailError::Transient(request_error.to_string()))?;
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+        // The provider's error body says which field it rejected; keep it for the outbox row.
+        let body = response.text().await.unwrap_or_default();
+        Err(failure_for_status(status, body))
+    }
+}
+
+/// Rate limits and provider-side errors clear up on their own; any other rejection of the
+/// request (bad key, bad address, bad payload) would be rejected again.
+fn failure_for_status(status: StatusCode, body: String) -> MailError {
+    let message = format!("{status}: {body}");
+    if status == StatusCode::TOO_MANY_REQUESTS
+        || status == StatusCode::REQUEST_TIMEOUT
+        || status.is_server_error()
+    {
+        MailError::Transient(message)
+    } else {
+        MailError::Permanent(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn provider_failures_are_classified_by_status() {
+        for status in [
+            StatusCode::TOO_MANY_REQUESTS,
+            StatusCode::REQUEST_TIMEOUT,
+            StatusCode::INTERNAL_SERVER_ERROR,
+            StatusCode::SERVICE_UNAVAILABLE,
+        ] {
+            assert!(matches!(
+                failure_for_status(status, String::new()),
+                MailError::Transient(_)
+            ));
+        }
+        for status in [
+            StatusCode::BAD_REQUEST,
+            StatusCode::UNAUTHORIZED,
+            StatusCode::FORBIDDEN,
+            StatusCode::PAYLOAD_TOO_LARGE,
+        ] {
+            assert!(matches!(
+                failure_for_status(status, String::new()),
+                MailError::Permanent(_)
+            ));
+        }
+    }
+}
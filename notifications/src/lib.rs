@@ -0,0 +1,25 @@
+//! Outbound email for FBKL.
+//!
+//! Nothing sends mail inline. Callers [`enqueue_email`] inside the DB transaction of the event
+//! that caused it, which writes an `email_outbox` row, and the `fbkl_jobs` scheduler later hands
+//! due rows to a [`Mailer`] through [`deliver_due_emails`], retrying transient failures and
+//! dead-lettering the rest.
+//!
+//! Which [`Mailer`] runs is deployment config (see [`ConfiguredMailer::from_env`]): SMTP, an HTTP
+//! email provider, or a directory of plain-text files for local development and tests.
+
+mod configured_mailer;
+mod email_templates;
+mod file_mailer;
+mod http_mailer;
+mod mailer;
+mod outbox;
+mod smtp_mailer;
+
+pub use configured_mailer::*;
+pub use email_templates::*;
+pub use file_mailer::*;
+pub use http_mailer::*;
+pub use mailer::*;
+pub use outbox::*;
+pub use smtp_mailer::*;
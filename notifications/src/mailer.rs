@@ -0,0 +1,34 @@
+use std::{future::Future, time::Duration};
+
+use thiserror::Error;
+
+/// How long a mailer waits to reach its provider.
+pub const MAIL_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+/// How long a mailer waits on one send (for SMTP, on each command) before calling it transient.
+/// A full outbox batch of timed-out sends has to finish inside the claim lease, or another
+/// scheduler could claim and re-send the messages still in flight.
+pub const MAIL_SEND_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A plain-text email to a single recipient. The sender address belongs to the [`Mailer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutboundEmail {
+    pub to: String,
+    pub subject: String,
+    pub body_text: String,
+}
+
+/// Why a send failed, which decides whether the outbox tries again.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum MailError {
+    /// Worth retrying later: a network error, a rate limit, a provider outage.
+    #[error("transient mail failure: {0}")]
+    Transient(String),
+    /// Retrying won't help: a malformed address, a rejected recipient, bad credentials.
+    #[error("permanent mail failure: {0}")]
+    Permanent(String),
+}
+
+/// Delivers one email. Implementations must not retry internally; the outbox owns retries.
+pub trait Mailer: Send + Sync {
+    fn send(&self, email: &OutboundEmail) -> impl Future<Output = Result<(), MailError>> + Send;
+}
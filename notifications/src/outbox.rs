@@ -0,0 +1,150 @@
+use chrono::TimeDelta;
+use color_eyre::Result;
+use fbkl_entity::{
+    email_outbox, email_outbox_queries,
+    sea_orm::{ActiveValue, ConnectionTrait, TransactionTrait, prelude::DateTimeWithTimeZone},
+};
+use tracing::{instrument, warn};
+
+use crate::{MailError, Mailer, OutboundEmail};
+
+/// Attempts a message gets before it is dead-lettered. With the doubling delay this spans about
+/// two hours, long enough to ride out a provider outage.
+pub const EMAIL_OUTBOX_MAX_ATTEMPTS: i32 = 8;
+/// Messages claimed per delivery pass, so one tick can't spend minutes sending.
+pub const EMAIL_OUTBOX_BATCH_SIZE: u64 = 50;
+/// How long a claimed message stays hidden from other schedulers while its send is in flight.
+pub const EMAIL_OUTBOX_CLAIM_LEASE_MINUTES: i64 = 10;
+const EMAIL_OUTBOX_MAX_RETRY_DELAY_MINUTES: i64 = 6 * 60;
+
+/// What one [`deliver_due_emails`] pass did.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DeliverySummary {
+    pub sent: usize,
+    /// Failed transiently and left pending for a later pass.
+    pub retried: usize,
+    pub dead_lettered: usize,
+}
+
+/// Queues `email` for delivery. Pass the transaction of the event that triggered it, so the
+/// email is only ever sent if that event commits.
+///
+/// Bodies often carry single-use tokens, so the email is kept out of the tracing span, and the
+/// stored body is cleared once the message is sent or dead-lettered.
+#[instrument(skip_all)]
+pub async fn enqueue_email<C>(email: &OutboundEmail, db: &C) -> Result<email_outbox::Model>
+where
+    C: ConnectionTrait,
+{
+    email_outbox_queries::insert_email_outbox(
+        email_outbox::ActiveModel {
+            to_address: ActiveValue::Set(email.to.clone()),
+            subject: ActiveValue::Set(email.subject.clone()),
+            body_text: ActiveValue::Set(email.body_text.clone()),
+            ..Default::default()
+        },
+        db,
+    )
+    .await
+}
+
+/// Sends one batch of the outbox messages due at `now` through `mailer`.
+///
+/// A transient failure leaves the message pending behind [`retry_delay`]; a permanent failure, or
+/// a transient one on the last allowed attempt, dead-letters it.
+#[instrument(skip(mailer, db))]
+pub async fn deliver_due_emails<M, C>(
+    mailer: &M,
+    now: DateTimeWithTimeZone,
+    db: &C,
+) -> Result<DeliverySummary>
+where
+    M: Mailer,
+    C: ConnectionTrait + TransactionTrait,
+{
+    let claimed_messages = email_outbox_queries::claim_due_email_outbox_messages(
+        now,
+        now + TimeDelta::minutes(EMAIL_OUTBOX_CLAIM_LEASE_MINUTES),
+        EMAIL_OUTBOX_BATCH_SIZE,
+        db,
+    )
+    .await?;
+
+    let mut summary = DeliverySummary::default();
+    for message_model in claimed_messages {
+        let email = OutboundEmail {
+            to: message_model.to_address.clone(),
+            subject: message_model.subject.clone(),
+            body_text: message_model.body_text.clone(),
+        };
+        match mailer.send(&email).await {
+            Ok(()) => {
+                email_outbox_queries::mark_email_outbox_message_sent(message_model, now, db)
+                    .await?;
+                summary.sent += 1;
+            }
+            Err(MailError::Transient(error_message))
+                if message_model.attempts < EMAIL_OUTBOX_MAX_ATTEMPTS =>
+            {
+                let next_attempt_at = now + retry_delay(message_model.attempts);
+                email_outbox_queries::reschedule_email_outbox_message(
+                    message_model,
+                    error_message,
+                    next_attempt_at,
+                    db,
+                )
+                .await?;
+                summary.retried += 1;
+            }
+            Err(MailError::Transient(error_message) | MailError::Permanent(error_message)) => {
+                warn!(
+                    "Dead-lettering email (outbox id = {}) after {} attempt(s): {error_message}",
+                    message_model.id, message_model.attempts
+                );
+                email_outbox_queries::dead_letter_email_outbox_message(
+                    message_model,
+                    error_message,
+                    db,
+                )
+                .await?;
+                summary.dead_lettered += 1;
+            }
+        }
+    }
+    Ok(summary)
+}
+
+/// The wait after a message's `attempts`-th failed attempt: one minute, doubling each time, capped
+/// at six hours.
+pub fn retry_delay(attempts: i32) -> TimeDelta {
+    // Past 2^9 minutes the cap applies anyway; stopping there keeps the shift from overflowing.
+    let doublings = u32::try_from(attempts.saturating_sub(1))
+        .unwrap_or_default()
+        .min(9);
+    let delay_minutes = (1_i64 << doublings).min(EMAIL_OUTBOX_MAX_RETRY_DELAY_MINUTES);
+    TimeDelta::minutes(delay_minutes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MAIL_SEND_TIMEOUT;
+
+    #[test]
+    fn retry_delay_doubles_up_to_the_cap() {
+        assert_eq!(retry_delay(1), TimeDelta::minutes(1));
+        assert_eq!(retry_delay(2), TimeDelta::minutes(2));
+        assert_eq!(retry_delay(7), TimeDelta::minutes(64));
+        assert_eq!(retry_delay(10), TimeDelta::minutes(6 * 60));
+        assert_eq!(retry_delay(100), TimeDelta::minutes(6 * 60));
+    }
+
+    #[test]
+    fn a_batch_of_timed_out_sends_ends_inside_the_claim_lease() {
+        let worst_case = MAIL_SEND_TIMEOUT * u32::try_from(EMAIL_OUTBOX_BATCH_SIZE).unwrap();
+        assert!(
+            TimeDelta::from_std(worst_case).unwrap()
+                < TimeDelta::minutes(EMAIL_OUTBOX_CLAIM_LEASE_MINUTES)
+        );
+    }
+}
@@ -0,0 +1,51 @@
+//! One-shot: import an NBA injury report file into `player.nba_injury_status` (rules §10.1).
+//!
+//! The report is the `nba_id,status` CSV described in `fbkl_logic::ir::parse_nba_injury_report`.
+//! `source` is where the report came from (`Nba`, `Espn` or `BasketballReference`); `asof` is
+//! when it was pulled, as RFC 3339, and defaults to now. Players whose status is newer than the
+//! report keep it. The import runs in one database transaction.
+//!
+//!   `DATABASE_URL`=<db> cargo run -p fbkl-server --bin `import_nba_injuries` -- <report.csv> <source> [asof]
+
+use chrono::{DateTime, Utc};
+use color_eyre::eyre::{bail, eyre};
+use fbkl_entity::{
+    player::NbaRosterSource,
+    sea_orm::{Database, TransactionTrait},
+};
+use fbkl_logic::ir::{import_nba_injury_report, parse_nba_injury_report};
+
+#[tokio::main]
+async fn main() -> color_eyre::Result<()> {
+    let mut args = std::env::args().skip(1);
+    let usage = "usage: import_nba_injuries <report.csv> <Nba|Espn|BasketballReference> [asof]";
+    let report_path = args.next().ok_or_else(|| eyre!(usage))?;
+    let source = match args.next().as_deref() {
+        Some("Nba") => NbaRosterSource::Nba,
+        Some("Espn") => NbaRosterSource::Espn,
+        Some("BasketballReference") => NbaRosterSource::BasketballReference,
+        _ => bail!(usage),
+    };
+    let asof = match args.next() {
+        Some(asof) => DateTime::parse_from_rfc3339(&asof)?,
+        None => Utc::now().fixed_offset(),
+    };
+
+    let rows = parse_nba_injury_report(&std::fs::read_to_string(&report_path)?)?;
+
+    let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let db = Database::connect(&url).await?;
+    let db_txn = db.begin().await?;
+    let import = import_nba_injury_report(&rows, source, asof, &db_txn).await?;
+    db_txn.commit().await?;
+
+    println!(
+        "updated {} players from {} report lines",
+        import.updated_player_ids.len(),
+        rows.len()
+    );
+    if !import.unmatched_nba_ids.is_empty() {
+        println!("no player for NBA ids: {:?}", import.unmatched_nba_ids);
+    }
+    Ok(())
+}
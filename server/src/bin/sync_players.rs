@@ -0,0 +1,97 @@
+//! One-shot: sync `player` rows with an NBA player index file (spec 12).
+//!
+//! The index is the `stats.nba.com` `playerindex` JSON described in
+//! `fbkl_logic::player_sync::parse_nba_player_index`, optionally with an added `ESPN_ID` column so
+//! players imported from ESPN without an NBA id are matched. `end_of_season_year` is the current
+//! season, which decides retirements. Without `--apply` this only prints what would change, for the
+//! commissioner to review; with it the changes are written in one database transaction. Running
+//! it again on the same file changes nothing.
+//!
+//!   `DATABASE_URL`=<db> cargo run -p fbkl-server --bin `sync_players` -- <index.json> <end_of_season_year> [--apply]
+
+use chrono::Utc;
+use color_eyre::eyre::{bail, eyre};
+use fbkl_entity::{
+    player::NbaRosterSource,
+    sea_orm::{Database, TransactionTrait},
+};
+use fbkl_logic::player_sync::{
+    PlayerSyncReport, apply_player_sync, parse_nba_player_index, plan_player_sync,
+};
+
+#[tokio::main]
+async fn main() -> color_eyre::Result<()> {
+    let mut args = std::env::args().skip(1);
+    let usage = "usage: sync_players <index.json> <end_of_season_year> [--apply]";
+    let index_path = args.next().ok_or_else(|| eyre!(usage))?;
+    let end_of_season_year: i16 = args.next().ok_or_else(|| eyre!(usage))?.parse()?;
+    let should_apply = match args.next().as_deref() {
+        None => false,
+        Some("--apply") => true,
+        Some(_) => bail!(usage),
+    };
+
+    let entries = parse_nba_player_index(&std::fs::read_to_string(&index_path)?)?;
+
+    let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let db = Database::connect(&url).await?;
+    let db_txn = db.begin().await?;
+    let report = plan_player_sync(&entries, end_of_season_year, &db_txn).await?;
+    print_report(&report);
+    if !should_apply || report.has_no_changes() {
+        db_txn.rollback().await?;
+        return Ok(());
+    }
+
+    let applied = apply_player_sync(
+        &report,
+        NbaRosterSource::Nba,
+        Utc::now().fixed_offset(),
+        &db_txn,
+    )
+    .await?;
+    db_txn.commit().await?;
+
+    println!(
+        "inserted {} players, updated {} players",
+        applied.inserted_player_ids.len(),
+        applied.updated_player_ids.len()
+    );
+    Ok(())
+}
+
+fn print_report(report: &PlayerSyncReport) {
+    for new_player in &report.new_players {
+        println!(
+            "new: {} (nba_id {:?}, real team {})",
+            new_player.entry.name, new_player.entry.nba_id, new_player.real_team_id
+        );
+    }
+    for rename in &report.renames {
+        println!(
+            "rename: player {} {} -> {}",
+            rename.player_id, rename.from_name, rename.to_name
+        );
+    }
+    for change in &report.real_team_changes {
+        println!(
+            "real team: player {} {}: {} -> {}",
+            change.player_id, change.name, change.from_real_team_id, change.to_real_team_id
+        );
+    }
+    for change in &report.status_changes {
+        println!(
+            "status: player {} {}: {:?} -> {:?}",
+            change.player_id, change.name, change.from_status, change.to_status
+        );
+    }
+    for unresolved in &report.unresolved {
+        println!(
+            "unresolved: {} (nba_id {:?}): {:?}",
+            unresolved.entry.name, unresolved.entry.nba_id, unresolved.reason
+        );
+    }
+    if report.has_no_changes() {
+        println!("nothing to change");
+    }
+}
@@ -0,0 +1,107 @@
+//! The address a request came from, for per-IP limits and the security event log.
+//!
+//! By default that is the socket peer, which the local server records as `ConnectInfo`. Behind a
+//! trusted proxy, set `FBKL_TRUST_X_FORWARDED_FOR=true`: in production the Lambda Function URL
+//! appends the peer address to `X-Forwarded-For`, and entries to its left are whatever the client
+//! sent, so only the last one is trusted. Without that setting the header is ignored, since a
+//! client talking to the server directly can put anything in it.
+
+use std::{convert::Infallible, net::SocketAddr, sync::LazyLock};
+
+use axum::{
+    extract::{ConnectInfo, FromRequestParts},
+    http::{HeaderMap, request::Parts},
+};
+
+/// Stands in for the address when neither a trusted header nor the peer gives one, e.g. a request
+/// handed to the router in a test. Those requests share one per-IP limit.
+pub const UNKNOWN_CLIENT_IP_ADDRESS: &str = "unknown";
+
+static TRUSTS_X_FORWARDED_FOR: LazyLock<bool> = LazyLock::new(|| {
+    std::env::var("FBKL_TRUST_X_FORWARDED_FOR").is_ok_and(|value| value == "true")
+});
+
+/// The requesting client's address, as an extractor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientIp(pub String);
+
+impl<S> FromRequestParts<S> for ClientIp
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let peer_address = parts
+            .extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(peer_address)| *peer_address);
+        Ok(Self(client_ip_address(
+            &parts.headers,
+            peer_address,
+            *TRUSTS_X_FORWARDED_FOR,
+        )))
+    }
+}
+
+fn client_ip_address(
+    headers: &HeaderMap,
+    peer_address: Option<SocketAddr>,
+    trusts_x_forwarded_for: bool,
+) -> String {
+    let forwarded_address = headers
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|header_value| header_value.to_str().ok())
+        .flat_map(|header_value| header_value.split(','))
+        .map(str::trim)
+        .rfind(|address| !address.is_empty())
+        .filter(|_| trusts_x_forwarded_for);
+    forwarded_address
+        .map(str::to_owned)
+        .or_else(|| peer_address.map(|peer_address| peer_address.ip().to_string()))
+        .unwrap_or_else(|| UNKNOWN_CLIENT_IP_ADDRESS.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use axum::http::{HeaderMap, HeaderValue};
+
+    use super::{UNKNOWN_CLIENT_IP_ADDRESS, client_ip_address};
+
+    #[test]
+    fn the_proxy_appended_address_wins_over_client_supplied_ones() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(
+            client_ip_address(&headers, None, true),
+            UNKNOWN_CLIENT_IP_ADDRESS
+        );
+
+        headers.insert(
+            "x-forwarded-for",
+            HeaderValue::from_static("10.0.0.1, 203.0.113.7"),
+        );
+        assert_eq!(client_ip_address(&headers, None, true), "203.0.113.7");
+
+        headers.append("x-forwarded-for", HeaderValue::from_static("198.51.100.2 "));
+        assert_eq!(client_ip_address(&headers, None, true), "198.51.100.2");
+    }
+
+    #[test]
+    fn without_a_trusted_proxy_the_peer_address_is_used() {
+        let peer_address: SocketAddr = "192.0.2.10:51234".parse().unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", HeaderValue::from_static("203.0.113.7"));
+
+        assert_eq!(
+            client_ip_address(&headers, Some(peer_address), false),
+            "192.0.2.10"
+        );
+        assert_eq!(
+            client_ip_address(&headers, None, false),
+            UNKNOWN_CLIENT_IP_ADDRESS
+        );
+    }
+}
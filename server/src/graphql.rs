@@ -4,6 +4,7 @@
 use async_graphql::{EmptySubscription, MergedObject, Schema};
 
 use self::{
+    api_token::{ApiTokenMutation, ApiTokenQuery},
     auction::{AuctionMutation, AuctionQuery},
     contract::ContractQuery,
     deadline::{DeadlineMutation, DeadlineQuery},
@@ -11,17 +12,21 @@ use self::{
     eligibility::{EligibilityMutation, EligibilityQuery},
     keeper::{KeeperMutation, KeeperQuery},
     league::{LeagueMutation, LeagueQuery},
-    player::PlayerQuery,
+    notification::{NotificationMutation, NotificationQuery},
+    player::{PlayerMutation, PlayerQuery},
     rfa::{RfaMutation, RfaQuery},
-    roster::RosterMutation,
-    team::TeamQuery,
+    roster::{RosterMutation, RosterQuery},
+    security_event::SecurityEventQuery,
+    team::{TeamMutation, TeamQuery},
     trade::{TradeMutation, TradeQuery},
+    trade_block::{TradeBlockMutation, TradeBlockQuery},
     transaction::TransactionQuery,
     user::UserQuery,
 };
 
-pub use self::{authz::*, error::*, loaders::*, season::*};
+pub use self::{api_token::authenticate_api_token, authz::*, error::*, loaders::*, season::*};
 
+mod api_token;
 mod auction;
 mod authz;
 mod contract;
@@ -32,12 +37,15 @@ mod error;
 mod keeper;
 mod league;
 mod loaders;
+mod notification;
 mod player;
 mod rfa;
 mod roster;
 mod season;
+mod security_event;
 mod team;
 mod trade;
+mod trade_block;
 mod transaction;
 mod user;
 
@@ -51,6 +59,7 @@ pub struct QueryRoot(
     PlayerQuery,
     ContractQuery,
     TradeQuery,
+    TradeBlockQuery,
     TransactionQuery,
     KeeperQuery,
     DeadlineQuery,
@@ -58,12 +67,18 @@ pub struct QueryRoot(
     DraftQuery,
     EligibilityQuery,
     RfaQuery,
+    RosterQuery,
+    NotificationQuery,
+    SecurityEventQuery,
+    ApiTokenQuery,
 );
 
 #[derive(Default, MergedObject)]
 pub struct MutationRoot(
     LeagueMutation,
+    TeamMutation,
     TradeMutation,
+    TradeBlockMutation,
     RosterMutation,
     KeeperMutation,
     DeadlineMutation,
@@ -71,4 +86,7 @@ pub struct MutationRoot(
     AuctionMutation,
     DraftMutation,
     RfaMutation,
+    PlayerMutation,
+    NotificationMutation,
+    ApiTokenMutation,
 );
@@ -0,0 +1,6 @@
+mod api_token_resolvers;
+mod api_token_scopes;
+mod api_token_types;
+pub use api_token_resolvers::*;
+pub use api_token_scopes::*;
+pub use api_token_types::*;
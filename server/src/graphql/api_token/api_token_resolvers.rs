@@ -0,0 +1,218 @@
+//! Personal API tokens for scripts and bots: creating, listing and revoking them, and
+//! authenticating the requests that carry one.
+//!
+//! A token acts as its user in the league it was created in, so `require_league_role` resolves
+//! the same `team_user` role a session in that league would. Managing tokens needs a session,
+//! so a token can never mint or extend access for itself.
+
+use async_graphql::{Context, Error as GraphQlError, Object, Request, Result};
+use chrono::Utc;
+use color_eyre::Report;
+use fbkl_auth::{decode_token, encode_token, generate_token, hash_token};
+use fbkl_entity::{
+    api_token::{self, ApiTokenScope},
+    api_token_queries::{
+        NewApiToken, find_active_api_tokens_for_user, find_api_token_by_id,
+        find_api_token_by_token_hash, insert_api_token, revoke_api_token, touch_api_token,
+    },
+    sea_orm::{ConnectionTrait, DatabaseConnection, TransactionTrait},
+    security_event::SecurityEventKind,
+    security_event_queries::{NewSecurityEvent, insert_security_event},
+    user, user_queries,
+};
+
+use super::{ApiToken, CreatedApiToken, ungranted_mutation_fields};
+use crate::graphql::{
+    ErrorCode, RoleRequirement, code_error, graphql_error, require_current_user,
+    require_league_role,
+};
+
+#[derive(Default)]
+pub struct ApiTokenQuery;
+
+#[Object]
+impl ApiTokenQuery {
+    /// The caller's tokens that have not been revoked, newest first.
+    async fn api_tokens(&self, ctx: &Context<'_>) -> Result<Vec<ApiToken>> {
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+        let user_model = require_current_user(ctx)?;
+
+        let token_models = find_active_api_tokens_for_user(user_model.id, db)
+            .await
+            .map_err(|err| internal("failed to load API tokens", &err))?;
+
+        token_models.iter().map(api_token_from_model).collect()
+    }
+}
+
+#[derive(Default)]
+pub struct ApiTokenMutation;
+
+#[Object]
+impl ApiTokenMutation {
+    /// Creates a token that acts as the caller in the selected league, with read access plus
+    /// `scopes`. The returned token is shown only here.
+    async fn create_api_token(
+        &self,
+        ctx: &Context<'_>,
+        name: String,
+        scopes: Vec<ApiTokenScope>,
+    ) -> Result<CreatedApiToken> {
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+        let user_model = require_current_user(ctx)?;
+        let (_, caller_team) = require_league_role(ctx, RoleRequirement::Member).await?;
+        let name = name.trim().to_owned();
+        if name.is_empty() {
+            return Err(graphql_error(
+                ErrorCode::BadRequest,
+                "the token needs a name",
+            ));
+        }
+        let mut unique_scopes = Vec::with_capacity(scopes.len());
+        for scope in scopes {
+            if !unique_scopes.contains(&scope) {
+                unique_scopes.push(scope);
+            }
+        }
+
+        let token = generate_token();
+        let db_txn = db
+            .begin()
+            .await
+            .map_err(|err| internal("failed to start transaction", &err.into()))?;
+        let token_model = insert_api_token(
+            NewApiToken {
+                user_id: user_model.id,
+                league_id: caller_team.league_id,
+                name,
+                token_hash: hash_token(&token),
+                scopes: unique_scopes.clone(),
+            },
+            &db_txn,
+        )
+        .await
+        .map_err(|err| internal("failed to create API token", &err))?;
+        record_api_token_event(SecurityEventKind::ApiTokenCreated, &token_model, &db_txn).await?;
+        db_txn
+            .commit()
+            .await
+            .map_err(|err| internal("failed to commit API token", &err.into()))?;
+
+        Ok(CreatedApiToken {
+            token: encode_token(&token.to_vec()),
+            api_token: ApiToken::from_model(&token_model, unique_scopes),
+        })
+    }
+
+    /// Revokes one of the caller's tokens. Every later request made with it is refused.
+    async fn revoke_api_token(&self, ctx: &Context<'_>, id: i64) -> Result<ApiToken> {
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+        let user_model = require_current_user(ctx)?;
+
+        let Some(token_model) = find_api_token_by_id(id, db)
+            .await
+            .map_err(|err| internal("failed to load API token", &err))?
+            .filter(|token_model| {
+                token_model.user_id == user_model.id && !token_model.is_revoked()
+            })
+        else {
+            return Err(code_error(ErrorCode::NotFound));
+        };
+
+        let db_txn = db
+            .begin()
+            .await
+            .map_err(|err| internal("failed to start transaction", &err.into()))?;
+        let revoked = revoke_api_token(token_model, Utc::now().fixed_offset(), &db_txn)
+            .await
+            .map_err(|err| internal("failed to revoke API token", &err))?;
+        record_api_token_event(SecurityEventKind::ApiTokenRevoked, &revoked, &db_txn).await?;
+        db_txn
+            .commit()
+            .await
+            .map_err(|err| internal("failed to commit API token revocation", &err.into()))?;
+
+        api_token_from_model(&revoked)
+    }
+}
+
+/// Authenticates a request sent with `Authorization: Bearer <token>`, returning the token and
+/// its user. Refuses an unknown or revoked token, and any request calling a mutation the token's
+/// scopes don't grant. Stamps the token's last use once the request is let through.
+pub async fn authenticate_api_token(
+    bearer_token: &str,
+    request: &Request,
+    db: &DatabaseConnection,
+) -> Result<(api_token::Model, user::Model)> {
+    let token_bytes =
+        decode_token(bearer_token).map_err(|_| code_error(ErrorCode::Unauthenticated))?;
+    let Some(token_model) = find_api_token_by_token_hash(hash_token(&token_bytes), db)
+        .await
+        .map_err(|err| internal("failed to look up API token", &err))?
+        .filter(|token_model| !token_model.is_revoked())
+    else {
+        return Err(code_error(ErrorCode::Unauthenticated));
+    };
+    let Some(user_model) = user_queries::find_user_by_id(token_model.user_id, db)
+        .await
+        .map_err(|err| internal("failed to load API token user", &err))?
+    else {
+        return Err(code_error(ErrorCode::Unauthenticated));
+    };
+
+    let scopes = token_model
+        .get_scopes()
+        .map_err(|err| internal("failed to read API token scopes", &err))?;
+    let ungranted_fields = ungranted_mutation_fields(&request.query, &scopes);
+    if !ungranted_fields.is_empty() {
+        return Err(graphql_error(
+            ErrorCode::Forbidden,
+            format!(
+                "this API token may not call {}",
+                ungranted_fields.join(", ")
+            ),
+        ));
+    }
+
+    touch_api_token(token_model.id, Utc::now().fixed_offset(), db)
+        .await
+        .map_err(|err| internal("failed to stamp API token use", &err))?;
+
+    Ok((token_model, user_model))
+}
+
+fn api_token_from_model(token_model: &api_token::Model) -> Result<ApiToken> {
+    let scopes = token_model
+        .get_scopes()
+        .map_err(|err| internal("failed to read API token scopes", &err))?;
+    Ok(ApiToken::from_model(token_model, scopes))
+}
+
+async fn record_api_token_event<C>(
+    kind: SecurityEventKind,
+    token_model: &api_token::Model,
+    db: &C,
+) -> Result<()>
+where
+    C: ConnectionTrait,
+{
+    insert_security_event(
+        NewSecurityEvent {
+            kind,
+            user_id: Some(token_model.user_id),
+            email: None,
+            ip_address: None,
+            detail: Some(token_model.name.clone()),
+        },
+        Utc::now().fixed_offset(),
+        db,
+    )
+    .await
+    .map_err(|err| internal("failed to record API token event", &err))?;
+    Ok(())
+}
+
+fn internal(context: &str, error: &Report) -> GraphQlError {
+    tracing::error!(error = ?error, context);
+    code_error(ErrorCode::Internal)
+}
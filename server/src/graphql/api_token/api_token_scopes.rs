@@ -0,0 +1,158 @@
+//! Which mutations an API token's scopes let it call.
+//!
+//! Checked once per request, before the schema runs, against the request's own document. A mutation
+//! missing from [`mutation_scope`] is session-only, so a new mutation stays out of reach of tokens
+//! until it is deliberately given a scope.
+
+use async_graphql::parser::{
+    parse_query,
+    types::{OperationType, Selection},
+};
+use fbkl_entity::api_token::ApiTokenScope;
+
+/// The scope that grants a top-level mutation field, or `None` if tokens may never call it.
+///
+/// Account, notification, token and league-switching mutations are session-only: a token is
+/// limited to one league and must not be able to mint or extend its own access.
+pub fn mutation_scope(field_name: &str) -> Option<ApiTokenScope> {
+    let scope = match field_name {
+        "placeBid" | "setVeteranAuctionMinBidTiers" | "setVeteranAuctionRanking" => {
+            ApiTokenScope::Auction
+        }
+        "saveLeagueTeamSeasonStandings"
+        | "runRookieDraftLottery"
+        | "startRookieDraft"
+        | "makeRookieDraftPick"
+        | "passRookieDraftPick" => ApiTokenScope::RookieDraft,
+        "raiseRfaBid"
+        | "declineToRaiseRfa"
+        | "changeRfaCompensationPick"
+        | "matchRfa"
+        | "declineRfa"
+        | "resignUnbidRfa"
+        | "releaseUnbidRfaToAuction" => ApiTokenScope::Rfa,
+        "moveContractToIr"
+        | "activateContractFromIr"
+        | "dropContract"
+        | "activateRookieContract"
+        | "moveRookieToInternational"
+        | "moveRookieToStateside"
+        | "reorderWeeklyMoves"
+        | "declareSeasonStartRoster"
+        | "acknowledgeRosterLegalizationMoves"
+        | "waiveDropPenalty" => ApiTokenScope::Roster,
+        "declareKeepers" => ApiTokenScope::Keeper,
+        "proposeTrade"
+        | "acceptTrade"
+        | "rejectTrade"
+        | "reproposeTradeWithoutRejectingTeams"
+        | "addToTradeBlock"
+        | "addTradeBlockNeed"
+        | "removeFromTradeBlock" => ApiTokenScope::Trade,
+        "triggerDeadline"
+        | "setPlayerNbaStatus"
+        | "setPlayerNbaInjuryStatus"
+        | "overridePlayerEligibility"
+        | "setTradeRejectionPolicy"
+        | "setRosterLegalizationPolicy"
+        | "ruleOnHeldRosterMoves"
+        | "createTeam"
+        | "createLeagueInvitation"
+        | "revokeLeagueInvitation"
+        | "mergeLeaguePlayer"
+        | "transferTeamOwnership" => ApiTokenScope::Commissioner,
+        _ => return None,
+    };
+    Some(scope)
+}
+
+/// The top-level mutation selections in `query` that `scopes` do not grant, in document order.
+///
+/// Fragments at the top of a mutation are reported as `...`, since a token may only call the
+/// mutations it names outright. A document that does not parse has nothing to report; the schema
+/// refuses it before anything runs.
+pub fn ungranted_mutation_fields(query: &str, scopes: &[ApiTokenScope]) -> Vec<String> {
+    let Ok(document) = parse_query(query) else {
+        return vec![];
+    };
+
+    document
+        .operations
+        .iter()
+        .filter(|(_, operation)| operation.node.ty == OperationType::Mutation)
+        .flat_map(|(_, operation)| operation.node.selection_set.node.items.iter())
+        .filter_map(|selection| match &selection.node {
+            Selection::Field(field) => {
+                let field_name = field.node.name.node.as_str();
+                let granted =
+                    mutation_scope(field_name).is_some_and(|scope| scopes.contains(&scope));
+                (!granted).then(|| field_name.to_owned())
+            }
+            Selection::FragmentSpread(_) | Selection::InlineFragment(_) => Some("...".to_owned()),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_only_tokens_may_query_but_not_mutate() {
+        assert!(ungranted_mutation_fields("{ teams { id name } }", &[]).is_empty());
+        assert_eq!(
+            ungranted_mutation_fields("mutation { placeBid(auctionId: 1, amount: 5) { id } }", &[]),
+            vec!["placeBid"]
+        );
+    }
+
+    #[test]
+    fn scopes_grant_only_their_own_mutations() {
+        let scopes = [ApiTokenScope::Auction];
+        assert!(
+            ungranted_mutation_fields(
+                "mutation { placeBid(auctionId: 1, amount: 5) { id } }",
+                &scopes
+            )
+            .is_empty()
+        );
+        assert_eq!(
+            ungranted_mutation_fields(
+                "mutation { placeBid(auctionId: 1, amount: 5) { id } dropContract(contractId: 2) { id } }",
+                &scopes
+            ),
+            vec!["dropContract"]
+        );
+    }
+
+    #[test]
+    fn session_only_mutations_are_never_granted() {
+        let every_scope = [
+            ApiTokenScope::Auction,
+            ApiTokenScope::RookieDraft,
+            ApiTokenScope::Rfa,
+            ApiTokenScope::Roster,
+            ApiTokenScope::Keeper,
+            ApiTokenScope::Trade,
+            ApiTokenScope::Commissioner,
+        ];
+        assert_eq!(
+            ungranted_mutation_fields(
+                "mutation { createApiToken(name: \"bot\", scopes: []) { token } selectLeague(leagueId: 2) { id } }",
+                &every_scope
+            ),
+            vec!["createApiToken", "selectLeague"]
+        );
+    }
+
+    #[test]
+    fn fragments_cannot_hide_a_mutation() {
+        assert_eq!(
+            ungranted_mutation_fields(
+                "mutation { ...bid } fragment bid on MutationRoot { dropContract(contractId: 2) { id } }",
+                &[ApiTokenScope::Auction]
+            ),
+            vec!["..."]
+        );
+    }
+}
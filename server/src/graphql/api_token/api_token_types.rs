@@ -0,0 +1,34 @@
+use async_graphql::SimpleObject;
+use fbkl_entity::api_token::{self, ApiTokenScope};
+
+/// One of the caller's personal API tokens. The token itself is only ever shown on creation.
+#[derive(SimpleObject)]
+pub struct ApiToken {
+    pub id: i64,
+    pub league_id: i64,
+    pub name: String,
+    /// The mutation scopes granted on top of read access. Empty for a read-only token.
+    pub scopes: Vec<ApiTokenScope>,
+    pub last_used_at: Option<String>,
+    pub created_at: String,
+}
+
+impl ApiToken {
+    pub(crate) fn from_model(entity: &api_token::Model, scopes: Vec<ApiTokenScope>) -> Self {
+        Self {
+            id: entity.id,
+            league_id: entity.league_id,
+            name: entity.name.clone(),
+            scopes,
+            last_used_at: entity.last_used_at.map(|used_at| used_at.to_rfc3339()),
+            created_at: entity.created_at.to_rfc3339(),
+        }
+    }
+}
+
+/// A newly created token, with the secret to send as `Authorization: Bearer <token>`.
+#[derive(SimpleObject)]
+pub struct CreatedApiToken {
+    pub token: String,
+    pub api_token: ApiToken,
+}
@@ -4,7 +4,7 @@
 //! The commissioner's two per-season veteran-auction inputs (§6.3.6) also live here, since they are
 //! what pool assembly reads when the auction-start deadline fires.
 
-use async_graphql::{Context, Error as GraphQlError, Object, Result, SimpleObject};
+use async_graphql::{ComplexObject, Context, Error as GraphQlError, Object, Result, SimpleObject};
 use chrono::Utc;
 use color_eyre::Report;
 use fbkl_constants::league_rules::compensation_round_for_bid;
@@ -19,9 +19,11 @@ use fbkl_entity::{
         find_auction_schedule_rows_for_season, set_min_bid_tiers, set_veteran_auction_ranking,
         validate_min_bid_tiers,
     },
+    contract::FreeAgentException,
     deadline::DeadlineKind,
     deadline_queries::find_sorted_deadlines_for_league_season,
     sea_orm::DatabaseConnection,
+    ufa_exception_queries::find_free_agent_exception_holder,
 };
 use fbkl_entity::{
     rfa_resolution::RfaResolutionStatus, rfa_resolution_queries::find_rfa_resolution_for_contract,
@@ -52,6 +54,7 @@ const AUCTION_DEADLINE_KINDS: [DeadlineKind; 6] = [
 
 /// An auction on one contract. `transactionId` is null while the auction is still open.
 #[derive(SimpleObject)]
+#[graphql(complex)]
 pub struct Auction {
     pub id: i64,
     pub kind: AuctionKind,
@@ -66,6 +69,8 @@ pub struct Auction {
     pub original_owner_team_id: Option<i64>,
     pub contract_id: i64,
     pub transaction_id: Option<i64>,
+    #[graphql(skip)]
+    model: auction::Model,
 }
 
 impl Auction {
@@ -83,10 +88,71 @@ impl Auction {
             original_owner_team_id: model.original_owner_team_id,
             contract_id: model.contract_id,
             transaction_id: model.transaction_id,
+            model: model.clone(),
         }
     }
 }
 
+#[ComplexObject]
+impl Auction {
+    /// RFA/UFA only: the team that holds the re-sign discount, i.e. the owner at the keeper deadline
+    /// (rules §15.4.2, §16.4.1). A trade during the auction does not change it.
+    async fn exception_holder_team_id(&self, ctx: &Context<'_>) -> Result<Option<i64>> {
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+
+        let auction_contract = self
+            .model
+            .get_contract(db)
+            .await
+            .map_err(|err| internal("failed to load the auction contract", &err))?;
+        find_free_agent_exception_holder(&auction_contract, db)
+            .await
+            .map_err(|err| internal("failed to load the exception holder", &err))
+    }
+
+    /// What the exception holder would pay if it won at the current high bid (or the minimum bid
+    /// before anyone has bid), after its discount.
+    ///
+    /// Null for an auction with no exception, and once the player has signed, because there is no
+    /// free agent contract left to price.
+    async fn projected_exception_salary(&self, ctx: &Context<'_>) -> Result<Option<i16>> {
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+
+        let auction_contract = self
+            .model
+            .get_contract(db)
+            .await
+            .map_err(|err| internal("failed to load the auction contract", &err))?;
+        let Some(exception_holder_team_id) =
+            find_free_agent_exception_holder(&auction_contract, db)
+                .await
+                .map_err(|err| internal("failed to load the exception holder", &err))?
+        else {
+            return Ok(None);
+        };
+        // A trade during the auction replaces the row the auction points at.
+        let fa_contract = auction_contract
+            .get_latest_in_chain(db)
+            .await
+            .map_err(|err| internal("failed to load the auction contract", &err))?;
+        let signing_amount = self
+            .model
+            .get_latest_bid(db)
+            .await
+            .map_err(|err| internal("failed to load the high bid", &err))?
+            .map_or(self.model.minimum_bid_amount, |bid| bid.bid_amount);
+        let Ok(resigned_contract) = fa_contract.sign_rfa_or_ufa_contract_to_team(
+            exception_holder_team_id,
+            signing_amount,
+            FreeAgentException::Held,
+        ) else {
+            return Ok(None);
+        };
+
+        Ok(resigned_contract.salary.try_as_ref().copied())
+    }
+}
+
 #[derive(SimpleObject)]
 pub struct AuctionBid {
     pub id: i64,
@@ -429,6 +495,11 @@ async fn load_auction_in_league(ctx: &Context<'_>, auction_id: i64) -> Result<au
     Ok(auction_model)
 }
 
+fn internal(context: &str, error: &Report) -> GraphQlError {
+    tracing::error!(error = ?error, context);
+    code_error(ErrorCode::Internal)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
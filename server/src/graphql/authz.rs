@@ -1,17 +1,20 @@
 //! League-role authorization shared by every resolver.
 //!
 //! Roles come from the caller's `team_user` row in the session's selected league,
-//! so nothing about authorization is client-supplied. The declarative half (is the
+//! or in the league of the API token the request was made with, so nothing about
+//! authorization is client-supplied. The declarative half (is the
 //! caller a member / a commissioner?) is an async-graphql [`Guard`]; the
 //! caller-team-owns-this-asset half stays in the resolver, because it needs the
 //! resolved asset.
 
 use async_graphql::{Context, Guard, Result};
 use fbkl_entity::{
+    api_token,
     sea_orm::DatabaseConnection,
     team,
     team_user::{self, LeagueRole},
     team_user_queries::get_team_user_by_user_and_league,
+    user,
 };
 use tower_sessions::Session;
 
@@ -39,15 +42,8 @@ pub async fn require_league_role(
     ctx: &Context<'_>,
     requirement: RoleRequirement,
 ) -> Result<(team_user::Model, team::Model)> {
-    let session = ctx.data_unchecked::<Session>();
     let db = ctx.data_unchecked::<DatabaseConnection>();
-
-    let Some(user_id) = session_value(session, "user_id").await? else {
-        return Err(code_error(ErrorCode::Unauthenticated));
-    };
-    let Some(league_id) = session_value(session, "selected_league_id").await? else {
-        return Err(graphql_error(ErrorCode::BadRequest, "no league selected"));
-    };
+    let (user_id, league_id) = caller_user_and_league(ctx).await?;
 
     let Some((team_user, maybe_team)) = get_team_user_by_user_and_league(&user_id, &league_id, db)
         .await
@@ -72,6 +68,34 @@ pub async fn require_league_role(
     Ok((team_user, team))
 }
 
+/// The logged-in user, for fields that belong to the user rather than to a league. An API token
+/// is limited to its league, so these fields need a session.
+pub fn require_current_user(ctx: &Context<'_>) -> Result<user::Model> {
+    if ctx.data_opt::<api_token::Model>().is_some() {
+        return Err(code_error(ErrorCode::Forbidden));
+    }
+    ctx.data_unchecked::<Option<user::Model>>()
+        .clone()
+        .ok_or_else(|| code_error(ErrorCode::Unauthenticated))
+}
+
+/// The caller's user and league: an API token's own, or else the session's user and selected
+/// league.
+async fn caller_user_and_league(ctx: &Context<'_>) -> Result<(i64, i64)> {
+    if let Some(api_token) = ctx.data_opt::<api_token::Model>() {
+        return Ok((api_token.user_id, api_token.league_id));
+    }
+
+    let session = ctx.data_unchecked::<Session>();
+    let Some(user_id) = session_value(session, "user_id").await? else {
+        return Err(code_error(ErrorCode::Unauthenticated));
+    };
+    let Some(league_id) = session_value(session, "selected_league_id").await? else {
+        return Err(graphql_error(ErrorCode::BadRequest, "no league selected"));
+    };
+    Ok((user_id, league_id))
+}
+
 async fn session_value(session: &Session, key: &str) -> Result<Option<i64>> {
     session.get::<i64>(key).await.map_err(|session_err| {
         tracing::error!(error = ?session_err, key, "failed to read session");
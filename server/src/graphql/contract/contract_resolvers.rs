@@ -5,9 +5,11 @@ use async_graphql::{Context, Object, Result};
 use fbkl_entity::{
     contract_queries::{find_contract_by_id, find_contract_chain},
     sea_orm::DatabaseConnection,
+    team_user::LeagueRole,
 };
+use fbkl_logic::annual_contract_advancement::preview_league_contract_advancement;
 
-use super::Contract;
+use super::{Contract, TeamAdvancementPreview};
 use crate::graphql::{
     ErrorCode, LeagueRoleGuard, RoleRequirement, code_error, require_league_role,
 };
@@ -56,4 +58,44 @@ impl ContractQuery {
             .map(|model| Contract::from_model(model).map_err(|_| code_error(ErrorCode::Internal)))
             .collect()
     }
+
+    /// Dry run of the annual contract advancement that runs at `PreseasonStart`: every active
+    /// contract's next kind, year and salary, which become RFAs or UFAs, and which free agent
+    /// contracts expire. Nothing is written. Owners see their own team; the commissioner sees the
+    /// whole league, or one team when `team_id` is given.
+    #[graphql(guard = "LeagueRoleGuard(RoleRequirement::Member)")]
+    async fn contract_advancement_preview(
+        &self,
+        ctx: &Context<'_>,
+        team_id: Option<i64>,
+    ) -> Result<Vec<TeamAdvancementPreview>> {
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+        let (team_user, caller_team) = require_league_role(ctx, RoleRequirement::Member).await?;
+        let is_commissioner = team_user.league_role == LeagueRole::LeagueCommissioner;
+
+        let visible_team_id = match team_id {
+            Some(requested) if requested != team_user.team_id && !is_commissioner => {
+                return Err(code_error(ErrorCode::Forbidden));
+            }
+            Some(requested) => Some(requested),
+            None if is_commissioner => None,
+            None => Some(team_user.team_id),
+        };
+
+        let previews = preview_league_contract_advancement(caller_team.league_id, db)
+            .await
+            .map_err(|err| {
+                tracing::error!(error = ?err, "failed to preview contract advancement");
+                code_error(ErrorCode::Internal)
+            })?;
+
+        previews
+            .into_iter()
+            .filter(|preview| visible_team_id.is_none_or(|id| preview.team_id == Some(id)))
+            .map(|preview| {
+                TeamAdvancementPreview::from_preview(preview)
+                    .map_err(|_| code_error(ErrorCode::Internal))
+            })
+            .collect()
+    }
 }
@@ -1,6 +1,7 @@
-use async_graphql::{Context, Object, Result, dataloader::DataLoader};
+use async_graphql::{Context, Enum, Object, Result, SimpleObject, dataloader::DataLoader};
 use color_eyre::eyre::eyre;
 use fbkl_entity::contract::{self, ContractKind, ContractStatus};
+use fbkl_logic::annual_contract_advancement;
 
 use crate::{
     error::FbklError,
@@ -141,3 +142,83 @@ impl Contract {
         self.team_id
     }
 }
+
+/// `fbkl_logic::annual_contract_advancement::ContractAdvancementOutcome`.
+#[derive(Copy, Clone, Debug, Enum, Eq, PartialEq)]
+pub enum ContractAdvancementOutcome {
+    Advances,
+    BecomesRestrictedFreeAgent,
+    BecomesUnrestrictedFreeAgent,
+    Expires,
+    Fails,
+}
+
+impl ContractAdvancementOutcome {
+    const fn from_logic(outcome: annual_contract_advancement::ContractAdvancementOutcome) -> Self {
+        match outcome {
+            annual_contract_advancement::ContractAdvancementOutcome::Advances => Self::Advances,
+            annual_contract_advancement::ContractAdvancementOutcome::BecomesRestrictedFreeAgent => {
+                Self::BecomesRestrictedFreeAgent
+            }
+            annual_contract_advancement::ContractAdvancementOutcome::BecomesUnrestrictedFreeAgent => {
+                Self::BecomesUnrestrictedFreeAgent
+            }
+            annual_contract_advancement::ContractAdvancementOutcome::Expires => Self::Expires,
+            annual_contract_advancement::ContractAdvancementOutcome::Fails => Self::Fails,
+        }
+    }
+}
+
+/// One active contract and what the season rollover would make of it.
+#[derive(SimpleObject)]
+pub struct ContractAdvancementPreview {
+    pub contract: Contract,
+    pub outcome: ContractAdvancementOutcome,
+    /// The next season's kind, year and salary; `null` when the contract expires or fails.
+    pub next_kind: Option<ContractKind>,
+    pub next_year_number: Option<i16>,
+    /// Also `null` for a new UFA, whose salary is set at the veteran auction.
+    pub next_salary: Option<i16>,
+    /// Why the rollover would fail on this contract, failing the whole league's advancement.
+    pub failure: Option<String>,
+}
+
+/// A team's active contracts through the season rollover.
+#[derive(SimpleObject)]
+pub struct TeamAdvancementPreview {
+    /// `null` for contracts no team holds.
+    pub team_id: Option<i64>,
+    pub contracts: Vec<ContractAdvancementPreview>,
+}
+
+impl TeamAdvancementPreview {
+    pub fn from_preview(
+        preview: annual_contract_advancement::TeamAdvancementPreview,
+    ) -> Result<Self, FbklError> {
+        let contracts = preview
+            .contracts
+            .into_iter()
+            .map(|contract_preview| {
+                let next_season = contract_preview.next_season;
+                let outcome = ContractAdvancementOutcome::from_logic(contract_preview.outcome);
+                Ok(ContractAdvancementPreview {
+                    contract: Contract::from_model(&contract_preview.contract)?,
+                    outcome,
+                    next_kind: next_season.as_ref().map(|season| season.kind),
+                    next_year_number: next_season.as_ref().map(|season| season.year_number),
+                    next_salary: next_season
+                        .filter(|_| {
+                            outcome != ContractAdvancementOutcome::BecomesUnrestrictedFreeAgent
+                        })
+                        .map(|season| season.salary),
+                    failure: contract_preview.failure,
+                })
+            })
+            .collect::<Result<Vec<_>, FbklError>>()?;
+
+        Ok(Self {
+            team_id: preview.team_id,
+            contracts,
+        })
+    }
+}
@@ -3,9 +3,11 @@
 //! Every classification rule lives in `fbkl_logic::eligibility`; these resolvers only fetch,
 //! authorize, and map to GraphQL types. The two mutations stay deliberately separate:
 //! `setPlayerNbaStatus` corrects the underlying *facts*, `overridePlayerEligibility`
-//! overrides the *derived classification*.
+//! overrides the *derived classification*. `setPlayerNbaInjuryStatus` corrects the NBA injury
+//! report fact that gates the IR slot (rules §10.1).
 
 use async_graphql::{Context, Object, Result, SimpleObject};
+use chrono::Utc;
 use fbkl_entity::{
     contract::RelatedPlayer,
     eligibility_queries::{
@@ -13,13 +15,13 @@ use fbkl_entity::{
         set_player_eligibility_override, set_player_nba_status,
     },
     league_player_queries::find_league_player_by_id,
-    player::EligibilityClassification,
-    player_queries::find_player_by_id,
+    player::{EligibilityClassification, NbaInjuryStatus, NbaRosterSource},
+    player_queries::{find_player_by_id, set_player_nba_injury_status},
     sea_orm::DatabaseConnection,
 };
 use fbkl_logic::eligibility;
 
-use super::super::player::{LeagueOrRealPlayer, PlayerSearchKind};
+use super::super::player::{LeagueOrRealPlayer, PlayerSearchKind, RealPlayer};
 use crate::graphql::{
     ErrorCode, LeagueRoleGuard, RoleRequirement, code_error, current_season, graphql_error,
     require_league_role,
@@ -151,6 +153,35 @@ impl EligibilityMutation {
         Ok(LeagueOrRealPlayer::from_related_player(updated, season))
     }
 
+    /// Commissioner correction of a real player's NBA injury status. Stands until an injury report
+    /// newer than the correction is imported.
+    #[graphql(guard = "LeagueRoleGuard(RoleRequirement::Commissioner)")]
+    async fn set_player_nba_injury_status(
+        &self,
+        ctx: &Context<'_>,
+        id: i64,
+        status: NbaInjuryStatus,
+    ) -> Result<RealPlayer> {
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+        let (_, caller_team) = require_league_role(ctx, RoleRequirement::Commissioner).await?;
+        let season = current_season(ctx, caller_team.league_id).await?;
+        let player_model = find_player_by_id(id, db)
+            .await
+            .map_err(|_| code_error(ErrorCode::NotFound))?;
+
+        let updated = set_player_nba_injury_status(
+            player_model,
+            status,
+            NbaRosterSource::CommissionerOverride,
+            Utc::now().into(),
+            db,
+        )
+        .await
+        .map_err(|err| internal("failed to set the NBA injury status", &err))?;
+
+        Ok(RealPlayer::from_model(updated, season))
+    }
+
     /// Commissioner override of the *derived* classification. `classification: null` clears it.
     #[graphql(guard = "LeagueRoleGuard(RoleRequirement::Commissioner)")]
     async fn override_player_eligibility(
@@ -59,6 +59,57 @@ pub enum ErrorCode {
     DraftNoRosterSpace,
     /// The season's lottery has already been drawn and cannot be re-rolled (rules §7.2.5).
     DraftLotteryAlreadyRun,
+    /// The team listing or searching with an asset does not hold it.
+    TradeBlockAssetNotOwned,
+    /// The asset or need is already on the team's trade block.
+    TradeBlockAlreadyListed,
+    /// The trade cannot be re-proposed without its rejecting teams (not the proposer, nobody
+    /// rejected, or too little would be left).
+    TradeCannotBeReproposed,
+    /// A weekly move reorder did not list exactly the week's pending moves.
+    WeeklyMoveOrderInvalid,
+    /// A season-start roster declaration was refused: an ineligible move, or a roster it leaves
+    /// still illegal (rules §5.1.3, §11.4).
+    SeasonStartDeclarationRejected,
+    /// A drop-penalty waiver was refused: not a penalized drop, already waived, or outside the
+    /// keeper-deadline-to-season-end window (rules §9.1.4).
+    DropPenaltyWaiverRejected,
+    /// A ruling on held roster moves was refused: the deadline is not a passed roster lock, the
+    /// team has nothing held there, or no later weekly lock is left to send rejected moves to.
+    HeldRosterMovesRulingRejected,
+    /// Only an RD or RDI contract can be activated into a rookie contract.
+    RookieActivationWrongKind,
+    /// The contract to activate has been replaced or expired.
+    RookieActivationInactive,
+    /// Activating would put the team over its salary cap.
+    RookieActivationInsufficientCap,
+    /// Activating would need a rookie/veteran roster spot the team does not have.
+    RookieActivationNoRosterSpace,
+    /// An RD↔RDI move was asked of a contract of the wrong kind.
+    RdiMoveWrongKind,
+    /// The contract was already activated, so it cannot go back to RD or RDI.
+    RdiMoveAfterActivation,
+    /// The team already holds the one RDI contract a roster may have (rules §11.3).
+    RdiSlotTaken,
+    /// RDI needs a rookie-draft-eligible player (rules §11.3.1).
+    RdiNotRookieDraftEligible,
+    /// RDI needs a player who has never been on an NBA roster (rules §11.3.1).
+    RdiPlayerOnNbaRoster,
+    /// The player was RD after an in-season legalization, so cannot be RDI (rules §11.3.1).
+    RdiFormerRookieDevelopment,
+    /// Only players on the NBA injured reserve may move to IR (rules §10.1).
+    IrPlayerNotOnNbaIr,
+    /// A player won at auction this week cannot be dropped for another won the same week (rules
+    /// §8.3.7).
+    SameWeekAuctionWinDrop,
+    /// A team ownership transfer was refused (rules §2.4.1–§2.4.2).
+    OwnershipTransferRejected,
+    /// A league player could not be merged into that real player: already merged, or the real
+    /// player is already linked or under contract in the league.
+    LeaguePlayerMergeRejected,
+    /// A league invitation could not be created, accepted or revoked: expired, already used,
+    /// revoked, sent to someone else, or the team's seat is not open.
+    LeagueInvitationRejected,
     /// Server-side fault; message is deliberately generic.
     Internal,
 }
@@ -89,6 +140,28 @@ impl ErrorCode {
             Self::DraftReDraftBanned => "DRAFT_RE_DRAFT_BANNED",
             Self::DraftNoRosterSpace => "DRAFT_NO_ROSTER_SPACE",
             Self::DraftLotteryAlreadyRun => "DRAFT_LOTTERY_ALREADY_RUN",
+            Self::TradeBlockAssetNotOwned => "TRADE_BLOCK_ASSET_NOT_OWNED",
+            Self::TradeBlockAlreadyListed => "TRADE_BLOCK_ALREADY_LISTED",
+            Self::TradeCannotBeReproposed => "TRADE_CANNOT_BE_REPROPOSED",
+            Self::WeeklyMoveOrderInvalid => "WEEKLY_MOVE_ORDER_INVALID",
+            Self::SeasonStartDeclarationRejected => "SEASON_START_DECLARATION_REJECTED",
+            Self::DropPenaltyWaiverRejected => "DROP_PENALTY_WAIVER_REJECTED",
+            Self::HeldRosterMovesRulingRejected => "HELD_ROSTER_MOVES_RULING_REJECTED",
+            Self::RookieActivationWrongKind => "ROOKIE_ACTIVATION_WRONG_KIND",
+            Self::RookieActivationInactive => "ROOKIE_ACTIVATION_INACTIVE",
+            Self::RookieActivationInsufficientCap => "ROOKIE_ACTIVATION_INSUFFICIENT_CAP",
+            Self::RookieActivationNoRosterSpace => "ROOKIE_ACTIVATION_NO_ROSTER_SPACE",
+            Self::RdiMoveWrongKind => "RDI_MOVE_WRONG_KIND",
+            Self::RdiMoveAfterActivation => "RDI_MOVE_AFTER_ACTIVATION",
+            Self::RdiSlotTaken => "RDI_SLOT_TAKEN",
+            Self::RdiNotRookieDraftEligible => "RDI_NOT_ROOKIE_DRAFT_ELIGIBLE",
+            Self::RdiPlayerOnNbaRoster => "RDI_PLAYER_ON_NBA_ROSTER",
+            Self::RdiFormerRookieDevelopment => "RDI_FORMER_ROOKIE_DEVELOPMENT",
+            Self::IrPlayerNotOnNbaIr => "IR_PLAYER_NOT_ON_NBA_IR",
+            Self::SameWeekAuctionWinDrop => "SAME_WEEK_AUCTION_WIN_DROP",
+            Self::OwnershipTransferRejected => "OWNERSHIP_TRANSFER_REJECTED",
+            Self::LeaguePlayerMergeRejected => "LEAGUE_PLAYER_MERGE_REJECTED",
+            Self::LeagueInvitationRejected => "LEAGUE_INVITATION_REJECTED",
             Self::Internal => "INTERNAL",
         }
     }
@@ -124,6 +197,38 @@ impl ErrorCode {
             Self::DraftReDraftBanned => "that player was dropped during this draft",
             Self::DraftNoRosterSpace => "drafting would exceed your roster limit",
             Self::DraftLotteryAlreadyRun => "this season's lottery has already been drawn",
+            Self::TradeBlockAssetNotOwned => "your team does not hold that asset",
+            Self::TradeBlockAlreadyListed => "that is already on your trade block",
+            Self::TradeCannotBeReproposed => "this trade cannot be re-proposed",
+            Self::WeeklyMoveOrderInvalid => "the new order must list each pending move once",
+            Self::SeasonStartDeclarationRejected => {
+                "the season-start roster declaration is not legal"
+            }
+            Self::DropPenaltyWaiverRejected => "that drop's penalty cannot be waived",
+            Self::HeldRosterMovesRulingRejected => "those held moves cannot be ruled on",
+            Self::RookieActivationWrongKind => "only RD and RDI contracts can be activated",
+            Self::RookieActivationInactive => "that contract is no longer active",
+            Self::RookieActivationInsufficientCap => {
+                "activating would put the team over its salary cap"
+            }
+            Self::RookieActivationNoRosterSpace => "there is no open roster spot to activate into",
+            Self::RdiMoveWrongKind => "that contract cannot make this move",
+            Self::RdiMoveAfterActivation => {
+                "an activated rookie cannot return to rookie development"
+            }
+            Self::RdiSlotTaken => "the team already has an RDI contract",
+            Self::RdiNotRookieDraftEligible => "that player is not rookie-draft-eligible",
+            Self::RdiPlayerOnNbaRoster => "that player has been on an NBA roster",
+            Self::RdiFormerRookieDevelopment => "a former RD player cannot move to RDI",
+            Self::IrPlayerNotOnNbaIr => "that player is not on the NBA injured reserve",
+            Self::SameWeekAuctionWinDrop => {
+                "a player won this week cannot be dropped to make room for another won the same week"
+            }
+            Self::OwnershipTransferRejected => "the team cannot be transferred to that user",
+            Self::LeaguePlayerMergeRejected => {
+                "the league player cannot be merged into that player"
+            }
+            Self::LeagueInvitationRejected => "that invitation cannot be used",
             Self::Internal => "internal server error",
         }
     }
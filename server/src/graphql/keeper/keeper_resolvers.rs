@@ -6,7 +6,7 @@
 use async_graphql::{Context, Error as GraphQlError, Object, Result, SimpleObject};
 use color_eyre::Report;
 use fbkl_entity::{
-    contract,
+    contract::{self, ContractKind},
     contract_queries::find_contract_by_id,
     sea_orm::{DatabaseConnection, TransactionTrait},
     team,
@@ -15,7 +15,7 @@ use fbkl_entity::{
     transaction_queries::find_keeper_deadline_transaction,
 };
 use fbkl_logic::deadline_processing::keeper_deadline::{
-    KeeperValidationError, save_keeper_team_update, validate_team_keepers,
+    self, KeeperValidationError, plan_keepers, save_keeper_team_update, validate_team_keepers,
 };
 
 use super::super::team::TeamUpdate;
@@ -31,6 +31,100 @@ pub struct KeeperValidation {
     pub error: Option<String>,
 }
 
+/// Next season's keeper totals for a candidate set, against the league's keeper limits.
+#[derive(SimpleObject)]
+pub struct KeeperSeasonTotals {
+    pub end_of_season_year: i16,
+    /// Contracts counted toward the limits; RD(I) contracts are exempt.
+    pub contract_count: usize,
+    pub total_salary: i16,
+    pub contract_count_limit: usize,
+    pub total_salary_limit: i16,
+    /// Candidates that will be RFAs or UFAs next season and so cannot be kept.
+    pub unkeepable_contract_ids: Vec<i64>,
+    pub within_limits: bool,
+}
+
+/// One projected season of a candidate keeper's contract.
+#[derive(SimpleObject)]
+pub struct ProjectedContractSeason {
+    pub end_of_season_year: i16,
+    pub kind: ContractKind,
+    pub year_number: i16,
+    pub salary: i16,
+}
+
+/// When a candidate keeper becomes an RFA or UFA, and the re-sign discount the team would hold.
+#[derive(SimpleObject)]
+pub struct ProjectedFreeAgency {
+    pub end_of_season_year: i16,
+    pub kind: ContractKind,
+    /// Share of the winning bid taken off the re-sign, rounded up.
+    pub discount_percent: i16,
+    /// Most the discount can take off, if capped.
+    pub max_discount: Option<i16>,
+    /// Lowest a held RFA re-sign can come to; `null` for UFAs.
+    pub minimum_salary: Option<i16>,
+}
+
+#[derive(SimpleObject)]
+pub struct KeeperProjection {
+    pub contract_id: i64,
+    pub seasons: Vec<ProjectedContractSeason>,
+    /// `null` if the contract is still running at the end of the horizon.
+    pub free_agency: Option<ProjectedFreeAgency>,
+}
+
+#[derive(SimpleObject)]
+pub struct KeeperPlan {
+    pub next_season: KeeperSeasonTotals,
+    pub projections: Vec<KeeperProjection>,
+}
+
+impl From<keeper_deadline::KeeperPlan> for KeeperPlan {
+    fn from(plan: keeper_deadline::KeeperPlan) -> Self {
+        let within_limits = plan.next_season.within_limits();
+        let next_season = plan.next_season;
+        Self {
+            next_season: KeeperSeasonTotals {
+                end_of_season_year: next_season.end_of_season_year,
+                contract_count: next_season.contract_count,
+                total_salary: next_season.total_salary,
+                contract_count_limit: next_season.contract_count_limit,
+                total_salary_limit: next_season.total_salary_limit,
+                unkeepable_contract_ids: next_season.unkeepable_contract_ids,
+                within_limits,
+            },
+            projections: plan
+                .projections
+                .into_iter()
+                .map(|projection| KeeperProjection {
+                    contract_id: projection.contract_id,
+                    seasons: projection
+                        .seasons
+                        .into_iter()
+                        .map(|season| ProjectedContractSeason {
+                            end_of_season_year: season.end_of_season_year,
+                            kind: season.kind,
+                            year_number: season.year_number,
+                            salary: season.salary,
+                        })
+                        .collect(),
+                    free_agency: projection
+                        .free_agency
+                        .map(|free_agency| ProjectedFreeAgency {
+                            end_of_season_year: free_agency.end_of_season_year,
+                            kind: free_agency.kind,
+                            discount_percent: free_agency.exception.percent,
+                            max_discount: free_agency.exception.max_discount,
+                            minimum_salary: free_agency.minimum_salary,
+                        }),
+                })
+                .collect(),
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct KeeperQuery;
 
@@ -97,6 +191,21 @@ impl KeeperQuery {
             },
         })
     }
+
+    /// Projects candidate keepers three seasons out with the real advancement math: next season's
+    /// totals against the 14-contract and $100 limits, and when each player reaches RFA/UFA.
+    #[graphql(guard = "LeagueRoleGuard(RoleRequirement::Member)")]
+    async fn keeper_planner(
+        &self,
+        ctx: &Context<'_>,
+        contract_ids: Vec<i64>,
+    ) -> Result<KeeperPlan> {
+        let (_, contracts) = load_own_keeper_contracts(ctx, &contract_ids).await?;
+
+        let plan = plan_keepers(&contracts)
+            .map_err(|err| internal("failed to project keeper contracts", &err))?;
+        Ok(plan.into())
+    }
 }
 
 #[derive(Default)]
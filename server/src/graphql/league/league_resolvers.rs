@@ -1,20 +1,40 @@
 use crate::{
     error::FbklError,
-    graphql::team::{Team, TeamUser},
+    graphql::{
+        ErrorCode, LeagueRoleGuard, RoleRequirement, code_error, current_season, graphql_error,
+        require_league_role,
+        team::{Team, TeamUser},
+    },
     session::{enforce_logged_in, get_current_user},
 };
 
-use super::League;
+use super::{League, LeagueInvitation};
 use async_graphql::{Context, Error as GraphQlError, Object, Result};
 use axum::http::StatusCode;
+use chrono::Utc;
+use color_eyre::Report;
+use fbkl_auth::{decode_token, generate_token};
 use fbkl_entity::{
+    api_token,
+    league::{RosterLegalizationPolicy, TradeRejectionPolicy},
+    league_invitation::LeagueInvitationKind,
+    league_invitation_queries::{
+        find_league_invitation_by_id_in_league, find_league_invitation_by_token,
+        find_pending_league_invitations,
+    },
     league_queries::{
-        NewLeagueWithCommissioner, create_league_with_commissioner, find_league_by_user,
-        find_leagues_by_user,
+        NewLeagueWithCommissioner, create_league_with_commissioner, find_league_by_id,
+        find_league_by_user, find_leagues_by_user, update_league_roster_legalization_policy,
+        update_league_trade_rejection_policy,
     },
-    sea_orm::DatabaseConnection,
+    sea_orm::{DatabaseConnection, TransactionTrait},
+    team_queries::{find_team_by_id_in_league, find_teams_by_name_in_league, insert_team},
     user,
 };
+use fbkl_logic::league_invitations::{
+    LeagueInvitationRejection, NewLeagueInvitation, accept_league_invitation,
+    create_league_invitation, revoke_league_invitation,
+};
 use tower_sessions::Session;
 
 #[derive(Default)]
@@ -28,16 +48,31 @@ impl LeagueQuery {
         };
         let db = ctx.data_unchecked::<DatabaseConnection>();
         let league_models = find_leagues_by_user(&user_model, db).await?;
+        // An API token only sees the league it was created in.
+        let maybe_token_league_id = ctx
+            .data_opt::<api_token::Model>()
+            .map(|api_token| api_token.league_id);
 
-        let leagues = league_models.into_iter().map(League::from_model).collect();
+        let leagues = league_models
+            .into_iter()
+            .filter(|league_model| maybe_token_league_id.is_none_or(|id| id == league_model.id))
+            .map(League::from_model)
+            .collect();
         Ok(leagues)
     }
 
     async fn league(&self, ctx: &Context<'_>) -> Result<League, FbklError> {
-        let session = ctx.data_unchecked::<Session>();
-        let selected_league_id: i64 = match session.get("selected_league_id").await? {
-            None => return Err(StatusCode::BAD_REQUEST.into()),
-            Some(id) => id,
+        // An API token acts in its own league; a session in the one it selected.
+        let selected_league_id: i64 = match ctx.data_opt::<api_token::Model>() {
+            Some(api_token) => api_token.league_id,
+            None => match ctx
+                .data_unchecked::<Session>()
+                .get("selected_league_id")
+                .await?
+            {
+                None => return Err(StatusCode::BAD_REQUEST.into()),
+                Some(id) => id,
+            },
         };
 
         let Some(user_model) = ctx.data_unchecked::<Option<user::Model>>().to_owned() else {
@@ -52,6 +87,23 @@ impl LeagueQuery {
                 |league_model| Ok(League::from_model(league_model)),
             )
     }
+
+    /// The selected league's invitations that can still be accepted, oldest first.
+    #[graphql(guard = "LeagueRoleGuard(RoleRequirement::Commissioner)")]
+    async fn pending_league_invitations(&self, ctx: &Context<'_>) -> Result<Vec<LeagueInvitation>> {
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+        let (_, caller_team) = require_league_role(ctx, RoleRequirement::Commissioner).await?;
+
+        let invitations =
+            find_pending_league_invitations(caller_team.league_id, Utc::now().fixed_offset(), db)
+                .await
+                .map_err(|err| internal("failed to load pending invitations", &err))?;
+
+        Ok(invitations
+            .into_iter()
+            .map(LeagueInvitation::from_model)
+            .collect())
+    }
 }
 
 #[derive(Default)]
@@ -117,4 +169,209 @@ impl LeagueMutation {
             },
         }
     }
+
+    /// Sets what one team's rejection does to a trade between three or more teams. Applies to
+    /// rejections from now on; trades already closed stay closed.
+    #[graphql(guard = "LeagueRoleGuard(RoleRequirement::Commissioner)")]
+    async fn set_trade_rejection_policy(
+        &self,
+        ctx: &Context<'_>,
+        trade_rejection_policy: TradeRejectionPolicy,
+    ) -> Result<League> {
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+        let (_, caller_team) = require_league_role(ctx, RoleRequirement::Commissioner).await?;
+
+        let league_model = find_league_by_id(caller_team.league_id, db)
+            .await
+            .map_err(|_| code_error(ErrorCode::NotFound))?;
+        let updated_league =
+            update_league_trade_rejection_policy(league_model, trade_rejection_policy, db)
+                .await
+                .map_err(|err| {
+                    tracing::error!(error = ?err, "failed to set the trade rejection policy");
+                    code_error(ErrorCode::Internal)
+                })?;
+
+        Ok(League::from_model(updated_league))
+    }
+
+    /// Sets what a weekly roster lock does with a team whose roster is still illegal. Applies from
+    /// the next lock on.
+    #[graphql(guard = "LeagueRoleGuard(RoleRequirement::Commissioner)")]
+    async fn set_roster_legalization_policy(
+        &self,
+        ctx: &Context<'_>,
+        roster_legalization_policy: RosterLegalizationPolicy,
+    ) -> Result<League> {
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+        let (_, caller_team) = require_league_role(ctx, RoleRequirement::Commissioner).await?;
+
+        let league_model = find_league_by_id(caller_team.league_id, db)
+            .await
+            .map_err(|_| code_error(ErrorCode::NotFound))?;
+        let updated_league =
+            update_league_roster_legalization_policy(league_model, roster_legalization_policy, db)
+                .await
+                .map_err(|err| {
+                    tracing::error!(error = ?err, "failed to set the roster legalization policy");
+                    code_error(ErrorCode::Internal)
+                })?;
+
+        Ok(League::from_model(updated_league))
+    }
+
+    /// Adds a team to the selected league. It has no owner until an invitation to it is accepted.
+    #[graphql(guard = "LeagueRoleGuard(RoleRequirement::Commissioner)")]
+    async fn create_team(&self, ctx: &Context<'_>, name: String) -> Result<Team> {
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+        let (_, caller_team) = require_league_role(ctx, RoleRequirement::Commissioner).await?;
+        let name = name.trim().to_owned();
+        if name.is_empty() {
+            return Err(graphql_error(
+                ErrorCode::BadRequest,
+                "the team needs a name",
+            ));
+        }
+
+        let teams_by_name = find_teams_by_name_in_league(caller_team.league_id, db)
+            .await
+            .map_err(|err| internal("failed to list teams in league", &err))?;
+        if teams_by_name.contains_key(&name) {
+            return Err(graphql_error(
+                ErrorCode::BadRequest,
+                "a team in this league already has that name",
+            ));
+        }
+        let team_model = insert_team(caller_team.league_id, name, db)
+            .await
+            .map_err(|err| internal("failed to create team", &err))?;
+
+        Ok(Team::from_model(team_model))
+    }
+
+    /// Creates a single-use invitation to own (`OWNER`) or co-own (`CO_OWNER`) a team. The returned
+    /// token is shown only here. `firstEndOfSeasonYear` defaults to the current season; `email`
+    /// restricts who can accept.
+    #[graphql(guard = "LeagueRoleGuard(RoleRequirement::Commissioner)")]
+    async fn create_league_invitation(
+        &self,
+        ctx: &Context<'_>,
+        team_id: i64,
+        kind: LeagueInvitationKind,
+        email: Option<String>,
+        first_end_of_season_year: Option<i16>,
+    ) -> Result<LeagueInvitation> {
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+        let (team_user, caller_team) =
+            require_league_role(ctx, RoleRequirement::Commissioner).await?;
+
+        let team_model = find_team_by_id_in_league(team_id, caller_team.league_id, db)
+            .await
+            .map_err(|_| code_error(ErrorCode::NotFound))?;
+        let first_end_of_season_year = match first_end_of_season_year {
+            Some(end_of_season_year) => end_of_season_year,
+            None => current_season(ctx, caller_team.league_id).await?,
+        };
+
+        let invitation_model = create_league_invitation(
+            &team_model,
+            NewLeagueInvitation {
+                kind,
+                email,
+                first_end_of_season_year,
+                token: generate_token().into_iter().collect(),
+            },
+            &team_user,
+            Utc::now().fixed_offset(),
+            db,
+        )
+        .await
+        .map_err(|err| map_invitation_error(&err))?;
+
+        Ok(LeagueInvitation::with_token(invitation_model))
+    }
+
+    /// Withdraws an invitation that has not been accepted.
+    #[graphql(guard = "LeagueRoleGuard(RoleRequirement::Commissioner)")]
+    async fn revoke_league_invitation(
+        &self,
+        ctx: &Context<'_>,
+        invitation_id: i64,
+    ) -> Result<LeagueInvitation> {
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+        let (_, caller_team) = require_league_role(ctx, RoleRequirement::Commissioner).await?;
+
+        let invitation_model =
+            find_league_invitation_by_id_in_league(invitation_id, caller_team.league_id, db)
+                .await
+                .map_err(|_| code_error(ErrorCode::NotFound))?;
+        let revoked = revoke_league_invitation(invitation_model, db)
+            .await
+            .map_err(|err| map_invitation_error(&err))?;
+
+        Ok(LeagueInvitation::from_model(revoked))
+    }
+
+    /// Accepts an invitation as the logged-in user, who becomes an owner of the invited team under
+    /// `nickname`. Returns the league joined.
+    async fn accept_league_invitation(
+        &self,
+        ctx: &Context<'_>,
+        token: String,
+        nickname: String,
+    ) -> Result<League> {
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+        let session = ctx.data_unchecked::<Session>();
+        let Some(user_model) = get_current_user(session.clone(), db).await? else {
+            return Err(code_error(ErrorCode::Unauthenticated));
+        };
+        if nickname.trim().is_empty() {
+            return Err(graphql_error(ErrorCode::BadRequest, "you need a nickname"));
+        }
+
+        let token_bytes =
+            decode_token(token.trim()).map_err(|_| code_error(ErrorCode::NotFound))?;
+        let invitation_model = find_league_invitation_by_token(token_bytes, db)
+            .await
+            .map_err(|err| internal("failed to look up invitation", &err))?
+            .ok_or_else(|| code_error(ErrorCode::NotFound))?;
+        let league_id = invitation_model.league_id;
+
+        let db_txn = db
+            .begin()
+            .await
+            .map_err(|err| internal("failed to start transaction", &err.into()))?;
+        accept_league_invitation(
+            invitation_model,
+            &user_model,
+            nickname.trim().to_owned(),
+            Utc::now().fixed_offset(),
+            &db_txn,
+        )
+        .await
+        .map_err(|err| map_invitation_error(&err))?;
+        db_txn
+            .commit()
+            .await
+            .map_err(|err| internal("failed to commit invitation acceptance", &err.into()))?;
+
+        let league_model = find_league_by_id(league_id, db)
+            .await
+            .map_err(|err| internal("failed to load the joined league", &err))?;
+        Ok(League::from_model(league_model))
+    }
+}
+
+fn map_invitation_error(error: &Report) -> GraphQlError {
+    error
+        .downcast_ref::<LeagueInvitationRejection>()
+        .map_or_else(
+            || internal("failed to process league invitation", error),
+            |rejection| graphql_error(ErrorCode::LeagueInvitationRejected, rejection.to_string()),
+        )
+}
+
+fn internal(context: &str, error: &Report) -> GraphQlError {
+    tracing::error!(error = ?error, context);
+    code_error(ErrorCode::Internal)
 }
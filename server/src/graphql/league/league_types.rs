@@ -1,7 +1,13 @@
-use async_graphql::{Context, Object, Result};
+use async_graphql::{Context, Object, Result, SimpleObject};
+use fbkl_auth::encode_token;
 use fbkl_entity::{
-    league, sea_orm::DatabaseConnection, team_queries::find_teams_in_league,
-    team_user_queries::get_team_user_by_user_and_league, user,
+    api_token,
+    league::{self, RosterLegalizationPolicy, TradeRejectionPolicy},
+    league_invitation::{self, LeagueInvitationKind, LeagueInvitationStatus},
+    sea_orm::DatabaseConnection,
+    team_queries::find_teams_in_league,
+    team_user_queries::get_team_user_by_user_and_league,
+    user,
 };
 
 use crate::{
@@ -16,6 +22,8 @@ use crate::{
 pub struct League {
     pub id: i64,
     pub name: String,
+    pub trade_rejection_policy: TradeRejectionPolicy,
+    pub roster_legalization_policy: RosterLegalizationPolicy,
 }
 
 impl League {
@@ -23,6 +31,8 @@ impl League {
         Self {
             id: league_model.id,
             name: league_model.name,
+            trade_rejection_policy: league_model.trade_rejection_policy,
+            roster_legalization_policy: league_model.roster_legalization_policy,
         }
     }
 }
@@ -37,6 +47,16 @@ impl League {
         self.name.clone()
     }
 
+    /// Whether one team's rejection ends a trade between three or more teams.
+    async fn trade_rejection_policy(&self) -> TradeRejectionPolicy {
+        self.trade_rejection_policy
+    }
+
+    /// What a weekly roster lock does with a team whose roster is still illegal.
+    async fn roster_legalization_policy(&self) -> RosterLegalizationPolicy {
+        self.roster_legalization_policy
+    }
+
     async fn teams(&self, ctx: &Context<'_>) -> Result<Vec<Team>, FbklError> {
         let db = ctx.data_unchecked::<DatabaseConnection>();
 
@@ -49,6 +69,8 @@ impl League {
         Ok(league_teams)
     }
 
+    /// The caller's team user in this league. Its `user` carries the caller's email, so it is
+    /// `null` for an API token, which may not read it.
     async fn current_team_user(
         &self,
         ctx: &Context<'_>,
@@ -69,8 +91,54 @@ impl League {
             nickname: team_user_model.nickname,
             team: Some(Team::from_model(team_model)),
             team_id: team_user_model.team_id,
-            user: Some(User::from_model(current_user)),
+            user: ctx
+                .data_opt::<api_token::Model>()
+                .is_none()
+                .then(|| User::from_model(current_user)),
             user_id: current_user.id,
         })))
     }
 }
+
+/// An invitation to own or co-own a team.
+#[derive(SimpleObject)]
+pub struct LeagueInvitation {
+    pub id: i64,
+    pub team_id: i64,
+    pub kind: LeagueInvitationKind,
+    /// Only the user with this email can accept, when set.
+    pub email: Option<String>,
+    pub status: LeagueInvitationStatus,
+    pub first_end_of_season_year: i16,
+    pub expires_at: String,
+    pub invited_by_team_user_id: i64,
+    /// The token to hand the invitee. Only returned when the invitation is created.
+    pub token: Option<String>,
+    pub created_at: String,
+}
+
+impl LeagueInvitation {
+    pub fn from_model(invitation_model: league_invitation::Model) -> Self {
+        Self {
+            id: invitation_model.id,
+            team_id: invitation_model.team_id,
+            kind: invitation_model.kind,
+            email: invitation_model.email,
+            status: invitation_model.status,
+            first_end_of_season_year: invitation_model.first_end_of_season_year,
+            expires_at: invitation_model.expires_at.to_rfc3339(),
+            invited_by_team_user_id: invitation_model.invited_by_team_user_id,
+            token: None,
+            created_at: invitation_model.created_at.to_rfc3339(),
+        }
+    }
+
+    /// The freshly created invitation, with its token.
+    pub fn with_token(invitation_model: league_invitation::Model) -> Self {
+        let token = encode_token(&invitation_model.token);
+        Self {
+            token: Some(token),
+            ..Self::from_model(invitation_model)
+        }
+    }
+}
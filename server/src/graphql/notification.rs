@@ -0,0 +1,4 @@
+mod notification_resolvers;
+mod notification_types;
+pub use notification_resolvers::*;
+pub use notification_types::*;
@@ -0,0 +1,156 @@
+//! The caller's notification center and how they want to be notified.
+//!
+//! Notifications belong to the user, not to the selected league: one list covers every league the
+//! user plays in. Rows are only ever written by `fbkl_logic::notifications::notify_team`, so the
+//! only mutations here are reading them and setting preferences.
+
+use async_graphql::{Context, Object, Result};
+use chrono::Utc;
+use fbkl_entity::{
+    notification::NotificationKind,
+    notification_preference::{self, NotificationEmailDelivery},
+    notification_preference_queries::{
+        find_notification_preferences_for_user, upsert_notification_preference,
+    },
+    notification_queries::{
+        count_unread_notifications, find_notifications_for_user, mark_notifications_read,
+    },
+    sea_orm::{DatabaseConnection, Iterable},
+};
+
+use super::{Notification, NotificationPreference, PagedNotifications};
+use crate::graphql::{ErrorCode, code_error, require_current_user};
+
+const MAX_PAGE_SIZE: u64 = 100;
+
+#[derive(Default)]
+pub struct NotificationQuery;
+
+#[Object]
+impl NotificationQuery {
+    /// One page of the caller's notifications, newest first.
+    async fn notifications(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = false)] unread_only: bool,
+        #[graphql(default = 0)] page: u64,
+        #[graphql(default = 25)] page_size: u64,
+    ) -> Result<PagedNotifications> {
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+        let user_model = require_current_user(ctx)?;
+
+        let paged = find_notifications_for_user(
+            user_model.id,
+            unread_only,
+            page,
+            page_size.min(MAX_PAGE_SIZE),
+            db,
+        )
+        .await
+        .map_err(|db_err| {
+            tracing::error!(error = ?db_err, user_id = user_model.id, "failed to load notifications");
+            code_error(ErrorCode::Internal)
+        })?;
+
+        Ok(PagedNotifications {
+            items: paged.items.iter().map(Notification::from_model).collect(),
+            total_items: paged.total_items,
+        })
+    }
+
+    /// How many of the caller's notifications are unread, for a badge.
+    async fn unread_notification_count(&self, ctx: &Context<'_>) -> Result<u64> {
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+        let user_model = require_current_user(ctx)?;
+
+        count_unread_notifications(user_model.id, db)
+            .await
+            .map_err(|db_err| {
+                tracing::error!(error = ?db_err, user_id = user_model.id, "failed to count notifications");
+                code_error(ErrorCode::Internal)
+            })
+    }
+
+    /// The caller's preference for every notification kind, defaults included.
+    async fn notification_preferences(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<Vec<NotificationPreference>> {
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+        let user_model = require_current_user(ctx)?;
+
+        let saved_preferences = find_notification_preferences_for_user(user_model.id, db)
+            .await
+            .map_err(|db_err| {
+                tracing::error!(error = ?db_err, user_id = user_model.id, "failed to load notification preferences");
+                code_error(ErrorCode::Internal)
+            })?;
+
+        Ok(NotificationKind::iter()
+            .map(|kind| {
+                let preference = saved_preferences
+                    .iter()
+                    .find(|preference| preference.kind == kind)
+                    .cloned()
+                    .unwrap_or_else(|| {
+                        notification_preference::Model::default_for(user_model.id, kind)
+                    });
+                NotificationPreference::from_model(&preference)
+            })
+            .collect())
+    }
+}
+
+#[derive(Default)]
+pub struct NotificationMutation;
+
+#[Object]
+impl NotificationMutation {
+    /// Marks the given notifications of the caller as read. Returns how many were unread.
+    async fn mark_notifications_read(&self, ctx: &Context<'_>, ids: Vec<i64>) -> Result<u64> {
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+        let user_model = require_current_user(ctx)?;
+
+        mark_notifications_read(user_model.id, Some(ids), Utc::now().into(), db)
+            .await
+            .map_err(|db_err| {
+                tracing::error!(error = ?db_err, user_id = user_model.id, "failed to mark notifications read");
+                code_error(ErrorCode::Internal)
+            })
+    }
+
+    /// Marks every notification of the caller as read. Returns how many were unread.
+    async fn mark_all_notifications_read(&self, ctx: &Context<'_>) -> Result<u64> {
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+        let user_model = require_current_user(ctx)?;
+
+        mark_notifications_read(user_model.id, None, Utc::now().into(), db)
+            .await
+            .map_err(|db_err| {
+                tracing::error!(error = ?db_err, user_id = user_model.id, "failed to mark notifications read");
+                code_error(ErrorCode::Internal)
+            })
+    }
+
+    /// Sets how the caller hears about one kind of notification. Applies to notifications written
+    /// from now on.
+    async fn update_notification_preference(
+        &self,
+        ctx: &Context<'_>,
+        kind: NotificationKind,
+        in_app: bool,
+        email_delivery: NotificationEmailDelivery,
+    ) -> Result<NotificationPreference> {
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+        let user_model = require_current_user(ctx)?;
+
+        let saved = upsert_notification_preference(user_model.id, kind, in_app, email_delivery, db)
+            .await
+            .map_err(|db_err| {
+                tracing::error!(error = ?db_err, user_id = user_model.id, "failed to save notification preference");
+                code_error(ErrorCode::Internal)
+            })?;
+
+        Ok(NotificationPreference::from_model(&saved))
+    }
+}
@@ -0,0 +1,58 @@
+use async_graphql::SimpleObject;
+use fbkl_entity::{
+    notification::{self, NotificationKind},
+    notification_preference::{self, NotificationEmailDelivery},
+};
+
+/// One entry in the caller's notification center.
+#[derive(SimpleObject)]
+pub struct Notification {
+    pub id: i64,
+    pub league_id: i64,
+    pub kind: NotificationKind,
+    /// The auction, draft selection, trade, RFA resolution, or deadline the notification is about;
+    /// which one depends on `kind`.
+    pub subject_id: Option<i64>,
+    pub message: String,
+    pub read_at: Option<String>,
+    pub created_at: String,
+}
+
+impl Notification {
+    pub(crate) fn from_model(entity: &notification::Model) -> Self {
+        Self {
+            id: entity.id,
+            league_id: entity.league_id,
+            kind: entity.kind,
+            subject_id: entity.subject_id,
+            message: entity.message.clone(),
+            read_at: entity.read_at.map(|read_at| read_at.to_rfc3339()),
+            created_at: entity.created_at.to_rfc3339(),
+        }
+    }
+}
+
+/// One page of the notification center. `totalItems` is the count for the unpaginated filter.
+#[derive(SimpleObject)]
+pub struct PagedNotifications {
+    pub items: Vec<Notification>,
+    pub total_items: u64,
+}
+
+/// How the caller hears about one kind of notification.
+#[derive(SimpleObject)]
+pub struct NotificationPreference {
+    pub kind: NotificationKind,
+    pub in_app: bool,
+    pub email_delivery: NotificationEmailDelivery,
+}
+
+impl NotificationPreference {
+    pub(crate) const fn from_model(entity: &notification_preference::Model) -> Self {
+        Self {
+            kind: entity.kind,
+            in_app: entity.in_app,
+            email_delivery: entity.email_delivery,
+        }
+    }
+}
@@ -6,19 +6,36 @@
 //!
 //! Eligibility is per-season, so these resolvers classify for the caller league's current season —
 //! resolved once per call, not per row. `playerEligibility` takes an explicit season to override it.
+//!
+//! `playerLeagueHistory` replays a player's contract chains in the caller's league through
+//! `fbkl_logic::player_history`. The commissioner merges a league player into the real player he
+//! became through `fbkl_logic::league_player_merge`.
 
-use async_graphql::{Context, Enum, Object, Result};
+use async_graphql::{Context, Enum, Error as GraphQlError, Object, Result};
+use chrono::Utc;
+use color_eyre::Report;
 use fbkl_entity::{
+    deadline_queries::find_most_recent_deadline_by_datetime,
     league_player_queries::{find_league_player_by_id, search_league_players_by_name},
     player::EligibilityClassification,
     player_queries::{find_player_by_id, search_players_by_name},
-    sea_orm::DatabaseConnection,
+    sea_orm::{DatabaseConnection, TransactionTrait},
+};
+use fbkl_logic::{
+    eligibility::{PlayerEligibilityFacts, classify_player},
+    league_player_merge::{
+        LeaguePlayerMergeRejection, find_real_player_match_candidates, merge_league_player,
+    },
+    player_history::player_league_history,
 };
-use fbkl_logic::eligibility::{PlayerEligibilityFacts, classify_player};
 
-use super::{LeagueOrRealPlayer, LeaguePlayer, RealPlayer};
+use super::{
+    LeagueOrRealPlayer, LeaguePlayer, LeaguePlayerMergeCandidate, LeaguePlayerMergeResult,
+    PlayerLeagueHistory, RealPlayer,
+};
 use crate::graphql::{
-    ErrorCode, LeagueRoleGuard, RoleRequirement, code_error, current_season, require_league_role,
+    ErrorCode, LeagueRoleGuard, RoleRequirement, code_error, contract::Contract, current_season,
+    graphql_error, require_league_role,
 };
 
 /// Bounds each search leg — the real-player table spans every NBA season.
@@ -139,4 +156,150 @@ impl PlayerQuery {
             season,
         ))
     }
+
+    /// Every contract a player has had in the caller's league, oldest first, with the transaction
+    /// behind each change and a season-by-season summary. `playerId` is a league player's id when
+    /// `isLeaguePlayer` is set, a real player's otherwise.
+    #[graphql(guard = "LeagueRoleGuard(RoleRequirement::Member)")]
+    async fn player_league_history(
+        &self,
+        ctx: &Context<'_>,
+        player_id: i64,
+        #[graphql(default = false)] is_league_player: bool,
+    ) -> Result<PlayerLeagueHistory> {
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+        let (_, caller_team) = require_league_role(ctx, RoleRequirement::Member).await?;
+
+        if is_league_player {
+            let model = find_league_player_by_id(player_id, db)
+                .await
+                .map_err(|_| code_error(ErrorCode::NotFound))?;
+            if model.league_id != caller_team.league_id {
+                return Err(code_error(ErrorCode::NotFound));
+            }
+        } else {
+            find_player_by_id(player_id, db)
+                .await
+                .map_err(|_| code_error(ErrorCode::NotFound))?;
+        }
+
+        let history = player_league_history(caller_team.league_id, player_id, is_league_player, db)
+            .await
+            .map_err(|history_err| {
+                tracing::error!(error = ?history_err, "failed to build player league history");
+                code_error(ErrorCode::Internal)
+            })?;
+        PlayerLeagueHistory::from_history(&history).map_err(|_| code_error(ErrorCode::Internal))
+    }
+
+    /// Real players who may be the given league player, by normalized name, with those whose NBA
+    /// debut fits the league player's draft year first. For the commissioner's merge.
+    #[graphql(guard = "LeagueRoleGuard(RoleRequirement::Commissioner)")]
+    async fn league_player_merge_candidates(
+        &self,
+        ctx: &Context<'_>,
+        league_player_id: i64,
+    ) -> Result<Vec<LeaguePlayerMergeCandidate>> {
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+        let (_, caller_team) = require_league_role(ctx, RoleRequirement::Commissioner).await?;
+
+        let league_player_model = find_league_player_by_id(league_player_id, db)
+            .await
+            .map_err(|_| code_error(ErrorCode::NotFound))?;
+        if league_player_model.league_id != caller_team.league_id {
+            return Err(code_error(ErrorCode::NotFound));
+        }
+
+        let season = current_season(ctx, caller_team.league_id).await?;
+        let candidates = find_real_player_match_candidates(&league_player_model, db)
+            .await
+            .map_err(|err| internal("failed to find league player merge candidates", &err))?;
+        Ok(candidates
+            .into_iter()
+            .map(|candidate| LeaguePlayerMergeCandidate {
+                player: RealPlayer::from_model(candidate.player, season),
+                is_draft_year_consistent: candidate.is_draft_year_consistent,
+            })
+            .collect())
+    }
+}
+
+#[derive(Default)]
+pub struct PlayerMutation;
+
+#[Object]
+impl PlayerMutation {
+    /// Merges a league player into the real (NBA) player he became. Each active contract chain
+    /// gains a link naming the real player, and the league player's eligibility override carries
+    /// over unless the real player has one of his own.
+    #[graphql(guard = "LeagueRoleGuard(RoleRequirement::Commissioner)")]
+    async fn merge_league_player(
+        &self,
+        ctx: &Context<'_>,
+        league_player_id: i64,
+        player_id: i64,
+    ) -> Result<LeaguePlayerMergeResult> {
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+        let (_, caller_team) = require_league_role(ctx, RoleRequirement::Commissioner).await?;
+
+        let league_player_model = find_league_player_by_id(league_player_id, db)
+            .await
+            .map_err(|_| code_error(ErrorCode::NotFound))?;
+        if league_player_model.league_id != caller_team.league_id {
+            return Err(code_error(ErrorCode::NotFound));
+        }
+        let player_model = find_player_by_id(player_id, db)
+            .await
+            .map_err(|_| code_error(ErrorCode::NotFound))?;
+        let current_deadline = find_most_recent_deadline_by_datetime(
+            caller_team.league_id,
+            Utc::now().fixed_offset(),
+            db,
+        )
+        .await
+        .map_err(|err| internal("failed to resolve the current deadline", &err))?;
+
+        let db_txn = db
+            .begin()
+            .await
+            .map_err(|err| internal("failed to start transaction", &err.into()))?;
+        let merge = merge_league_player(
+            league_player_model,
+            player_model,
+            &current_deadline,
+            &db_txn,
+        )
+        .await
+        .map_err(|err| map_merge_error(&err))?;
+        db_txn
+            .commit()
+            .await
+            .map_err(|err| internal("failed to commit league player merge", &err.into()))?;
+
+        let season = current_season(ctx, caller_team.league_id).await?;
+        Ok(LeaguePlayerMergeResult {
+            league_player: LeaguePlayer::from_model(merge.league_player, season),
+            relinked_contracts: merge
+                .relinked_contracts
+                .iter()
+                .map(Contract::from_model)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| code_error(ErrorCode::Internal))?,
+            carried_eligibility_override: merge.carried_eligibility_override,
+        })
+    }
+}
+
+fn map_merge_error(error: &Report) -> GraphQlError {
+    match error.downcast_ref::<LeaguePlayerMergeRejection>() {
+        Some(rejection) => {
+            graphql_error(ErrorCode::LeaguePlayerMergeRejected, rejection.to_string())
+        }
+        None => internal("failed to merge league player", error),
+    }
+}
+
+fn internal(context: &str, error: &Report) -> GraphQlError {
+    tracing::error!(error = ?error, context);
+    code_error(ErrorCode::Internal)
 }
@@ -1,15 +1,20 @@
-use async_graphql::{Context, Object, Result, SimpleObject, Union, dataloader::DataLoader};
+use async_graphql::{Context, Enum, Object, Result, SimpleObject, Union, dataloader::DataLoader};
 use color_eyre::eyre::eyre;
 use fbkl_entity::{
-    contract::RelatedPlayer,
+    contract::{ContractKind, RelatedPlayer},
     league_player,
-    player::{self, EligibilityClassification, NbaRosterSource},
+    player::{self, EligibilityClassification, NbaInjuryStatus, NbaRosterSource},
+};
+use fbkl_logic::{
+    eligibility::{PlayerEligibilityFacts, classify_player},
+    player_history,
 };
-use fbkl_logic::eligibility::{PlayerEligibilityFacts, classify_player};
 
 use crate::{
     error::FbklError,
-    graphql::{PlayerLoader, PositionLoader, RealTeamLoader},
+    graphql::{
+        PlayerLoader, PositionLoader, RealTeamLoader, contract::Contract, transaction::Transaction,
+    },
 };
 
 #[derive(Debug, Clone, Eq, PartialEq, Union)]
@@ -144,6 +149,24 @@ impl LeaguePlayer {
     }
 }
 
+/// The player's NBA injury status, which decides IR eligibility (rules §10.1).
+#[derive(Clone, Debug, Eq, PartialEq, SimpleObject)]
+pub struct NbaInjury {
+    pub status: NbaInjuryStatus,
+    pub source: NbaRosterSource,
+    pub asof: Option<String>,
+}
+
+impl NbaInjury {
+    fn from_player(entity: &player::Model) -> Self {
+        Self {
+            status: entity.nba_injury_status,
+            source: entity.nba_injury_source,
+            asof: entity.nba_injury_asof.map(|asof| asof.to_rfc3339()),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct RealPlayer {
     pub id: i64,
@@ -155,12 +178,14 @@ pub struct RealPlayer {
     // pub position: String,
     pub real_team_id: i64,
     pub eligibility: PlayerEligibility,
+    pub nba_injury: NbaInjury,
     // pub real_team_name: String,
 }
 
 impl RealPlayer {
     pub fn from_model(entity: player::Model, end_of_season_year: i16) -> Self {
         let eligibility = PlayerEligibility::from_player(&entity, end_of_season_year);
+        let nba_injury = NbaInjury::from_player(&entity);
         Self {
             id: entity.id,
             is_rdi_eligible: entity.is_rdi_eligible,
@@ -171,6 +196,7 @@ impl RealPlayer {
             // position: "".to_string(),
             real_team_id: entity.current_real_team_id,
             eligibility,
+            nba_injury,
             // real_team_name: "".to_string(),
         }
     }
@@ -220,6 +246,10 @@ impl RealPlayer {
         self.eligibility.clone()
     }
 
+    async fn nba_injury(&self) -> NbaInjury {
+        self.nba_injury.clone()
+    }
+
     async fn real_team_name(&self, ctx: &Context<'_>) -> Result<String, FbklError> {
         let real_team = ctx
             .data_unchecked::<DataLoader<RealTeamLoader>>()
@@ -230,3 +260,137 @@ impl RealPlayer {
         Ok(real_team.name)
     }
 }
+
+/// What one link in a player's contract history was. Mirrors
+/// `fbkl_logic::player_history::PlayerHistoryStepKind`.
+#[derive(Copy, Clone, Debug, Enum, Eq, PartialEq)]
+pub enum PlayerHistoryStepKind {
+    ContractCreated,
+    Drafted,
+    WonAtAuction,
+    Traded,
+    RfaResigned,
+    TakenInReplacementDraft,
+    Dropped,
+    Advanced,
+    ToIr,
+    FromIr,
+    ToRdi,
+    FromRdi,
+    RookieActivated,
+    MergedIntoRealPlayer,
+    Expired,
+    ContractChanged,
+}
+
+impl PlayerHistoryStepKind {
+    const fn from_logic(kind: player_history::PlayerHistoryStepKind) -> Self {
+        match kind {
+            player_history::PlayerHistoryStepKind::ContractCreated => Self::ContractCreated,
+            player_history::PlayerHistoryStepKind::Drafted => Self::Drafted,
+            player_history::PlayerHistoryStepKind::WonAtAuction => Self::WonAtAuction,
+            player_history::PlayerHistoryStepKind::Traded => Self::Traded,
+            player_history::PlayerHistoryStepKind::RfaResigned => Self::RfaResigned,
+            player_history::PlayerHistoryStepKind::TakenInReplacementDraft => {
+                Self::TakenInReplacementDraft
+            }
+            player_history::PlayerHistoryStepKind::Dropped => Self::Dropped,
+            player_history::PlayerHistoryStepKind::Advanced => Self::Advanced,
+            player_history::PlayerHistoryStepKind::ToIr => Self::ToIr,
+            player_history::PlayerHistoryStepKind::FromIr => Self::FromIr,
+            player_history::PlayerHistoryStepKind::ToRdi => Self::ToRdi,
+            player_history::PlayerHistoryStepKind::FromRdi => Self::FromRdi,
+            player_history::PlayerHistoryStepKind::RookieActivated => Self::RookieActivated,
+            player_history::PlayerHistoryStepKind::MergedIntoRealPlayer => {
+                Self::MergedIntoRealPlayer
+            }
+            player_history::PlayerHistoryStepKind::Expired => Self::Expired,
+            player_history::PlayerHistoryStepKind::ContractChanged => Self::ContractChanged,
+        }
+    }
+}
+
+/// One link of a player's contract history. `contract` is the link itself — its team, kind, year
+/// and salary — and `transaction` the move that produced it, when one was recorded.
+#[derive(SimpleObject)]
+pub struct PlayerHistoryStep {
+    pub kind: PlayerHistoryStepKind,
+    /// Whether this step brought the player to a team (draft, auction, trade, RFA re-sign,
+    /// replacement draft).
+    pub is_acquisition: bool,
+    pub contract: Contract,
+    pub previous_team_id: Option<i64>,
+    /// The cap penalty the dropping team paid (rules §9.1). Null unless this is a penalized drop.
+    pub drop_penalty: Option<i16>,
+    pub transaction: Option<Transaction>,
+}
+
+/// Where a player finished a season.
+#[derive(SimpleObject)]
+pub struct PlayerSeasonLine {
+    pub end_of_season_year: i16,
+    pub team_id: i64,
+    pub contract_kind: ContractKind,
+    pub year_number: i16,
+    pub salary: i16,
+}
+
+/// A player's league career, oldest link first.
+#[derive(SimpleObject)]
+pub struct PlayerLeagueHistory {
+    pub steps: Vec<PlayerHistoryStep>,
+    pub seasons: Vec<PlayerSeasonLine>,
+}
+
+impl PlayerLeagueHistory {
+    pub(super) fn from_history(
+        history: &player_history::PlayerLeagueHistory,
+    ) -> Result<Self, FbklError> {
+        Ok(Self {
+            steps: history
+                .steps
+                .iter()
+                .map(|step| {
+                    Ok(PlayerHistoryStep {
+                        kind: PlayerHistoryStepKind::from_logic(step.kind),
+                        is_acquisition: step.kind.is_acquisition(),
+                        contract: Contract::from_model(&step.contract)?,
+                        previous_team_id: step.previous_team_id,
+                        drop_penalty: step.drop_penalty,
+                        transaction: step.transaction.as_ref().map(Transaction::from_model),
+                    })
+                })
+                .collect::<Result<Vec<_>, FbklError>>()?,
+            seasons: history
+                .seasons
+                .iter()
+                .map(|line| PlayerSeasonLine {
+                    end_of_season_year: line.end_of_season_year,
+                    team_id: line.team_id,
+                    contract_kind: line.contract_kind,
+                    year_number: line.year_number,
+                    salary: line.salary,
+                })
+                .collect(),
+        })
+    }
+}
+
+/// A real player who may be the league player being merged.
+#[derive(SimpleObject)]
+pub struct LeaguePlayerMergeCandidate {
+    pub player: RealPlayer,
+    /// False when the player was in NBA data before the league player was drafted.
+    pub is_draft_year_consistent: bool,
+}
+
+/// What `mergeLeaguePlayer` changed.
+#[derive(SimpleObject)]
+pub struct LeaguePlayerMergeResult {
+    pub league_player: LeaguePlayer,
+    /// The new link, naming the real player, at the end of each active contract chain.
+    pub relinked_contracts: Vec<Contract>,
+    /// Whether the league player's eligibility override now applies to the real player in this
+    /// league. It does not when he already has one of his own, which applies in every league.
+    pub carried_eligibility_override: bool,
+}
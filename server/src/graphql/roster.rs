@@ -1,2 +1,4 @@
 mod roster_resolvers;
+mod roster_types;
 pub use roster_resolvers::*;
+pub use roster_types::*;
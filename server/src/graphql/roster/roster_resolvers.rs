@@ -1,33 +1,199 @@
-//! Single-team roster moves: IR, drops, and rookie-development transitions.
+//! Single-team roster moves: IR, drops, and rookie-development transitions, plus the week's
+//! queue of those moves.
 //!
-//! The rookie-development moves have no eligibility guards in `logic/` yet (see
-//! `logic/CLAUDE.md`); fbkl-rust-22o adds them, and they belong there rather than here.
+//! In season each move is recorded against the upcoming weekly roster lock, so it joins that
+//! week's queue (`teamWeek`) and only the roster it leaves at the lock has to be legal.
+//!
+//! IR moves and the rookie-development moves are guarded in `logic/` (NBA injured reserve status
+//! for IR, cap and roster room for activations, RDI eligibility and the one RDI slot); their typed
+//! rejections each map to their own error code here.
 
 use async_graphql::{Context, Error as GraphQlError, Object, Result};
 use chrono::Utc;
 use color_eyre::Report;
 use fbkl_entity::{
     contract,
-    contract_queries::find_contract_by_id,
-    deadline,
-    deadline_queries::find_most_recent_deadline_by_datetime,
+    contract_queries::{find_active_contracts_for_team, find_contract_by_id},
+    deadline::{self, DeadlineKind},
+    deadline_queries::{find_deadline_by_id, find_deadline_for_season_by_type},
+    roster_legalization_move_queries,
     sea_orm::{DatabaseConnection, DatabaseTransaction, TransactionTrait},
+    team_queries::find_team_by_id_in_league,
+    team_user::LeagueRole,
+    transaction_queries::find_transaction_by_id,
 };
 use fbkl_logic::{
-    drop_contract::drop_contract_from_team,
-    ir::{activate_contract_from_ir, move_contract_to_ir},
-    rookie_development_activation::activate_rookie_development_contract,
+    deadline_processing::{
+        HeldRosterMovesRejection, find_rdi_contracts_due_for_rd_conversion,
+        rule_on_held_roster_moves,
+    },
+    drop_contract::{
+        DropPenaltyWaiverRejection, drop_contract_from_team, waive_drop_contract_penalty,
+    },
+    eligibility::RdiIneligibility,
+    ir::{
+        IrRejection, activate_contract_from_ir, find_recovered_ir_contracts, move_contract_to_ir,
+    },
+    legalization::{DeclarationMove, DeclarationRejection, submit_season_start_declaration},
+    rookie_development_activation::{ActivationRejection, activate_rookie_development_contract},
     rookie_development_international::{
-        move_rookie_development_contract_to_international,
+        RdiMoveRejection, move_rookie_development_contract_to_international,
         move_rookie_development_international_contract_to_stateside,
     },
+    weekly_moves::{
+        SameWeekDropRejection, WeeklyMoveRejection, find_team_week,
+        find_upcoming_weekly_roster_lock, find_weekly_move_deadline, reorder_weekly_moves,
+    },
 };
 
-use super::super::contract::Contract;
+use super::{
+    super::{contract::Contract, team::TeamUpdate, transaction::Transaction},
+    RosterLegalizationMove, RosterLockRuling, SeasonStartMoveInput, TeamWeek,
+};
 use crate::graphql::{
-    ErrorCode, LeagueRoleGuard, RoleRequirement, code_error, require_league_role,
+    ErrorCode, LeagueRoleGuard, RoleRequirement, code_error, current_season, graphql_error,
+    require_league_role,
 };
 
+#[derive(Default)]
+pub struct RosterQuery;
+
+#[Object]
+impl RosterQuery {
+    /// A team's pending moves for the upcoming weekly roster lock (default: the caller's own
+    /// team), with a live "legal at lock?" status. `null` when no weekly lock is left this season.
+    /// Only the team's own members and the commissioner may read it.
+    #[graphql(guard = "LeagueRoleGuard(RoleRequirement::Member)")]
+    async fn team_week(&self, ctx: &Context<'_>, team_id: Option<i64>) -> Result<Option<TeamWeek>> {
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+        let (team_user, caller_team) = require_league_role(ctx, RoleRequirement::Member).await?;
+
+        let team_id = team_id.unwrap_or(team_user.team_id);
+        let is_commissioner = team_user.league_role == LeagueRole::LeagueCommissioner;
+        if team_id != team_user.team_id && !is_commissioner {
+            return Err(code_error(ErrorCode::Forbidden));
+        }
+        find_team_by_id_in_league(team_id, caller_team.league_id, db)
+            .await
+            .map_err(|_| code_error(ErrorCode::NotFound))?;
+
+        let season = current_season(ctx, caller_team.league_id).await?;
+        let Some(lock_deadline) = find_upcoming_weekly_roster_lock(
+            caller_team.league_id,
+            season,
+            Utc::now().fixed_offset(),
+            db,
+        )
+        .await
+        .map_err(|err| internal("failed to resolve the upcoming roster lock", &err))?
+        else {
+            return Ok(None);
+        };
+
+        let team_week = find_team_week(team_id, &lock_deadline, db)
+            .await
+            .map_err(|err| internal("failed to load the team's week", &err))?;
+
+        TeamWeek::from_team_week(team_id, &team_week)
+            .map(Some)
+            .map_err(|_| code_error(ErrorCode::Internal))
+    }
+
+    /// Moves weekly roster locks made to legalize the caller's roster, newest first; only the ones
+    /// not yet acknowledged when `unacknowledgedOnly` is set.
+    #[graphql(guard = "LeagueRoleGuard(RoleRequirement::Member)")]
+    async fn roster_legalization_moves(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = false)] unacknowledged_only: bool,
+    ) -> Result<Vec<RosterLegalizationMove>> {
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+        let (team_user, _) = require_league_role(ctx, RoleRequirement::Member).await?;
+
+        let move_models =
+            roster_legalization_move_queries::find_roster_legalization_moves_for_team(
+                team_user.team_id,
+                unacknowledged_only,
+                db,
+            )
+            .await
+            .map_err(|err| internal("failed to load roster legalization moves", &err))?;
+
+        let mut legalization_moves = Vec::with_capacity(move_models.len());
+        for move_model in &move_models {
+            let contract_model = find_contract_by_id(move_model.contract_id, db)
+                .await
+                .map_err(|err| internal("failed to load a legalized contract", &err))?;
+            legalization_moves.push(
+                RosterLegalizationMove::from_model(move_model, &contract_model)
+                    .map_err(|_| code_error(ErrorCode::Internal))?,
+            );
+        }
+
+        Ok(legalization_moves)
+    }
+
+    /// The caller's RDI contracts whose players have reached an NBA roster, which the next
+    /// season-start legalization will move to RD/1 unless the owner moves, activates or drops them
+    /// first (rules §11.3.1, §11.3.5).
+    #[graphql(guard = "LeagueRoleGuard(RoleRequirement::Member)")]
+    async fn rdi_contracts_due_for_rd_conversion(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<Vec<Contract>> {
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+        let (team_user, caller_team) = require_league_role(ctx, RoleRequirement::Member).await?;
+
+        let season = current_season(ctx, caller_team.league_id).await?;
+        let final_roster_lock = find_deadline_for_season_by_type(
+            caller_team.league_id,
+            season,
+            DeadlineKind::PreseasonFinalRosterLock,
+            db,
+        )
+        .await
+        .map_err(|_| code_error(ErrorCode::NotFound))?;
+        let legalization_season = if Utc::now() < final_roster_lock.date_time {
+            season
+        } else {
+            season + 1
+        };
+
+        let contract_models =
+            find_rdi_contracts_due_for_rd_conversion(team_user.team_id, legalization_season, db)
+                .await
+                .map_err(|err| internal("failed to load RDI contracts due for conversion", &err))?;
+
+        contract_models
+            .iter()
+            .map(Contract::from_model)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| code_error(ErrorCode::Internal))
+    }
+
+    /// The caller's IR contracts whose players have left the NBA injured reserve. Each must come
+    /// off IR with the team's next pickup or trade; until then the week's lock flags the roster
+    /// (rules §10.1.3).
+    #[graphql(guard = "LeagueRoleGuard(RoleRequirement::Member)")]
+    async fn recovered_ir_contracts(&self, ctx: &Context<'_>) -> Result<Vec<Contract>> {
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+        let (team_user, _) = require_league_role(ctx, RoleRequirement::Member).await?;
+
+        let team_contracts = find_active_contracts_for_team(team_user.team_id, db)
+            .await
+            .map_err(|err| internal("failed to load the team's contracts", &err))?;
+        let recovered = find_recovered_ir_contracts(&team_contracts, db)
+            .await
+            .map_err(|err| internal("failed to load recovered IR contracts", &err))?;
+
+        recovered
+            .iter()
+            .map(|recovered_contract| Contract::from_model(&recovered_contract.contract))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| code_error(ErrorCode::Internal))
+    }
+}
+
 #[derive(Default)]
 pub struct RosterMutation;
 
@@ -88,6 +254,194 @@ impl RosterMutation {
         )
         .await
     }
+
+    /// Orders the caller's pending moves for the upcoming weekly roster lock. Every pending move
+    /// must be listed exactly once. The lock commits the moves in this order, though legality at
+    /// the lock is judged on the final roster (§13.1.1).
+    #[graphql(guard = "LeagueRoleGuard(RoleRequirement::Member)")]
+    async fn reorder_weekly_moves(
+        &self,
+        ctx: &Context<'_>,
+        ordered_team_update_ids: Vec<i64>,
+    ) -> Result<TeamWeek> {
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+        let (team_user, caller_team) = require_league_role(ctx, RoleRequirement::Member).await?;
+
+        let season = current_season(ctx, caller_team.league_id).await?;
+        let lock_deadline = find_upcoming_weekly_roster_lock(
+            caller_team.league_id,
+            season,
+            Utc::now().fixed_offset(),
+            db,
+        )
+        .await
+        .map_err(|err| internal("failed to resolve the upcoming roster lock", &err))?
+        .ok_or_else(|| {
+            graphql_error(
+                ErrorCode::BadRequest,
+                "there is no upcoming weekly roster lock this season",
+            )
+        })?;
+
+        reorder_weekly_moves(
+            team_user.team_id,
+            lock_deadline.id,
+            &ordered_team_update_ids,
+            db,
+        )
+        .await
+        .map_err(|err| map_weekly_move_error(&err))?;
+
+        let team_week = find_team_week(team_user.team_id, &lock_deadline, db)
+            .await
+            .map_err(|err| internal("failed to load the team's week", &err))?;
+
+        TeamWeek::from_team_week(team_user.team_id, &team_week)
+            .map_err(|_| code_error(ErrorCode::Internal))
+    }
+
+    /// Declares the caller's in-season roster at season start (22 active + 1 IR + 6 RD + 1 RDI):
+    /// direct-to-IR moves, RD/RDI activations, RDI-to-RD moves and drops, judged together on the
+    /// roster they leave and applied all at once (§5.1.3, §11.4.3). Open until the final preseason
+    /// roster lock. Returns the team's roster afterwards.
+    #[graphql(guard = "LeagueRoleGuard(RoleRequirement::Member)")]
+    async fn declare_season_start_roster(
+        &self,
+        ctx: &Context<'_>,
+        moves: Vec<SeasonStartMoveInput>,
+    ) -> Result<Vec<Contract>> {
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+        let (team_user, caller_team) = require_league_role(ctx, RoleRequirement::Member).await?;
+
+        let season = current_season(ctx, caller_team.league_id).await?;
+        let final_roster_lock = find_deadline_for_season_by_type(
+            caller_team.league_id,
+            season,
+            DeadlineKind::PreseasonFinalRosterLock,
+            db,
+        )
+        .await
+        .map_err(|_| code_error(ErrorCode::NotFound))?;
+        if Utc::now() >= final_roster_lock.date_time {
+            return Err(graphql_error(
+                ErrorCode::BadRequest,
+                "the season-start roster declaration closed at the final preseason roster lock",
+            ));
+        }
+
+        let declaration: Vec<DeclarationMove> = moves.iter().map(DeclarationMove::from).collect();
+        let contract_models = submit_season_start_declaration(
+            team_user.team_id,
+            &declaration,
+            &final_roster_lock,
+            db,
+        )
+        .await
+        .map_err(|err| map_declaration_error(&err))?;
+
+        contract_models
+            .iter()
+            .map(Contract::from_model)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| code_error(ErrorCode::Internal))
+    }
+
+    /// Marks every roster legalization move on the caller's team as seen. Returns how many were
+    /// newly acknowledged.
+    #[graphql(guard = "LeagueRoleGuard(RoleRequirement::Member)")]
+    async fn acknowledge_roster_legalization_moves(&self, ctx: &Context<'_>) -> Result<u64> {
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+        let (team_user, _) = require_league_role(ctx, RoleRequirement::Member).await?;
+
+        roster_legalization_move_queries::acknowledge_roster_legalization_moves_for_team(
+            team_user.team_id,
+            db,
+        )
+        .await
+        .map_err(|err| internal("failed to acknowledge roster legalization moves", &err))
+    }
+
+    /// Rules on the moves a roster lock held because the team's roster was illegal there (§13.1.2):
+    /// approving commits them; rejecting sends them to the next weekly lock, which judges the
+    /// roster again. The team is notified either way. Returns the moves ruled on.
+    #[graphql(guard = "LeagueRoleGuard(RoleRequirement::Commissioner)")]
+    async fn rule_on_held_roster_moves(
+        &self,
+        ctx: &Context<'_>,
+        team_id: i64,
+        deadline_id: i64,
+        ruling: RosterLockRuling,
+    ) -> Result<Vec<TeamUpdate>> {
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+        let (_, caller_team) = require_league_role(ctx, RoleRequirement::Commissioner).await?;
+
+        find_team_by_id_in_league(team_id, caller_team.league_id, db)
+            .await
+            .map_err(|_| code_error(ErrorCode::NotFound))?;
+        let lock_deadline = find_deadline_by_id(deadline_id, db)
+            .await
+            .map_err(|_| code_error(ErrorCode::NotFound))?;
+        if lock_deadline.league_id != caller_team.league_id {
+            return Err(code_error(ErrorCode::NotFound));
+        }
+
+        let db_txn = db
+            .begin()
+            .await
+            .map_err(|err| internal("failed to start transaction", &err.into()))?;
+        let ruled_moves = rule_on_held_roster_moves(
+            team_id,
+            &lock_deadline,
+            ruling.into(),
+            Utc::now().fixed_offset(),
+            &db_txn,
+        )
+        .await
+        .map_err(|err| map_held_moves_error(&err))?;
+        db_txn
+            .commit()
+            .await
+            .map_err(|err| internal("failed to commit the ruling", &err.into()))?;
+
+        Ok(ruled_moves.iter().map(TeamUpdate::from_model).collect())
+    }
+
+    /// Waives the cap penalty on a regular-season drop of a player who has died (rules §9.1.4).
+    /// `reason` is kept with the waiver as its audit trail. Open from the keeper deadline until the
+    /// end of the drop's season.
+    #[graphql(guard = "LeagueRoleGuard(RoleRequirement::Commissioner)")]
+    async fn waive_drop_penalty(
+        &self,
+        ctx: &Context<'_>,
+        transaction_id: i64,
+        reason: String,
+    ) -> Result<Transaction> {
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+        let (team_user, caller_team) =
+            require_league_role(ctx, RoleRequirement::Commissioner).await?;
+        if reason.trim().is_empty() {
+            return Err(graphql_error(
+                ErrorCode::BadRequest,
+                "a reason is required to waive a drop penalty",
+            ));
+        }
+
+        let drop_transaction = find_transaction_by_id(transaction_id, db)
+            .await
+            .map_err(|_| code_error(ErrorCode::NotFound))?;
+        let waived_transaction = waive_drop_contract_penalty(
+            caller_team.league_id,
+            drop_transaction,
+            team_user.id,
+            reason,
+            Utc::now().into(),
+            db,
+        )
+        .await
+        .map_err(|err| map_waiver_error(&err))?;
+
+        Ok(Transaction::from_model(&waived_transaction))
+    }
 }
 
 /// Runs one roster move on a contract the caller's own team owns.
@@ -116,7 +470,7 @@ where
     }
 
     let deadline_model =
-        find_most_recent_deadline_by_datetime(caller_team.league_id, Utc::now().fixed_offset(), db)
+        find_weekly_move_deadline(caller_team.league_id, Utc::now().fixed_offset(), db)
             .await
             .map_err(|err| internal("failed to resolve the current deadline", &err))?;
 
@@ -126,7 +480,7 @@ where
         .map_err(|err| internal("failed to start transaction", &err.into()))?;
     let updated = op(contract_model, &deadline_model, &db_txn)
         .await
-        .map_err(|err| internal("roster move failed", &err))?;
+        .map_err(|err| roster_move_error(&err))?;
     db_txn
         .commit()
         .await
@@ -135,7 +489,128 @@ where
     Contract::from_model(&updated).map_err(|_| code_error(ErrorCode::Internal))
 }
 
+/// A rule-breaking drop, IR or RD/RDI move is the client's fault and gets its own code; anything else is a
+/// server fault.
+fn roster_move_error(error: &Report) -> GraphQlError {
+    let code = if let Some(rejection) = error.downcast_ref::<ActivationRejection>() {
+        match rejection {
+            ActivationRejection::NotRookieDevelopment { .. } => {
+                ErrorCode::RookieActivationWrongKind
+            }
+            ActivationRejection::NotActive { .. } => ErrorCode::RookieActivationInactive,
+            ActivationRejection::InsufficientCap { .. } => {
+                ErrorCode::RookieActivationInsufficientCap
+            }
+            ActivationRejection::NoRosterSpace { .. } => ErrorCode::RookieActivationNoRosterSpace,
+        }
+    } else if let Some(rejection) = error.downcast_ref::<RdiMoveRejection>() {
+        match rejection {
+            RdiMoveRejection::WrongKind { .. } => ErrorCode::RdiMoveWrongKind,
+            RdiMoveRejection::AlreadyActivated { .. } => ErrorCode::RdiMoveAfterActivation,
+            RdiMoveRejection::RdiSlotTaken { .. } => ErrorCode::RdiSlotTaken,
+        }
+    } else if let Some(rejection) = error.downcast_ref::<IrRejection>() {
+        match rejection {
+            IrRejection::NotOnNbaInjuredReserve { .. } => ErrorCode::IrPlayerNotOnNbaIr,
+        }
+    } else if error.downcast_ref::<SameWeekDropRejection>().is_some() {
+        ErrorCode::SameWeekAuctionWinDrop
+    } else if let Some(ineligibility) = error.downcast_ref::<RdiIneligibility>() {
+        match ineligibility {
+            RdiIneligibility::NotRookieDraftEligible { .. } => ErrorCode::RdiNotRookieDraftEligible,
+            RdiIneligibility::OnNbaRosterBefore { .. } => ErrorCode::RdiPlayerOnNbaRoster,
+            RdiIneligibility::FormerRookieDevelopment { .. } => {
+                ErrorCode::RdiFormerRookieDevelopment
+            }
+        }
+    } else {
+        return internal("roster move failed", error);
+    };
+
+    graphql_error(code, error.to_string())
+}
+
+fn map_weekly_move_error(error: &Report) -> GraphQlError {
+    if let Some(rejection) = error.downcast_ref::<WeeklyMoveRejection>() {
+        return graphql_error(ErrorCode::WeeklyMoveOrderInvalid, rejection.to_string());
+    }
+
+    internal("failed to reorder weekly moves", error)
+}
+
+fn map_declaration_error(error: &Report) -> GraphQlError {
+    if let Some(rejection) = error.downcast_ref::<DeclarationRejection>() {
+        return graphql_error(
+            ErrorCode::SeasonStartDeclarationRejected,
+            rejection.to_string(),
+        );
+    }
+    if let Some(rejection) = error.downcast_ref::<IrRejection>() {
+        return graphql_error(ErrorCode::IrPlayerNotOnNbaIr, rejection.to_string());
+    }
+
+    internal("failed to apply the season-start declaration", error)
+}
+
+fn map_held_moves_error(error: &Report) -> GraphQlError {
+    if let Some(rejection) = error.downcast_ref::<HeldRosterMovesRejection>() {
+        return graphql_error(
+            ErrorCode::HeldRosterMovesRulingRejected,
+            rejection.to_string(),
+        );
+    }
+
+    internal("failed to rule on held roster moves", error)
+}
+
+fn map_waiver_error(error: &Report) -> GraphQlError {
+    match error.downcast_ref::<DropPenaltyWaiverRejection>() {
+        Some(DropPenaltyWaiverRejection::WrongLeague { .. }) => code_error(ErrorCode::NotFound),
+        Some(rejection) => {
+            graphql_error(ErrorCode::DropPenaltyWaiverRejected, rejection.to_string())
+        }
+        None => internal("failed to waive the drop penalty", error),
+    }
+}
+
 fn internal(message: &str, error: &Report) -> GraphQlError {
     tracing::error!(error = ?error, message);
     code_error(ErrorCode::Internal)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code_of(error: &GraphQlError) -> Option<&async_graphql::Value> {
+        error.extensions.as_ref().and_then(|ext| ext.get("code"))
+    }
+
+    #[test]
+    fn rd_and_rdi_rejections_get_their_own_codes() {
+        let error = roster_move_error(&Report::new(ActivationRejection::InsufficientCap {
+            salary: 215,
+            cap: 210,
+        }));
+        assert_eq!(
+            code_of(&error),
+            Some(&"ROOKIE_ACTIVATION_INSUFFICIENT_CAP".into())
+        );
+
+        let error = roster_move_error(&Report::new(RdiMoveRejection::RdiSlotTaken { limit: 1 }));
+        assert_eq!(code_of(&error), Some(&"RDI_SLOT_TAKEN".into()));
+
+        let error = roster_move_error(&Report::new(RdiIneligibility::OnNbaRosterBefore {
+            contract_id: 3,
+            season: 2026,
+        }));
+        assert_eq!(code_of(&error), Some(&"RDI_PLAYER_ON_NBA_ROSTER".into()));
+    }
+
+    #[test]
+    fn other_roster_move_failures_stay_internal() {
+        let error = roster_move_error(&color_eyre::eyre::eyre!("db exploded"));
+
+        assert_eq!(code_of(&error), Some(&"INTERNAL".into()));
+    }
+}
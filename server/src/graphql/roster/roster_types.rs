@@ -0,0 +1,205 @@
+use async_graphql::{Enum, InputObject, SimpleObject};
+use fbkl_entity::{
+    contract,
+    roster_legalization_move::{self, RosterLegalizationMoveKind},
+};
+use fbkl_logic::{
+    deadline_processing::{HeldRosterMovesRuling, RosterViolation},
+    legalization::{DeclarationAction, DeclarationMove},
+    weekly_moves,
+};
+
+use crate::{
+    error::FbklError,
+    graphql::{contract::Contract, deadline::Deadline, team::TeamUpdate},
+};
+
+/// A team's pending moves for the upcoming weekly roster lock, and whether the roster they leave
+/// would pass that lock. Being illegal mid-week is allowed (§13.1.3); only the lock must be legal.
+#[derive(SimpleObject)]
+pub struct TeamWeek {
+    pub team_id: i64,
+    pub lock_deadline: Deadline,
+    /// Pending moves in the owner's order; moves never reordered follow in the order made.
+    pub moves: Vec<TeamUpdate>,
+    /// The roster with every move applied.
+    pub contracts: Vec<Contract>,
+    pub legal_at_lock: bool,
+    pub violations: Vec<RosterLockViolation>,
+}
+
+impl TeamWeek {
+    pub(super) fn from_team_week(
+        team_id: i64,
+        team_week: &weekly_moves::TeamWeek,
+    ) -> Result<Self, FbklError> {
+        Ok(Self {
+            team_id,
+            lock_deadline: Deadline::from_model(&team_week.lock_deadline),
+            moves: team_week.moves.iter().map(TeamUpdate::from_model).collect(),
+            contracts: team_week
+                .contracts
+                .iter()
+                .map(Contract::from_model)
+                .collect::<Result<Vec<_>, _>>()?,
+            legal_at_lock: team_week.is_legal_at_lock(),
+            violations: team_week
+                .violations
+                .iter()
+                .map(RosterLockViolation::from_violation)
+                .collect(),
+        })
+    }
+}
+
+/// The roster rule a violation breaks.
+#[derive(Copy, Clone, Debug, Enum, Eq, PartialEq)]
+pub enum RosterLockRule {
+    OverCap,
+    OverPreseasonContractLimit,
+    OverVetOrRookieLimit,
+    OverRookieDevelopmentLimit,
+    OverRookieDevelopmentInternationalLimit,
+    OverIrLimit,
+    RecoveredPlayersOnIr,
+    DroppedSameWeekAuctionWins,
+}
+
+/// One rule the roster would break at the lock. For `OVER_CAP`, `actual` and `limit` are salary
+/// and cap; otherwise they are contract counts. `RECOVERED_PLAYERS_ON_IR` and
+/// `DROPPED_SAME_WEEK_AUCTION_WINS` allow none.
+#[derive(SimpleObject)]
+pub struct RosterLockViolation {
+    pub rule: RosterLockRule,
+    pub actual: i16,
+    pub limit: i16,
+    pub message: String,
+}
+
+impl RosterLockViolation {
+    fn from_violation(violation: &RosterViolation) -> Self {
+        let (rule, actual, limit) = match violation {
+            RosterViolation::OverCap { salary, cap } => (RosterLockRule::OverCap, *salary, *cap),
+            RosterViolation::OverPreseasonContractLimit { count, limit } => {
+                (RosterLockRule::OverPreseasonContractLimit, *count, *limit)
+            }
+            RosterViolation::OverVetOrRookieLimit { count, limit } => {
+                (RosterLockRule::OverVetOrRookieLimit, *count, *limit)
+            }
+            RosterViolation::OverRookieDevelopmentLimit { count, limit } => {
+                (RosterLockRule::OverRookieDevelopmentLimit, *count, *limit)
+            }
+            RosterViolation::OverRookieDevelopmentInternationalLimit { count, limit } => (
+                RosterLockRule::OverRookieDevelopmentInternationalLimit,
+                *count,
+                *limit,
+            ),
+            RosterViolation::OverIrLimit { count, limit } => {
+                (RosterLockRule::OverIrLimit, *count, *limit)
+            }
+            RosterViolation::RecoveredPlayersOnIr { count } => {
+                (RosterLockRule::RecoveredPlayersOnIr, *count, 0)
+            }
+            RosterViolation::DroppedSameWeekAuctionWins { drops } => (
+                RosterLockRule::DroppedSameWeekAuctionWins,
+                i16::try_from(drops.len()).unwrap_or(i16::MAX),
+                0,
+            ),
+        };
+
+        Self {
+            rule,
+            actual,
+            limit,
+            message: violation.to_string(),
+        }
+    }
+}
+
+/// A move a weekly roster lock made on the team's behalf to legalize its roster, in leagues that
+/// auto-legalize. It was recorded like any other drop or IR move.
+#[derive(SimpleObject)]
+pub struct RosterLegalizationMove {
+    pub id: i64,
+    pub team_id: i64,
+    /// The roster lock that made the move.
+    pub deadline_id: i64,
+    pub kind: RosterLegalizationMoveKind,
+    /// The contract as the move left it.
+    pub contract: Contract,
+    pub acknowledged: bool,
+    pub created_at: String,
+}
+
+impl RosterLegalizationMove {
+    pub(super) fn from_model(
+        move_model: &roster_legalization_move::Model,
+        contract_model: &contract::Model,
+    ) -> Result<Self, FbklError> {
+        Ok(Self {
+            id: move_model.id,
+            team_id: move_model.team_id,
+            deadline_id: move_model.deadline_id,
+            kind: move_model.kind,
+            contract: Contract::from_model(contract_model)?,
+            acknowledged: move_model.acknowledged_at.is_some(),
+            created_at: move_model.created_at.to_rfc3339(),
+        })
+    }
+}
+
+/// What the owner does with one contract in the season-start roster declaration.
+#[derive(Copy, Clone, Debug, Enum, Eq, PartialEq)]
+pub enum SeasonStartAction {
+    /// Straight to IR; only allowed in this declaration (§5.1.3).
+    MoveToIr,
+    /// RD/RDI to a first-year rookie contract.
+    ActivateRookieDevelopment,
+    /// RDI back to RD/1, for a player now on an NBA roster.
+    MoveToRookieDevelopment,
+    Drop,
+}
+
+/// One contract's part in the season-start roster declaration.
+#[derive(InputObject)]
+pub struct SeasonStartMoveInput {
+    pub contract_id: i64,
+    pub action: SeasonStartAction,
+}
+
+impl From<&SeasonStartMoveInput> for DeclarationMove {
+    fn from(input: &SeasonStartMoveInput) -> Self {
+        Self {
+            contract_id: input.contract_id,
+            action: match input.action {
+                SeasonStartAction::MoveToIr => DeclarationAction::MoveToIr,
+                SeasonStartAction::ActivateRookieDevelopment => {
+                    DeclarationAction::ActivateRookieDevelopment
+                }
+                SeasonStartAction::MoveToRookieDevelopment => {
+                    DeclarationAction::MoveToRookieDevelopment
+                }
+                SeasonStartAction::Drop => DeclarationAction::Drop,
+            },
+        }
+    }
+}
+
+/// The commissioner's ruling on the moves a roster lock held for an illegal roster (§13.1.2).
+#[derive(Copy, Clone, Debug, Enum, Eq, PartialEq)]
+pub enum RosterLockRuling {
+    /// Commit the moves and let the roster stand.
+    Approve,
+    /// Send the moves to the next weekly lock's queue, to be judged again once the owner has made
+    /// the roster legal.
+    Reject,
+}
+
+impl From<RosterLockRuling> for HeldRosterMovesRuling {
+    fn from(ruling: RosterLockRuling) -> Self {
+        match ruling {
+            RosterLockRuling::Approve => Self::Approve,
+            RosterLockRuling::Reject => Self::Reject,
+        }
+    }
+}
@@ -0,0 +1,4 @@
+mod security_event_resolvers;
+mod security_event_types;
+pub use security_event_resolvers::*;
+pub use security_event_types::*;
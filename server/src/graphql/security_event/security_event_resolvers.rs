@@ -0,0 +1,57 @@
+//! The security event log: logins, throttled and locked-out attempts, and account changes.
+//!
+//! App admins see every event, including attempts on emails no account has. A commissioner sees
+//! the events of users who have held a seat in the selected league. The log is written only by
+//! the login and account handlers, so there are no mutations.
+
+use async_graphql::{Context, Object, Result};
+use fbkl_entity::{
+    sea_orm::DatabaseConnection,
+    security_event::SecurityEventKind,
+    security_event_queries::{find_security_events, find_security_events_for_league},
+    user::UserAppAdminStatus,
+};
+
+use super::{PagedSecurityEvents, SecurityEvent};
+use crate::graphql::{
+    ErrorCode, RoleRequirement, code_error, require_current_user, require_league_role,
+};
+
+const MAX_PAGE_SIZE: u64 = 100;
+
+#[derive(Default)]
+pub struct SecurityEventQuery;
+
+#[Object]
+impl SecurityEventQuery {
+    /// One page of the security events the caller may see, newest first.
+    async fn security_events(
+        &self,
+        ctx: &Context<'_>,
+        kind: Option<SecurityEventKind>,
+        #[graphql(default = 0)] page: u64,
+        #[graphql(default = 25)] page_size: u64,
+    ) -> Result<PagedSecurityEvents> {
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+        let user_model = require_current_user(ctx)?;
+        let page_size = page_size.min(MAX_PAGE_SIZE);
+
+        let paged_result = if user_model.app_admin_status == UserAppAdminStatus::Admin {
+            find_security_events(kind, page, page_size, db).await
+        } else {
+            let (_, commissioner_team) =
+                require_league_role(ctx, RoleRequirement::Commissioner).await?;
+            find_security_events_for_league(commissioner_team.league_id, kind, page, page_size, db)
+                .await
+        };
+        let paged = paged_result.map_err(|db_err| {
+            tracing::error!(error = ?db_err, user_id = user_model.id, "failed to load security events");
+            code_error(ErrorCode::Internal)
+        })?;
+
+        Ok(PagedSecurityEvents {
+            items: paged.items.iter().map(SecurityEvent::from_model).collect(),
+            total_items: paged.total_items,
+        })
+    }
+}
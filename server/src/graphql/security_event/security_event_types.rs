@@ -0,0 +1,37 @@
+use async_graphql::SimpleObject;
+use fbkl_entity::security_event::{self, SecurityEventKind};
+
+/// One entry in the security event log.
+#[derive(SimpleObject)]
+pub struct SecurityEvent {
+    pub id: i64,
+    pub kind: SecurityEventKind,
+    /// Null for login attempts on an email no account has.
+    pub user_id: Option<i64>,
+    /// The attempted email, for login events.
+    pub email: Option<String>,
+    pub ip_address: Option<String>,
+    pub detail: Option<String>,
+    pub created_at: String,
+}
+
+impl SecurityEvent {
+    pub(crate) fn from_model(entity: &security_event::Model) -> Self {
+        Self {
+            id: entity.id,
+            kind: entity.kind,
+            user_id: entity.user_id,
+            email: entity.email.clone(),
+            ip_address: entity.ip_address.clone(),
+            detail: entity.detail.clone(),
+            created_at: entity.created_at.to_rfc3339(),
+        }
+    }
+}
+
+/// One page of the security event log. `totalItems` is the count for the unpaginated filter.
+#[derive(SimpleObject)]
+pub struct PagedSecurityEvents {
+    pub items: Vec<SecurityEvent>,
+    pub total_items: u64,
+}
@@ -1,19 +1,26 @@
-//! Team reads for the selected league.
+//! Team reads and the commissioner's ownership transfers for the selected league.
 //!
 //! Every team lookup is scoped to the league in the caller's session, so a team id
 //! from another league resolves to `NOT_FOUND` rather than leaking across leagues.
 //! There is deliberately no `selected_team_id` session key — the caller's own team
 //! comes from their `team_user` row in the active league.
 
-use async_graphql::{Context, Object, Result};
+use async_graphql::{Context, Error as GraphQlError, Object, Result};
+use chrono::Utc;
+use color_eyre::Report;
 use fbkl_entity::{
-    sea_orm::DatabaseConnection,
+    deadline_queries::find_most_recent_deadline_by_datetime,
+    sea_orm::{DatabaseConnection, TransactionTrait},
     team_queries::{find_team_by_id_in_league, find_teams_in_league},
+    team_update::OwnershipChangeKind,
+};
+use fbkl_logic::team_ownership::{
+    OwnershipTransfer, OwnershipTransferRejection, transfer_team_ownership,
 };
 
-use super::Team;
+use super::{Team, TeamUser};
 use crate::graphql::{
-    ErrorCode, LeagueRoleGuard, RoleRequirement, code_error, require_league_role,
+    ErrorCode, LeagueRoleGuard, RoleRequirement, code_error, graphql_error, require_league_role,
 };
 
 #[derive(Default)]
@@ -50,3 +57,87 @@ impl TeamQuery {
         Ok(Team::from_model(team_model))
     }
 }
+
+#[derive(Default)]
+pub struct TeamMutation;
+
+#[Object]
+impl TeamMutation {
+    /// Hands a team to a new owner (rules §2.4.1–§2.4.2). The current owner is retired as
+    /// `INACTIVE` after this season; the team keeps every contract and pick. A `FORCED_REMOVAL`
+    /// requires a `reason`. Returns the new owner.
+    #[graphql(guard = "LeagueRoleGuard(RoleRequirement::Commissioner)")]
+    async fn transfer_team_ownership(
+        &self,
+        ctx: &Context<'_>,
+        team_id: i64,
+        new_user_id: i64,
+        new_owner_nickname: String,
+        kind: OwnershipChangeKind,
+        reason: Option<String>,
+    ) -> Result<TeamUser> {
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+        let (_, caller_team) = require_league_role(ctx, RoleRequirement::Commissioner).await?;
+        if new_owner_nickname.trim().is_empty() {
+            return Err(graphql_error(
+                ErrorCode::BadRequest,
+                "the new owner needs a nickname",
+            ));
+        }
+
+        let team_model = find_team_by_id_in_league(team_id, caller_team.league_id, db)
+            .await
+            .map_err(|_| code_error(ErrorCode::NotFound))?;
+        let now = Utc::now().fixed_offset();
+        let current_deadline = find_most_recent_deadline_by_datetime(team_model.league_id, now, db)
+            .await
+            .map_err(|err| internal("failed to resolve the current deadline", &err))?;
+
+        let db_txn = db
+            .begin()
+            .await
+            .map_err(|err| internal("failed to start transaction", &err.into()))?;
+        let new_owner = transfer_team_ownership(
+            &team_model,
+            OwnershipTransfer {
+                new_user_id,
+                new_owner_nickname,
+                kind,
+                reason,
+            },
+            &current_deadline,
+            now,
+            &db_txn,
+        )
+        .await
+        .map_err(|err| map_ownership_transfer_error(&err))?;
+        db_txn
+            .commit()
+            .await
+            .map_err(|err| internal("failed to commit ownership transfer", &err.into()))?;
+
+        Ok(TeamUser {
+            league_role: new_owner.league_role,
+            nickname: new_owner.nickname,
+            team: Some(Team::from_model(team_model)),
+            team_id: new_owner.team_id,
+            user: None,
+            user_id: new_owner.user_id,
+        })
+    }
+}
+
+fn map_ownership_transfer_error(error: &Report) -> GraphQlError {
+    match error.downcast_ref::<OwnershipTransferRejection>() {
+        Some(OwnershipTransferRejection::UnknownUser { .. }) => code_error(ErrorCode::NotFound),
+        Some(rejection) => {
+            graphql_error(ErrorCode::OwnershipTransferRejected, rejection.to_string())
+        }
+        None => internal("failed to transfer team ownership", error),
+    }
+}
+
+fn internal(context: &str, error: &Report) -> GraphQlError {
+    tracing::error!(error = ?error, context);
+    code_error(ErrorCode::Internal)
+}
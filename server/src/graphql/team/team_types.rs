@@ -1,18 +1,26 @@
 use async_graphql::{Context, Object, Result, SimpleObject};
+use chrono::{Days, NaiveDate, Utc};
 use fbkl_entity::{
     contract_queries::find_active_contracts_for_team,
     sea_orm::{DatabaseConnection, prelude::DateTimeWithTimeZone},
     team,
+    team_queries::find_team_by_id_in_league,
     team_update::{self, TeamUpdateStatus},
     team_update_queries::find_team_updates_by_team,
     team_user::LeagueRole,
     team_user_queries::get_team_users_by_team,
 };
-use fbkl_logic::roster::calculate_team_contract_salary_at_datetime;
+use fbkl_logic::{
+    reports::{self, RosterReportLine},
+    roster::calculate_team_contract_salary_at_datetime,
+};
 
 use crate::{
     error::FbklError,
-    graphql::{ErrorCode, RoleRequirement, code_error, contract::Contract, require_league_role},
+    graphql::{
+        ErrorCode, RoleRequirement, code_error, contract::Contract, graphql_error,
+        require_league_role,
+    },
 };
 
 use super::TeamUser;
@@ -42,6 +50,8 @@ pub struct TeamUpdate {
     pub effective_date: String,
     pub status: TeamUpdateStatus,
     pub transaction_id: Option<i64>,
+    /// Owner-assigned position among the week's pending moves, if they have been reordered.
+    pub sequence: Option<i16>,
     pub data: String,
 }
 
@@ -53,11 +63,89 @@ impl TeamUpdate {
             effective_date: entity.effective_date.to_string(),
             status: entity.status,
             transaction_id: entity.transaction_id,
+            sequence: entity.sequence,
             data: entity.data.to_string(),
         }
     }
 }
 
+/// A §11.11 roster line, both rendered and broken out.
+#[derive(SimpleObject)]
+pub struct RosterLine {
+    /// e.g. `$207/$210, 22/22 + 1 IR + 2/6 RD + 1 RDI`.
+    pub text: String,
+    pub salary: i16,
+    pub salary_cap: i16,
+    pub vet_or_rookie: i16,
+    pub ir: i16,
+    pub rookie_development: i16,
+    pub rookie_development_international: i16,
+}
+
+impl RosterLine {
+    fn from_line(line: &RosterReportLine) -> Self {
+        Self {
+            text: line.to_string(),
+            salary: line.salary.salary,
+            salary_cap: line.salary.cap,
+            vet_or_rookie: line.counts.vet_or_rookie,
+            ir: line.counts.ir,
+            rookie_development: line.counts.rookie_development,
+            rookie_development_international: line.counts.rookie_development_international,
+        }
+    }
+}
+
+/// One move in a roster report, with the team's salary before it and its roster line after.
+#[derive(SimpleObject)]
+pub struct RosterReportMove {
+    pub team_update_id: i64,
+    pub effective_date: String,
+    /// e.g. `Dropped Jane Doe`, one per contract the move changed.
+    pub changes: Vec<String>,
+    pub salary_before: i16,
+    pub salary_cap_before: i16,
+    pub after: RosterLine,
+}
+
+/// The roster report owners email after every trade and free-agent week (rules §11.11).
+#[derive(SimpleObject)]
+pub struct RosterReport {
+    pub current: RosterLine,
+    /// Oldest first.
+    pub moves: Vec<RosterReportMove>,
+    /// The whole report, ready to paste into the league's Google Group.
+    pub markdown: String,
+}
+
+impl RosterReport {
+    fn from_report(report: &reports::RosterReport) -> Self {
+        Self {
+            current: RosterLine::from_line(&report.current),
+            moves: report
+                .moves
+                .iter()
+                .map(|roster_move| RosterReportMove {
+                    team_update_id: roster_move.team_update_id,
+                    effective_date: roster_move.effective_date.to_string(),
+                    changes: roster_move
+                        .changes
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect(),
+                    salary_before: roster_move.before.salary,
+                    salary_cap_before: roster_move.before.cap,
+                    after: RosterLine::from_line(&roster_move.after),
+                })
+                .collect(),
+            markdown: report.to_markdown(),
+        }
+    }
+}
+
+/// How far back a roster report looks when no `since` is given: one free-agent week.
+const ROSTER_REPORT_DEFAULT_DAYS: u64 = 7;
+
 impl Team {
     pub fn from_model(entity: team::Model) -> Self {
         Self {
@@ -145,6 +233,41 @@ impl Team {
             .collect())
     }
 
+    /// The team's §11.11 roster report: its current roster line plus every move effective on or
+    /// after `since` (a `YYYY-MM-DD` date, default a week ago). Any member of the league may read it,
+    /// since it is meant to be posted to the whole league.
+    async fn roster_report(
+        &self,
+        ctx: &Context<'_>,
+        since: Option<String>,
+    ) -> Result<RosterReport> {
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+        let (_, caller_team) = require_league_role(ctx, RoleRequirement::Member).await?;
+        if caller_team.league_id != self.league_id {
+            return Err(code_error(ErrorCode::NotFound));
+        }
+
+        let now = Utc::now();
+        let since = match since {
+            Some(since) => since.parse::<NaiveDate>().map_err(|_| {
+                graphql_error(ErrorCode::BadRequest, "`since` must be a YYYY-MM-DD date")
+            })?,
+            None => now.date_naive() - Days::new(ROSTER_REPORT_DEFAULT_DAYS),
+        };
+        let team_model = find_team_by_id_in_league(self.id, self.league_id, db)
+            .await
+            .map_err(|_| code_error(ErrorCode::NotFound))?;
+
+        let report = reports::roster_report(&team_model, since, now.fixed_offset(), db)
+            .await
+            .map_err(|err| {
+                tracing::error!(error = ?err, team_id = self.id, "failed to build the roster report");
+                code_error(ErrorCode::Internal)
+            })?;
+
+        Ok(RosterReport::from_report(&report))
+    }
+
     async fn team_users(&self, ctx: &Context<'_>) -> Result<Vec<TeamUser>, FbklError> {
         let db = ctx.data_unchecked::<DatabaseConnection>();
         let team_user_models = get_team_users_by_team(self.id, db).await?;
@@ -1,7 +1,8 @@
 //! Trade proposal / acceptance / rejection.
 //!
-//! Cap and roster legality are deliberately *not* checked here — `logic::trade` validates asset
-//! ownership only (see `logic/CLAUDE.md`); legality lands with fbkl-rust-8zs.
+//! Cap and roster legality are deliberately *not* enforced here — `logic::trade` validates asset
+//! ownership only (see `logic/CLAUDE.md`). `Trade.preview` surfaces the projected cap and roster
+//! hit as a warning instead, since §13 only requires legality by the next roster lock.
 
 use async_graphql::{Context, Error as GraphQlError, Object, Result};
 use chrono::Utc;
@@ -14,7 +15,10 @@ use fbkl_entity::{
     trade_asset_queries::new_trade_asset_active_model_by_id,
     trade_queries::{find_active_trades_for_team, find_active_trades_in_league, find_trade_by_id},
 };
-use fbkl_logic::trade::{MissingPreTradeSalary, accept_trade, propose_trade, reject_trade};
+use fbkl_logic::trade::{
+    MissingPreTradeSalary, ReproposeRejection, accept_trade, propose_trade, reject_trade,
+    repropose_trade_without_rejecting_teams,
+};
 
 use super::{ProposeTradeInput, Trade};
 use crate::graphql::{
@@ -175,7 +179,8 @@ impl TradeMutation {
         Ok(Trade::from_model(maybe_processed.unwrap_or(model)))
     }
 
-    /// Rejects a trade, closing it for every team involved.
+    /// Rejects a trade. This closes it for every team unless the league's `tradeRejectionPolicy`
+    /// keeps a trade between three or more teams open for the proposer to re-propose.
     #[graphql(guard = "LeagueRoleGuard(RoleRequirement::Member)")]
     async fn reject_trade(&self, ctx: &Context<'_>, trade_id: i64) -> Result<Trade> {
         let db = ctx.data_unchecked::<DatabaseConnection>();
@@ -189,6 +194,33 @@ impl TradeMutation {
 
         Ok(Trade::from_model(rejected))
     }
+
+    /// Re-proposes an open trade without the teams that rejected it, as the next trade in its
+    /// chain. Only the proposer may do this, and only assets moving between the remaining teams
+    /// carry over.
+    #[graphql(guard = "LeagueRoleGuard(RoleRequirement::Member)")]
+    async fn repropose_trade_without_rejecting_teams(
+        &self,
+        ctx: &Context<'_>,
+        trade_id: i64,
+    ) -> Result<Trade> {
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+        let (team_user, caller_team) = require_league_role(ctx, RoleRequirement::Member).await?;
+        let model =
+            load_actionable_trade(ctx, trade_id, team_user.team_id, caller_team.league_id).await?;
+        if !model.is_active() {
+            return Err(graphql_error(
+                ErrorCode::TradeCannotBeReproposed,
+                "this trade is no longer open",
+            ));
+        }
+
+        let reproposed = repropose_trade_without_rejecting_teams(model, &team_user, db)
+            .await
+            .map_err(|err| map_repropose_error(&err))?;
+
+        Ok(Trade::from_model(reproposed))
+    }
 }
 
 /// Loads a trade the caller may act on: in their league, involving their team, and not superseded.
@@ -238,6 +270,14 @@ fn map_trade_processing_error(error: &Report) -> GraphQlError {
     internal("failed to accept trade", error)
 }
 
+fn map_repropose_error(error: &Report) -> GraphQlError {
+    if let Some(rejection) = error.downcast_ref::<ReproposeRejection>() {
+        return graphql_error(ErrorCode::TradeCannotBeReproposed, rejection.to_string());
+    }
+
+    internal("failed to re-propose trade", error)
+}
+
 fn internal(message: &str, error: &Report) -> GraphQlError {
     tracing::error!(error = ?error, message);
     code_error(ErrorCode::Internal)
@@ -263,6 +303,20 @@ mod tests {
         assert!(error.message.contains("team (id = 7)"));
     }
 
+    #[test]
+    fn repropose_rejections_are_typed() {
+        let error = map_repropose_error(&Report::new(ReproposeRejection::NotProposer));
+
+        assert_eq!(
+            error_code(&error),
+            Some("TRADE_CANNOT_BE_REPROPOSED".into())
+        );
+        assert_eq!(
+            error.message,
+            "Only the proposing team can re-propose a trade."
+        );
+    }
+
     #[test]
     fn other_trade_failures_stay_internal_and_generic() {
         let error = map_trade_processing_error(&color_eyre::eyre::eyre!("db exploded"));
@@ -1,11 +1,15 @@
 use async_graphql::{ComplexObject, Context, InputObject, Result, SimpleObject};
+use chrono::Utc;
 use fbkl_entity::{
     sea_orm::DatabaseConnection,
     trade::{self, TradeStatus},
-    trade_action, trade_asset,
+    trade_action::{self, TradeActionType},
+    trade_asset,
     trade_asset::TradeAssetType,
 };
 
+use fbkl_logic::trade::{self as trade_logic, find_trade_team_sides, preview_trade};
+
 use crate::graphql::{ErrorCode, code_error, team::Team};
 
 /// A trade between two or more teams. `status` drives what the client may still do with it; the
@@ -75,6 +79,128 @@ impl Trade {
 
         Ok(action_models.iter().map(TradeAction::from_model).collect())
     }
+
+    /// What each team sends and receives, and where it stands on the trade, ordered by team id.
+    async fn team_sides(&self, ctx: &Context<'_>) -> Result<Vec<TradeTeamSide>> {
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+        let sides = find_trade_team_sides(&self.model, db)
+            .await
+            .map_err(|err| {
+                tracing::error!(error = ?err, trade_id = self.id, "failed to load trade sides");
+                code_error(ErrorCode::Internal)
+            })?;
+
+        Ok(sides.iter().map(TradeTeamSide::from_side).collect())
+    }
+
+    /// Teams that have not yet accepted or rejected. Empty once the trade is no longer active.
+    async fn awaiting_team_ids(&self, ctx: &Context<'_>) -> Result<Vec<i64>> {
+        if !self.model.is_active() {
+            return Ok(vec![]);
+        }
+
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+        let sides = find_trade_team_sides(&self.model, db)
+            .await
+            .map_err(|err| {
+                tracing::error!(error = ?err, trade_id = self.id, "failed to load trade sides");
+                code_error(ErrorCode::Internal)
+            })?;
+
+        Ok(sides
+            .iter()
+            .filter(|side| side.is_awaiting())
+            .map(|side| side.team_id)
+            .collect())
+    }
+
+    /// Each team's projected salary and roster if the trade were processed now. `null` once the
+    /// trade is no longer active. Overages are warnings: the roster must only be legal by the next
+    /// roster lock.
+    async fn preview(&self, ctx: &Context<'_>) -> Result<Option<Vec<TradeTeamPreview>>> {
+        if !self.model.is_active() {
+            return Ok(None);
+        }
+
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+        let previews = preview_trade(&self.model, &Utc::now().fixed_offset(), db)
+            .await
+            .map_err(|err| {
+                tracing::error!(error = ?err, trade_id = self.id, "failed to preview trade");
+                code_error(ErrorCode::Internal)
+            })?;
+
+        Ok(Some(
+            previews
+                .iter()
+                .map(TradeTeamPreview::from_preview)
+                .collect(),
+        ))
+    }
+}
+
+/// One team's net view of a trade.
+#[derive(SimpleObject)]
+pub struct TradeTeamSide {
+    pub team_id: i64,
+    pub is_proposer: bool,
+    pub sends: Vec<TradeAsset>,
+    pub receives: Vec<TradeAsset>,
+    /// The team's most recent action; `null` while it has yet to respond.
+    pub latest_action: Option<TradeActionType>,
+    pub awaiting: bool,
+}
+
+impl TradeTeamSide {
+    fn from_side(side: &trade_logic::TradeTeamSide) -> Self {
+        Self {
+            team_id: side.team_id,
+            is_proposer: side.is_proposer,
+            sends: side.sends.iter().map(TradeAsset::from_model).collect(),
+            receives: side.receives.iter().map(TradeAsset::from_model).collect(),
+            latest_action: side.latest_action,
+            awaiting: side.is_awaiting(),
+        }
+    }
+}
+
+/// One team's side of a trade preview.
+#[derive(SimpleObject)]
+pub struct TradeTeamPreview {
+    pub team_id: i64,
+    pub salary_before: i16,
+    pub salary_after: i16,
+    pub salary_cap: i16,
+    pub over_cap_by: i16,
+    pub over_roster_limit_by: i16,
+    pub vet_or_rookie_count_after: i16,
+    pub ir_count_after: i16,
+    pub rookie_development_count_after: i16,
+    pub rookie_development_international_count_after: i16,
+    /// Incoming IR contracts, which come off IR on arrival (rules §10.3.2).
+    pub contract_ids_activated_from_ir: Vec<i64>,
+    pub warnings: Vec<String>,
+}
+
+impl TradeTeamPreview {
+    fn from_preview(preview: &trade_logic::TradeTeamPreview) -> Self {
+        Self {
+            team_id: preview.team_id,
+            salary_before: preview.salary_before,
+            salary_after: preview.salary_after,
+            salary_cap: preview.salary_cap,
+            over_cap_by: preview.over_cap_by,
+            over_roster_limit_by: preview.over_roster_limit_by,
+            vet_or_rookie_count_after: preview.roster_after.vet_or_rookie,
+            ir_count_after: preview.roster_after.ir,
+            rookie_development_count_after: preview.roster_after.rookie_development,
+            rookie_development_international_count_after: preview
+                .roster_after
+                .rookie_development_international,
+            contract_ids_activated_from_ir: preview.contract_ids_activated_from_ir.clone(),
+            warnings: preview.warnings(),
+        }
+    }
 }
 
 /// One asset moving between two teams. Exactly one of the three id fields is set, per `assetType`.
@@ -107,7 +233,7 @@ impl TradeAsset {
 #[derive(SimpleObject)]
 pub struct TradeAction {
     pub id: i64,
-    pub action_type: trade_action::TradeActionType,
+    pub action_type: TradeActionType,
     pub team_user_id: i64,
     pub trade_id: i64,
     pub created_at: String,
@@ -0,0 +1,4 @@
+mod trade_block_resolvers;
+mod trade_block_types;
+pub use trade_block_resolvers::*;
+pub use trade_block_types::*;
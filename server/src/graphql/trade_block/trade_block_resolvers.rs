@@ -0,0 +1,231 @@
+//! Trade block listings and the trade finder.
+//!
+//! Listings are always for the caller's own team and the league's current season. The finder only
+//! suggests; turning a suggestion into a trade still goes through `proposeTrade`.
+
+use async_graphql::{Context, Error as GraphQlError, Object, Result};
+use chrono::Utc;
+use color_eyre::Report;
+use fbkl_entity::{
+    sea_orm::DatabaseConnection, trade_asset::TradeAssetType, trade_block::TradeBlockNeed,
+};
+use fbkl_logic::trade::{
+    TradeBlockRejection, find_standing_trade_block_listings,
+    finder::{TradeFinderTarget, find_trade_packages},
+    list_contract_on_trade_block, list_draft_pick_on_trade_block, list_trade_block_need,
+    remove_trade_block_listing,
+};
+
+use super::{TradeBlockAssetInput, TradeBlockListing, TradeFinderPackage};
+use crate::graphql::{
+    ErrorCode, LeagueRoleGuard, RoleRequirement, code_error, current_season, graphql_error,
+    require_league_role,
+};
+
+/// Finder results are sorted best-first, so a page is just the head of the list.
+const DEFAULT_FINDER_LIMIT: usize = 25;
+const MAX_FINDER_LIMIT: usize = 100;
+
+#[derive(Default)]
+pub struct TradeBlockQuery;
+
+#[Object]
+impl TradeBlockQuery {
+    /// Every standing listing in the caller's league this season, optionally narrowed to one team.
+    #[graphql(guard = "LeagueRoleGuard(RoleRequirement::Member)")]
+    async fn trade_block(
+        &self,
+        ctx: &Context<'_>,
+        team_id: Option<i64>,
+    ) -> Result<Vec<TradeBlockListing>> {
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+        let (_, caller_team) = require_league_role(ctx, RoleRequirement::Member).await?;
+        let end_of_season_year = current_season(ctx, caller_team.league_id).await?;
+
+        let standing_listings =
+            find_standing_trade_block_listings(caller_team.league_id, end_of_season_year, db)
+                .await
+                .map_err(|err| internal("failed to load the trade block", &err))?;
+
+        standing_listings
+            .iter()
+            .filter(|standing| team_id.is_none_or(|team_id| standing.listing.team_id == team_id))
+            .map(TradeBlockListing::from_listing)
+            .collect()
+    }
+
+    /// Packages from other teams' trade blocks that would take one of the caller's assets, closest
+    /// salary balance first. Only packages that keep both rosters legal are returned.
+    #[graphql(guard = "LeagueRoleGuard(RoleRequirement::Member)")]
+    async fn trade_finder(
+        &self,
+        ctx: &Context<'_>,
+        asset_type: TradeAssetType,
+        asset_id: i64,
+        limit: Option<usize>,
+    ) -> Result<Vec<TradeFinderPackage>> {
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+        let (team_user, caller_team) = require_league_role(ctx, RoleRequirement::Member).await?;
+        let end_of_season_year = current_season(ctx, caller_team.league_id).await?;
+
+        let target = match asset_type {
+            TradeAssetType::Contract => TradeFinderTarget::Contract(asset_id),
+            TradeAssetType::DraftPick => TradeFinderTarget::DraftPick(asset_id),
+            TradeAssetType::DraftPickOption => {
+                return Err(graphql_error(
+                    ErrorCode::BadRequest,
+                    "the trade finder searches for contracts and draft picks only",
+                ));
+            }
+        };
+
+        let packages = find_trade_packages(
+            caller_team.league_id,
+            end_of_season_year,
+            team_user.team_id,
+            target,
+            &Utc::now().fixed_offset(),
+            db,
+        )
+        .await
+        .map_err(|err| trade_block_error(&err, "failed to search the trade block"))?;
+
+        packages
+            .iter()
+            .take(limit.unwrap_or(DEFAULT_FINDER_LIMIT).min(MAX_FINDER_LIMIT))
+            .map(TradeFinderPackage::from_package)
+            .collect()
+    }
+}
+
+#[derive(Default)]
+pub struct TradeBlockMutation;
+
+#[Object]
+impl TradeBlockMutation {
+    /// Puts one of the caller's contracts or draft picks on their trade block.
+    #[graphql(guard = "LeagueRoleGuard(RoleRequirement::Member)")]
+    async fn add_to_trade_block(
+        &self,
+        ctx: &Context<'_>,
+        input: TradeBlockAssetInput,
+    ) -> Result<TradeBlockListing> {
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+        let (team_user, caller_team) = require_league_role(ctx, RoleRequirement::Member).await?;
+        let end_of_season_year = current_season(ctx, caller_team.league_id).await?;
+
+        let listing_model = match input.asset_type {
+            TradeAssetType::Contract => {
+                list_contract_on_trade_block(team_user.team_id, input.asset_id, input.note, db)
+                    .await
+            }
+            TradeAssetType::DraftPick => {
+                list_draft_pick_on_trade_block(
+                    team_user.team_id,
+                    end_of_season_year,
+                    input.asset_id,
+                    input.note,
+                    db,
+                )
+                .await
+            }
+            TradeAssetType::DraftPickOption => {
+                return Err(graphql_error(
+                    ErrorCode::BadRequest,
+                    "only contracts and draft picks can be put on the trade block",
+                ));
+            }
+        }
+        .map_err(|err| trade_block_error(&err, "failed to add to the trade block"))?;
+        if listing_model.league_id != caller_team.league_id {
+            return Err(code_error(ErrorCode::NotFound));
+        }
+
+        load_standing_listing(ctx, listing_model.id, listing_model.end_of_season_year).await
+    }
+
+    /// Lists something the caller's team wants back in a trade.
+    #[graphql(guard = "LeagueRoleGuard(RoleRequirement::Member)")]
+    async fn add_trade_block_need(
+        &self,
+        ctx: &Context<'_>,
+        need: TradeBlockNeed,
+        note: Option<String>,
+    ) -> Result<TradeBlockListing> {
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+        let (team_user, caller_team) = require_league_role(ctx, RoleRequirement::Member).await?;
+        let end_of_season_year = current_season(ctx, caller_team.league_id).await?;
+
+        let listing_model = list_trade_block_need(
+            caller_team.league_id,
+            team_user.team_id,
+            end_of_season_year,
+            need,
+            note,
+            db,
+        )
+        .await
+        .map_err(|err| trade_block_error(&err, "failed to add a trade block need"))?;
+
+        load_standing_listing(ctx, listing_model.id, end_of_season_year).await
+    }
+
+    /// Takes one of the caller's listings off their trade block.
+    #[graphql(guard = "LeagueRoleGuard(RoleRequirement::Member)")]
+    async fn remove_from_trade_block(
+        &self,
+        ctx: &Context<'_>,
+        trade_block_id: i64,
+    ) -> Result<bool> {
+        let db = ctx.data_unchecked::<DatabaseConnection>();
+        let (team_user, _) = require_league_role(ctx, RoleRequirement::Member).await?;
+
+        remove_trade_block_listing(team_user.team_id, trade_block_id, db)
+            .await
+            .map_err(|err| trade_block_error(&err, "failed to remove a trade block listing"))?;
+
+        Ok(true)
+    }
+}
+
+/// Re-reads a just-written listing through the same resolution the `tradeBlock` query uses.
+async fn load_standing_listing(
+    ctx: &Context<'_>,
+    trade_block_id: i64,
+    end_of_season_year: i16,
+) -> Result<TradeBlockListing> {
+    let db = ctx.data_unchecked::<DatabaseConnection>();
+    let (_, caller_team) = require_league_role(ctx, RoleRequirement::Member).await?;
+
+    let standing_listings =
+        find_standing_trade_block_listings(caller_team.league_id, end_of_season_year, db)
+            .await
+            .map_err(|err| internal("failed to load the trade block", &err))?;
+    let standing = standing_listings
+        .iter()
+        .find(|standing| standing.listing.id == trade_block_id)
+        .ok_or_else(|| code_error(ErrorCode::NotFound))?;
+
+    TradeBlockListing::from_listing(standing)
+}
+
+/// A refused listing is the client's fault and gets its own code; anything else is a server fault.
+fn trade_block_error(error: &Report, message: &str) -> GraphQlError {
+    let Some(rejection) = error.downcast_ref::<TradeBlockRejection>() else {
+        return internal(message, error);
+    };
+
+    let code = match rejection {
+        TradeBlockRejection::NotOwnedByTeam { .. } => ErrorCode::TradeBlockAssetNotOwned,
+        TradeBlockRejection::AlreadyListed => ErrorCode::TradeBlockAlreadyListed,
+        TradeBlockRejection::WrongSeason { .. } => ErrorCode::BadRequest,
+        TradeBlockRejection::NotYourListing { .. } => ErrorCode::Forbidden,
+    };
+
+    graphql_error(code, rejection.to_string())
+}
+
+fn internal(message: &str, error: &Report) -> GraphQlError {
+    tracing::error!(error = ?error, message);
+    code_error(ErrorCode::Internal)
+}
@@ -0,0 +1,121 @@
+use async_graphql::{InputObject, Result, SimpleObject};
+use fbkl_entity::{
+    trade_asset::TradeAssetType,
+    trade_block::{TradeBlockKind, TradeBlockNeed},
+};
+use fbkl_logic::trade::{self as trade_logic, finder};
+
+use crate::graphql::{ErrorCode, code_error, contract::Contract, draft::DraftPick};
+
+/// One standing listing on a team's trade block. Exactly one of `contract`, `draftPick` and `need`
+/// is set, matching `kind`.
+#[derive(SimpleObject)]
+pub struct TradeBlockListing {
+    pub id: i64,
+    pub team_id: i64,
+    pub end_of_season_year: i16,
+    pub kind: TradeBlockKind,
+    /// The latest contract in the listed contract's chain.
+    pub contract: Option<Contract>,
+    pub draft_pick: Option<DraftPick>,
+    pub need: Option<TradeBlockNeed>,
+    pub note: Option<String>,
+    pub created_at: String,
+}
+
+impl TradeBlockListing {
+    pub fn from_listing(standing: &trade_logic::TradeBlockListing) -> Result<Self> {
+        let asset = standing
+            .asset
+            .as_ref()
+            .map(TradeBlockAsset::from_asset)
+            .transpose()?;
+        let (contract, draft_pick) =
+            asset.map_or((None, None), |asset| (asset.contract, asset.draft_pick));
+
+        Ok(Self {
+            id: standing.listing.id,
+            team_id: standing.listing.team_id,
+            end_of_season_year: standing.listing.end_of_season_year,
+            kind: standing.listing.kind,
+            contract,
+            draft_pick,
+            need: standing.listing.need,
+            note: standing.listing.note.clone(),
+            created_at: standing.listing.created_at.to_rfc3339(),
+        })
+    }
+}
+
+/// A contract or pick in a finder package. `assetType` and the set field's id feed straight into
+/// `proposeTrade`.
+#[derive(SimpleObject)]
+pub struct TradeBlockAsset {
+    pub asset_type: TradeAssetType,
+    pub contract: Option<Contract>,
+    pub draft_pick: Option<DraftPick>,
+}
+
+impl TradeBlockAsset {
+    fn from_asset(asset: &trade_logic::TradeBlockAsset) -> Result<Self> {
+        Ok(match asset {
+            trade_logic::TradeBlockAsset::Contract(contract_model) => Self {
+                asset_type: TradeAssetType::Contract,
+                contract: Some(Contract::from_model(contract_model).map_err(|err| {
+                    tracing::error!(error = ?err, "trade block contract has no player");
+                    code_error(ErrorCode::Internal)
+                })?),
+                draft_pick: None,
+            },
+            trade_logic::TradeBlockAsset::DraftPick(draft_pick_model) => Self {
+                asset_type: TradeAssetType::DraftPick,
+                contract: None,
+                draft_pick: Some(DraftPick::from_model(draft_pick_model)),
+            },
+        })
+    }
+}
+
+/// A suggested trade from the finder. `sent` is what the caller's team gives up.
+#[derive(SimpleObject)]
+pub struct TradeFinderPackage {
+    pub counterparty_team_id: i64,
+    pub sent: Vec<TradeBlockAsset>,
+    pub received: Vec<TradeBlockAsset>,
+    pub salary_sent: i16,
+    pub salary_received: i16,
+    /// How far apart the two sides' salaries are; suggestions are sorted on it.
+    pub salary_balance: i16,
+    pub counterparty_needs_met: Vec<TradeBlockNeed>,
+}
+
+impl TradeFinderPackage {
+    pub fn from_package(package: &finder::TradeFinderPackage) -> Result<Self> {
+        Ok(Self {
+            counterparty_team_id: package.counterparty_team_id,
+            sent: package
+                .sent
+                .iter()
+                .map(TradeBlockAsset::from_asset)
+                .collect::<Result<_>>()?,
+            received: package
+                .received
+                .iter()
+                .map(TradeBlockAsset::from_asset)
+                .collect::<Result<_>>()?,
+            salary_sent: package.salary_sent,
+            salary_received: package.salary_received,
+            salary_balance: package.salary_balance,
+            counterparty_needs_met: package.counterparty_needs_met.clone(),
+        })
+    }
+}
+
+/// A contract or pick to put on the caller's trade block.
+#[derive(InputObject)]
+pub struct TradeBlockAssetInput {
+    /// `Contract` or `DraftPick`; draft pick options cannot be listed.
+    pub asset_type: TradeAssetType,
+    pub asset_id: i64,
+    pub note: Option<String>,
+}
@@ -11,11 +11,15 @@ pub struct Transaction {
     pub league_id: i64,
     pub deadline_id: i64,
     pub contract_id: Option<i64>,
+    /// Set when the commissioner waived this drop's cap penalty (rules §9.1.4).
+    pub drop_penalty_waived_at: Option<String>,
+    pub drop_penalty_waived_by_team_user_id: Option<i64>,
+    pub drop_penalty_waiver_reason: Option<String>,
     pub created_at: String,
 }
 
 impl Transaction {
-    pub(super) fn from_model(entity: &transaction::Model) -> Self {
+    pub(crate) fn from_model(entity: &transaction::Model) -> Self {
         Self {
             id: entity.id,
             end_of_season_year: entity.end_of_season_year,
@@ -23,6 +27,11 @@ impl Transaction {
             league_id: entity.league_id,
             deadline_id: entity.deadline_id,
             contract_id: entity.contract_id,
+            drop_penalty_waived_at: entity
+                .drop_penalty_waived_at
+                .map(|waived_at| waived_at.to_rfc3339()),
+            drop_penalty_waived_by_team_user_id: entity.drop_penalty_waived_by_team_user_id,
+            drop_penalty_waiver_reason: entity.drop_penalty_waiver_reason.clone(),
             created_at: entity.created_at.to_string(),
         }
     }
@@ -1,15 +1,22 @@
 use super::User;
-use async_graphql::{Context, Object};
-use fbkl_entity::user;
+use async_graphql::{Context, Object, Result};
+use fbkl_entity::{api_token, user};
+
+use crate::graphql::{ErrorCode, code_error};
 
 #[derive(Default)]
 pub struct UserQuery;
 
 #[Object]
 impl UserQuery {
-    async fn current_user<'a>(&self, ctx: &Context<'a>) -> Option<User> {
+    /// The logged-in user, or `null` without a session. Carries the user's email, so an API token
+    /// may not read it.
+    async fn current_user<'a>(&self, ctx: &Context<'a>) -> Result<Option<User>> {
+        if ctx.data_opt::<api_token::Model>().is_some() {
+            return Err(code_error(ErrorCode::Forbidden));
+        }
         let user_model = ctx.data_unchecked::<Option<user::Model>>().to_owned();
 
-        user_model.map(|model| User::from_model(&model))
+        Ok(user_model.map(|model| User::from_model(&model)))
     }
 }
@@ -1,3 +1,4 @@
+pub mod account_handlers;
 pub mod graphql_handlers;
 pub mod login_handlers;
 pub mod public_handlers;
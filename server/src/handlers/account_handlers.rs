@@ -0,0 +1,338 @@
+//! Password resets, password changes, and email changes.
+//!
+//! Each flow that can't rely on the current password proves control of an address instead: a
+//! single-use `user_verification` token, of which only the hash is stored. Changing the password
+//! bumps the user's `session_generation`, which logs out every other session.
+//!
+//! Each completed change is recorded in the `security_event` log.
+
+use axum::{
+    Form,
+    extract::{Query, State},
+    http::StatusCode,
+    response::Response,
+};
+use chrono::{TimeDelta, Utc};
+use fbkl_auth::{
+    decode_token, encode_token, generate_password_hash, generate_token, hash_token, validate_email,
+    validate_password, verify_password_against_hash,
+};
+use fbkl_entity::{
+    api_token_queries,
+    sea_orm::{ConnectionTrait, TransactionTrait},
+    security_event::SecurityEventKind,
+    security_event_queries::{self, NewSecurityEvent},
+    user, user_queries,
+    user_verification::UserVerificationKind,
+    user_verification_queries,
+};
+use fbkl_notifications::{email_change_confirmation_email, enqueue_email, password_reset_email};
+use serde::Deserialize;
+use std::sync::Arc;
+use tower_sessions::Session;
+use tracing::instrument;
+
+use crate::{
+    client_ip::ClientIp,
+    error::FbklError,
+    handlers::user_registration_handlers::TokenQuery,
+    read_public_url,
+    server::AppState,
+    session::{get_current_user, start_user_session},
+};
+
+/// How long a password reset link stays valid. Short, since it grants the account outright.
+pub const PASSWORD_RESET_LIFETIME_HOURS: i64 = 1;
+/// How long an email change link stays valid.
+pub const EMAIL_CHANGE_LIFETIME_HOURS: i64 = 24;
+
+#[derive(Debug, Deserialize)]
+pub struct PasswordResetRequestFormData {
+    pub email: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PasswordResetFormData {
+    pub token: String,
+    pub password: String,
+    pub confirm_password: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PasswordChangeFormData {
+    pub current_password: String,
+    pub password: String,
+    pub confirm_password: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EmailChangeFormData {
+    pub current_password: String,
+    pub email: String,
+}
+
+fn client_error(status: StatusCode, code: &str) -> Result<Response<String>, FbklError> {
+    Ok(Response::builder().status(status).body(code.to_string())?)
+}
+
+async fn record_account_event<C>(
+    kind: SecurityEventKind,
+    user_model: &user::Model,
+    client_ip: ClientIp,
+    db: &C,
+) -> Result<(), FbklError>
+where
+    C: ConnectionTrait,
+{
+    security_event_queries::insert_security_event(
+        NewSecurityEvent {
+            kind,
+            user_id: Some(user_model.id),
+            email: None,
+            ip_address: Some(client_ip.0),
+            detail: None,
+        },
+        Utc::now().fixed_offset(),
+        db,
+    )
+    .await?;
+    Ok(())
+}
+
+/// Issues a password reset token for the account with this email, if there is one.
+///
+/// Always answers "ok", so the endpoint can't be used to find out which emails have accounts.
+#[instrument(skip_all)]
+pub async fn request_password_reset(
+    State(state): State<Arc<AppState>>,
+    Form(form): Form<PasswordResetRequestFormData>,
+) -> Result<Response<String>, FbklError> {
+    let Some(user_model) = user_queries::find_user_by_email(form.email.trim(), &state.db).await?
+    else {
+        return Ok(Response::new("ok".to_string()));
+    };
+
+    let token = generate_token();
+    let expires_at = Utc::now().fixed_offset() + TimeDelta::hours(PASSWORD_RESET_LIFETIME_HOURS);
+    let db_txn = state.db.begin().await?;
+    user_verification_queries::insert_user_verification(
+        user_model.id,
+        UserVerificationKind::PasswordReset,
+        hash_token(&token),
+        None,
+        expires_at,
+        &db_txn,
+    )
+    .await?;
+    enqueue_email(
+        &password_reset_email(
+            &user_model.email,
+            &read_public_url(),
+            &encode_token(&token.to_vec()),
+            PASSWORD_RESET_LIFETIME_HOURS,
+        ),
+        &db_txn,
+    )
+    .await?;
+    db_txn.commit().await?;
+
+    Ok(Response::new("ok".to_string()))
+}
+
+/// Sets a new password with a reset token, logging out every existing session of the user and
+/// revoking their API tokens.
+#[instrument(skip_all)]
+pub async fn reset_password(
+    State(state): State<Arc<AppState>>,
+    client_ip: ClientIp,
+    Form(form): Form<PasswordResetFormData>,
+) -> Result<Response<String>, FbklError> {
+    if form.password != form.confirm_password {
+        return client_error(StatusCode::BAD_REQUEST, "PASSWORDS_NOT_MATCHING");
+    }
+    if validate_password(&form.password).is_err() {
+        return client_error(StatusCode::BAD_REQUEST, "INVALID_PASSWORD");
+    }
+
+    let token_hash = hash_token(&decode_token(form.token.trim())?);
+    // Hashed before the token row is locked, so the lock is not held through Argon2.
+    let hashed_password = generate_password_hash(&form.password)?;
+    let now = Utc::now().fixed_offset();
+    let db_txn = state.db.begin().await?;
+    let Some(verification) = user_verification_queries::find_user_verification_by_token_hash(
+        token_hash,
+        UserVerificationKind::PasswordReset,
+        &db_txn,
+    )
+    .await?
+    .filter(|verification| verification.is_open(now)) else {
+        return client_error(StatusCode::BAD_REQUEST, "TOKEN_NOT_OPEN");
+    };
+    let Some(user_model) = user_queries::find_user_by_id(verification.user_id, &db_txn).await?
+    else {
+        return client_error(StatusCode::BAD_REQUEST, "TOKEN_NOT_OPEN");
+    };
+
+    user_verification_queries::mark_user_verification_used(verification, &db_txn).await?;
+    let updated_user =
+        user_queries::update_user_password(user_model, hashed_password, &db_txn).await?;
+    api_token_queries::revoke_api_tokens_for_user(updated_user.id, now, &db_txn).await?;
+    record_account_event(
+        SecurityEventKind::PasswordReset,
+        &updated_user,
+        client_ip,
+        &db_txn,
+    )
+    .await?;
+    db_txn.commit().await?;
+
+    Ok(Response::new("ok".to_string()))
+}
+
+/// Changes the logged-in user's password. This session stays logged in; every other one is
+/// logged out, and the user's API tokens are revoked.
+#[instrument(skip_all)]
+pub async fn change_password(
+    State(state): State<Arc<AppState>>,
+    session: Session,
+    client_ip: ClientIp,
+    Form(form): Form<PasswordChangeFormData>,
+) -> Result<Response<String>, FbklError> {
+    let Some(user_model) = get_current_user(session.clone(), &state.db).await? else {
+        return Err(StatusCode::UNAUTHORIZED.into());
+    };
+
+    verify_password_against_hash(&form.current_password, &user_model.hashed_password)?;
+    if form.password != form.confirm_password {
+        return client_error(StatusCode::BAD_REQUEST, "PASSWORDS_NOT_MATCHING");
+    }
+    if validate_password(&form.password).is_err() {
+        return client_error(StatusCode::BAD_REQUEST, "INVALID_PASSWORD");
+    }
+
+    let hashed_password = generate_password_hash(&form.password)?;
+    let db_txn = state.db.begin().await?;
+    let updated_user =
+        user_queries::update_user_password(user_model, hashed_password, &db_txn).await?;
+    api_token_queries::revoke_api_tokens_for_user(
+        updated_user.id,
+        Utc::now().fixed_offset(),
+        &db_txn,
+    )
+    .await?;
+    record_account_event(
+        SecurityEventKind::PasswordChanged,
+        &updated_user,
+        client_ip,
+        &db_txn,
+    )
+    .await?;
+    db_txn.commit().await?;
+    start_user_session(&session, &updated_user).await?;
+
+    Ok(Response::new("ok".to_string()))
+}
+
+/// Starts moving the logged-in user to a new email. The change only happens once a link sent to
+/// the new address is followed.
+#[instrument(skip_all)]
+pub async fn request_email_change(
+    State(state): State<Arc<AppState>>,
+    session: Session,
+    Form(form): Form<EmailChangeFormData>,
+) -> Result<Response<String>, FbklError> {
+    let Some(user_model) = get_current_user(session, &state.db).await? else {
+        return Err(StatusCode::UNAUTHORIZED.into());
+    };
+
+    verify_password_against_hash(&form.current_password, &user_model.hashed_password)?;
+    let new_email = form.email.trim().to_owned();
+    if validate_email(&new_email).is_err() {
+        return client_error(StatusCode::BAD_REQUEST, "INVALID_EMAIL");
+    }
+    if user_queries::find_user_by_email(&new_email, &state.db)
+        .await?
+        .is_some()
+    {
+        return client_error(StatusCode::BAD_REQUEST, "EMAIL_TAKEN");
+    }
+
+    let token = generate_token();
+    let expires_at = Utc::now().fixed_offset() + TimeDelta::hours(EMAIL_CHANGE_LIFETIME_HOURS);
+    let db_txn = state.db.begin().await?;
+    user_verification_queries::insert_user_verification(
+        user_model.id,
+        UserVerificationKind::EmailChange,
+        hash_token(&token),
+        Some(new_email.clone()),
+        expires_at,
+        &db_txn,
+    )
+    .await?;
+    // Sent to the new address: following the link is what proves the user controls it.
+    enqueue_email(
+        &email_change_confirmation_email(
+            &new_email,
+            &read_public_url(),
+            &encode_token(&token.to_vec()),
+            EMAIL_CHANGE_LIFETIME_HOURS,
+        ),
+        &db_txn,
+    )
+    .await?;
+    db_txn.commit().await?;
+
+    Ok(Response::new("ok".to_string()))
+}
+
+/// Completes an email change from the link sent to the new address.
+#[instrument(skip_all)]
+pub async fn confirm_email_change(
+    Query(token_query): Query<TokenQuery>,
+    State(state): State<Arc<AppState>>,
+    client_ip: ClientIp,
+) -> Result<Response<String>, FbklError> {
+    let token = token_query.token.trim();
+    if token.is_empty() {
+        return client_error(StatusCode::BAD_REQUEST, "REQUIRED_TOKEN_MISSING");
+    }
+
+    let token_hash = hash_token(&decode_token(token)?);
+    let now = Utc::now().fixed_offset();
+    let db_txn = state.db.begin().await?;
+    let Some(verification) = user_verification_queries::find_user_verification_by_token_hash(
+        token_hash,
+        UserVerificationKind::EmailChange,
+        &db_txn,
+    )
+    .await?
+    .filter(|verification| verification.is_open(now)) else {
+        return client_error(StatusCode::BAD_REQUEST, "TOKEN_NOT_OPEN");
+    };
+    let (Some(new_email), Some(user_model)) = (
+        verification.new_email.clone(),
+        user_queries::find_user_by_id(verification.user_id, &db_txn).await?,
+    ) else {
+        return client_error(StatusCode::BAD_REQUEST, "TOKEN_NOT_OPEN");
+    };
+    // Someone may have registered the address since the change was requested.
+    if user_queries::find_user_by_email(&new_email, &db_txn)
+        .await?
+        .is_some()
+    {
+        return client_error(StatusCode::BAD_REQUEST, "EMAIL_TAKEN");
+    }
+
+    user_verification_queries::mark_user_verification_used(verification, &db_txn).await?;
+    let updated_user = user_queries::update_user_email(user_model, new_email, &db_txn).await?;
+    record_account_event(
+        SecurityEventKind::EmailChanged,
+        &updated_user,
+        client_ip,
+        &db_txn,
+    )
+    .await?;
+    db_txn.commit().await?;
+
+    Ok(Response::new("ok".to_string()))
+}
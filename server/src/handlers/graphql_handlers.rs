@@ -1,23 +1,53 @@
 use std::sync::Arc;
 
-use async_graphql::http::GraphiQLSource;
+use async_graphql::{Pos, http::GraphiQLSource};
 use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
 use axum::{
     Extension,
     extract::State,
+    http::{HeaderMap, header::AUTHORIZATION},
     response::{Html, IntoResponse},
 };
-use tower_sessions::Session;
+use tower_sessions::{MemoryStore, Session};
 
-use crate::{graphql::FbklSchema, server::AppState, session::get_current_user};
+use crate::{
+    graphql::{FbklSchema, authenticate_api_token},
+    server::AppState,
+    session::get_current_user,
+};
 
 /// This handler is the endpoint for all graphql queries.
+///
+/// A request with an `Authorization: Bearer` header is authenticated by that API token alone, and
+/// runs with an empty session so nothing from a cookie leaks into it.
 pub async fn process_graphql(
     schema: Extension<FbklSchema>,
     session: Session,
     State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
     req: GraphQLRequest,
 ) -> GraphQLResponse {
+    if let Some(bearer_token) = bearer_token(&headers) {
+        let request = req.into_inner();
+        return match authenticate_api_token(bearer_token, &request, &state.db).await {
+            Ok((api_token, user_model)) => schema
+                .execute(
+                    request
+                        .data(Session::new(None, Arc::new(MemoryStore::default()), None))
+                        .data(Some(user_model))
+                        .data(api_token)
+                        .data(state.db.clone()),
+                )
+                .await
+                .into(),
+            Err(error) => {
+                let mut server_error = error.into_server_error(Pos::default());
+                server_error.locations.clear();
+                async_graphql::Response::from_errors(vec![server_error]).into()
+            }
+        };
+    }
+
     let user_model = match get_current_user(session.clone(), &state.db).await {
         Ok(user_model) => user_model,
         // Fail closed: a session/DB outage must not be served as "not logged in".
@@ -42,6 +72,14 @@ pub async fn process_graphql(
         .into()
 }
 
+/// The token from an `Authorization: Bearer <token>` header, if there is one.
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    let (scheme, token) = headers.get(AUTHORIZATION)?.to_str().ok()?.split_once(' ')?;
+    scheme
+        .eq_ignore_ascii_case("bearer")
+        .then_some(token.trim())
+}
+
 pub async fn graphiql() -> impl IntoResponse {
     Html(
         GraphiQLSource::build()
@@ -49,3 +87,37 @@ pub async fn graphiql() -> impl IntoResponse {
             .finish(),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use axum::http::HeaderValue;
+
+    use super::*;
+
+    fn headers_with_authorization(value: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, HeaderValue::from_static(value));
+        headers
+    }
+
+    #[test]
+    fn reads_the_token_from_a_bearer_header() {
+        assert_eq!(
+            bearer_token(&headers_with_authorization("Bearer abc123")),
+            Some("abc123")
+        );
+        assert_eq!(
+            bearer_token(&headers_with_authorization("bearer abc123 ")),
+            Some("abc123")
+        );
+    }
+
+    #[test]
+    fn ignores_other_authorization_schemes() {
+        assert_eq!(
+            bearer_token(&headers_with_authorization("Basic dXNlcjpwYXNz")),
+            None
+        );
+        assert_eq!(bearer_token(&HeaderMap::new()), None);
+    }
+}
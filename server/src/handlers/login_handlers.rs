@@ -1,19 +1,35 @@
 use axum::{
     Form, Json,
     extract::State,
-    http::StatusCode,
+    http::{StatusCode, header::RETRY_AFTER},
     response::{IntoResponse, Response},
 };
-use fbkl_auth::verify_password_against_hash;
-use fbkl_entity::user_queries;
-use fbkl_logic::team_ownership::get_team_user_access_for_user_in_league;
+use chrono::Utc;
+use fbkl_auth::{AuthError, verify_password_against_hash, verify_password_for_unknown_user};
+use fbkl_entity::{sea_orm::TransactionTrait, user_queries};
+use fbkl_logic::{
+    login_security::{
+        LoginAttempt, check_login_throttle, lock_login_attempt, reserve_login_attempt,
+        settle_login_attempt,
+    },
+    team_ownership::get_team_user_access_for_user_in_league,
+};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use time::Duration;
-use tower_sessions::{Expiry, Session};
+use tower_sessions::Session;
 use tracing::instrument;
 
-use crate::{error::FbklError, server::AppState, session::get_current_user};
+use crate::{
+    client_ip::ClientIp,
+    error::FbklError,
+    server::AppState,
+    session::{get_current_user, start_user_session},
+};
+
+/// The answer to every failed login, whether the email has no account or the password is wrong.
+const INVALID_CREDENTIALS: &str = "INVALID_CREDENTIALS";
+/// The answer to every throttled login, whichever limit it hit.
+const TOO_MANY_LOGIN_ATTEMPTS: &str = "TOO_MANY_LOGIN_ATTEMPTS";
 
 #[derive(Debug, Deserialize)]
 pub struct LoginFormData {
@@ -21,27 +37,78 @@ pub struct LoginFormData {
     password: String,
 }
 
+/// Logs a user in with their email and password.
+///
+/// An unknown email and a wrong password get the same response after the same Argon2 work, and
+/// both count toward the same per-IP and per-email limits, so the endpoint can't be used to find
+/// out which emails have accounts. Throttled attempts are refused before any hashing.
+///
+/// Attempts sharing an address or email take turns from the throttle check until the attempt is
+/// reserved as a failure, so a parallel burst is counted one attempt at a time. Argon2 then runs
+/// with no transaction open, so a slow hash holds no pooled connection, and a second short
+/// transaction settles the attempt's outcome.
 #[instrument(skip_all, fields(email = %form.email))]
 pub async fn process_login(
     State(state): State<Arc<AppState>>,
     session: Session,
+    ClientIp(ip_address): ClientIp,
     Form(form): Form<LoginFormData>,
 ) -> Result<Response<String>, FbklError> {
-    let email = form.email;
-
-    let Some(matching_user) = user_queries::find_user_by_email(&email, &state.db).await? else {
+    let attempt = LoginAttempt {
+        email: form.email,
+        ip_address,
+    };
+    let db_txn = state.db.begin().await?;
+    lock_login_attempt(&attempt, &db_txn).await?;
+    // Read after the lock, so attempts are stamped in the order they were counted.
+    let now = Utc::now().fixed_offset();
+    if let Some(throttle) = check_login_throttle(&attempt, now, &db_txn).await? {
+        db_txn.commit().await?;
+        let retry_after_seconds = (throttle.retry_at() - now).num_seconds().max(1);
         let err_response = Response::builder()
-            .status(StatusCode::NOT_FOUND)
-            .body("USER_NOT_FOUND".to_string())?;
+            .status(StatusCode::TOO_MANY_REQUESTS)
+            .header(RETRY_AFTER, retry_after_seconds)
+            .body(TOO_MANY_LOGIN_ATTEMPTS.to_string())?;
         return Ok(err_response);
+    }
+
+    let maybe_user = user_queries::find_user_by_email(attempt.email.trim(), &db_txn).await?;
+    let reserved_attempt = reserve_login_attempt(
+        &attempt,
+        maybe_user.as_ref().map(|user_model| user_model.id),
+        now,
+        &db_txn,
+    )
+    .await?;
+    db_txn.commit().await?;
+
+    let password_matches = match &maybe_user {
+        Some(user_model) => {
+            match verify_password_against_hash(&form.password, &user_model.hashed_password) {
+                Ok(()) => true,
+                Err(AuthError::PasswordMismatch) => false,
+                Err(auth_error) => return Err(auth_error.into()),
+            }
+        }
+        None => {
+            verify_password_for_unknown_user(&form.password);
+            false
+        }
     };
 
-    verify_password_against_hash(&form.password, &matching_user.hashed_password)?;
+    let db_txn = state.db.begin().await?;
+    lock_login_attempt(&attempt, &db_txn).await?;
+    settle_login_attempt(&attempt, reserved_attempt, password_matches, &db_txn).await?;
+    db_txn.commit().await?;
+
+    let (Some(matching_user), true) = (maybe_user, password_matches) else {
+        let err_response = Response::builder()
+            .status(StatusCode::UNAUTHORIZED)
+            .body(INVALID_CREDENTIALS.to_string())?;
+        return Ok(err_response);
+    };
 
-    // create session
-    session.cycle_id().await?;
-    session.set_expiry(Some(Expiry::OnInactivity(Duration::days(90)))); // 90 days
-    session.insert("user_id", matching_user.id).await?;
+    start_user_session(&session, &matching_user).await?;
 
     // TODO: Separate page for login success
 
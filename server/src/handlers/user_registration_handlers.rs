@@ -1,17 +1,24 @@
-use crate::{error::FbklError, server::AppState};
+use crate::{error::FbklError, read_public_url, server::AppState};
 use axum::{
     Form,
     extract::{Query, State},
     http::StatusCode,
     response::{Html, Response},
 };
-use fbkl_auth::{decode_token, generate_password_hash, generate_token};
+use chrono::Utc;
+use fbkl_auth::{
+    decode_token, encode_token, generate_password_hash, generate_token, validate_email,
+    validate_password,
+};
 use fbkl_entity::{
-    sea_orm::{ActiveModelTrait, ActiveValue::NotSet, Set},
+    league_invitation_queries,
+    sea_orm::{ActiveModelTrait, ActiveValue::NotSet, Set, TransactionTrait},
     user, user_queries,
     user_registration::{self, UserRegistrationStatus},
     user_registration_queries,
 };
+use fbkl_logic::league_invitations::{LeagueInvitationRejection, accept_league_invitation};
+use fbkl_notifications::{enqueue_email, registration_confirmation_email};
 use serde::Deserialize;
 use std::sync::Arc;
 
@@ -20,6 +27,9 @@ pub struct RegistrationFormData {
     pub email: String,
     pub password: String,
     pub confirm_password: String,
+    /// A league invitation to accept as the new user, with the nickname to join under.
+    pub invitation_token: Option<String>,
+    pub nickname: Option<String>,
 }
 
 pub async fn get_registration_page() -> Html<&'static str> {
@@ -35,6 +45,8 @@ pub async fn get_registration_page() -> Html<&'static str> {
             <input type="email" name="email" placeholder="Email">
             <input type="password" name="password">
             <input type="password" name="confirm_password">
+            <input type="hidden" name="invitation_token">
+            <input type="text" name="nickname" placeholder="Nickname">
             <button type="submit">Submit</button>
         </form>
     </body>
@@ -55,20 +67,103 @@ pub async fn process_registration(
         return Ok(err_response);
     }
 
-    let token = generate_token();
+    let email = form.email.trim().to_owned();
+    if validate_email(&email).is_err() {
+        let err_response = Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body("INVALID_EMAIL".to_string())?;
+        return Ok(err_response);
+    }
+    if validate_password(&form.password).is_err() {
+        let err_response = Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body("INVALID_PASSWORD".to_string())?;
+        return Ok(err_response);
+    }
+
+    let invitation_token = form
+        .invitation_token
+        .as_deref()
+        .map(str::trim)
+        .filter(|token| !token.is_empty());
+    let invitation = match invitation_token {
+        None => None,
+        Some(invitation_token) => {
+            let now = Utc::now().fixed_offset();
+            match league_invitation_queries::find_league_invitation_by_token(
+                decode_token(invitation_token)?,
+                &state.db,
+            )
+            .await?
+            .filter(|invitation| invitation.is_open(now))
+            {
+                Some(invitation) => Some(invitation),
+                None => {
+                    let err_response = Response::builder()
+                        .status(StatusCode::BAD_REQUEST)
+                        .body("INVITATION_NOT_OPEN".to_string())?;
+                    return Ok(err_response);
+                }
+            }
+        }
+    };
+
+    // The nickname is shown to the whole league, so it is never defaulted to the email address.
+    let nickname = form
+        .nickname
+        .as_deref()
+        .map(str::trim)
+        .filter(|nickname| !nickname.is_empty())
+        .map(str::to_owned);
+    if invitation.is_some() && nickname.is_none() {
+        let err_response = Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body("NICKNAME_REQUIRED".to_string())?;
+        return Ok(err_response);
+    }
+
+    let token: Vec<u8> = generate_token().into_iter().collect();
     let hashed_password = generate_password_hash(&form.password)?;
 
-    let (_new_user, _new_user_token) = user_queries::insert_user(
+    // One transaction, so a refused invitation leaves no account behind to block a retry.
+    let db_txn = state.db.begin().await?;
+    let (new_user, _new_user_token) = user_queries::insert_user(
         user::ActiveModel {
             id: NotSet,
-            email: Set(form.email),
+            email: Set(email),
             hashed_password: Set(hashed_password),
             ..Default::default()
         },
-        token.into_iter().collect(),
-        &state.db,
+        token.clone(),
+        &db_txn,
     )
     .await?;
+    enqueue_email(
+        &registration_confirmation_email(
+            &new_user.email,
+            &read_public_url(),
+            &encode_token(&token),
+        ),
+        &db_txn,
+    )
+    .await?;
+    if let (Some(invitation), Some(nickname)) = (invitation, nickname) {
+        accept_league_invitation(
+            invitation,
+            &new_user,
+            nickname,
+            Utc::now().fixed_offset(),
+            &db_txn,
+        )
+        .await
+        .map_err(
+            |err| match err.downcast_ref::<LeagueInvitationRejection>() {
+                Some(rejection) => FbklError::BadRequest(rejection.to_string()),
+                None => err.into(),
+            },
+        )?;
+    }
+    db_txn.commit().await?;
 
     // TODO: Separate page for user registration confirmation
     //     let html = format!(
@@ -92,7 +187,7 @@ pub async fn process_registration(
 
 #[derive(Deserialize)]
 pub struct TokenQuery {
-    token: String,
+    pub token: String,
 }
 
 pub async fn confirm_registration(
@@ -109,19 +204,23 @@ pub async fn confirm_registration(
 
     let token_bytes = decode_token(token)?;
 
-    let mut found_user_registration: user_registration::ActiveModel =
-        if let Some(user_registration) =
-            user_registration_queries::find_user_registration_by_token(token_bytes, &state.db)
-                .await?
-        {
-            user_registration.into()
-        } else {
-            let err_response = Response::builder()
-                .status(StatusCode::NOT_FOUND)
-                .body("USER_REGISTRATION_NOT_FOUND".to_string())?;
-            return Ok(err_response);
-        };
+    let Some(user_registration) =
+        user_registration_queries::find_user_registration_by_token(token_bytes, &state.db).await?
+    else {
+        let err_response = Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body("USER_REGISTRATION_NOT_FOUND".to_string())?;
+        return Ok(err_response);
+    };
+
+    if user_registration.status == UserRegistrationStatus::Confirmed {
+        let err_response = Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body("USER_REGISTRATION_ALREADY_CONFIRMED".to_string())?;
+        return Ok(err_response);
+    }
 
+    let mut found_user_registration: user_registration::ActiveModel = user_registration.into();
     found_user_registration.status = Set(UserRegistrationStatus::Confirmed);
     found_user_registration.update(&state.db).await?;
 
@@ -1,3 +1,4 @@
+mod client_ip;
 mod error;
 mod graphql;
 mod handlers;
@@ -40,6 +41,14 @@ pub fn read_database_url() -> Result<String, DbErr> {
         .map_err(|_| DbErr::Custom("FBKL_DATABASE_URL must be set".to_owned()))
 }
 
+/// Read `FBKL_PUBLIC_URL`, the origin links in outgoing email point at. Defaults to the local
+/// server.
+pub fn read_public_url() -> String {
+    std::env::var("FBKL_PUBLIC_URL")
+        .map(|public_url| public_url.trim_end_matches('/').to_owned())
+        .unwrap_or_else(|_| "http://localhost:9001".to_owned())
+}
+
 /// Build the async-graphql schema with the configured complexity/depth limits.
 pub fn build_graphql_schema(db: DatabaseConnection) -> AppSchema {
     Schema::build(
@@ -1,10 +1,12 @@
 #![deny(clippy::all)]
 
+use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::Duration;
 
 use axum::serve;
 use color_eyre::Result;
+use fbkl_notifications::ConfiguredMailer;
 use fbkl_server::{
     AppState, build_graphql_schema, build_router, build_session_layer, init_db, setup,
     shutdown_signal,
@@ -20,9 +22,7 @@ async fn main() -> Result<()> {
     // DB connection pool
     info!("Connecting to database...");
     let db_connection = init_db().await?;
-    let shared_state = Arc::new(AppState {
-        db: db_connection.clone(),
-    });
+    let shared_state = Arc::new(AppState::new(db_connection.clone()));
 
     // Session store: migrate schema + spawn the expired-session deletion loop.
     // On Lambda these move to a one-time deploy step + a dedicated session-gc Lambda.
@@ -41,17 +41,26 @@ async fn main() -> Result<()> {
     let router = build_router(shared_state, session_layer, graphql_schema);
 
     // Deadline scheduler: polls for due deadlines across all leagues and dispatches them
-    // to the transaction processor (see notes/implementation-specs/05).
+    // to the transaction processor (see notes/implementation-specs/05). It also delivers the
+    // email outbox, unless FBKL_MAILER is unset, in which case mail stays queued.
     info!("Starting scheduler...");
-    let scheduler_task = fbkl_jobs::spawn_scheduler(db_connection.clone());
+    let mailer = ConfiguredMailer::from_env()?;
+    if mailer.is_none() {
+        info!("FBKL_MAILER is not set; outbound email will stay queued.");
+    }
+    let scheduler_task = fbkl_jobs::spawn_scheduler(db_connection.clone(), mailer);
 
     info!("Starting server...");
     let listener = tokio::net::TcpListener::bind("0.0.0.0:9001").await?;
-    let server =
-        serve(listener, router.into_make_service()).with_graceful_shutdown(shutdown_signal(vec![
-            session_deletion_task.abort_handle(),
-            scheduler_task.abort_handle(),
-        ]));
+    // The peer address is the client's: nothing sits in front of this server.
+    let server = serve(
+        listener,
+        router.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(shutdown_signal(vec![
+        session_deletion_task.abort_handle(),
+        scheduler_task.abort_handle(),
+    ]));
 
     info!("Starting fbkl/server on port 9001...");
 
@@ -59,26 +68,17 @@ async fn main() -> Result<()> {
     // TODO: Functionality for draft picks
     // TODO: Functionality for trades
     // TODO: GQL: Add argument for only active team users in GetLeague
-    // TODO: Need to update players db table with new players from the NBA API.
-    // TODO: Players change names.
     // TODO: Need some kind of storage for NBA dates (start of season, ASB start and end dates, MLK week early start times)
     // TODO: Maybe ping NBA API for game start times each week?
     // TODO: Reconciling end dates of different transaction types w/ when they go into effect.
-    // TODO: Account for roster legalization (pre-season).
-    // TODO: Account for roster legalization (weekly).
     // TODO: import data (transactions)
     // TODO: Rest of DB migrations (incl. FK relations)
-    // TODO: Auto-drop logic for weekly locks to ensure teams are legal.
-    // TODO: login/registration needs validation (password length, email is correct, etc.)
     // TODO: Handle errors with actual HTTP status codes + logging (test w/ graphql errors)
-    // TODO: Check if user confirmation already happened
     // TODO: Add CSP header: https://developer.mozilla.org/en-US/docs/Web/HTTP/CSP
-    // TODO: hook up user registration to sendgrid/similar.
     // TODO: Use Next.JS for public path? Turbopack seems interesting.
     // TODO: Possibly use https://github.com/casbin/casbin-rs for access control?
     // TODO: Create league config data structure to hold deadlines for each season.
     // TODO: advancing contracts should take into account custom players created for a league (merging them w/ nba/espn data if they exist in official datasets)
-    // TODO: process for associating a league player with a real player and updating contracts related to league_player.
 
     server.await?;
     session_deletion_task.await??;
@@ -4,9 +4,14 @@ use axum::{
     Router,
     routing::{get, post},
 };
+use fbkl_auth::prepare_unknown_user_password_hash;
 use fbkl_entity::sea_orm::DatabaseConnection;
 
 use crate::handlers::{
+    account_handlers::{
+        change_password, confirm_email_change, request_email_change, request_password_reset,
+        reset_password,
+    },
     graphql_handlers::{graphiql, process_graphql},
     login_handlers::{logged_in_data, logout, process_login},
     public_handlers::get_public_page,
@@ -22,6 +27,15 @@ pub struct AppState {
     pub db: DatabaseConnection,
 }
 
+impl AppState {
+    /// Also readies the login route's unknown-email hash, so it is made before the first request
+    /// rather than during one, where the extra Argon2 run would show in that response's timing.
+    pub fn new(db: DatabaseConnection) -> Self {
+        prepare_unknown_user_password_hash();
+        Self { db }
+    }
+}
+
 pub fn setup_server_router() -> Router<Arc<AppState>> {
     Router::new()
         .route("/", get(get_public_page))
@@ -31,6 +45,11 @@ pub fn setup_server_router() -> Router<Arc<AppState>> {
         .route("/api/login", post(process_login))
         .route("/api/user", get(logged_in_data))
         .route("/logout", get(logout))
+        .route("/api/password_reset", post(request_password_reset))
+        .route("/api/password_reset/confirm", post(reset_password))
+        .route("/api/account/password", post(change_password))
+        .route("/api/account/email", post(request_email_change))
+        .route("/confirm_email_change", get(confirm_email_change))
         .route(
             "/register",
             get(get_registration_page).post(process_registration),
@@ -3,7 +3,8 @@ use fbkl_entity::{
     sea_orm::{DatabaseConnection, EntityTrait},
     user,
 };
-use tower_sessions::Session;
+use time::Duration;
+use tower_sessions::{Expiry, Session};
 
 use crate::error::FbklError;
 
@@ -19,6 +20,10 @@ pub async fn enforce_logged_in(session: Session) -> Result<i64, FbklError> {
 ///
 /// Distinguishes "no session / not logged in" (`Ok(None)`) from a real session-store
 /// or DB failure (`Err`), so an outage can't masquerade as a logged-out user.
+///
+/// A session stamped with an older `session_generation` than the user's (the password changed
+/// since it logged in) is flushed and treated as logged out. Every authenticated request goes
+/// through here first, so the later raw `user_id` reads see the flushed session too.
 pub async fn get_current_user(
     session: Session,
     db: &DatabaseConnection,
@@ -27,5 +32,35 @@ pub async fn get_current_user(
         return Ok(None);
     };
 
-    Ok(user::Entity::find_by_id(user_id).one(db).await?)
+    let Some(user_model) = user::Entity::find_by_id(user_id).one(db).await? else {
+        return Ok(None);
+    };
+
+    // Sessions from before generations existed carry no stamp; they belong to generation 0.
+    let session_generation = session
+        .get::<i32>("session_generation")
+        .await?
+        .unwrap_or_default();
+    if session_generation != user_model.session_generation {
+        session.flush().await?;
+        return Ok(None);
+    }
+
+    Ok(Some(user_model))
+}
+
+/// Logs `user_model` in on this session, stamped with their current `session_generation`.
+///
+/// Cycles the session ID first so a pre-login ID can't be fixated.
+pub async fn start_user_session(
+    session: &Session,
+    user_model: &user::Model,
+) -> Result<(), FbklError> {
+    session.cycle_id().await?;
+    session.set_expiry(Some(Expiry::OnInactivity(Duration::days(90)))); // 90 days
+    session.insert("user_id", user_model.id).await?;
+    session
+        .insert("session_generation", user_model.session_generation)
+        .await?;
+    Ok(())
 }
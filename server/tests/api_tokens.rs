@@ -0,0 +1,255 @@
+//! Personal API tokens: created through a session, then used on their own to act as the user in
+//! one league, within their scopes, until revoked.
+
+use std::sync::Arc;
+
+use async_graphql::{Request, Value, value};
+use chrono::Utc;
+use fbkl_auth::{decode_token, hash_token};
+use fbkl_entity::{
+    api_token, api_token_queries, deadline::DeadlineKind, team_user::LeagueRole, user, user_queries,
+};
+use fbkl_server::{AppSchema, authenticate_api_token, build_graphql_schema};
+use fbkl_test_support::{TestLeague, central};
+use tower_sessions::{MemoryStore, Session};
+
+#[tokio::test]
+async fn tokens_act_within_their_league_role_and_scopes_until_revoked() {
+    let Some(league) = TestLeague::create("api_tokens", 2026).await else {
+        return;
+    };
+    league
+        .add_deadline(
+            DeadlineKind::PreseasonVeteranAuctionStart,
+            central("2025-09-01T12:00:00"),
+        )
+        .await;
+    let commissioner = league.add_team_user(LeagueRole::LeagueCommissioner).await;
+    let owner = league.add_team_user(LeagueRole::TeamOwner).await;
+    let schema = build_graphql_schema(league.db.clone());
+
+    let commissioner_token = create_token(&schema, &league, commissioner.user_id, "AUCTION").await;
+    let owner_token = create_token(&schema, &league, owner.user_id, "AUCTION").await;
+    let read_only_token = create_token(&schema, &league, owner.user_id, "").await;
+
+    // Reads need no scope, and stamp the token as used.
+    let teams = run_with_token(&schema, &league, &read_only_token, "{ teams { id } }").await;
+    assert!(teams.is_ok(), "{teams:?}");
+    let read_only_model = find_token(&league, &read_only_token).await;
+    assert!(read_only_model.last_used_at.is_some());
+    assert!(read_only_model.get_scopes().unwrap().is_empty());
+
+    // A scope grants its mutations, but the caller's league role still applies.
+    let tiers_mutation = "mutation { setVeteranAuctionMinBidTiers(minBidAmounts: [20, 10]) }";
+    assert_eq!(
+        run_with_token(&schema, &league, &commissioner_token, tiers_mutation).await,
+        Ok(Value::List(vec![Value::from(20), Value::from(10)]))
+    );
+    assert_eq!(
+        run_with_token(&schema, &league, &owner_token, tiers_mutation).await,
+        Err("FORBIDDEN".to_owned())
+    );
+    assert_eq!(
+        run_with_token(&schema, &league, &read_only_token, tiers_mutation).await,
+        Err("FORBIDDEN".to_owned())
+    );
+
+    // Fields that belong to the user rather than the league need a session.
+    assert_eq!(
+        run_with_token(&schema, &league, &owner_token, "{ apiTokens { id } }").await,
+        Err("FORBIDDEN".to_owned())
+    );
+    assert_eq!(
+        run_with_token(&schema, &league, &owner_token, "{ currentUser { email } }").await,
+        Err("FORBIDDEN".to_owned())
+    );
+    assert_eq!(
+        run_with_token(
+            &schema,
+            &league,
+            &owner_token,
+            "{ league { currentTeamUser { userId user { email } } } }"
+        )
+        .await,
+        Ok(value!({ "currentTeamUser": { "userId": owner.user_id, "user": null } }))
+    );
+
+    let owner_session = session_for(owner.user_id, league.league_id).await;
+    let owner_model = find_user(&league, owner.user_id).await;
+    let owner_token_id = find_token(&league, &owner_token).await.id;
+    let listed = run(
+        &schema,
+        "{ apiTokens { id } }",
+        &owner_session,
+        &owner_model,
+    )
+    .await;
+    let Ok(Value::List(listed)) = listed else {
+        panic!("listing tokens failed: {listed:?}");
+    };
+    assert_eq!(listed.len(), 2);
+
+    // Someone else's token is not found; a revoked one no longer authenticates.
+    let commissioner_token_id = find_token(&league, &commissioner_token).await.id;
+    assert_eq!(
+        run(
+            &schema,
+            &format!("mutation {{ revokeApiToken(id: {commissioner_token_id}) {{ id }} }}"),
+            &owner_session,
+            &owner_model,
+        )
+        .await,
+        Err("NOT_FOUND".to_owned())
+    );
+    run(
+        &schema,
+        &format!("mutation {{ revokeApiToken(id: {owner_token_id}) {{ id }} }}"),
+        &owner_session,
+        &owner_model,
+    )
+    .await
+    .expect("revoke the owner's token");
+    assert_eq!(
+        run_with_token(&schema, &league, &owner_token, "{ teams { id } }").await,
+        Err("UNAUTHENTICATED".to_owned())
+    );
+
+    // A new password revokes every token the user still has.
+    let revoked_count = api_token_queries::revoke_api_tokens_for_user(
+        owner.user_id,
+        Utc::now().fixed_offset(),
+        &league.db,
+    )
+    .await
+    .unwrap();
+    assert_eq!(revoked_count, 1);
+    assert_eq!(
+        run_with_token(&schema, &league, &read_only_token, "{ teams { id } }").await,
+        Err("UNAUTHENTICATED".to_owned())
+    );
+    assert!(
+        run_with_token(&schema, &league, &commissioner_token, "{ teams { id } }")
+            .await
+            .is_ok()
+    );
+}
+
+/// Creates a token through the user's session, returning the secret.
+async fn create_token(
+    schema: &AppSchema,
+    league: &TestLeague,
+    user_id: i64,
+    scopes: &str,
+) -> String {
+    let session = session_for(user_id, league.league_id).await;
+    let user_model = find_user(league, user_id).await;
+    let created = run(
+        schema,
+        &format!("mutation {{ createApiToken(name: \"script\", scopes: [{scopes}]) {{ token }} }}"),
+        &session,
+        &user_model,
+    )
+    .await
+    .expect("create a token");
+    let Value::Object(fields) = created else {
+        panic!("createApiToken returned no object");
+    };
+    let Some(Value::String(token)) = fields.get("token") else {
+        panic!("createApiToken returned no token");
+    };
+    token.clone()
+}
+
+/// Runs one operation the way the GraphQL handler does for a bearer token.
+async fn run_with_token(
+    schema: &AppSchema,
+    league: &TestLeague,
+    token: &str,
+    operation: &str,
+) -> Result<Value, String> {
+    let request = Request::new(operation);
+    let (token_model, user_model) = authenticate_api_token(token, &request, &league.db)
+        .await
+        .map_err(|error| error_code(error.extensions.as_ref().and_then(|ext| ext.get("code"))))?;
+    let response = schema
+        .execute(
+            request
+                .data(Session::new(None, Arc::new(MemoryStore::default()), None))
+                .data(Some(user_model))
+                .data(token_model),
+        )
+        .await;
+    first_field(response)
+}
+
+/// Runs one operation as the session's user.
+async fn run(
+    schema: &AppSchema,
+    operation: &str,
+    session: &Session,
+    user_model: &user::Model,
+) -> Result<Value, String> {
+    let response = schema
+        .execute(
+            Request::new(operation)
+                .data(session.clone())
+                .data(Some(user_model.clone())),
+        )
+        .await;
+    first_field(response)
+}
+
+fn first_field(response: async_graphql::Response) -> Result<Value, String> {
+    if let Some(error) = response.errors.first() {
+        return Err(error_code(
+            error
+                .extensions
+                .as_ref()
+                .and_then(|extensions| extensions.get("code")),
+        ));
+    }
+
+    let Value::Object(fields) = response.data else {
+        panic!("operation returned no fields");
+    };
+    Ok(fields
+        .values()
+        .next()
+        .cloned()
+        .expect("operation returned no fields"))
+}
+
+fn error_code(maybe_code: Option<&Value>) -> String {
+    maybe_code
+        .map_or_else(String::new, ToString::to_string)
+        .trim_matches('"')
+        .to_owned()
+}
+
+async fn session_for(user_id: i64, league_id: i64) -> Session {
+    let session = Session::new(None, Arc::new(MemoryStore::default()), None);
+    session
+        .insert("user_id", user_id)
+        .await
+        .expect("set the session user");
+    session
+        .insert("selected_league_id", league_id)
+        .await
+        .expect("set the session league");
+    session
+}
+
+async fn find_user(league: &TestLeague, user_id: i64) -> user::Model {
+    user_queries::find_user_by_id(user_id, &league.db)
+        .await
+        .unwrap()
+        .unwrap()
+}
+
+async fn find_token(league: &TestLeague, token: &str) -> api_token::Model {
+    let token_hash = hash_token(&decode_token(token).unwrap());
+    api_token_queries::find_api_token_by_token_hash(token_hash, &league.db)
+        .await
+        .unwrap()
+        .unwrap()
+}
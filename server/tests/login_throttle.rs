@@ -0,0 +1,144 @@
+//! Login throttling is counted from `security_event` rows, so it holds across server instances and
+//! treats emails without an account exactly like real ones.
+
+use chrono::{SubsecRound, TimeDelta, Utc};
+use fbkl_entity::{
+    sea_orm::{ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter},
+    security_event::{self, SecurityEventKind},
+};
+use fbkl_logic::login_security::{
+    LOGIN_ATTEMPTS_PER_IP_ADDRESS, LOGIN_LOCKOUT_THRESHOLD, LoginAttempt, LoginThrottle,
+    check_login_throttle, record_login_attempt, reserve_login_attempt, settle_login_attempt,
+};
+use fbkl_test_support::TestLeague;
+
+fn attempt(email: &str, ip_address: &str) -> LoginAttempt {
+    LoginAttempt {
+        email: email.to_owned(),
+        ip_address: ip_address.to_owned(),
+    }
+}
+
+#[tokio::test]
+async fn repeated_failures_lock_an_email_out_for_longer_each_time() {
+    let Some(league) = TestLeague::create("login_lockout", 2026).await else {
+        return;
+    };
+    let db = &league.db;
+    // Postgres keeps microseconds, so whole seconds round-trip exactly.
+    let start = Utc::now().fixed_offset().trunc_subsecs(0);
+    // Nobody has this email; it is throttled the same as if someone did.
+    let guess = attempt("Nobody@Example.com", "203.0.113.7");
+
+    let mut now = start;
+    for _ in 0..LOGIN_LOCKOUT_THRESHOLD {
+        assert_eq!(check_login_throttle(&guess, now, db).await.unwrap(), None);
+        record_login_attempt(&guess, None, false, now, db)
+            .await
+            .unwrap();
+        now += TimeDelta::seconds(1);
+    }
+    let last_failure_at = now - TimeDelta::seconds(1);
+
+    // Case and whitespace don't dodge the lock, and neither does another address.
+    let same_email_elsewhere = attempt(" nobody@example.com", "198.51.100.2");
+    assert_eq!(
+        check_login_throttle(&same_email_elsewhere, now, db)
+            .await
+            .unwrap(),
+        Some(LoginThrottle::AccountLocked {
+            locked_until: last_failure_at + TimeDelta::minutes(1)
+        })
+    );
+    let locked_events = security_event::Entity::find()
+        .filter(security_event::Column::Kind.eq(SecurityEventKind::AccountLocked))
+        .count(db)
+        .await
+        .unwrap();
+    assert_eq!(locked_events, 1);
+
+    // Hammering the locked account from one address is logged once, not once per refusal; the
+    // other address above has its own entry.
+    for _ in 0..3 {
+        assert!(
+            check_login_throttle(&guess, now, db)
+                .await
+                .unwrap()
+                .is_some()
+        );
+    }
+    let throttled_events = security_event::Entity::find()
+        .filter(security_event::Column::Kind.eq(SecurityEventKind::LoginThrottled))
+        .count(db)
+        .await
+        .unwrap();
+    assert_eq!(throttled_events, 2);
+
+    // Once the lock lapses, one more failure locks it again for twice as long.
+    now = last_failure_at + TimeDelta::minutes(1);
+    assert_eq!(check_login_throttle(&guess, now, db).await.unwrap(), None);
+    record_login_attempt(&guess, None, false, now, db)
+        .await
+        .unwrap();
+    assert_eq!(
+        check_login_throttle(&guess, now, db).await.unwrap(),
+        Some(LoginThrottle::AccountLocked {
+            locked_until: now + TimeDelta::minutes(2)
+        })
+    );
+}
+
+#[tokio::test]
+async fn one_address_is_limited_across_every_email() {
+    let Some(league) = TestLeague::create("login_ip_rate_limit", 2026).await else {
+        return;
+    };
+    let db = &league.db;
+    let now = Utc::now().fixed_offset();
+
+    for guess_number in 0..LOGIN_ATTEMPTS_PER_IP_ADDRESS {
+        let guess = attempt(&format!("owner{guess_number}@example.com"), "203.0.113.7");
+        assert_eq!(check_login_throttle(&guess, now, db).await.unwrap(), None);
+        record_login_attempt(&guess, None, false, now, db)
+            .await
+            .unwrap();
+    }
+
+    let next_guess = attempt("someone-new@example.com", "203.0.113.7");
+    assert!(matches!(
+        check_login_throttle(&next_guess, now, db).await.unwrap(),
+        Some(LoginThrottle::IpAddressRateLimited { .. })
+    ));
+    let other_address = attempt("someone-new@example.com", "198.51.100.2");
+    assert_eq!(
+        check_login_throttle(&other_address, now, db).await.unwrap(),
+        None
+    );
+}
+
+#[tokio::test]
+async fn a_reserved_attempt_counts_as_a_failure_until_it_is_settled() {
+    let Some(league) = TestLeague::create("login_reserved_attempt", 2026).await else {
+        return;
+    };
+    let db = &league.db;
+    let now = Utc::now().fixed_offset();
+    let login = attempt("owner@example.com", "203.0.113.7");
+    let count_kind = |kind: SecurityEventKind| {
+        security_event::Entity::find()
+            .filter(security_event::Column::Kind.eq(kind))
+            .count(db)
+    };
+
+    let reserved_attempt = reserve_login_attempt(&login, None, now, db).await.unwrap();
+    assert_eq!(count_kind(SecurityEventKind::LoginFailed).await.unwrap(), 1);
+
+    settle_login_attempt(&login, reserved_attempt, true, db)
+        .await
+        .unwrap();
+    assert_eq!(count_kind(SecurityEventKind::LoginFailed).await.unwrap(), 0);
+    assert_eq!(
+        count_kind(SecurityEventKind::LoginSucceeded).await.unwrap(),
+        1
+    );
+}
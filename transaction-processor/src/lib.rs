@@ -19,7 +19,6 @@ use std::fmt::Debug;
 use color_eyre::eyre::{Result, eyre};
 use fbkl_entity::{
     deadline::{self, DeadlineKind},
-    deadline_queries,
     job_run::JobEventKind,
     job_run_queries::{
         ClaimOutcome, NewJobRun, claim_job_run, deadline_idempotency_key, mark_job_run_failed,
@@ -35,6 +34,7 @@ use fbkl_logic::{
         RfaMatchDecision, decline_to_raise, lock_rosters, match_or_decline,
         process_keeper_deadline_transaction,
     },
+    weekly_moves::find_weekly_move_deadline,
 };
 use tracing::{error, info, instrument};
 
@@ -251,7 +251,20 @@ where
         }
         DeadlineKind::PreseasonFinalRosterLock
         | DeadlineKind::Week1RosterLock
-        | DeadlineKind::InSeasonRosterLock => lock_rosters(deadline_model, txn).await,
+        | DeadlineKind::InSeasonRosterLock => {
+            // Illegal teams are logged by `lock_rosters` and left pending; the lock still succeeds
+            // for everyone else.
+            let illegal_teams = lock_rosters(deadline_model, txn).await?;
+            if !illegal_teams.is_empty() {
+                info!(
+                    "Deadline {:?} (id = {}) locked with {} illegal roster(s) left pending",
+                    deadline_model.kind,
+                    deadline_model.id,
+                    illegal_teams.len()
+                );
+            }
+            Ok(())
+        }
         // §6.3.1: this deadline builds the season's release schedule; the tick then opens each row on its date.
         DeadlineKind::PreseasonVeteranAuctionStart => {
             assemble_veteran_auction_pool(
@@ -310,10 +323,9 @@ where
     let now = chrono::Utc::now().fixed_offset();
     match kind {
         ProcessableEventKind::FaAuctionClose | ProcessableEventKind::FaExtensionExpiry => {
-            // The most recently passed deadline supplies the signed contract's effective date.
-            let deadline_model =
-                deadline_queries::find_most_recent_deadline_by_datetime(league_id, now, txn)
-                    .await?;
+            // In season the win joins the week's moves for the upcoming roster lock, which also
+            // supplies the signed contract's effective date; otherwise the most recent deadline does.
+            let deadline_model = find_weekly_move_deadline(league_id, now, txn).await?;
             end_fa_auction(&deadline_model, subject_id, None, txn).await?;
             Ok(())
         }